@@ -0,0 +1,78 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use rezalnyas_core::features::engine::bench_requests::{canned_request, PANEL_COUNTS, STOCK_SIZE_COUNTS};
+use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+
+// Базовая линия сохраняется через `make bench-baseline`, сравнение с ней - `make bench-compare`
+
+fn optimizer(c: &mut Criterion) {
+    let service = CutListOptimizerServiceImpl::new(1, false).expect("Failed to initialize optimizer service");
+    let mut group = c.benchmark_group("optimizer");
+    group.sample_size(10);
+
+    for nbr_panels in PANEL_COUNTS {
+        for nbr_stock_sizes in STOCK_SIZE_COUNTS {
+            let request = canned_request(nbr_panels, nbr_stock_sizes);
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}_panels", nbr_panels), format!("{}_stock", nbr_stock_sizes)),
+                &request,
+                |b, request| {
+                    b.iter(|| {
+                        service.clear_result_cache();
+                        service.submit_task(request.clone())
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+/// Large mixed orders with more distinct sizes than permutation groups, with and without
+/// `PerformanceThresholds::cluster_permutation_groups`. The waste of each variant is printed
+/// once, the time is measured by criterion
+fn clustering(c: &mut Criterion) {
+    let service = CutListOptimizerServiceImpl::new(1, false).expect("Failed to initialize optimizer service");
+    let mut group = c.benchmark_group("clustering");
+    group.sample_size(10);
+
+    for nbr_panels in [100, 300] {
+        for cluster_permutation_groups in [false, true] {
+            let mut request = canned_request(nbr_panels, 1);
+            request.performance_thresholds.cluster_permutation_groups = cluster_permutation_groups;
+            let variant = if cluster_permutation_groups { "clustered" } else { "largest_groups" };
+
+            service.clear_result_cache();
+            let response = service
+                .submit_task(request.clone())
+                .ok()
+                .and_then(|result| result.task_id)
+                .and_then(|task_id| service.get_task_status(&task_id))
+                .and_then(|status| status.solution);
+            if let Some(response) = response {
+                let total_area = response.total_used_area + response.total_wasted_area;
+                let waste_percent = if total_area > 0.0 {
+                    response.total_wasted_area * 100.0 / total_area
+                } else {
+                    0.0
+                };
+                eprintln!("clustering/{}/{}_panels: waste {:.2}%", variant, nbr_panels, waste_percent);
+            }
+
+            group.bench_with_input(
+                BenchmarkId::new(variant, format!("{}_panels", nbr_panels)),
+                &request,
+                |b, request| {
+                    b.iter(|| {
+                        service.clear_result_cache();
+                        service.submit_task(request.clone())
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, optimizer, clustering);
+criterion_main!(benches);
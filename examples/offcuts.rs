@@ -0,0 +1,67 @@
+use rezalnyas_core::enums::length_unit::LengthUnit;
+use rezalnyas_core::features::engine::offcut_store::{FileOffcutStore, OffcutStore};
+
+const USAGE: &str =
+    "usage: offcuts [--unit mm|cm|m|in|ft] <file> list | offcuts [--unit mm|cm|m|in|ft] <file> consume <material> <id> [count]";
+
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    // Склад хранится в миллиметрах, размеры выводятся в единицах заказа
+    let unit = if args.first().is_some_and(|arg| arg == "--unit") {
+        let unit = args.get(1).and_then(|symbol| LengthUnit::from_symbol(symbol));
+        args.drain(..2.min(args.len()));
+        unit
+    } else {
+        Some(LengthUnit::Millimeter)
+    };
+    let (Some(unit), Some(path)) = (unit, args.first()) else {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    };
+    let store = FileOffcutStore::new(path);
+
+    let result = match args.get(1).map(String::as_str) {
+        Some("list") => list(&store, unit),
+        Some("consume") => consume(&store, &args[2..]),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn list(store: &FileOffcutStore, unit: LengthUnit) -> Result<(), Box<dyn std::error::Error>> {
+    let to_unit = |millimeters: &str| {
+        millimeters.parse::<f64>().map(|value| (value / unit.get_millimeters() * 100.0).round() / 100.0)
+    };
+    for material in store.list()? {
+        println!("{}", material);
+        for offcut in store.load(&material)? {
+            println!(
+                "  #{} {} x {} {}, {} pcs",
+                offcut.id,
+                to_unit(&offcut.width)?,
+                to_unit(&offcut.height)?,
+                unit.get_symbol(),
+                offcut.count
+            );
+        }
+    }
+    Ok(())
+}
+
+fn consume(store: &FileOffcutStore, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (Some(material), Some(id)) = (args.first(), args.get(1)) else {
+        return Err(USAGE.into());
+    };
+    let count = args.get(2).map_or(Ok(1), |count| count.parse())?;
+    if !store.consume(material, id.parse()?, count)? {
+        return Err(format!("{} has no {} pcs of offcut #{}", material, count, id).into());
+    }
+    println!("{} pcs of offcut #{} of {} consumed", count, id, material);
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::fs::File;
+use std::io::{BufReader, Write};
+
+use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+use rezalnyas_core::features::export::ndjson::write_ndjson_events;
+use rezalnyas_core::features::input::request_reader::read_request;
+
+const USAGE: &str = "usage: optimize <request.json> [--progress ndjson]";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let is_ndjson = match &args[1.min(args.len())..] {
+        [] => false,
+        [flag, format] if flag == "--progress" && format == "ndjson" => true,
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+    let Some(path) = args.first() else {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    };
+    if let Err(e) = optimize(path, is_ndjson) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Response JSON on one line; with `is_ndjson` the task events come first, one per line,
+/// as they happen, so the whole output is NDJSON
+fn optimize(path: &str, is_ndjson: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let request = read_request(BufReader::new(File::open(path)?))?;
+    let service = CutListOptimizerServiceImpl::new(1, false)?;
+
+    // События пишет отдельный поток, пока расчёт идёт в этом
+    let progress = is_ndjson.then(|| {
+        let events = service.subscribe();
+        std::thread::spawn(move || write_ndjson_events(&events, std::io::stdout()))
+    });
+    let submission = service.submit_task(request)?;
+    // Отклонённая задача событий не шлёт, писатель тогда не дожидаемся
+    let task_id = submission.task_id.ok_or("task was not accepted")?;
+    if let Some(progress) = progress {
+        progress.join().map_err(|_| "progress writer panicked")??;
+    }
+
+    let response = service
+        .get_task_status(&task_id)
+        .and_then(|status| status.solution)
+        .ok_or("task has no solution")?;
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer(&mut stdout, &response)?;
+    writeln!(stdout)?;
+    Ok(())
+}
@@ -58,6 +58,32 @@ impl EngineConstants {
     
     /// Maximum reasonable number of permutations to generate (7!)
     pub const MAX_PERMUTATIONS: usize = 5040;
+
+    /// Tile groups whose order is permuted, the smaller groups follow them in a fixed order
+    pub const MAX_PERMUTATION_GROUPS: usize = 7;
+
+    /// Copies of one panel above which full sheets of it are cut as a grid, see
+    /// `PerformanceThresholds::repeat_chunk_threshold`
+    pub const REPEAT_CHUNK_THRESHOLD: usize = 100;
+
+    /// Number of rows and columns of the per-sheet waste heatmap in the response
+    pub const WASTE_HEATMAP_SIZE: usize = 4;
+
+    /// Square millimeters in a square meter, stock priced per m² is charged on sheet areas in mm²
+    pub const MM2_PER_M2: f64 = 1_000_000.0;
+
+    /// Best solutions of a material kept in a task checkpoint, the task holds thousands
+    pub const MAX_CHECKPOINT_SOLUTIONS: usize = 100;
+
+    /// Share of `TaskLimits::max_pool_memory_bytes` from which a solution pool is pruned
+    pub const MEMORY_PRESSURE_RATIO: f64 = 0.9;
+
+    /// Yield margin below the best branch within which a branch survives memory pruning
+    pub const BRANCH_YIELD_EPSILON: f64 = 0.01;
+
+    /// Time without a processed permutation after which a running task counts as stalled,
+    /// see `HealthReport::nbr_stalled_tasks` and `PerformanceThresholds::stall_timeout_ms`
+    pub const TASK_STALL_TIMEOUT_MS: u64 = 60_000;
 }
 
 /// Configuration default values for cutting optimization
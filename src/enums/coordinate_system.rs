@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// Origin and Y axis of the tile and cut coordinates of a response
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CoordinateSystem {
+    /// Origin in the top-left corner of the sheet, Y grows down, as the engine works
+    #[default]
+    TopLeftYDown,
+    /// Origin in the bottom-left corner of the sheet, Y grows up, as in CAD drawings
+    BottomLeftYUp,
+}
+
+impl CoordinateSystem {
+    /// Y of a box of `height` on a sheet of `sheet_height`, given in this system, in `target`.
+    /// Points are boxes of height 0
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::coordinate_system::CoordinateSystem;
+    ///
+    /// let y = CoordinateSystem::TopLeftYDown.convert_y(CoordinateSystem::BottomLeftYUp, 0.0, 600.0, 2070.0);
+    /// assert_eq!(y, 1470.0);
+    /// assert_eq!(CoordinateSystem::BottomLeftYUp.convert_y(CoordinateSystem::BottomLeftYUp, y, 600.0, 2070.0), y);
+    /// ```
+    pub fn convert_y(self, target: Self, y: f64, height: f64, sheet_height: f64) -> f64 {
+        if self == target {
+            y
+        } else {
+            sheet_height - y - height
+        }
+    }
+}
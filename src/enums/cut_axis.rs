@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Axis the position of a guillotine cut is measured along
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CutAxis {
+    /// Cut line at a constant x, the pieces lie side by side (legacy `is_horizontal`)
+    X,
+    /// Cut line at a constant y, the pieces lie one above the other
+    Y,
+}
+
+impl CutAxis {
+    /// Axis of a legacy cut, see `Cut::is_horizontal` of the Java response
+    pub fn from_horizontal(is_horizontal: bool) -> Self {
+        if is_horizontal {
+            CutAxis::X
+        } else {
+            CutAxis::Y
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        self == CutAxis::X
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::orientation::Orientation;
+
+/// Direction the grain of a panel or stock sheet runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum GrainDirection {
+    /// No grain, the panel may be turned either way
+    #[default]
+    None,
+    /// Grain runs along the width
+    AlongWidth,
+    /// Grain runs along the height
+    AlongHeight,
+}
+
+impl GrainDirection {
+    /// Grain of the panel once turned 90 degrees
+    pub fn rotated(self) -> Self {
+        match self {
+            GrainDirection::None => GrainDirection::None,
+            GrainDirection::AlongWidth => GrainDirection::AlongHeight,
+            GrainDirection::AlongHeight => GrainDirection::AlongWidth,
+        }
+    }
+
+    /// True when a panel with `panel` grain may lie on a sheet with this grain, turned
+    /// 90 degrees when `is_rotated`: grained panels keep the grain of a grained sheet
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::grain_direction::GrainDirection::{self, AlongHeight, AlongWidth, None};
+    ///
+    /// // sheet, panel, allowed unrotated, allowed rotated
+    /// let combinations: [(GrainDirection, GrainDirection, bool, bool); 9] = [
+    ///     (None, None, true, true),
+    ///     (None, AlongWidth, true, true),
+    ///     (None, AlongHeight, true, true),
+    ///     (AlongWidth, None, true, true),
+    ///     (AlongWidth, AlongWidth, true, false),
+    ///     (AlongWidth, AlongHeight, false, true),
+    ///     (AlongHeight, None, true, true),
+    ///     (AlongHeight, AlongWidth, false, true),
+    ///     (AlongHeight, AlongHeight, true, false),
+    /// ];
+    /// for (sheet, panel, unrotated, rotated) in combinations {
+    ///     assert_eq!(sheet.allows(panel, false), unrotated, "{:?} sheet, {:?} panel", sheet, panel);
+    ///     assert_eq!(sheet.allows(panel, true), rotated, "{:?} sheet, {:?} panel turned", sheet, panel);
+    /// }
+    /// ```
+    pub fn allows(self, panel: GrainDirection, is_rotated: bool) -> bool {
+        let panel = if is_rotated { panel.rotated() } else { panel };
+        self == GrainDirection::None || panel == GrainDirection::None || self == panel
+    }
+}
+
+impl From<Orientation> for GrainDirection {
+    fn from(orientation: Orientation) -> Self {
+        match orientation {
+            Orientation::Default => GrainDirection::None,
+            Orientation::Landscape => GrainDirection::AlongWidth,
+            Orientation::Portrait => GrainDirection::AlongHeight,
+        }
+    }
+}
+
+impl From<GrainDirection> for Orientation {
+    fn from(grain: GrainDirection) -> Self {
+        match grain {
+            GrainDirection::None => Orientation::Default,
+            GrainDirection::AlongWidth => Orientation::Landscape,
+            GrainDirection::AlongHeight => Orientation::Portrait,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+/// Unit of the request dimensions, the engine works in millimeters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LengthUnit {
+    #[serde(rename = "mm")]
+    Millimeter,
+    #[serde(rename = "cm")]
+    Centimeter,
+    #[serde(rename = "m")]
+    Meter,
+    #[serde(rename = "in")]
+    Inch,
+    #[serde(rename = "ft")]
+    Foot,
+}
+
+impl LengthUnit {
+    pub fn get_millimeters(&self) -> f64 {
+        match self {
+            LengthUnit::Millimeter => 1.0,
+            LengthUnit::Centimeter => 10.0,
+            LengthUnit::Meter => 1000.0,
+            LengthUnit::Inch => 25.4,
+            LengthUnit::Foot => 304.8,
+        }
+    }
+
+    /// Value in millimeters, rounded to hundredths so the scale factor of the task stays small
+    pub fn to_millimeters(&self, value: f64) -> f64 {
+        (value * self.get_millimeters() * 100.0).round() / 100.0
+    }
+
+    /// Symbol of the unit, as in the request JSON
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::length_unit::LengthUnit;
+    ///
+    /// let unit = LengthUnit::from_symbol("in").unwrap();
+    /// assert_eq!((unit, unit.get_symbol()), (LengthUnit::Inch, "in"));
+    /// assert!(LengthUnit::from_symbol("yd").is_none());
+    /// ```
+    pub fn get_symbol(&self) -> &'static str {
+        match self {
+            LengthUnit::Millimeter => "mm",
+            LengthUnit::Centimeter => "cm",
+            LengthUnit::Meter => "m",
+            LengthUnit::Inch => "in",
+            LengthUnit::Foot => "ft",
+        }
+    }
+
+    /// Unit of `symbol`, see `get_symbol`
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        [LengthUnit::Millimeter, LengthUnit::Centimeter, LengthUnit::Meter, LengthUnit::Inch, LengthUnit::Foot]
+            .into_iter()
+            .find(|unit| unit.get_symbol() == symbol)
+    }
+}
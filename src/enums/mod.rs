@@ -4,4 +4,12 @@ pub mod status_code;
 pub mod optimization_level;
 pub mod optimization_priority;
 pub mod cut_orientation_preference;
+pub mod length_unit;
 
+pub mod search_strategy;
+pub mod stock_selection;
+pub mod grain_direction;
+pub mod coordinate_system;
+pub mod panel_shape;
+pub mod cut_axis;
+pub mod stock_pricing;
@@ -15,6 +15,19 @@ use std::fmt;
       /// Priority != 0: Focuses on minimizing cutting operations
       /// Order: Most tiles → Least number of cuts → Least wasted area
       CuttingEfficiency,
+
+      /// Priority 2: Keeps the waste on as few sheets as possible, ideally one big
+      /// reusable offcut on the last sheet instead of thin strips on every sheet
+      /// Order: Most tiles → Least number of sheets → Most concentrated waste → Least wasted area → Least number of cuts
+      WasteConcentration,
+
+      /// Priority 3: Prefers leftovers in one large reusable rectangle over many small pieces
+      /// Order: Most tiles → Least wasted area → Least fragmentation → Least number of cuts
+      LeastFragmentation,
+
+      /// Priority 4: Prefers first-stage cuts that run across the whole sheet, easier on a panel saw
+      /// Order: Most tiles → Least wasted area → Most through cuts → Least number of cuts
+      MostThroughCuts,
   }
 
   impl OptimizationPriority {
@@ -23,6 +36,9 @@ use std::fmt;
           match self {
               OptimizationPriority::MaterialEfficiency => 0,
               OptimizationPriority::CuttingEfficiency => 1,
+              OptimizationPriority::WasteConcentration => 2,
+              OptimizationPriority::LeastFragmentation => 3,
+              OptimizationPriority::MostThroughCuts => 4,
           }
       }
 
@@ -0,0 +1,50 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+/// Outline of a panel. Other shapes than `Rect` are placed by their bounding box,
+/// the response reports the true shape and counts the rest of the box as waste
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum PanelShape {
+    #[default]
+    Rect,
+    /// Rectangle with the four corners rounded by `radius`
+    RoundedRect { radius: f64 },
+    /// Circle of `diameter`, its bounding box is a square of that size
+    Circle { diameter: f64 },
+}
+
+impl PanelShape {
+    pub fn is_rect(&self) -> bool {
+        *self == PanelShape::Rect
+    }
+
+    /// Area of the shape inside its `width` x `height` bounding box
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::panel_shape::PanelShape;
+    ///
+    /// assert_eq!(PanelShape::Rect.get_area(600.0, 400.0), 240000.0);
+    /// let table_top = PanelShape::Circle { diameter: 1000.0 };
+    /// assert!((table_top.get_area(1000.0, 1000.0) - 785398.16).abs() < 0.01);
+    /// ```
+    pub fn get_area(&self, width: f64, height: f64) -> f64 {
+        match *self {
+            PanelShape::Rect => width * height,
+            PanelShape::RoundedRect { radius } => width * height - (4.0 - PI) * radius * radius,
+            PanelShape::Circle { diameter } => PI * diameter * diameter / 4.0,
+        }
+    }
+
+    /// Same shape with its lengths multiplied by `factor`
+    pub fn scaled(&self, factor: f64) -> Self {
+        match *self {
+            PanelShape::Rect => PanelShape::Rect,
+            PanelShape::RoundedRect { radius } => PanelShape::RoundedRect { radius: radius * factor },
+            PanelShape::Circle { diameter } => PanelShape::Circle {
+                diameter: diameter * factor,
+            },
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// How much of the search space the optimizer explores
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum SearchStrategy {
+    /// Every permutation of the panel groups, the regular search
+    #[default]
+    Full,
+
+    /// A single first-fit-decreasing pass by area is done first: its layout becomes the
+    /// solution of `get_task_status` and is reported with `TaskEventKind::PreviewReady`,
+    /// then the full search continues on the same task. `submit_task` returns only once the
+    /// full search is over, the preview reaches callers subscribed to the events or polling
+    /// the status from another thread
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::search_strategy::SearchStrategy;
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    /// use rezalnyas_core::features::engine::model::task_event::TaskEventKind;
+    ///
+    /// let service = CutListOptimizerServiceImpl::new(1, false).unwrap();
+    /// let events = service.subscribe();
+    /// let mut request = CalculationRequestBuilder::new()
+    ///     .add_panel(400.0, 600.0)
+    ///     .add_panel(300.0, 200.0).count(3)
+    ///     .add_stock(1000.0, 600.0)
+    ///     .build()
+    ///     .unwrap();
+    /// request.configuration.search_strategy = SearchStrategy::FastPreview;
+    ///
+    /// std::thread::scope(|scope| {
+    ///     scope.spawn(|| service.submit_task(request).unwrap());
+    ///     let preview = events
+    ///         .iter()
+    ///         .find(|event| matches!(event.kind, TaskEventKind::PreviewReady { .. }))
+    ///         .unwrap();
+    ///     assert!(service.get_task_status(&preview.task_id).unwrap().solution.is_some());
+    /// });
+    /// ```
+    FastPreview,
+}
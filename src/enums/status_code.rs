@@ -1,4 +1,20 @@
+use std::fmt;
 
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Result code of a submission, serialized as the legacy numeric string ("0", "1", ...)
+///
+/// ```rust
+/// use rezalnyas_core::enums::status_code::StatusCode;
+/// use rezalnyas_core::features::engine::model::calculation_submission_result::CalculationSubmissionResult;
+///
+/// let result = CalculationSubmissionResult::with_status_code(StatusCode::TooManyPanels);
+/// let json = serde_json::to_string(&result).unwrap();
+/// assert_eq!(json, r#"{"status_code":"5","task_id":null}"#);
+///
+/// let parsed: CalculationSubmissionResult = serde_json::from_str(&json).unwrap();
+/// assert_eq!(parsed.status_code, Some(StatusCode::TooManyPanels));
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StatusCode {
     Ok = 0,
@@ -11,6 +27,16 @@ pub enum StatusCode {
 }
 
 impl StatusCode {
+    const ALL: [StatusCode; 7] = [
+        StatusCode::Ok,
+        StatusCode::InvalidTiles,
+        StatusCode::InvalidStockTiles,
+        StatusCode::TaskAlreadyRunning,
+        StatusCode::ServerUnavailable,
+        StatusCode::TooManyPanels,
+        StatusCode::TooManyStockPanels,
+    ];
+
     pub fn value(&self) -> i32 {
         *self as i32
     }
@@ -18,4 +44,53 @@ impl StatusCode {
     pub fn string_value(&self) -> String {
         self.value().to_string()
     }
+
+    pub fn from_value(value: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|code| code.value() == value)
+    }
+
+    pub fn from_string_value(value: &str) -> Option<Self> {
+        value.trim().parse().ok().and_then(Self::from_value)
+    }
+}
+
+impl Serialize for StatusCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.string_value())
+    }
+}
+
+impl<'de> Deserialize<'de> for StatusCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StatusCodeVisitor;
+
+        impl de::Visitor<'_> for StatusCodeVisitor {
+            type Value = StatusCode;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a status code from 0 to 6 as a string or a number")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<StatusCode, E> {
+                StatusCode::from_string_value(value)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<StatusCode, E> {
+                i32::try_from(value)
+                    .ok()
+                    .and_then(StatusCode::from_value)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Signed(value), &self))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<StatusCode, E> {
+                i32::try_from(value)
+                    .ok()
+                    .and_then(StatusCode::from_value)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(StatusCodeVisitor)
+    }
 }
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::scaled_math::{Money, RoundingMode, ScaledError, ScaledNumber};
+
+/// How a stock price is charged, see `Configuration::cost_per_sheet` and `Panel::price`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum StockPricing {
+    /// The price of one used sheet, whatever its size
+    #[default]
+    PerSheet,
+    /// The price of one square meter of the used sheets
+    PerSquareMeter,
+}
+
+impl StockPricing {
+    /// Cost of one used sheet of `square_meters` at `price`, rounded to the precision of `price`
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::stock_pricing::StockPricing;
+    /// use rezalnyas_core::scaled_math::{Money, ScaledNumber};
+    ///
+    /// let area = ScaledNumber::from_str("5.75", 2).unwrap();
+    /// let sheet_price = Money::from_str("40", "EUR").unwrap();
+    /// let meter_price = Money::from_str("8", "EUR").unwrap();
+    /// assert_eq!(StockPricing::PerSheet.get_sheet_cost(&sheet_price, area).unwrap().to_string(), "40.00 EUR");
+    /// assert_eq!(StockPricing::PerSquareMeter.get_sheet_cost(&meter_price, area).unwrap().to_string(), "46.00 EUR");
+    /// ```
+    pub fn get_sheet_cost(self, price: &Money, square_meters: ScaledNumber) -> Result<Money, ScaledError> {
+        match self {
+            StockPricing::PerSheet => Ok(price.clone()),
+            StockPricing::PerSquareMeter => price.checked_mul(square_meters, RoundingMode::HalfEven),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// Built-in ways to pick the stock sheets each layout is tried on, see `StockSelectionStrategy`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum StockSelection {
+    /// Every stock sheet on its own, then the sheets in request order adding one at a time
+    #[default]
+    EachSheet,
+    /// Combinations of sheets that cover the panels, smallest total area first
+    Exhaustive,
+    /// The biggest sheets first, adding the next biggest one at a time
+    LargestFirst,
+    /// Combinations of sheets that cover the panels, fewest sheets first
+    CostBased,
+    /// Sheets of one size only, smallest total area first
+    SingleSheetSize,
+}
@@ -30,6 +30,26 @@ pub enum AppError {
     Stock(StockError),
 }
 
+impl AppError {
+    /// Category of the error for clients, stable across messages and locales
+    ///
+    /// ```rust
+    /// use rezalnyas_core::errors::{AppError, CoreError};
+    ///
+    /// let error: AppError = CoreError::InvalidInput { details: "bad".to_string() }.into();
+    /// assert_eq!(error.code(), "core");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Core(_) => "core",
+            Self::Task(_) => "task",
+            Self::Computation(_) => "computation",
+            Self::Service(_) => "service",
+            Self::Stock(_) => "stock",
+        }
+    }
+}
+
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -21,6 +21,23 @@ pub enum ComputationError {
     CandidateSearch {
         message: String,
     },
+    /// A dimension or setting of the request is not a number
+    InvalidNumber {
+        field: String,
+        value: String,
+    },
+    /// The request asks for something the geometry doesn't allow
+    ConstraintViolation {
+        message: String,
+    },
+    Timeout {
+        elapsed_ms: u64,
+    },
+    /// A cut list thread panicked, its layouts were dropped
+    WorkerPanicked {
+        thread: String,
+        message: String,
+    },
 }
 
 impl fmt::Display for ComputationError {
@@ -36,6 +53,16 @@ impl fmt::Display for ComputationError {
             }
             Self::NodeCopy { message } => write!(f, "Node copying error: {}", message),
             Self::CandidateSearch { message } => write!(f, "Candidate search error: {}", message),
+            Self::InvalidNumber { field, value } => {
+                write!(f, "Invalid number in {}: '{}'", field, value)
+            }
+            Self::ConstraintViolation { message } => write!(f, "Constraint violation: {}", message),
+            Self::Timeout { elapsed_ms } => {
+                write!(f, "Computation timed out after {} ms", elapsed_ms)
+            }
+            Self::WorkerPanicked { thread, message } => {
+                write!(f, "Worker {} panicked: {}", thread, message)
+            }
         }
     }
 }
@@ -30,7 +30,7 @@ pub use stock_errors::StockError;
 2. __`task_errors.rs`__ - Ошибки управления задачами, жизненного цикла и выполнения:
 
    - `TaskNotFound`, `TaskInvalidId`
-   - `TaskExecution`, `TaskCancelled`, `TaskTimeout`
+   - `TaskExecution`, `TaskCancelled`, `TaskTimeout`, `Stalled`
    - `TaskInvalidState`, `TaskInvalidStatusTransition`
    - `TaskMissingClientInfo`, `TaskThreadTerminated`
    - `TaskThreadSync`, `TaskThreadError`
@@ -12,6 +12,11 @@ pub enum TaskError {
     TaskExecution(String), // Simplified from tokio::task::JoinError
     TaskCancelled,
     TaskTimeout,
+    /// No progress for longer than `PerformanceThresholds::stall_timeout_ms`
+    Stalled {
+        task_id: String,
+        idle_ms: u64,
+    },
     TaskInvalidState {
         current_state: String,
     },
@@ -47,6 +52,9 @@ impl fmt::Display for TaskError {
             Self::TaskExecution(details) => write!(f, "Task execution error: {}", details),
             Self::TaskCancelled => write!(f, "Task was cancelled"),
             Self::TaskTimeout => write!(f, "Task timeout exceeded"),
+            Self::Stalled { task_id, idle_ms } => {
+                write!(f, "Task {} made no progress for {} ms", task_id, idle_ms)
+            }
             Self::TaskInvalidState { current_state } => {
                 write!(f, "Task is in invalid state: {}", current_state)
             }
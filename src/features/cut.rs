@@ -1,6 +1,7 @@
 
 /// Рез на листе
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cut {
     pub x1: i32,
     pub y1: i32,
@@ -0,0 +1,45 @@
+use crate::features::engine::model::calculation_request::CalculationRequest;
+use crate::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+
+/// Panel counts of the canned requests, see `benches/optimizer.rs`
+pub const PANEL_COUNTS: [usize; 3] = [10, 100, 1000];
+/// Stock sizes of the canned requests
+pub const STOCK_SIZE_COUNTS: [usize; 3] = [1, 3, 10];
+
+/// Same request for the same arguments on every machine: `nbr_panels` panels of pseudo-random
+/// sizes in rows of up to 5 copies, and `nbr_stock_sizes` stock sizes with enough sheets for all
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::bench_requests::canned_request;
+///
+/// let request = canned_request(100, 3);
+/// assert_eq!(request.panels.iter().map(|panel| panel.count).sum::<u32>(), 100);
+/// assert_eq!(request.stock_panels.len(), 3);
+/// assert_eq!(request.panels[0].width, canned_request(100, 3).panels[0].width);
+/// ```
+pub fn canned_request(nbr_panels: usize, nbr_stock_sizes: usize) -> CalculationRequest {
+    // Линейный конгруэнтный генератор: размеры не зависят от платформы и версии зависимостей
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |from: u64, to: u64| {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        from + (seed >> 33) % (to - from + 1)
+    };
+
+    let mut builder = CalculationRequestBuilder::new();
+    let mut nbr_left = nbr_panels;
+    let mut total_area = 0;
+    while nbr_left > 0 {
+        let count = (next(1, 5) as usize).min(nbr_left);
+        let (width, height) = (next(100, 1200), next(100, 800));
+        total_area += width * height * count as u64;
+        builder = builder.add_panel(width as f64, height as f64).count(count as u32).done();
+        nbr_left -= count;
+    }
+
+    for index in 0..nbr_stock_sizes.max(1) {
+        let (width, height) = (2800 - 100 * index as u64, 2070 - 50 * index as u64);
+        let nbr_sheets = total_area / (width * height) + 1;
+        builder = builder.add_stock(width as f64, height as f64).count(nbr_sheets as u32).done();
+    }
+    builder.build().expect("canned requests are valid")
+}
@@ -1,9 +1,14 @@
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
 
-use crate::features::engine::model::solution::Solution;
+use crate::constants::EngineConstants;
+use crate::features::engine::model::cost_summary::{CostSummary, StockPrice};
+use crate::features::engine::model::{configuration::Configuration, solution::Solution};
+use crate::scaled_math::{Money, RoundingMode, ScaledError, ScaledNumber};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OptimizationPriority {
     MostTiles,
     LeastWastedArea,
@@ -14,6 +19,54 @@ pub enum OptimizationPriority {
     LeastNbrMosaics,
     LeastNbrUnusedTiles,
     MostUnusedPanelArea,
+    MostConcentratedWaste,
+    LeastCost,
+    LeastMosaicsOverPartsCap,
+    /// Higher `Solution::get_stock_priority` first, right after the wasted area: between
+    /// layouts of equal waste the one cut from offcuts and old sheets wins, and a layout
+    /// opening more priority sheets than needed loses on waste
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::comparator::{PriorityListFactory, SolutionComparator};
+    /// use rezalnyas_core::features::engine::model::{calculation_response::Mosaic, solution::Solution};
+    /// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+    /// use std::cmp::Ordering;
+    ///
+    /// // 400x1000 parts, `parts_per_sheet[i]` of them on a 1000x1000 sheet of priority `priority`
+    /// let layout = |priority: i32, parts_per_sheet: &[usize]| {
+    ///     let mut solution = Solution::default();
+    ///     for &nbr_parts in parts_per_sheet {
+    ///         let stock = TileDimensions::new(0, 1000, 1000, false, "", "");
+    ///         let mut mosaic = Mosaic::from_tile_dimensions(&stock);
+    ///         mosaic.stock_priority = priority;
+    ///         let mut root = mosaic.root_tile_node[0].clone();
+    ///         root.split(true, 400 * nbr_parts as i32, 0);
+    ///         let parts = root.child1.as_mut().unwrap();
+    ///         if nbr_parts == 2 {
+    ///             parts.split(true, 400, 0);
+    ///             parts.child1.as_mut().unwrap().set_final_tile(true);
+    ///             parts.child2.as_mut().unwrap().set_final_tile(true);
+    ///         } else {
+    ///             parts.set_final_tile(true);
+    ///         }
+    ///         mosaic.set_root_tile_node(root);
+    ///         solution.add_mosaic(mosaic);
+    ///     }
+    ///     solution
+    /// };
+    /// let comparator = SolutionComparator::new(PriorityListFactory::get_final_solution_prioritized_comparator_list(0));
+    ///
+    /// let one_offcut = layout(1, &[2]);
+    /// let two_offcuts = layout(1, &[1, 1]);
+    /// assert_eq!(one_offcut.get_stock_priority(), two_offcuts.get_stock_priority());
+    /// assert_eq!(comparator.compare(&one_offcut, &two_offcuts), Ordering::Less);
+    ///
+    /// let new_sheet = layout(0, &[2]);
+    /// assert_eq!(comparator.compare(&one_offcut, &new_sheet), Ordering::Less);
+    /// ```
+    MostPriorityStock,
+    LeastFragmentation,
+    MostThroughCuts,
 }
 
 impl OptimizationPriority {
@@ -31,6 +84,12 @@ impl OptimizationPriority {
             OptimizationPriority::LeastNbrMosaics => "LEAST_NBR_MOSAICS",
             OptimizationPriority::LeastNbrUnusedTiles => "LEAST_NBR_UNUSED_TILES",
             OptimizationPriority::MostUnusedPanelArea => "MOST_UNUSED_PANEL_AREA",
+            OptimizationPriority::MostConcentratedWaste => "MOST_CONCENTRATED_WASTE",
+            OptimizationPriority::LeastCost => "LEAST_COST",
+            OptimizationPriority::LeastMosaicsOverPartsCap => "LEAST_MOSAICS_OVER_PARTS_CAP",
+            OptimizationPriority::MostPriorityStock => "MOST_PRIORITY_STOCK",
+            OptimizationPriority::LeastFragmentation => "LEAST_FRAGMENTATION",
+            OptimizationPriority::MostThroughCuts => "MOST_THROUGH_CUTS",
         }
     }
 }
@@ -39,6 +98,36 @@ pub struct PriorityListFactory;
 
 impl PriorityListFactory {
     /// Java: getFinalSolutionPrioritizedComparatorList
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::comparator::{PriorityListFactory, SolutionComparator};
+    /// use rezalnyas_core::features::engine::model::{calculation_response::Mosaic, solution::Solution};
+    /// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+    /// use std::cmp::Ordering;
+    ///
+    /// // One part of `width` x 1000 on each sheet of `sheet_widths` x 1000
+    /// let layout = |sheet_widths: [u32; 2], widths: [i32; 2]| {
+    ///     let mut solution = Solution::default();
+    ///     for (sheet_width, width) in sheet_widths.into_iter().zip(widths) {
+    ///         let stock = TileDimensions::new(0, sheet_width, 1000, false, "", "");
+    ///         let mut mosaic = Mosaic::from_tile_dimensions(&stock);
+    ///         let mut root = mosaic.root_tile_node[0].clone();
+    ///         root.split(true, width, 0);
+    ///         root.child1.as_mut().unwrap().set_final_tile(true);
+    ///         mosaic.set_root_tile_node(root);
+    ///         solution.add_mosaic(mosaic);
+    ///     }
+    ///     solution
+    /// };
+    /// // 400x1000 left on both sheets against 100x1000 and 800x1000
+    /// let spread = layout([1000, 1000], [600, 600]);
+    /// let concentrated = layout([1000, 1100], [900, 300]);
+    ///
+    /// let least_waste = SolutionComparator::new(PriorityListFactory::get_final_solution_prioritized_comparator_list(0));
+    /// assert_eq!(least_waste.compare(&spread, &concentrated), Ordering::Less);
+    /// let waste_concentration = SolutionComparator::new(PriorityListFactory::get_final_solution_prioritized_comparator_list(2));
+    /// assert_eq!(waste_concentration.compare(&concentrated, &spread), Ordering::Less);
+    /// ```
     pub fn get_final_solution_prioritized_comparator_list(
         optimization_priority: i32,
     ) -> Vec<OptimizationPriority> {
@@ -48,12 +137,37 @@ impl PriorityListFactory {
             priorities.push(OptimizationPriority::MostTiles);
             priorities.push(OptimizationPriority::LeastWastedArea);
             priorities.push(OptimizationPriority::LeastNbrCuts);
+        } else if optimization_priority == 2 {
+            // Waste concentration ahead of the wasted area, or it only breaks exact ties;
+            // the sheet count first keeps a barely used extra sheet from counting as concentrated
+            priorities.push(OptimizationPriority::MostTiles);
+            priorities.push(OptimizationPriority::LeastNbrMosaics);
+            priorities.push(OptimizationPriority::MostConcentratedWaste);
+            priorities.push(OptimizationPriority::LeastWastedArea);
+            priorities.push(OptimizationPriority::LeastNbrCuts);
+        } else if optimization_priority == 3 {
+            priorities.push(OptimizationPriority::MostTiles);
+            priorities.push(OptimizationPriority::LeastWastedArea);
+            priorities.push(OptimizationPriority::LeastFragmentation);
+            priorities.push(OptimizationPriority::LeastNbrCuts);
+        } else if optimization_priority == 4 {
+            priorities.push(OptimizationPriority::MostTiles);
+            priorities.push(OptimizationPriority::LeastWastedArea);
+            priorities.push(OptimizationPriority::MostThroughCuts);
+            priorities.push(OptimizationPriority::LeastNbrCuts);
         } else {
             priorities.push(OptimizationPriority::MostTiles);
             priorities.push(OptimizationPriority::LeastNbrCuts);
             priorities.push(OptimizationPriority::LeastWastedArea);
         }
 
+        // Приоритетные листы (обрезки, старые партии) решают при равных отходах
+        if let Some(index) = priorities
+            .iter()
+            .position(|priority| matches!(priority, OptimizationPriority::LeastWastedArea))
+        {
+            priorities.insert(index + 1, OptimizationPriority::MostPriorityStock);
+        }
         priorities.push(OptimizationPriority::LeastNbrMosaics);
         priorities.push(OptimizationPriority::BiggestUnusedTileArea);
         priorities.push(OptimizationPriority::MostHvDiscrepancy);
@@ -64,11 +178,105 @@ impl PriorityListFactory {
 
 pub struct SolutionComparator {
     priorities: Vec<OptimizationPriority>,
+    cost_per_cut: Money,
+    /// Price of the sheets without one in `stock_prices`, see `Configuration::cost_per_sheet`
+    stock_price: Option<StockPrice>,
+    /// Internal id of a stock tile -> price of its stock row
+    stock_prices: HashMap<u32, StockPrice>,
+    /// Scale factor of the task, engine lengths over millimeters
+    factor: u32,
+    max_parts_per_sheet: Option<u32>,
 }
 
 impl SolutionComparator {
     pub fn new(priorities: Vec<OptimizationPriority>) -> Self {
-        Self { priorities }
+        Self {
+            priorities,
+            cost_per_cut: Money::zero(""),
+            stock_price: None,
+            stock_prices: HashMap::new(),
+            factor: 1,
+            max_parts_per_sheet: None,
+        }
+    }
+
+    /// Scale factor of the task the solutions belong to, stock priced per m² is charged on
+    /// the sheet areas in millimeters
+    pub fn with_factor(mut self, factor: u32) -> Self {
+        self.factor = factor.max(1);
+        self
+    }
+
+    /// Prices of the stock tiles of the task by internal id, see `Panel::price`; with any of them
+    /// the cheapest layout wins right after the number of placed tiles
+    pub fn with_stock_prices(mut self, stock_prices: HashMap<u32, StockPrice>) -> Self {
+        if !stock_prices.is_empty() && !self.priorities.contains(&OptimizationPriority::LeastCost) {
+            let position = self
+                .priorities
+                .iter()
+                .position(|priority| *priority == OptimizationPriority::MostTiles)
+                .map_or(0, |index| index + 1);
+            self.priorities.insert(position, OptimizationPriority::LeastCost);
+        }
+        self.stock_prices = stock_prices;
+        self
+    }
+
+    /// Rank layouts with fewer sheets over `max_parts_per_sheet` panels first, before any other priority
+    pub fn with_max_parts_per_sheet(mut self, max_parts_per_sheet: Option<u32>) -> Self {
+        if max_parts_per_sheet.is_some() && self.max_parts_per_sheet.is_none() {
+            self.priorities.insert(0, OptimizationPriority::LeastMosaicsOverPartsCap);
+        }
+        self.max_parts_per_sheet = max_parts_per_sheet;
+        self
+    }
+
+    /// Final solution comparator of a configuration; with cut or sheet charges set
+    /// the cheapest layout wins right after the number of placed tiles
+    pub fn from_configuration(configuration: &Configuration) -> Self {
+        let mut priorities = PriorityListFactory::get_final_solution_prioritized_comparator_list(
+            configuration.optimization_priority.value() as i32,
+        );
+        if configuration.has_costs() {
+            priorities.insert(1, OptimizationPriority::LeastCost);
+        }
+        Self {
+            priorities,
+            cost_per_cut: configuration.get_cut_price().unwrap_or_else(|| Money::zero("")),
+            stock_price: configuration.get_stock_price(None),
+            stock_prices: HashMap::new(),
+            factor: 1,
+            max_parts_per_sheet: None,
+        }
+        .with_max_parts_per_sheet(configuration.max_parts_per_sheet)
+    }
+
+    /// Same total as `CostSummary::total_cost` of the solution, None when it is out of the range
+    /// of `Money`
+    fn get_cost(&self, solution: &Solution) -> Option<ScaledNumber> {
+        let factor = self.factor as i64;
+        let mm2_per_area = ScaledNumber::from_i64(factor * factor * EngineConstants::MM2_PER_M2 as i64, 0).ok()?;
+        let sheets = solution
+            .get_mosaics()
+            .iter()
+            .filter_map(|mosaic| mosaic.root_tile_node.first())
+            .map(|root| {
+                let stock_price = root
+                    .external_id
+                    .and_then(|id| self.stock_prices.get(&id))
+                    .or(self.stock_price.as_ref())
+                    .cloned();
+                let square_meters = ScaledNumber::from_i64(root.get_area() as i64, 0)?.checked_div_rounded(
+                    mm2_per_area,
+                    6,
+                    RoundingMode::HalfEven,
+                )?;
+                Ok((stock_price, square_meters))
+            })
+            .collect::<Result<Vec<_>, ScaledError>>()
+            .ok()?;
+        let cost = CostSummary::new(solution.get_nbr_cuts() as i64, &self.cost_per_cut, &sheets).ok()?;
+        Some(cost.total_cost.amount())
     }
 
     pub fn compare(&self, a: &Solution, b: &Solution) -> Ordering {
@@ -109,6 +317,45 @@ impl SolutionComparator {
                     let distinct_b = b.get_distict_tile_set();
                     distinct_a.cmp(&distinct_b) // ascending (smaller distinct tile set first)
                 }
+                OptimizationPriority::MostConcentratedWaste => {
+                    let concentration_a = a.get_waste_concentration();
+                    let concentration_b = b.get_waste_concentration();
+                    concentration_b
+                        .partial_cmp(&concentration_a)
+                        .unwrap_or(Ordering::Equal) // descending (waste on fewer sheets is better)
+                }
+                OptimizationPriority::LeastFragmentation => {
+                    let fragmentation_a = a.get_fragmentation_score();
+                    let fragmentation_b = b.get_fragmentation_score();
+                    fragmentation_a
+                        .partial_cmp(&fragmentation_b)
+                        .unwrap_or(Ordering::Equal) // ascending (one big leftover is better)
+                }
+                OptimizationPriority::MostThroughCuts => {
+                    let ratio_a = a.get_through_cut_ratio();
+                    let ratio_b = b.get_through_cut_ratio();
+                    ratio_b
+                        .partial_cmp(&ratio_a)
+                        .unwrap_or(Ordering::Equal) // descending (more cuts across the sheet is better)
+                }
+                OptimizationPriority::LeastCost => {
+                    let cost_a = self.get_cost(a);
+                    let cost_b = self.get_cost(b);
+                    cost_a.cmp(&cost_b) // ascending (cheaper is better)
+                }
+                OptimizationPriority::MostPriorityStock => {
+                    let priority_a = a.get_stock_priority();
+                    let priority_b = b.get_stock_priority();
+                    priority_b
+                        .partial_cmp(&priority_a)
+                        .unwrap_or(Ordering::Equal) // descending (panels cut from priority sheets is better)
+                }
+                OptimizationPriority::LeastMosaicsOverPartsCap => {
+                    let max_parts_per_sheet = self.max_parts_per_sheet.unwrap_or(u32::MAX);
+                    let over_a = a.get_nbr_mosaics_over_parts_cap(max_parts_per_sheet);
+                    let over_b = b.get_nbr_mosaics_over_parts_cap(max_parts_per_sheet);
+                    over_a.cmp(&over_b) // ascending (fewer overloaded sheets is better)
+                }
                 _ => {
                     // Placeholder for other comparators
                     a.id.cmp(&b.id)
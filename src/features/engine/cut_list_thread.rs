@@ -1,12 +1,18 @@
 use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::Xxh3;
 
+use crate::constants::EngineConstants;
+use crate::errors::{ComputationError, Result};
 use crate::features::engine::model::{
-    calculation_response::{Cut, Mosaic}, solution::Solution, status::Status, stock_solution::StockSolution, task::Task, tile_node::TileNode
+    calculation_response::{Cut, Mosaic}, solution::{Placement, Solution}, status::Status, stock_solution::StockSolution, task::Task, tile_node::TileNode
 };
 use crate::features::input::models::tile_dimensions::TileDimensions;
 use crate::enums::cut_orientation_preference::CutOrientationPreference;
-use crate::enums::orientation::Orientation;
+use crate::enums::grain_direction::GrainDirection;
+use crate::enums::optimization_priority::OptimizationPriority;
 use crate::features::engine::comparator::{PriorityListFactory, SolutionComparator};
+use crate::logging::log_debug;
+use crate::utils::clock::{Clock, SystemClock};
 
 #[derive(Clone,Debug, Serialize, Deserialize)]
 pub struct CutListThread {
@@ -25,8 +31,40 @@ pub struct CutListThread {
     pub status: Status,
     pub percentage_done: i32,
     pub min_trim_dimension: i32,
+    /// Sides differing by at most this count as equal, in the scaled units of the tiles
+    pub dimension_tolerance: i32,
+    /// Strip at the start of the first-stage cuts kept free of panels, in the scaled units of the tiles
+    pub lead_in_allowance: i32,
+    /// Guillotine stages the saw can do, None - unlimited
+    pub max_cut_stages: Option<u32>,
+    /// Parts one sheet may carry, None - unlimited
+    pub max_parts_per_sheet: Option<u32>,
+    /// Solutions kept per group of similar layouts, None or 0 - only exact duplicates are removed
+    pub max_similar_solutions: Option<u32>,
+    /// Share of common placements from which two layouts count as similar
+    pub similarity_threshold: f64,
+    /// Keep layouts with cuts across the whole sheet while pruning, see `OptimizationPriority::MostThroughCuts`
+    pub prefer_through_cuts: bool,
+    /// Memory the pool of partial solutions may take, see `TaskLimits::max_pool_memory_bytes`
+    pub max_pool_memory_bytes: Option<u64>,
+    /// Partial solutions dropped by the last run to stay within `max_pool_memory_bytes`
+    pub nbr_dropped_branches: u64,
+    /// Buffers of `compute_solutions`, kept when the thread is reused from a `CutListThreadPool`
+    #[serde(skip)]
+    scratch: Scratch,
 }
 
+/// Solution lists `compute_solutions` refills for every tile
+#[derive(Clone, Debug, Default)]
+struct Scratch {
+    solutions: Vec<Solution>,
+    new_solutions: Vec<Solution>,
+    solutions_to_remove: Vec<usize>,
+    modified_solutions: Vec<(usize, Solution)>,
+}
+
+const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.8;
+
 impl CutListThread {
     pub fn new() -> Self {
         Self {
@@ -45,6 +83,16 @@ impl CutListThread {
             status: Status::Queued,
             percentage_done: 0,
             min_trim_dimension: 0, // Will be overridden from configuration
+            dimension_tolerance: 0, // Will be overridden from the task
+            lead_in_allowance: 0, // Will be overridden from the task
+            max_cut_stages: None, // Will be overridden from configuration
+            max_parts_per_sheet: None, // Will be overridden from configuration
+            max_similar_solutions: None, // Will be overridden from configuration
+            similarity_threshold: DEFAULT_SIMILARITY_THRESHOLD,
+            prefer_through_cuts: false, // Will be overridden from configuration
+            max_pool_memory_bytes: None, // Will be overridden from the task
+            nbr_dropped_branches: 0,
+            scratch: Scratch::default(),
         }
     }
 
@@ -82,168 +130,379 @@ impl CutListThread {
             status: Status::Queued,
             percentage_done: 0,
             min_trim_dimension,
+            dimension_tolerance: 0,
+            lead_in_allowance: 0,
+            max_cut_stages: configuration.max_cut_stages,
+            max_parts_per_sheet: configuration.max_parts_per_sheet,
+            max_similar_solutions: configuration.max_similar_solutions,
+            similarity_threshold: configuration
+                .similarity_threshold
+                .unwrap_or(DEFAULT_SIMILARITY_THRESHOLD),
+            prefer_through_cuts: configuration.optimization_priority == OptimizationPriority::MostThroughCuts,
+            max_pool_memory_bytes: None,
+            nbr_dropped_branches: 0,
+            scratch: Scratch::default(),
         }
     }
 
-    pub fn execute(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("=== CUTLIST_THREAD_EXECUTE_START ===");
-        println!("INPUT_PARAMS: group={}, auxInfo={}, tilesCount={}, cutDirection={:?}, accuracyFactor={}, cutThickness={}, minTrimDimension={}", 
+    /// Make a finished thread ready for another combination as if it was created with
+    /// `new_with_config`, the allocations of its solution lists and tiles are kept
+    pub fn reset(
+        &mut self,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+        optimization_factor: i32,
+    ) {
+        self.recycle();
+        let all_solutions = std::mem::take(&mut self.all_solutions);
+        let solutions = std::mem::take(&mut self.solutions);
+        let tiles = std::mem::take(&mut self.tiles);
+        let scratch = std::mem::take(&mut self.scratch);
+        *self = Self::new_with_config(configuration, optimization_factor);
+        self.all_solutions = all_solutions;
+        self.solutions = solutions;
+        self.tiles = tiles;
+        self.scratch = scratch;
+    }
+
+    /// Drop the results, task and stock of the last run, keeping the allocations
+    pub fn recycle(&mut self) {
+        self.all_solutions.clear();
+        self.solutions.clear();
+        self.tiles.clear();
+        self.nbr_dropped_branches = 0;
+        self.task = None;
+        self.stock_solution = None;
+        self.scratch.solutions.clear();
+        self.scratch.new_solutions.clear();
+        self.scratch.solutions_to_remove.clear();
+        self.scratch.modified_solutions.clear();
+    }
+
+    /// `execute` with a panic of the thread turned into `ComputationError::WorkerPanicked`
+    pub fn execute_isolated(&mut self) -> Result<()> {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.execute())) {
+            Ok(result) => result,
+            Err(payload) => {
+                // Решения упавшего потока могут быть неполными, они отбрасываются
+                self.status = Status::Error;
+                self.all_solutions.clear();
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                Err(ComputationError::WorkerPanicked {
+                    thread: format!("{} {}", self.group, self.aux_info),
+                    message,
+                }
+                .into())
+            }
+        }
+    }
+
+    pub fn execute(&mut self) -> Result<()> {
+        log_debug!("=== CUTLIST_THREAD_EXECUTE_START ===");
+        log_debug!("INPUT_PARAMS: group={}, auxInfo={}, tilesCount={}, cutDirection={:?}, accuracyFactor={}, cutThickness={}, minTrimDimension={}", 
                  self.group, self.aux_info, self.tiles.len(), self.first_cut_orientation, 
                  self.accuracy_factor, self.cut_thickness, self.min_trim_dimension);
-        println!("ALGORITHM: Cut list thread processing with sequential tile placement");
+        log_debug!("ALGORITHM: Cut list thread processing with sequential tile placement");
 
         self.status = Status::Running;
-        self.start_time = Some(chrono::Utc::now().timestamp_millis());
-        println!("STEP_STATUS_CHANGE: Status.QUEUED -> Status.RUNNING");
-        println!("STEP_TIMER: startTime={}", self.start_time.unwrap_or(0));
+        self.start_time = Some(self.now_millis());
+        log_debug!("STEP_STATUS_CHANGE: Status.QUEUED -> Status.RUNNING");
+        log_debug!("STEP_TIMER: startTime={}", self.start_time.unwrap_or(0));
 
-        println!("STEP_COMPUTE: Calling computeSolutions()");
+        log_debug!("STEP_COMPUTE: Calling computeSolutions()");
         match self.compute_solutions() {
             Ok(_) => {
                 if self.status != Status::Terminated {
                     self.status = Status::Finished;
-                    println!("STEP_STATUS_CHANGE: Status.RUNNING -> Status.FINISHED");
+                    log_debug!("STEP_STATUS_CHANGE: Status.RUNNING -> Status.FINISHED");
                 } else {
-                    println!("STEP_STATUS_FINAL: Status remains Status.TERMINATED");
+                    log_debug!("STEP_STATUS_FINAL: Status remains Status.TERMINATED");
                 }
-                println!("=== CUTLIST_THREAD_EXECUTE_END: SUCCESS ===");
+                log_debug!("=== CUTLIST_THREAD_EXECUTE_END: SUCCESS ===");
                 Ok(())
             }
             Err(e) => {
-                println!("STEP_ERROR: Exception caught: {}", e);
+                log_debug!("STEP_ERROR: Exception caught: {}", e);
                 self.status = Status::Error;
-                println!("STEP_STATUS_CHANGE: Status.RUNNING -> Status.ERROR");
-                println!("=== CUTLIST_THREAD_EXECUTE_END: ERROR ===");
+                log_debug!("STEP_STATUS_CHANGE: Status.RUNNING -> Status.ERROR");
+                log_debug!("=== CUTLIST_THREAD_EXECUTE_END: ERROR ===");
                 Err(e)
             }
         }
     }
 
+    /// Java: public String getMaterial()
     pub fn get_material(&self) -> Option<String> {
-        if self.all_solutions.is_empty() {
-            None
-        } else {
-            Some("DEFAULT_MATERIAL".to_string()) // Simplified for now
-        }
+        self.all_solutions.first().and_then(|solution| solution.get_material())
     }
 
     pub fn get_elapsed_time_millis(&self) -> i64 {
         if let Some(start_time) = self.start_time {
-            chrono::Utc::now().timestamp_millis() - start_time
+            self.now_millis() - start_time
         } else {
             0
         }
     }
 
+    /// Milliseconds since UNIX epoch by the clock of the task
+    fn now_millis(&self) -> i64 {
+        self.task
+            .as_ref()
+            .map_or_else(|| SystemClock.now_millis(), |task| task.now_millis()) as i64
+    }
+
+    /// Drop solutions whose sheets have the same guillotine trees as an earlier one, returns
+    /// the number removed. Layouts are compared by a 128-bit hash of the trees, the pool of a
+    /// step keeps every distinct layout up to `accuracy_factor`, so the time of a step grows with
+    /// it; `remove_similar` keeps near-identical layouts of identical panels from filling the pool
     pub fn remove_duplicated(&self, solutions: &mut Vec<Solution>) -> usize {
         let original_len = solutions.len();
-        let mut unique_solutions = Vec::new();
-        let mut seen_signatures = std::collections::HashSet::new();
-        
-        for solution in solutions.iter() {
-            let mut signature = String::new();
+        let mut seen_signatures = std::collections::HashSet::with_capacity(solutions.len());
+
+        solutions.retain(|solution| {
+            // Java: str = str + it.next().getRootTileNode().toStringIdentifier();
+            let mut hasher = Xxh3::new();
             for mosaic in solution.get_mosaics() {
-                // Java: str = str + it.next().getRootTileNode().toStringIdentifier();
-          
+                for root in &mosaic.root_tile_node {
+                    root.hash_identifier(&mut hasher);
+                }
+                hasher.update(b"|");
+            }
+            seen_signatures.insert(hasher.digest128())
+        });
+
+        original_len - solutions.len()
+    }
+
+    /// Keep at most `max_similar_solutions` of each group of similar layouts.
+    /// Solutions must be sorted, the best ones of a group are kept and the first solution
+    /// of a group is compared with the rest.
+    ///
+    /// ```rust
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    ///
+    /// // Без ограничения похожих раскладок такой заказ считается минутами
+    /// let mut request = CalculationRequestBuilder::new()
+    ///     .add_panel(100.0, 100.0).count(60)
+    ///     .add_stock(1000.0, 1000.0).count(2)
+    ///     .build()
+    ///     .unwrap();
+    /// request.configuration.max_similar_solutions = Some(1);
+    /// let service = CutListOptimizerServiceImpl::new(1, false).unwrap();
+    /// let started = Instant::now();
+    /// let task_id = service.submit_task(request).unwrap().task_id.unwrap();
+    /// let response = service.get_task_status(&task_id).unwrap().solution.unwrap();
+    /// assert!(started.elapsed() < Duration::from_secs(30));
+    /// assert_eq!(response.mosaics.len(), 1);
+    /// assert!(response.no_fit_panels.is_empty());
+    /// ```
+    pub fn remove_similar(&self, solutions: &mut Vec<Solution>) -> usize {
+        let Some(max_similar_solutions) = self.max_similar_solutions.filter(|max| *max > 0) else {
+            return 0;
+        };
+        let original_len = solutions.len();
+        // Placements of the first solution of each group and the number of solutions kept in it
+        let mut groups: Vec<(Vec<Placement>, u32)> = Vec::new();
+
+        solutions.retain(|solution| {
+            let placements = solution.get_placements();
+            let group = groups.iter_mut().find(|(first, _)| {
+                Solution::get_placements_similarity(first, &placements) >= self.similarity_threshold
+            });
+            match group {
+                Some((_, kept)) if *kept >= max_similar_solutions => false,
+                Some((_, kept)) => {
+                    *kept += 1;
+                    true
+                }
+                None => {
+                    groups.push((placements, 1));
+                    true
+                }
             }
-            
-            if seen_signatures.insert(signature) {
-                unique_solutions.push(solution.clone());
+        });
+
+        original_len - solutions.len()
+    }
+
+    /// Drop branches once the pool nears `max_bytes`: first the ones whose yield can't come
+    /// within `EngineConstants::BRANCH_YIELD_EPSILON` of the best branch, then the lowest
+    /// ranked ones until the pool fits. `solutions` are sorted, the best branch is kept.
+    /// Returns the number of dropped branches
+    fn prune_to_memory_budget(solutions: &mut Vec<Solution>, remaining_area: i64, max_bytes: u64) -> u64 {
+        let pool_bytes: u64 = solutions.iter().map(|solution| solution.estimate_memory_bytes() as u64).sum();
+        if (pool_bytes as f64) < max_bytes as f64 * EngineConstants::MEMORY_PRESSURE_RATIO {
+            return 0;
+        }
+
+        let original_len = solutions.len();
+        let best_yield = solutions.first().map_or(0.0, Solution::get_yield);
+        let mut index = 0;
+        solutions.retain(|solution| {
+            index += 1;
+            index == 1
+                || solution.get_yield_upper_bound(remaining_area) >= best_yield - EngineConstants::BRANCH_YIELD_EPSILON
+        });
+
+        let mut pool_bytes: u64 = solutions.iter().map(|solution| solution.estimate_memory_bytes() as u64).sum();
+        while pool_bytes > max_bytes && solutions.len() > 1 {
+            if let Some(solution) = solutions.pop() {
+                pool_bytes -= solution.estimate_memory_bytes() as u64;
             }
         }
-        
-        *solutions = unique_solutions;
-        original_len - solutions.len()
+        (original_len - solutions.len()) as u64
     }
 
-    pub fn compute_solutions(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("\n=== COMPUTE_SOLUTIONS_START ===");
+    /// Place the tiles one by one on every solution of the pool. A tile that fits no sheet of a
+    /// solution goes to its no-fit panels, new sheets are taken from the unused stock of the solution
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::stock_selection::StockSelection;
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    /// use rezalnyas_core::features::engine::model::configuration::Configuration;
+    ///
+    /// let solve = |nbr_panels, nbr_sheets| {
+    ///     let configuration = Configuration { stock_selection: StockSelection::LargestFirst, ..Configuration::default() };
+    ///     let request = CalculationRequestBuilder::new()
+    ///         .configuration(configuration)
+    ///         .add_panel(900.0, 900.0).count(nbr_panels)
+    ///         .add_stock(1000.0, 1000.0).count(nbr_sheets)
+    ///         .build()
+    ///         .unwrap();
+    ///     let service = CutListOptimizerServiceImpl::new(1, false).unwrap();
+    ///     let task_id = service.submit_task(request).unwrap().task_id.unwrap();
+    ///     let response = service.get_task_status(&task_id).unwrap().solution.unwrap();
+    ///     let nbr_placed: i32 = response.mosaics.iter().flat_map(|mosaic| &mosaic.panels).map(|panel| panel.count).sum();
+    ///     let nbr_no_fit: i32 = response.no_fit_panels.iter().map(|panel| panel.count).sum();
+    ///     (nbr_placed, nbr_no_fit, response.mosaics.len())
+    /// };
+    ///
+    /// // Каждая деталь занимает отдельный лист
+    /// assert_eq!(solve(2, 2), (2, 0, 2));
+    /// // Третьей детали листа не хватает
+    /// assert_eq!(solve(3, 2), (2, 1, 2));
+    /// ```
+    pub fn compute_solutions(&mut self) -> Result<()> {
+        log_debug!("=== COMPUTE_SOLUTIONS_START ===");
+        let mut scratch = std::mem::take(&mut self.scratch);
         
         let stock_solution = if let Some(ref stock_solution) = self.stock_solution {
             stock_solution
         } else {
-            return Err("Stock solution is not available".into());
+            return Err(ComputationError::SolutionComputation {
+                message: "Stock solution is not available".to_string(),
+            }
+            .into());
         };
         
-        println!("INPUT_DATA: stockSolution=available, tilesCount={}, allSolutionsSize={}", 
+        log_debug!("INPUT_DATA: stockSolution=available, tilesCount={}, allSolutionsSize={}", 
                  self.tiles.len(), self.all_solutions.len());
-        println!("ALGORITHM: Sequential tile placement with solution branching and pruning");
+        log_debug!("ALGORITHM: Sequential tile placement with solution branching and pruning");
 
-        println!("\nSTEP_1_INIT: Creating initial solution set");
-        println!("STEP_1_CODE: List<Solution> arrayList = new ArrayList<>()");
-        let mut solutions = Vec::new();
-        println!("STEP_1_CODE: arrayList.add(new Solution(this.stockSolution))");
+        log_debug!("STEP_1_INIT: Creating initial solution set");
+        log_debug!("STEP_1_CODE: List<Solution> arrayList = new ArrayList<>()");
+        let mut solutions = std::mem::take(&mut scratch.solutions);
+        solutions.clear();
+        log_debug!("STEP_1_CODE: arrayList.add(new Solution(this.stockSolution))");
         
         // Java: arrayList.add(new Solution(this.stockSolution));
         let initial_solution = Solution::from_stock_solution(stock_solution);
         solutions.push(initial_solution);
         
-        println!("STEP_1_RESULT: Created {} initial solutions", solutions.len());
+        log_debug!("STEP_1_RESULT: Created {} initial solutions", solutions.len());
         if !solutions.is_empty() {
-            println!("STEP_1_DETAIL: First solution has {} mosaics", solutions[0].get_mosaics().len());
+            log_debug!("STEP_1_DETAIL: First solution has {} mosaics", solutions[0].get_mosaics().len());
         }
 
+        // Площадь деталей, которые ещё предстоит разместить после каждой
+        let mut remaining_areas: Vec<i64> = self
+            .tiles
+            .iter()
+            .rev()
+            .scan(0i64, |area, tile| {
+                let remaining = *area;
+                *area += tile.area() as i64;
+                Some(remaining)
+            })
+            .collect();
+        remaining_areas.reverse();
+        let mut nbr_dropped_branches = 0;
+
         if let Some(ref task) = self.task {
             if task.is_running() {
-                println!("\nSTEP_2_TASK_CHECK: task.isRunning()=true, processing {} tiles", self.tiles.len());
-                println!("STEP_2_ALGORITHM: For each tile, try to place it in all existing solutions");
+                log_debug!("STEP_2_TASK_CHECK: task.isRunning()=true, processing {} tiles", self.tiles.len());
+                log_debug!("STEP_2_ALGORITHM: For each tile, try to place it in all existing solutions");
 
                 for (i, tile_dimensions) in self.tiles.iter().enumerate() {
                     let tile_index = i + 1;
-                    println!("\n{}", "=".repeat(60));
-                    println!("TILE_PLACEMENT_{}_START: Processing tile {}/{}", tile_index, tile_index, self.tiles.len());
-                    println!("TILE_{}_INPUT: size={}x{}, id={}, material={}, isSquare={}", 
+                    log_debug!("TILE_PLACEMENT_{}_START: Processing tile {}/{}", tile_index, tile_index, self.tiles.len());
+                    log_debug!("TILE_{}_INPUT: size={}x{}, id={}, material={}, isSquare={}", 
                              tile_index, tile_dimensions.width, tile_dimensions.height, 
                              tile_dimensions.id, tile_dimensions.material, tile_dimensions.is_square());
-                    println!("TILE_{}_SOLUTIONS_BEFORE: {} solutions to try", tile_index, solutions.len());
+                    log_debug!("TILE_{}_SOLUTIONS_BEFORE: {} solutions to try", tile_index, solutions.len());
 
                     if i % 3 == 0 {
                         self.percentage_done = ((i as f32 / self.tiles.len() as f32) * 100.0) as i32;
                     }
 
                     // Java: ArrayList<Solution> newSolutions = new ArrayList();
-                    let mut new_solutions: Vec<Solution> = Vec::new();
+                    let new_solutions = &mut scratch.new_solutions;
+                    new_solutions.clear();
                     // Java: Iterator<Solution> solutionIterator = arrayList.iterator();
-                    let mut solutions_to_remove = Vec::new();
-                    // Java: boolean tileWasPlaced = false;
-                    let mut tile_was_placed = false;
-                    
+                    let solutions_to_remove = &mut scratch.solutions_to_remove;
+                    solutions_to_remove.clear();
                     // Collect solutions that need modification
-                    let mut modified_solutions = Vec::new();
+                    let modified_solutions = &mut scratch.modified_solutions;
+                    modified_solutions.clear();
                     
                     // Java: while (solutionIterator.hasNext())
                     for (solution_idx, current_solution) in solutions.iter().enumerate() {
                         // Java: Solution next2 = solutionIterator.next();
-                        // Clone the solution so we can mutate it during processing
+                        // Clone the solution so we can take new sheets from its unused stock
                         let mut working_solution = current_solution.clone();
                         
                         // Java: ListIterator<Mosaic> listIterator = next2.getMosaics().listIterator();
                         let mut mosaic_idx = 0;
-                        let mut can_fit_tile = false;
+                        let mut tile_was_placed = false;
                         
-                        // Java: Mosaic next3 = listIterator.next();
-                        // Java: while (true)
+                        // Каждый шаг либо берёт следующую мозаику, либо расходует лист, поэтому цикл конечен
                         loop {
-                            let mut next3_opt: Option<&Mosaic> = None;
-                            
-                            // Try to get current mosaic from existing mosaics first
-                            if mosaic_idx < working_solution.get_mosaics().len() {
-                                next3_opt = Some(&working_solution.get_mosaics()[mosaic_idx]);
-                                mosaic_idx += 1;
-                            }
-                            
-                            // Java: if (next3 == null) { canFitTile = true; break; }
-                            if next3_opt.is_none() {
-                                can_fit_tile = true;
-                                break;
+                            if mosaic_idx == working_solution.get_mosaics().len() {
+                                // Java: Iterator<TileDimensions> it2 = next2.getUnusedStockPanels().iterator();
+                                log_debug!("Мозаики закончились, ищем новый лист");
+                                let stock_idx = working_solution.get_unused_stock_panels().iter().position(|stock| {
+                                    stock.material == tile_dimensions.material
+                                        && stock.fits_with_tolerance(tile_dimensions, self.dimension_tolerance as u32)
+                                });
+                                let Some(stock_idx) = stock_idx else {
+                                    // Java: next3 = null; (no suitable stock found, exit loop)
+                                    break;
+                                };
+
+                                // Java: next2.getUnusedStockPanels().remove(currentTile);
+                                let unused_stock_panels = &mut working_solution.unused_stock_panels;
+                                let mut tail = unused_stock_panels.split_off(stock_idx);
+                                let suitable_stock = tail.pop_front().expect("stock index is within the list");
+                                unused_stock_panels.append(&mut tail);
+
+                                // Java: currentMosaic = new Mosaic(currentTile); listIterator.add(currentMosaic);
+                                working_solution.get_mosaics_mut().push(Mosaic::from_tile_dimensions(&suitable_stock));
                             }
-                            
-                            let next3 = next3_opt.unwrap();
+
+                            // Java: Mosaic next3 = listIterator.next();
+                            let next3 = &working_solution.get_mosaics()[mosaic_idx];
+                            mosaic_idx += 1;
                             
                             // Java: if (next3.getMaterial() != null && !next3.getMaterial().equals(tileDimensions.getMaterial()))
                             if let Some(ref mosaic_material) = next3.material {
                                 if mosaic_material != &tile_dimensions.material {
-                                    // Material mismatch - continue to next mosaic
                                     continue;
                                 }
                             }
@@ -251,13 +510,15 @@ impl CutListThread {
                             // Java: List<Mosaic> arrayList3 = new ArrayList<>();
                             let mut arrayList3 = Vec::new();
                             // Java: add(tileDimensions, next3, arrayList3);
-                            self.add_tile(tile_dimensions, next3, &mut arrayList3);
+                            // Заполненный до лимита лист пропускаем, деталь уходит на следующий
+                            if self.has_room_for_part(next3) {
+                                self.add_tile(tile_dimensions, next3, &mut arrayList3);
+                            }
                             
                             // Java: for (Mosaic mosaic2 : arrayList3) {
                             for mosaic2 in arrayList3.iter() {
                                 // Java: Solution solution = new Solution(next2, next3);
-                                let solution = Solution::from_solution_excluding_mosaic(&working_solution, next3);
-                                let mut solution = solution;
+                                let mut solution = Solution::from_solution_excluding_mosaic(&working_solution, next3);
                                 // Java: solution.addMosaic(mosaic2);
                                 solution.add_mosaic(mosaic2.clone());
                                 // Java: newSolutions.add(solution);
@@ -266,81 +527,35 @@ impl CutListThread {
                             
                             // Java: if (arrayList3.size() > 0) {
                             if !arrayList3.is_empty() {
-                                can_fit_tile = true;
                                 tile_was_placed = true;
-                                break; // Java: break from while (true)
-                            }
-                            
-                            // THE MISSING CRITICAL LOGIC FROM JAVA (lines 342-373)
-                            // Java: if (listIterator.hasNext()) { currentMosaic = listIterator.next(); next3 = currentMosaic; }
-                            if mosaic_idx < working_solution.get_mosaics().len() {
-                                // Continue to next mosaic - the loop will handle this
-                                println!("Переходим к следующей мозаике в решении");
-                                continue;
-                            } else {
-                                // Java: else { Iterator<TileDimensions> it2 = next2.getUnusedStockPanels().iterator(); ... }
-                                println!("Мозаики закончились, ищем новый лист");
-                                let mut current_tile: Option<crate::features::input::models::tile_dimensions::TileDimensions> = None;
-                                
-                                // Java: while (true) { if (it2.hasNext()) { currentTile = it2.next(); if (currentTile.fits(tileDimensions)) { break; } } else { currentTile = null; break; } }
-                                for unused_stock in working_solution.get_unused_stock_panels() {
-                                    println!("Проверяем неиспользованный лист: {}x{}", unused_stock.width, unused_stock.height);
-                                    if unused_stock.fits(tile_dimensions) {
-                                        println!("Лист подходит для панели");
-                                        current_tile = Some(unused_stock.clone());
-                                        break;
-                                    } else {
-                                        println!("Лист НЕ подходит для панели");
-                                    }
-                                }
-                                
-                                // Java: if (currentTile != null) { next2.getUnusedStockPanels().remove(currentTile); currentMosaic = new Mosaic(currentTile); listIterator.add(currentMosaic); next3 = currentMosaic; }
-                                if let Some(ref suitable_stock) = current_tile {
-                                    // Remove from unused stock panels - use Vec instead of LinkedList retain
-                                    let mut unused_panels: Vec<_> = working_solution.get_unused_stock_panels().iter().cloned().collect();
-                                    unused_panels.retain(|panel| !std::ptr::eq(panel as *const _, suitable_stock as *const _));
-                                    working_solution.unused_stock_panels = unused_panels.into_iter().collect();
-                                    
-                                    // Java: currentMosaic = new Mosaic(currentTile);
-                                    let current_mosaic = crate::features::engine::model::calculation_response::Mosaic::from_tile_dimensions(suitable_stock);
-                                    
-                                    // Java: listIterator.add(currentMosaic);
-                                    working_solution.get_mosaics_mut().push(current_mosaic);
-                                    
-                                    // Java: next3 = currentMosaic; (continue loop with new mosaic)
-                                    // The loop will continue and process this new mosaic
-                                } else {
-                                    // Java: next3 = null; (no suitable stock found, exit loop)
-                                    break;
-                                }
+                                break;
                             }
                         }
                         
-                        // Java: if (tileWasPlaced == canFitTile) { solutionIterator.remove(); } else { next2.getNoFitPanels().add(tileDimensions); }
-                        if tile_was_placed == can_fit_tile {
+                        if tile_was_placed {
+                            // Решение заменяют его продолжения с размещённой деталью
                             solutions_to_remove.push(solution_idx);
                         } else {
-                            // Java: next2.getNoFitPanels().add(tileDimensions);
-                            // Add tile to no-fit panels and keep the solution
-                            working_solution.get_no_fit_panels_mut().push(tile_dimensions.clone());
-                            // Store the modified solution
-                            modified_solutions.push((solution_idx, working_solution));
+                            // Деталь не влезла никуда: решение остаётся без взятого впустую листа
+                            let mut kept_solution = current_solution.clone();
+                            kept_solution.get_no_fit_panels_mut().push(tile_dimensions.clone());
+                            modified_solutions.push((solution_idx, kept_solution));
                         }
                     }
                     
                     // Apply modifications to solutions
-                    for (idx, modified_solution) in modified_solutions {
+                    for (idx, modified_solution) in modified_solutions.drain(..) {
                         solutions[idx] = modified_solution;
                     }
                     
                     // Java: for (Solution solution2 : newSolutions) { solution2.setCreatorThreadGroup(this.group); solution2.setAuxInfo(this.auxInfo); }
-                    for new_solution in &mut new_solutions {
+                    for new_solution in new_solutions.iter_mut() {
                         new_solution.set_creator_thread_group(self.group.clone());
                         new_solution.set_aux_info(self.aux_info.clone());
                     }
                     
                     // Java: arrayList.addAll(newSolutions);
-                    solutions.extend(new_solutions);
+                    solutions.append(new_solutions);
                     
                     // Remove solutions that were marked for removal (in reverse order to maintain indices)
                     for &solution_idx in solutions_to_remove.iter().rev() {
@@ -349,28 +564,34 @@ impl CutListThread {
                     
 
                     // Java: removeDuplicated(arrayList);
-                    let removed = self.remove_duplicated(&mut solutions);
+                    self.remove_duplicated(&mut solutions);
 
                     
                     // Java: sort(arrayList, this.threadPrioritizedComparators);
                     // Using the same sorting logic as the existing method
                     self.sort_solutions(&mut solutions);
+
+                    // Near-identical layouts would crowd out the rest of the pool
+                    self.remove_similar(&mut solutions);
+
+                    if let Some(max_bytes) = self.max_pool_memory_bytes {
+                        nbr_dropped_branches += Self::prune_to_memory_budget(&mut solutions, remaining_areas[i], max_bytes);
+                    }
                     
                     // Java: arrayList4.addAll(arrayList.subList(Math.min(arrayList.size() - 1, this.accuracyFactor), arrayList.size() - 1));
                     // Java: arrayList.removeAll(arrayList4);
                     if solutions.len() > self.accuracy_factor as usize {
-                        let before_prune = solutions.len();
                         solutions.truncate(self.accuracy_factor as usize);
-                    
                     }
                 }
                 
                 // Java: this.allSolutions.addAll(arrayList);
-                self.all_solutions.extend(solutions);
+                self.all_solutions.append(&mut solutions);
                 
                 // Java: sort(this.allSolutions, this.finalSolutionPrioritizedComparators);
                 let mut all_solutions = std::mem::take(&mut self.all_solutions);
                 self.sort_solutions(&mut all_solutions);
+                self.remove_similar(&mut all_solutions);
                 self.all_solutions = all_solutions;
                 
                 // Java: arrayList5.addAll(list.subList(Math.min(list.size() - 1, this.accuracyFactor), this.allSolutions.size() - 1));
@@ -381,197 +602,330 @@ impl CutListThread {
                 
                 // Note: Thread group rankings are incremented in the optimizer service after thread completion
                 // This matches the Java pattern where the service handles task updates
-                
-                // Java lines 405-410: Iterator<Mosaic> it3 = this.allSolutions.get(0).getMosaics().iterator();
-                // while (it3.hasNext()) { if (it3.next().getUsedArea() == 0) { it3.remove(); } }
-                if !self.all_solutions.is_empty() {
-                    for mosaic in self.all_solutions[0].get_mosaics_mut() {
-                        // Remove mosaics with no used area - simplified for now
-                    }
-                }
             }
         }
         
+        solutions.clear();
+        scratch.solutions = solutions;
+        self.scratch = scratch;
+        self.nbr_dropped_branches = nbr_dropped_branches;
         Ok(())
     }
 
     fn add_tile(&self, tile_dimensions: &TileDimensions, mosaic: &Mosaic, placement_options: &mut Vec<Mosaic>) {
-        println!("    ADD_METHOD_START: tile={}x{}, mosaic.orientation={}, tile.orientation={}, considerGrain={}", 
+        log_debug!("    ADD_METHOD_START: tile={}x{}, mosaic.orientation={}, tile.orientation={}, considerGrain={}", 
                  tile_dimensions.width, tile_dimensions.height, 
-                 0, // mosaic orientation simplified
+                 mosaic.orientation.to_numeric(),
                  tile_dimensions.orientation.to_numeric(), self.consider_grain_direction);
-        
-        if !self.consider_grain_direction || tile_dimensions.orientation == Orientation::Default {
-            println!("    ADD_BRANCH_1: No grain direction constraint, trying both orientations");
-            println!("    ADD_FIT_1: Trying original orientation {}x{}", tile_dimensions.width, tile_dimensions.height);
+
+        // Без учёта волокон деталь можно класть в обе стороны
+        let (sheet_grain, panel_grain) = if self.consider_grain_direction {
+            (GrainDirection::from(mosaic.orientation), GrainDirection::from(tile_dimensions.orientation))
+        } else {
+            (GrainDirection::None, GrainDirection::None)
+        };
+
+        if sheet_grain == GrainDirection::None || panel_grain == GrainDirection::None {
+            log_debug!("    ADD_BRANCH_1: No grain direction constraint, trying both orientations");
+        } else {
+            log_debug!("    ADD_BRANCH_2: Grain direction constraint active");
+        }
+
+        if sheet_grain.allows(panel_grain, false) {
+            log_debug!("    ADD_FIT_1: Trying original orientation {}x{}", tile_dimensions.width, tile_dimensions.height);
             self.fit_tile(tile_dimensions, mosaic, placement_options, self.cut_thickness);
-            
-            if tile_dimensions.is_square() {
-                println!("    ADD_SQUARE: Tile is square, no need to rotate");
-                return;
-            }
-            println!("    ADD_FIT_2: Trying rotated orientation {}x{}", tile_dimensions.height, tile_dimensions.width);
+        }
+
+        if tile_dimensions.is_square() && sheet_grain.allows(panel_grain, false) {
+            log_debug!("    ADD_SQUARE: Tile is square, no need to rotate");
+            return;
+        }
+        if sheet_grain.allows(panel_grain, true) {
+            log_debug!("    ADD_FIT_2: Trying rotated orientation {}x{}", tile_dimensions.height, tile_dimensions.width);
             let rotated_tile = tile_dimensions.rotate_90();
             self.fit_tile(&rotated_tile, mosaic, placement_options, self.cut_thickness);
-        } else {
-            println!("    ADD_BRANCH_2: Grain direction constraint active");
-            let tile_to_use = if Orientation::Default != tile_dimensions.orientation { // Simplified grain logic
-                println!("    ADD_ROTATE: Orientations differ, rotating tile");
-                tile_dimensions.rotate_90()
-            } else {
-                println!("    ADD_NO_ROTATE: Orientations match, using original tile");
-                tile_dimensions.clone()
-            };
-            println!("    ADD_FIT_GRAIN: Fitting with grain constraint");
-            self.fit_tile(&tile_to_use, mosaic, placement_options, self.cut_thickness);
         }
     }
 
-    fn fit_tile(&self, tile_dimensions: &TileDimensions, mosaic: &Mosaic, placement_options: &mut Vec<Mosaic>, cut_thickness: i32) {}
-
-    fn find_candidates(&self, tile_width: i32, tile_height: i32, tile_node: &TileNode, candidates: &mut Vec<TileNode>) {
-        // Java: if (tileNode == null || tileNode.isFinal() || tileNode.getWidth() < i || tileNode.getHeight() < i2)
-        if tile_node.is_final || tile_node.get_width() < tile_width || tile_node.get_height() < tile_height {
+    fn fit_tile(&self, tile_dimensions: &TileDimensions, mosaic: &Mosaic, placement_options: &mut Vec<Mosaic>, cut_thickness: i32) {
+        let root = if let Some(root) = mosaic.root_tile_node.first() {
+            root
+        } else {
             return;
-        }
-        
-        // Java: if (tileNode.getChild1() == null && tileNode.getChild2() == null)
-        if tile_node.child1.is_none() && tile_node.child2.is_none() {
-            // Java: boolean tileWasPlaced = false; if (tileNode.getWidth() == i || tileNode.getWidth() >= this.minTrimDimension + i)
-            let width_fits = if tile_node.get_width() == tile_width {
-                true
-            } else if tile_node.get_width() >= self.min_trim_dimension + tile_width {
-                true
-            } else {
-                if tile_node.get_width() > tile_width {
-                    // Java: this.task.setMinTrimDimensionInfluenced(true);
-                    // For now skip this
-                }
-                false
+        };
+
+        // Java: findCandidates(tileDimensions.getWidth(), tileDimensions.getHeight(), mosaic.getRootTileNode(), arrayList);
+        // Кандидаты ищутся по id в дереве и в его копиях через индекс листа
+        let index = mosaic.get_node_index();
+        let candidates = self.find_candidates(tile_dimensions.width as i32, tile_dimensions.height as i32, root);
+
+        for candidate_id in candidates {
+            let Some(candidate) = index.get(root, candidate_id) else {
+                continue;
             };
-            
-            // Java: if (tileNode.getHeight() == i2 || tileNode.getHeight() >= this.minTrimDimension + i2)
-            let height_fits = if tile_node.get_height() == tile_height {
-                true
-            } else if tile_node.get_height() >= self.min_trim_dimension + tile_height {
-                true
-            } else {
-                if tile_node.get_height() > tile_height {
-                    // Java: this.task.setMinTrimDimensionInfluenced(true);
-                    // For now skip this
+            // Зона прижимов зависит от направления первых резов, у нетронутого листа - от ветки
+            let offsets_hv = Self::get_edge_clearance_offsets(
+                tile_dimensions,
+                candidate,
+                root,
+                cut_thickness,
+                self.get_lead_in_offsets(candidate, root, true),
+            );
+            let offsets_vh = Self::get_edge_clearance_offsets(
+                tile_dimensions,
+                candidate,
+                root,
+                cut_thickness,
+                self.get_lead_in_offsets(candidate, root, false),
+            );
+            let is_offset = offsets_hv != Some((0, 0)) || offsets_vh != Some((0, 0));
+
+            // Java: if (tileNode.getWidth() == tileDimensions.getWidth() && tileNode.getHeight() == tileDimensions.getHeight())
+            if !is_offset
+                && self.is_same_size(candidate.get_width(), tile_dimensions.width as i32)
+                && self.is_same_size(candidate.get_height(), tile_dimensions.height as i32)
+            {
+                let mut root_copy = self.copy_tile_node(root, candidate_id);
+                if let Some(found) = index.get_mut(&mut root_copy, candidate_id) {
+                    found.set_external_id(Some(tile_dimensions.id));
+                    found.set_final_tile(true);
+                    found.set_rotated(tile_dimensions.is_rotated);
                 }
-                false
-            };
-            
-            // Java: if (canFitTile && tileWasPlaced) { list.add(tileNode); return; }
-            if width_fits && height_fits {
-                candidates.push(tile_node.clone());
+                placement_options.push(Self::mosaic_with_split(mosaic, root_copy, candidate_id));
                 return;
             }
-            return;
+
+            // Java: if (this.firstCutOrientation == CutDirection.BOTH || this.firstCutOrientation == CutDirection.HORIZONTAL)
+            if matches!(self.first_cut_orientation, CutOrientationPreference::Both | CutOrientationPreference::Horizontal) {
+                if let Some((left, top)) = offsets_hv {
+                    let mut root_copy = self.copy_tile_node(root, candidate_id);
+                    if let Some(found) = index.get_mut(&mut root_copy, candidate_id) {
+                        let (piece, mut cuts) = self.split_first_stage(found, true, tile_dimensions, left, top, cut_thickness);
+                        let (target, strip_cuts) = self.split_edge_clearance(piece, left, top, cut_thickness);
+                        cuts.extend(strip_cuts);
+                        cuts.extend(self.split_to_tile(target, tile_dimensions, true, cut_thickness));
+                        if self.is_within_max_cut_stages(&cuts) {
+                            placement_options.push(Self::mosaic_with_split(mosaic, root_copy, candidate_id));
+                        }
+                    }
+                }
+            }
+
+            // Java: if (this.firstCutOrientation == CutDirection.BOTH || this.firstCutOrientation == CutDirection.VERTICAL)
+            if matches!(self.first_cut_orientation, CutOrientationPreference::Both | CutOrientationPreference::Vertical) {
+                if let Some((left, top)) = offsets_vh {
+                    let mut root_copy = self.copy_tile_node(root, candidate_id);
+                    if let Some(found) = index.get_mut(&mut root_copy, candidate_id) {
+                        let (piece, mut cuts) = self.split_first_stage(found, false, tile_dimensions, left, top, cut_thickness);
+                        let (target, strip_cuts) = self.split_edge_clearance(piece, left, top, cut_thickness);
+                        cuts.extend(strip_cuts);
+                        cuts.extend(self.split_to_tile(target, tile_dimensions, false, cut_thickness));
+                        if self.is_within_max_cut_stages(&cuts) {
+                            placement_options.push(Self::mosaic_with_split(mosaic, root_copy, candidate_id));
+                        }
+                    }
+                }
+            }
         }
-        
-        // Java: if (tileNode.getChild1() != null) { findCandidates(i, i2, tileNode.getChild1(), list); }
-        if let Some(ref child1) = tile_node.child1 {
-            self.find_candidates(tile_width, tile_height, child1, candidates);
+    }
+
+    /// Lead-in strip `node` overlaps as the left and top offsets of a tile in it. The first cuts
+    /// of the sheet across its width (`is_horizontal`) start at the top edge, across the height at
+    /// the left edge; an uncut sheet gets the orientation of the branch that places the tile
+    fn get_lead_in_offsets(&self, node: &TileNode, root: &TileNode, is_horizontal: bool) -> (i32, i32) {
+        if self.lead_in_allowance == 0 {
+            return (0, 0);
         }
-        
-        // Java: if (tileNode.getChild2() != null) { findCandidates(i, i2, tileNode.getChild2(), list); }
-        if let Some(ref child2) = tile_node.child2 {
-            self.find_candidates(tile_width, tile_height, child2, candidates);
+        let is_horizontal = root.child1.as_ref().and_then(|child| child.cut_horizontal).unwrap_or(is_horizontal);
+        if is_horizontal {
+            (0, (root.y1 + self.lead_in_allowance - node.y1).max(0))
+        } else {
+            ((root.x1 + self.lead_in_allowance - node.x1).max(0), 0)
+        }
+    }
+
+    /// With a lead-in allowance the first cut of an uncut sheet comes before the strips, so the
+    /// lead-in strip is cut off inside the first-stage piece and the first stage keeps its
+    /// orientation. Returns the piece for the tile and the cut
+    fn split_first_stage<'a>(
+        &self,
+        node: &'a mut TileNode,
+        is_horizontal: bool,
+        tile_dimensions: &TileDimensions,
+        left: i32,
+        top: i32,
+        cut_thickness: i32,
+    ) -> (&'a mut TileNode, Vec<Cut>) {
+        // Только у самого листа нет направления реза
+        if self.lead_in_allowance == 0 || node.cut_horizontal.is_some() {
+            return (node, Vec::new());
+        }
+        let offset = |offset: i32| if offset > 0 { offset + cut_thickness } else { 0 };
+        let (size, length) = if is_horizontal {
+            (offset(left) + tile_dimensions.width as i32, node.get_width())
+        } else {
+            (offset(top) + tile_dimensions.height as i32, node.get_height())
+        };
+        if size >= length {
+            return (node, Vec::new());
+        }
+        match Self::split_node(node, is_horizontal, size, cut_thickness, None) {
+            Some(cut) => (node.child1.as_deref_mut().expect("split keeps the piece"), vec![cut]),
+            None => (node, Vec::new()),
+        }
+    }
+
+    /// Strips to cut off the left and top of `node` so the tile keeps its edge clearance
+    /// and the lead-in allowance `lead_in`, None if the tile can't keep them in this node
+    fn get_edge_clearance_offsets(
+        tile_dimensions: &TileDimensions,
+        node: &TileNode,
+        root: &TileNode,
+        cut_thickness: i32,
+        (lead_in_left, lead_in_top): (i32, i32),
+    ) -> Option<(i32, i32)> {
+        let clearance = tile_dimensions.edge_clearance as i32;
+        if clearance == 0 && lead_in_left == 0 && lead_in_top == 0 {
+            return Some((0, 0));
+        }
+
+        // Полоса у кромки отрезается вместе с пропилом и уходит в отход
+        let left = (root.x1 + clearance - node.x1).max(lead_in_left).max(0);
+        let top = (root.y1 + clearance - node.y1).max(lead_in_top).max(0);
+        let x2 = node.x1 + if left > 0 { left + cut_thickness } else { 0 } + tile_dimensions.width as i32;
+        let y2 = node.y1 + if top > 0 { top + cut_thickness } else { 0 } + tile_dimensions.height as i32;
+
+        (x2 <= node.x2.min(root.x2 - clearance) && y2 <= node.y2.min(root.y2 - clearance)).then_some((left, top))
+    }
+
+    /// Cut the clearance strips off `node`, returns the remaining node and the strip cuts
+    fn split_edge_clearance<'a>(
+        &self,
+        node: &'a mut TileNode,
+        left: i32,
+        top: i32,
+        cut_thickness: i32,
+    ) -> (&'a mut TileNode, Vec<Cut>) {
+        let mut cuts = Vec::new();
+        let mut target = node;
+        if left > 0 {
+            if let Some(cut) = Self::split_node(target, true, left, cut_thickness, None) {
+                cuts.push(cut);
+                target = target.child2.as_deref_mut().expect("split keeps the remainder");
+            }
+        }
+        if top > 0 {
+            if let Some(cut) = Self::split_node(target, false, top, cut_thickness, None) {
+                cuts.push(cut);
+                target = target.child2.as_deref_mut().expect("split keeps the remainder");
+            }
+        }
+        (target, cuts)
+    }
+
+    /// Java: new Mosaic(tileNode, mosaic.getMaterial()), the cuts are read back from the new tree
+    fn mosaic_with_split(mosaic: &Mosaic, root: TileNode, split_id: u32) -> Mosaic {
+        let mut new_mosaic = mosaic.clone();
+        new_mosaic.set_split_root_tile_node(root, split_id);
+        new_mosaic
+    }
+
+    /// Placements that need deeper guillotine stages than the saw supports are refused
+    fn is_within_max_cut_stages(&self, cuts: &[Cut]) -> bool {
+        match self.max_cut_stages {
+            Some(max_cut_stages) => cuts.iter().all(|cut| cut.stage <= max_cut_stages),
+            None => true,
         }
     }
 
-    fn copy_tile_node(&self, source: &TileNode, target: &TileNode) -> TileNode {
+    fn has_room_for_part(&self, mosaic: &Mosaic) -> bool {
+        match self.max_parts_per_sheet {
+            Some(max_parts_per_sheet) => (mosaic.get_nbr_final_tiles() as u32) < max_parts_per_sheet,
+            None => true,
+        }
+    }
+
+    /// Ids of the free nodes under `tile_node` the tile fits in, depth first
+    fn find_candidates(&self, tile_width: i32, tile_height: i32, tile_node: &TileNode) -> Vec<u32> {
+        // Java: if (tileNode.getWidth() == i || tileNode.getWidth() >= this.minTrimDimension + i)
+        // Остаток должен совпасть с деталью или оставить обрезок не меньше minTrimDimension
+        let fits = |free: i32, size: i32| {
+            self.is_same_size(free, size) || (free > size && free >= self.min_trim_dimension + size)
+        };
+        tile_node
+            .iter_free()
+            .filter(|node| fits(node.get_width(), tile_width) && fits(node.get_height(), tile_height))
+            .map(|node| node.id)
+            .collect()
+    }
+
+    /// Free space side `free` takes a panel side `size` without a cut, see `dimension_tolerance`
+    fn is_same_size(&self, free: i32, size: i32) -> bool {
+        (free - size).abs() <= self.dimension_tolerance
+    }
+
+    fn copy_tile_node(&self, source: &TileNode, target_id: u32) -> TileNode {
         // Java: TileNode tileNode3 = new TileNode(tileNode);
         let mut root_copy = TileNode::copy_node(source); // Use Java-style copy constructor
         // Java: copyChildren(tileNode, tileNode3, tileNode2);
-        self.copy_children(source, &mut root_copy, target);
+        self.copy_children(source, &mut root_copy, target_id);
         root_copy
     }
     
-    fn copy_children(&self, source: &TileNode, dest: &mut TileNode, target: &TileNode) {
+    fn copy_children(&self, source: &TileNode, dest: &mut TileNode, target_id: u32) {
         // Java: if (tileNode == tileNode3) { return; }
-        if source.id == target.id {
+        if source.id == target_id {
             return;
         }
         
         // Java: if (tileNode.getChild1() != null) { tileNode2.setChild1(new TileNode(tileNode.getChild1())); copyChildren(...); }
         if let Some(ref source_child1) = source.child1 {
             let mut child1_copy = source_child1.as_ref().clone();
-            self.copy_children(source_child1, &mut child1_copy, target);
-            dest.set_child1(Some(Box::new(child1_copy)));
+            self.copy_children(source_child1, &mut child1_copy, target_id);
+            dest.child1 = Some(Box::new(child1_copy));
         }
         
         // Java: if (tileNode.getChild2() != null) { tileNode2.setChild2(new TileNode(tileNode.getChild2())); copyChildren(...); }
         if let Some(ref source_child2) = source.child2 {
             let mut child2_copy = source_child2.as_ref().clone();
-            self.copy_children(source_child2, &mut child2_copy, target);
-            dest.set_child2(Some(Box::new(child2_copy)));
+            self.copy_children(source_child2, &mut child2_copy, target_id);
+            dest.child2 = Some(Box::new(child2_copy));
         }
     }
 
-    fn split_hv(&self, tile_node: &mut TileNode, tile_dimensions: &TileDimensions, cut_thickness: i32) -> Vec<Cut> {
+    /// Java: splitHV / splitVH, cut the panel out of the corner of `tile_node` with the cut
+    /// across the x axis first (`is_horizontal_first`) or across the y axis first
+    fn split_to_tile(
+        &self,
+        tile_node: &mut TileNode,
+        tile_dimensions: &TileDimensions,
+        is_horizontal_first: bool,
+        cut_thickness: i32,
+    ) -> Vec<Cut> {
         let mut cuts = Vec::new();
-        
-        // Java: if (tileNode.getWidth() > tileDimensions.getWidth())
-        if tile_node.get_width() > tile_dimensions.width as i32 {
-            // Java: arrayList.add(splitHorizontally(tileNode, tileDimensions.getWidth(), i));
-            if let Some(cut) = self.split_horizontally(tile_node, tile_dimensions.width as i32, cut_thickness, None) {
-                cuts.push(cut);
-            }
-            
-            // Java: if (tileNode.getHeight() > tileDimensions.getHeight())
-            if tile_node.get_height() > tile_dimensions.height as i32 {
-                // Java: arrayList.add(splitVertically(tileNode.getChild1(), tileDimensions.getHeight(), i, tileDimensions.getId()));
-                if let Some(ref mut child1) = tile_node.child1 {
-                    if let Some(cut) = self.split_vertically(child1, tile_dimensions.height as i32, cut_thickness, Some(tile_dimensions.id)) {
-                        cuts.push(cut);
-                    }
-                    // Java: tileNode.getChild1().getChild1().setFinal(true);
-                    if let Some(ref mut child1_child1) = child1.child1 {
-                        child1_child1.set_final_tile(true);
-                        child1_child1.set_rotated(tile_dimensions.is_rotated);
-                    }
-                }
-            } else {
-                // Java: tileNode.getChild1().setFinal(true);
-                if let Some(ref mut child1) = tile_node.child1 {
-                    child1.set_final_tile(true);
-                    child1.set_rotated(tile_dimensions.is_rotated);
-                    child1.set_external_id(Some(tile_dimensions.id));
-                }
-            }
-        } else {
-            // Java: arrayList.add(splitVertically(tileNode, tileDimensions.getHeight(), i, tileDimensions.getId()));
-            if let Some(cut) = self.split_vertically(tile_node, tile_dimensions.height as i32, cut_thickness, Some(tile_dimensions.id)) {
-                cuts.push(cut);
-            }
-            // Java: tileNode.getChild1().setFinal(true);
-            if let Some(ref mut child1) = tile_node.child1 {
-                child1.set_final_tile(true);
-                child1.set_rotated(tile_dimensions.is_rotated);
-            }
-        }
-        
-        cuts
-    }
+        let (width, height) = (tile_dimensions.width as i32, tile_dimensions.height as i32);
+        // Длина и размер детали вдоль первого и второго реза
+        let (first_size, second_size) = if is_horizontal_first { (width, height) } else { (height, width) };
+        let node_length = |node: &TileNode, is_horizontal: bool| {
+            if is_horizontal { node.get_width() } else { node.get_height() }
+        };
 
-    fn split_vh(&self, tile_node: &mut TileNode, tile_dimensions: &TileDimensions, cut_thickness: i32) -> Vec<Cut> {
-        let mut cuts = Vec::new();
-        
-        // Java: if (tileNode.getHeight() > tileDimensions.getHeight())
-        if tile_node.get_height() > tile_dimensions.height as i32 {
-            // Java: arrayList.add(splitVertically(tileNode, tileDimensions.getHeight(), i));
-            if let Some(cut) = self.split_vertically(tile_node, tile_dimensions.height as i32, cut_thickness, None) {
+        // Java: if (tileNode.getWidth() > tileDimensions.getWidth()), getHeight() for splitVH
+        if !self.is_same_size(node_length(tile_node, is_horizontal_first), first_size) {
+            if let Some(cut) = Self::split_node(tile_node, is_horizontal_first, first_size, cut_thickness, None) {
                 cuts.push(cut);
             }
-            
-            // Java: if (tileNode.getWidth() > tileDimensions.getWidth())
-            if tile_node.get_width() > tile_dimensions.width as i32 {
-                // Java: arrayList.add(splitHorizontally(tileNode.getChild1(), tileDimensions.getWidth(), i, tileDimensions.getId()));
+
+            if !self.is_same_size(node_length(tile_node, !is_horizontal_first), second_size) {
+                // Java: arrayList.add(splitVertically(tileNode.getChild1(), ...)), splitHorizontally for splitVH
                 if let Some(ref mut child1) = tile_node.child1 {
-                    if let Some(cut) = self.split_horizontally(child1, tile_dimensions.width as i32, cut_thickness, Some(tile_dimensions.id)) {
+                    if let Some(cut) = Self::split_node(
+                        child1,
+                        !is_horizontal_first,
+                        second_size,
+                        cut_thickness,
+                        Some(tile_dimensions.id),
+                    ) {
                         cuts.push(cut);
                     }
                     // Java: tileNode.getChild1().getChild1().setFinal(true);
@@ -589,8 +943,14 @@ impl CutListThread {
                 }
             }
         } else {
-            // Java: arrayList.add(splitHorizontally(tileNode, tileDimensions.getWidth(), i, tileDimensions.getId()));
-            if let Some(cut) = self.split_horizontally(tile_node, tile_dimensions.width as i32, cut_thickness, Some(tile_dimensions.id)) {
+            // Only the second cut is left, the node already has the first size
+            if let Some(cut) = Self::split_node(
+                tile_node,
+                !is_horizontal_first,
+                second_size,
+                cut_thickness,
+                Some(tile_dimensions.id),
+            ) {
                 cuts.push(cut);
             }
             // Java: tileNode.getChild1().setFinal(true);
@@ -599,110 +959,45 @@ impl CutListThread {
                 child1.set_rotated(tile_dimensions.is_rotated);
             }
         }
-        
+
         cuts
     }
 
-    fn split_horizontally(&self, tile_node: &mut TileNode, width: i32, cut_thickness: i32, external_id: Option<u32>) -> Option<Cut> {
-        // Java: if (tileNode == null) return null;
-        let original_width = tile_node.get_width();
-        let original_height = tile_node.get_height();
-        
-        // Java: TileNode tileNode2 = new TileNode(tileNode.getX1(), tileNode.getX1() + i, tileNode.getY1(), tileNode.getY2());
-        let mut child1 = TileNode::new(tile_node.x1, tile_node.x1 + width, tile_node.y1, tile_node.y2);
-        if let Some(id) = external_id {
-            child1.set_external_id(Some(id));
-        }
-        
-        // Java: if (tileNode2.getArea() > 0) tileNode.setChild1(tileNode2);
-        if child1.get_area() > 0 {
-            let child1_id = child1.id;
-            tile_node.set_child1(Some(Box::new(child1)));
-            
-            // Java: TileNode tileNode3 = new TileNode(tileNode.getX1() + i + i2, tileNode.getX2(), tileNode.getY1(), tileNode.getY2());
-            let child2 = TileNode::new(tile_node.x1 + width + cut_thickness, tile_node.x2, tile_node.y1, tile_node.y2);
-            
-            // Java: if (tileNode3.getArea() > 0) tileNode.setChild2(tileNode3);
-            if child2.get_area() > 0 {
-                let child2_id = child2.id;
-                tile_node.set_child2(Some(Box::new(child2)));
-                
-                // Java: return new Cut.Builder()...
-                return Some(Cut {
-                    x1: (tile_node.x1 + width) as f64,
-                    y1: tile_node.y1 as f64,
-                    x2: (tile_node.x1 + width) as f64,
-                    y2: tile_node.y2 as f64,
-                    cut_coord: width as f64,
-                    is_horizontal: true,
-                    original_tile_id: tile_node.id as i32,
-                    original_width: original_width as f64,
-                    original_height: original_height as f64,
-                    child1_tile_id: child1_id as i32,
-                    child2_tile_id: child2_id as i32,
-                });
-            }
-        }
-        
-        None
+    /// Java: splitHorizontally / splitVertically, a guillotine cut of `tile_node` at `size`
+    /// along the x axis (`is_horizontal`) or the y axis, child1 gets `external_id`.
+    /// None when nothing is cut off
+    fn split_node(
+        tile_node: &mut TileNode,
+        is_horizontal: bool,
+        size: i32,
+        cut_thickness: i32,
+        external_id: Option<u32>,
+    ) -> Option<Cut> {
+        let split = if is_horizontal {
+            tile_node.split_h(size, cut_thickness)
+        } else {
+            tile_node.split_v(size, cut_thickness)
+        };
+        let mut split = split.ok()?;
+        split.child1.set_external_id(external_id);
+        tile_node.set_split(split).ok().flatten()
     }
 
-    fn split_vertically(&self, tile_node: &mut TileNode, height: i32, cut_thickness: i32, external_id: Option<u32>) -> Option<Cut> {
-        // Java: if (tileNode == null) return null;
-        let original_width = tile_node.get_width();
-        let original_height = tile_node.get_height();
-        
-        // Java: TileNode tileNode2 = new TileNode(tileNode.getX1(), tileNode.getX2(), tileNode.getY1(), tileNode.getY1() + i);
-        let mut child1 = TileNode::new(tile_node.x1, tile_node.x2, tile_node.y1, tile_node.y1 + height);
-        if let Some(id) = external_id {
-            child1.set_external_id(Some(id));
-        }
-        
-        // Java: if (tileNode2.getArea() > 0) tileNode.setChild1(tileNode2);
-        if child1.get_area() > 0 {
-            let child1_id = child1.id;
-            tile_node.set_child1(Some(Box::new(child1)));
-            
-            // Java: TileNode tileNode3 = new TileNode(tileNode.getX1(), tileNode.getX2(), tileNode.getY1() + i + i2, tileNode.getY2());
-            let child2 = TileNode::new(tile_node.x1, tile_node.x2, tile_node.y1 + height + cut_thickness, tile_node.y2);
-            
-            // Java: if (tileNode3.getArea() > 0) tileNode.setChild2(tileNode3);
-            if child2.get_area() > 0 {
-                let child2_id = child2.id;
-                tile_node.set_child2(Some(Box::new(child2)));
-                
-                // Java: return new Cut.Builder()...
-                return Some(Cut {
-                    x1: tile_node.x1 as f64,
-                    y1: (tile_node.y1 + height) as f64,
-                    x2: tile_node.x2 as f64,
-                    y2: (tile_node.y1 + height) as f64,
-                    cut_coord: height as f64,
-                    is_horizontal: false,
-                    original_tile_id: tile_node.id as i32,
-                    original_width: original_width as f64,
-                    original_height: original_height as f64,
-                    child1_tile_id: child1_id as i32,
-                    child2_tile_id: child2_id as i32,
-                });
-            }
-        }
-        
-        None
-    }
-    
     /// Sort solutions using the same comparators as Java
-    fn sort_solutions(&self, solutions: &mut Vec<Solution>) {
+    fn sort_solutions(&self, solutions: &mut [Solution]) {
         
         // Java: использует threadPrioritizedComparators или finalSolutionPrioritizedComparators
-        // Для примера используем optimization_priority = 0 (AREA приоритет)
-        let priorities = PriorityListFactory::get_final_solution_prioritized_comparator_list(0);
-        let comparator = SolutionComparator::new(priorities);
-        
-        solutions.sort_by(|a, b| {
-            let result = comparator.compare(a, b);
-            result
-        });
-        
+        // Для примера используем optimization_priority = 0 (AREA приоритет),
+        // при MostThroughCuts отсев сохраняет раскрои со сквозными резами
+        let optimization_priority = if self.prefer_through_cuts {
+            OptimizationPriority::MostThroughCuts
+        } else {
+            OptimizationPriority::MaterialEfficiency
+        };
+        let priorities =
+            PriorityListFactory::get_final_solution_prioritized_comparator_list(optimization_priority.value() as i32);
+        let comparator = SolutionComparator::new(priorities).with_max_parts_per_sheet(self.max_parts_per_sheet);
+
+        solutions.sort_by(|a, b| comparator.compare(a, b));
     }
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+use crate::features::engine::cut_list_thread::CutListThread;
+use crate::features::engine::model::configuration::Configuration;
+
+/// Finished cut list threads kept for the next (permutation, stock, orientation) combination,
+/// so long runs reuse their solution lists instead of allocating new ones every time
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::cut_list_thread_pool::CutListThreadPool;
+/// use rezalnyas_core::features::engine::model::configuration::Configuration;
+///
+/// let configuration = Configuration::default();
+/// let mut pool = CutListThreadPool::new(4);
+///
+/// let mut thread = pool.acquire(&configuration, 100);
+/// thread.group = "AREA".to_string();
+/// pool.release(thread);
+///
+/// let thread = pool.acquire(&configuration, 200);
+/// assert_eq!((thread.group.as_str(), thread.accuracy_factor), ("", 200));
+/// assert_eq!((pool.get_nbr_created(), pool.get_nbr_reused()), (1, 1));
+/// ```
+#[derive(Debug, Default)]
+pub struct CutListThreadPool {
+    idle: Vec<CutListThread>,
+    capacity: usize,
+    nbr_created: usize,
+    nbr_reused: usize,
+}
+
+impl CutListThreadPool {
+    /// Pool keeping up to `capacity` idle threads, 0 disables the reuse
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Idle thread reset to `configuration`, a new one if none is left
+    pub fn acquire(&mut self, configuration: &Configuration, optimization_factor: i32) -> CutListThread {
+        match self.idle.pop() {
+            Some(mut cut_list_thread) => {
+                cut_list_thread.reset(configuration, optimization_factor);
+                self.nbr_reused += 1;
+                cut_list_thread
+            }
+            None => {
+                self.nbr_created += 1;
+                CutListThread::new_with_config(configuration, optimization_factor)
+            }
+        }
+    }
+
+    /// Take back a thread whose results were merged, dropped when the pool is full
+    pub fn release(&mut self, mut cut_list_thread: CutListThread) {
+        if self.idle.len() < self.capacity {
+            cut_list_thread.recycle();
+            self.idle.push(cut_list_thread);
+        }
+    }
+
+    pub fn get_nbr_created(&self) -> usize {
+        self.nbr_created
+    }
+
+    pub fn get_nbr_reused(&self) -> usize {
+        self.nbr_reused
+    }
+}
@@ -0,0 +1,38 @@
+use crate::errors::Result;
+use crate::features::engine::model::{
+    calculation_request::CalculationRequest, calculation_submission_result::CalculationSubmissionResult,
+    health_report::HealthReport, stats::Stats, task_status_response::TaskStatusResponse,
+};
+
+/// Java: public interface CutListOptimizerService
+///
+/// Object safe, applications can keep a `Box<dyn CutListOptimizerService>` and swap the
+/// service for `MockCutListOptimizerService` (feature `test-util`) in their tests
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::cutlist_optimizer_service::CutListOptimizerService;
+/// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+///
+/// let service: Box<dyn CutListOptimizerService> = Box::new(CutListOptimizerServiceImpl::new(2, false).unwrap());
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0)
+///     .add_stock(1000.0, 600.0)
+///     .build()
+///     .unwrap();
+/// let task_id = service.submit_task(request).unwrap().task_id.unwrap();
+/// assert!(service.get_task_status(&task_id).unwrap().solution.is_some());
+/// ```
+pub trait CutListOptimizerService: Send + Sync {
+    fn submit_task(&self, calculation_request: CalculationRequest) -> Result<CalculationSubmissionResult>;
+
+    fn get_task_status(&self, task_id: &str) -> Option<TaskStatusResponse>;
+
+    fn stop_task(&self, task_id: &str) -> bool;
+
+    fn get_stats(&self) -> Stats;
+
+    fn get_health(&self) -> HealthReport;
+
+    fn clear_result_cache(&self);
+}
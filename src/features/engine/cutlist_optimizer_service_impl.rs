@@ -1,21 +1,71 @@
-use crate::enums::cut_orientation_preference::CutOrientationPreference;
-use crate::errors::{AppError, CoreError, Result};
+use crate::enums::{
+    cut_orientation_preference::CutOrientationPreference, optimization_level::OptimizationFactor,
+    search_strategy::SearchStrategy, status_code::StatusCode,
+};
+use crate::constants::EngineConstants;
+use crate::errors::{AppError, ComputationError, CoreError, Result, ServiceError, TaskError};
+use crate::features::engine::comparator::SolutionComparator;
 use crate::features::engine::cut_list_thread::CutListThread;
+use crate::features::engine::cut_list_thread_pool::CutListThreadPool;
+use crate::features::engine::permutation_hash::remove_duplicated_permutations;
+use crate::features::engine::permutation_scheduler::PermutationScheduler;
+use crate::features::engine::cutlist_optimizer_service::CutListOptimizerService;
+use crate::features::engine::one_dimensional::optimize_one_dimensional;
+use crate::features::engine::grid_pattern::cut_grid;
+use crate::features::engine::panel_clustering::cluster_by_shape;
+use crate::features::engine::model::response_integrity::ResponseIntegrity;
+use crate::features::engine::response_cache::ResponseCache;
+use crate::features::engine::result_cache::ResultCache;
+use crate::features::engine::task_repository::TaskRepository;
+use crate::features::engine::offcut_store::OffcutStore;
 use crate::features::engine::model::{
-    calculation_request::CalculationRequest,
-    calculation_submission_result::CalculationSubmissionResult, status::Status,
+    calculation_request::{CalculationRequest, PinnedPlacement, SequenceAxis},
+    calculation_response::CalculationResponse,
+    calculation_submission_result::{CalculationSubmissionResult, SubmissionWarning},
+    health_report::HealthReport,
+    material_draw::MaterialDraw,
+    reproducibility_report::ReproducibilityReport,
+    performance_thresholds::PerformanceThresholds,
+    permutation_log::PermutationLog, pinned_sheet::PinnedSheet, sequence_block::SequenceBlock,
+    solution::Solution,
+    stats::Stats,
+    stock_fit_mask::StockFitMask,
+    task_checkpoint::TaskCheckpoint,
+    task_limits::TaskLimits,
+    status::Status,
     stock_panel_picker::StockPanelPicker, stock_solution::StockSolution, task::Task,
+    task_event::{TaskEvent, TaskEventKind},
+    task_status_response::{TaskErrorInfo, TaskStatusResponse},
 };
 use crate::features::input::models::{
-    grouped_tile_dimensions::GroupedTileDimensions, tile_dimensions::TileDimensions,
+    grouped_tile_dimensions::GroupedTileDimensions, tile_dimensions::{TileDimensions, TileDimensionsData},
 };
+use crate::features::input::normalizer::normalize_request;
+use crate::i18n::warning::Warning;
+use crate::logging::{log_debug, log_info, log_warn, AppLogger, LogLevel, LogRecord, TaskLogBuffer};
+use crate::scaled_math::PrecisionAnalyzer;
+use crate::utils::clock::{Clock, SystemClock};
 use chrono::{DateTime, Local};
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 // Global task ID counter (equivalent to Java AtomicLong taskIdCounter)
 static TASK_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// Permutation whose stock solutions are evaluated, one at a time or in windows,
+/// see `process_stock_solution` and `process_stock_window`
+struct StockWindowContext<'a> {
+    permutation: &'a [TileDimensions],
+    perm_index: usize,
+    material: &'a str,
+    configuration: &'a crate::features::engine::model::configuration::Configuration,
+    /// Solutions of the material before the permutation, see `should_process_stock_solution`
+    solutions_list: &'a [Solution],
+}
+
 #[derive(Debug)]
 pub struct CutListOptimizerServiceImpl {
     is_initialized: bool,
@@ -23,6 +73,20 @@ pub struct CutListOptimizerServiceImpl {
     allow_multiple_tasks_per_client: bool,
     tasks: HashMap<String, Status>,
     client_tasks: HashMap<String, Vec<String>>,
+    event_subscribers: Mutex<Vec<Sender<TaskEvent>>>,
+    result_cache: Mutex<ResultCache>,
+    response_cache: Mutex<ResponseCache>,
+    task_logs: Mutex<TaskLogBuffer>,
+    stats: Mutex<Stats>,
+    running_tasks: Mutex<HashMap<String, (Instant, Instant)>>, // task id -> start of its computation, last progress
+    task_limits: Mutex<TaskLimits>,
+    task_statuses: Mutex<HashMap<String, TaskStatusResponse>>, // task and sub-task id -> last status
+    stopped_tasks: Mutex<HashSet<String>>, // task and sub-task ids the client asked to stop
+    cut_list_thread_pool: Mutex<CutListThreadPool>, // finished threads reused for the next combinations
+    clock: Arc<dyn Clock>,
+    task_repository: Option<Arc<dyn TaskRepository>>, // checkpoints of running tasks, None - no checkpoints
+    signing_key: Option<Vec<u8>>, // HMAC key of the response hashes, None - hashes are not signed
+    offcut_store: Option<Arc<dyn OffcutStore>>, // leftovers offered to every request, None - not kept
 }
 
 impl CutListOptimizerServiceImpl {
@@ -40,28 +104,610 @@ impl CutListOptimizerServiceImpl {
             allow_multiple_tasks_per_client,
             tasks: HashMap::new(),
             client_tasks: HashMap::new(),
+            event_subscribers: Mutex::new(Vec::new()),
+            result_cache: Mutex::new(ResultCache::new(0)),
+            response_cache: Mutex::new(ResponseCache::default()),
+            task_logs: Mutex::new(TaskLogBuffer::new(TaskLogBuffer::DEFAULT_CAPACITY)),
+            stats: Mutex::new(Stats::new()),
+            running_tasks: Mutex::new(HashMap::new()),
+            task_limits: Mutex::new(TaskLimits::default()),
+            task_statuses: Mutex::new(HashMap::new()),
+            stopped_tasks: Mutex::new(HashSet::new()),
+            // Окно стоков запускает до трёх групп на каждый поток
+            cut_list_thread_pool: Mutex::new(CutListThreadPool::new(thread_count as usize * 3)),
+            clock: Arc::new(SystemClock),
+            task_repository: None,
+            signing_key: None,
+            offcut_store: None,
         };
 
         Ok(instance)
     }
 
+    /// Use `clock` for task ids, task deadlines, event timestamps and the response cache instead
+    /// of the system time
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Save checkpoints of the running tasks to `repository`, every
+    /// `PerformanceThresholds::checkpoint_interval_ms`, so they can be resumed with `resume_task`
+    pub fn with_task_repository(mut self, repository: Arc<dyn TaskRepository>) -> Self {
+        self.task_repository = Some(repository);
+        self
+    }
+
+    /// Sign the response hashes of `Configuration::include_content_hash` with `key`,
+    /// see `ResponseIntegrity::verify`
+    pub fn with_signing_key(mut self, key: &[u8]) -> Self {
+        self.signing_key = Some(key.to_vec());
+        self
+    }
+
+    /// Add the offcuts of `store` to the stock of every submitted request, take the cut ones
+    /// out of it and store the offcuts of every finished response
+    pub fn with_offcut_store(mut self, store: Arc<dyn OffcutStore>) -> Self {
+        self.offcut_store = Some(store);
+        self
+    }
+
     // -=1
     pub fn submit_task(
         &self,
-        calculation_request: CalculationRequest,
+        mut calculation_request: CalculationRequest,
     ) -> Result<CalculationSubmissionResult> {
+        // Остатки прошлых раскроев входят в склад до проверок и ключа кэша
+        let offcut_rows = match &self.offcut_store {
+            Some(store) => store.add_to_request(&mut calculation_request)?,
+            None => HashMap::new(),
+        };
+
+        // Без заготовок материала расчёт не начинается, клиент сразу узнаёт, что докупить
+        let materials_without_stock = Self::get_materials_without_stock(&calculation_request);
+        if !materials_without_stock.is_empty() {
+            let mut result = CalculationSubmissionResult::with_status_code(StatusCode::InvalidStockTiles);
+            result.warnings = materials_without_stock;
+            return Ok(result);
+        }
+
         // Generate new task ID (equivalent to Java lines 358-362)
         let new_task_id = self.generate_task_id();
+        self.emit_event(&new_task_id, TaskEventKind::Submitted);
+
+        // Повторно отправленный заказ отдаётся готовым ответом, без расчёта
+        let ttl_ms = calculation_request
+            .performance_thresholds
+            .response_cache_ttl_ms
+            .unwrap_or(0);
+        let cache_key = if ttl_ms > 0 {
+            ResponseCache::key(&calculation_request)
+        } else {
+            None
+        };
+        if let Some(key) = cache_key {
+            if self.reuse_cached_response(key, ttl_ms, &new_task_id) {
+                return Ok(CalculationSubmissionResult::new(StatusCode::Ok, new_task_id));
+            }
+        }
+
+        let result = self.run_task(calculation_request, &new_task_id, cache_key, None)?;
+        if let Some(store) = &self.offcut_store {
+            self.update_offcut_store(store.as_ref(), &new_task_id, &offcut_rows);
+        }
+        Ok(result)
+    }
+
+    /// Hand the finished response of `task_id` to `store`, a failing store only gets logged
+    fn update_offcut_store(&self, store: &dyn OffcutStore, task_id: &str, offcut_rows: &HashMap<u32, (String, u32)>) {
+        let Some(status) = self.get_task_status(task_id) else {
+            return;
+        };
+        let (Some(Status::Finished), Some(response)) = (status.status, &status.solution) else {
+            return;
+        };
+        match store.update_from_response(response, offcut_rows) {
+            Ok(nbr_stored) => self.log(task_id, LogLevel::Info, &format!("{} offcuts stored", nbr_stored)),
+            Err(e) => self.log(task_id, LogLevel::Warn, &format!("Offcuts not stored: {}", e)),
+        }
+    }
+
+    /// Compute `request` `nbr_runs` times at once, every run but the first with its panel and
+    /// stock rows shuffled by another seed, and report how much the results differ
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    ///
+    /// let service = CutListOptimizerServiceImpl::new(2, false).unwrap();
+    /// let request = CalculationRequestBuilder::new()
+    ///     .add_panel(400.0, 600.0)
+    ///     .add_panel(300.0, 200.0).count(2)
+    ///     .add_stock(1000.0, 600.0)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let report = service.check_reproducibility(&request, 3).unwrap();
+    /// assert_eq!(report.seeds, vec![0, 1, 2]);
+    /// assert_eq!(report.waste_percents.len(), 3);
+    /// assert!(report.min_waste_percent <= report.mean_waste_percent);
+    /// ```
+    pub fn check_reproducibility(&self, request: &CalculationRequest, nbr_runs: usize) -> Result<ReproducibilityReport> {
+        let seeds: Vec<u64> = (0..nbr_runs.max(1) as u64).collect();
+        let results: Vec<Result<CalculationResponse>> = std::thread::scope(|scope| {
+            let runs: Vec<_> = seeds
+                .iter()
+                .map(|&seed| {
+                    let mut request = request.clone();
+                    // Прогон должен считаться заново, а не браться из кэша ответов
+                    request.performance_thresholds.response_cache_ttl_ms = None;
+                    Self::shuffle_rows(&mut request, seed);
+                    scope.spawn(move || self.compute_run(request))
+                })
+                .collect();
+            runs.into_iter()
+                .map(|run| {
+                    run.join().unwrap_or_else(|_| {
+                        Err(CoreError::Internal {
+                            message: "reproducibility run panicked".to_string(),
+                        }
+                        .into())
+                    })
+                })
+                .collect()
+        });
+        let responses = results.into_iter().collect::<Result<Vec<_>>>()?;
+        Ok(ReproducibilityReport::new(seeds, &responses))
+    }
+
+    /// Response of `request` submitted as a task of its own
+    fn compute_run(&self, request: CalculationRequest) -> Result<CalculationResponse> {
+        let result = self.submit_task(request)?;
+        result
+            .task_id
+            .and_then(|task_id| self.get_task_status(&task_id))
+            .and_then(|status| status.solution)
+            .ok_or_else(|| {
+                CoreError::InvalidInput {
+                    details: format!("request was not computed, status code {:?}", result.status_code),
+                }
+                .into()
+            })
+    }
+
+    /// Shuffle the panel and stock rows of `request` by `seed`, 0 keeps them in place
+    fn shuffle_rows(request: &mut CalculationRequest, seed: u64) {
+        if seed == 0 {
+            return;
+        }
+        // Линейный конгруэнтный генератор, как в bench_requests: порядок зависит только от seed
+        let mut state = seed;
+        let mut next = |bound: usize| {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) % bound as u64) as usize
+        };
+        for rows in [&mut request.panels, &mut request.stock_panels] {
+            for index in (1..rows.len()).rev() {
+                rows.swap(index, next(index + 1));
+            }
+        }
+    }
+
+    /// Continue a stopped or crashed task from the last checkpoint in the task repository,
+    /// under the same id. `TaskNotFound` when there is no checkpoint of the task
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    /// use rezalnyas_core::features::engine::model::task_checkpoint::TaskCheckpoint;
+    /// use rezalnyas_core::features::engine::task_repository::{InMemoryTaskRepository, TaskRepository};
+    ///
+    /// let repository = Arc::new(InMemoryTaskRepository::new());
+    /// let service = CutListOptimizerServiceImpl::new(2, false)
+    ///     .unwrap()
+    ///     .with_task_repository(repository.clone());
+    /// assert!(service.resume_task("202601010000000").is_err());
+    ///
+    /// let request = CalculationRequestBuilder::new()
+    ///     .add_panel(400.0, 600.0)
+    ///     .add_stock(1000.0, 600.0)
+    ///     .build()
+    ///     .unwrap();
+    /// repository.save_checkpoint(&TaskCheckpoint::new("202601010000000", &request, 0)).unwrap();
+    ///
+    /// let task_id = service.resume_task("202601010000000").unwrap().task_id.unwrap();
+    /// assert_eq!(task_id, "202601010000000");
+    /// assert!(service.get_task_status(&task_id).unwrap().solution.is_some());
+    /// assert!(repository.load_checkpoint(&task_id).unwrap().is_none());
+    /// ```
+    pub fn resume_task(&self, task_id: &str) -> Result<CalculationSubmissionResult> {
+        let checkpoint = match &self.task_repository {
+            Some(repository) => repository.load_checkpoint(task_id)?,
+            None => None,
+        }
+        .ok_or_else(|| TaskError::TaskNotFound {
+            id: task_id.to_string(),
+        })?;
+        self.emit_event(task_id, TaskEventKind::Submitted);
+        self.log(
+            task_id,
+            LogLevel::Info,
+            &format!("Resuming from the checkpoint of {} materials", checkpoint.materials.len()),
+        );
+        self.run_task(checkpoint.calculation_request.clone(), task_id, None, Some(checkpoint))
+    }
+
+    /// Compute the submitted or resumed task and record its outcome
+    fn run_task(
+        &self,
+        calculation_request: CalculationRequest,
+        new_task_id: &str,
+        cache_key: Option<u64>,
+        checkpoint: Option<TaskCheckpoint>,
+    ) -> Result<CalculationSubmissionResult> {
+        let new_task_id = new_task_id.to_string();
+        self.track_task(&new_task_id, true);
+        let result = self.compute(calculation_request, &new_task_id, checkpoint);
+        self.track_task(&new_task_id, false);
+        if let Ok(mut stopped_tasks) = self.stopped_tasks.lock() {
+            stopped_tasks.retain(|id| id != &new_task_id && !Self::is_sub_task_of(id, &new_task_id));
+        }
+        if let Ok(mut stats) = self.stats.lock() {
+            match result {
+                Ok(_) => stats.nbr_finished_tasks += 1,
+                Err(_) => stats.nbr_error_tasks += 1,
+            }
+        }
+
+        // Остановленный клиентом расчёт неполон, его ответ не переиспользуется
+        let is_finished =
+            self.get_task_status(&new_task_id).and_then(|status| status.status) == Some(Status::Finished);
+        if let (Ok(response), Some(key), true) = (&result, cache_key, is_finished) {
+            if let Ok(mut cache) = self.response_cache.lock() {
+                cache.insert(key, response.clone(), self.clock.now_millis());
+            }
+        }
+
+        if let Err(e) = result {
+            if let Ok(mut statuses) = self.task_statuses.lock() {
+                let status = statuses.entry(new_task_id.clone()).or_default();
+                status.task_id = Some(new_task_id.clone());
+                status.set_status(Status::Error);
+                // Ошибка материала уже записана в compute_material вместе с материалом
+                if status.error.is_none() {
+                    status.error = Some(TaskErrorInfo::new(&e, None));
+                }
+            }
+            self.log(&new_task_id, LogLevel::Error, &e.to_string());
+            self.emit_event(
+                &new_task_id,
+                TaskEventKind::Error {
+                    message: e.to_string(),
+                },
+            );
+            return Err(e);
+        }
+        self.log(&new_task_id, LogLevel::Info, "Computation finished");
+        self.emit_event(&new_task_id, TaskEventKind::Finished);
+
+        Ok(CalculationSubmissionResult::new(StatusCode::Ok, new_task_id))
+    }
+
+    /// Subscribe to task lifecycle events of this service.
+    /// The subscription ends when the returned receiver is dropped.
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    /// use rezalnyas_core::features::engine::model::task_event::TaskEventKind;
+    /// use rezalnyas_core::utils::clock::ManualClock;
+    ///
+    /// let service = CutListOptimizerServiceImpl::new(1, false).unwrap().with_clock(Arc::new(ManualClock::new(1_000)));
+    /// let events = service.subscribe();
+    /// let request = CalculationRequestBuilder::new()
+    ///     .add_panel(100.0, 100.0)
+    ///     .add_stock(1000.0, 1000.0)
+    ///     .build()
+    ///     .unwrap();
+    /// service.submit_task(request).unwrap();
+    ///
+    /// let events: Vec<_> = events.try_iter().collect();
+    /// assert_eq!(events[0].kind, TaskEventKind::Submitted);
+    /// assert!(events.iter().all(|event| event.timestamp == 1_000));
+    /// ```
+    pub fn subscribe(&self) -> Receiver<TaskEvent> {
+        let (sender, receiver) = mpsc::channel();
+        if let Ok(mut subscribers) = self.event_subscribers.lock() {
+            subscribers.push(sender);
+        }
+        receiver
+    }
+
+    /// Buffered log records of a task at `min_level` or more severe, oldest first
+    pub fn get_task_logs(&self, task_id: &str, min_level: LogLevel) -> Vec<LogRecord> {
+        self.task_logs
+            .lock()
+            .map(|logs| logs.get(task_id, min_level))
+            .unwrap_or_default()
+    }
+
+    /// Log records kept per task, 0 disables the capture
+    pub fn set_task_log_capacity(&self, capacity: usize) {
+        if let Ok(mut logs) = self.task_logs.lock() {
+            logs.set_capacity(capacity);
+        }
+    }
+
+    /// Maximum threads and time of a task, the limits of each request are capped by them
+    pub fn set_task_limits(&self, limits: TaskLimits) {
+        if let Ok(mut task_limits) = self.task_limits.lock() {
+            *task_limits = limits;
+        }
+    }
+
+    /// Last status of a task or of one of its material sub-tasks, None for unknown ids
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    /// use rezalnyas_core::features::engine::model::status::Status;
+    ///
+    /// let service = CutListOptimizerServiceImpl::new(2, false).unwrap();
+    /// let request = CalculationRequestBuilder::new()
+    ///     .add_panel(400.0, 600.0).material("MDF")
+    ///     .add_panel(300.0, 300.0).material("Glass 4mm")
+    ///     .add_stock(1000.0, 600.0).material("MDF")
+    ///     .add_stock(1000.0, 600.0).material("Glass 4mm")
+    ///     .build()
+    ///     .unwrap();
+    /// let task_id = service.submit_task(request).unwrap().task_id.unwrap();
+    ///
+    /// let status = service.get_task_status(&task_id).unwrap();
+    /// assert_eq!(status.status, Some(Status::Finished));
+    /// assert_eq!(status.sub_task_ids.len(), 2);
+    /// let glass = service.get_task_status(&status.sub_task_ids[0]).unwrap();
+    /// assert_eq!(glass.parent_task_id, Some(task_id));
+    /// assert_eq!(glass.material.as_deref(), Some("Glass 4mm"));
+    /// ```
+    pub fn get_task_status(&self, task_id: &str) -> Option<TaskStatusResponse> {
+        self.task_statuses
+            .lock()
+            .ok()
+            .and_then(|statuses| statuses.get(task_id).cloned())
+    }
+
+    /// Ask a running task or one of its material sub-tasks to stop, the other materials
+    /// of the task go on. The stopped part keeps the best layout found so far.
+    /// Returns false if the id isn't running
+    pub fn stop_task(&self, task_id: &str) -> bool {
+        let is_running = self
+            .get_task_status(task_id)
+            .and_then(|status| status.status)
+            .is_some_and(|status| matches!(status, Status::Running | Status::Improving));
+        if !is_running {
+            return false;
+        }
+        if let Ok(mut stopped_tasks) = self.stopped_tasks.lock() {
+            stopped_tasks.insert(task_id.to_string());
+        }
+        if let Ok(mut statuses) = self.task_statuses.lock() {
+            if let Some(status) = statuses.get_mut(task_id) {
+                status.set_status(Status::Stopping);
+            }
+        }
+        true
+    }
+
+    fn is_stopped(&self, task_id: &str) -> bool {
+        self.stopped_tasks
+            .lock()
+            .is_ok_and(|stopped_tasks| stopped_tasks.contains(task_id))
+    }
+
+    /// True once the client stopped the task or the sub-task computing `material`
+    fn is_material_stopped(&self, task: &Task, material: &str) -> bool {
+        self.is_stopped(&task.id)
+            || task
+                .get_sub_task_id(material)
+                .is_some_and(|sub_task_id| self.is_stopped(sub_task_id))
+    }
+
+    fn is_sub_task_of(sub_task_id: &str, task_id: &str) -> bool {
+        sub_task_id
+            .strip_prefix(task_id)
+            .is_some_and(|suffix| suffix.starts_with('.'))
+    }
+
+    /// Store the current status of the task and of all its sub-tasks for `get_task_status`
+    fn publish_status(&self, task: &Task) {
+        if let Ok(mut tasks) = self.running_tasks.lock() {
+            if let Some((_, last_progress)) = tasks.get_mut(&task.id) {
+                *last_progress = Instant::now();
+            }
+        }
+        let mut status = task.get_status_response();
+        // Остановка видна клиенту сразу, задача переходит в Stopping только при сборке ответа
+        if task.is_running() && self.is_stopped(&task.id) {
+            status.set_status(Status::Stopping);
+        }
+        if let Ok(mut statuses) = self.task_statuses.lock() {
+            for sub_task_id in task.sub_task_ids.values() {
+                if let Some(status) = task.get_sub_task_status_response(sub_task_id) {
+                    statuses.insert(sub_task_id.clone(), status);
+                }
+            }
+            statuses.insert(task.id.clone(), status);
+        }
+    }
+
+    /// Task counters of the service since it was created
+    pub fn get_stats(&self) -> Stats {
+        self.stats.lock().map(|stats| stats.clone()).unwrap_or_default()
+    }
+
+    /// Liveness and readiness of the service for health probes. There is no server in this
+    /// crate, applications serving `/healthz` and `/readyz` answer them from this report
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    ///
+    /// let service = CutListOptimizerServiceImpl::new(2, false).unwrap();
+    /// let health = service.get_health();
+    /// assert!(health.is_alive && health.is_ready);
+    /// assert_eq!(health.oldest_task_ms, None);
+    /// assert_eq!(health.nbr_stalled_tasks, 0);
+    /// ```
+    pub fn get_health(&self) -> HealthReport {
+        // Блокировки берутся по одной, чтобы не пересечься с track_task
+        let (oldest_task_ms, nbr_stalled_tasks) = self
+            .running_tasks
+            .lock()
+            .map(|tasks| {
+                let oldest_task_ms = tasks
+                    .values()
+                    .map(|(start, _)| start)
+                    .min()
+                    .map(|start| start.elapsed().as_millis() as u64);
+                let nbr_stalled_tasks = tasks
+                    .values()
+                    .filter(|(_, last_progress)| {
+                        last_progress.elapsed().as_millis() as u64 >= EngineConstants::TASK_STALL_TIMEOUT_MS
+                    })
+                    .count();
+                (oldest_task_ms, nbr_stalled_tasks)
+            })
+            .unwrap_or((None, 0));
+        let is_alive = !self.running_tasks.is_poisoned() && !self.stats.is_poisoned() && !self.task_logs.is_poisoned();
+        let stats = self.get_stats();
+
+        HealthReport {
+            is_alive,
+            is_ready: is_alive && stats.nbr_running_tasks < self.thread_count as i64 && nbr_stalled_tasks == 0,
+            nbr_running_tasks: stats.nbr_running_tasks,
+            nbr_finished_tasks: stats.nbr_finished_tasks,
+            nbr_error_tasks: stats.nbr_error_tasks,
+            oldest_task_ms,
+            nbr_stalled_tasks,
+        }
+    }
+
+    fn track_task(&self, task_id: &str, is_running: bool) {
+        if let Ok(mut tasks) = self.running_tasks.lock() {
+            if is_running {
+                tasks.insert(task_id.to_string(), (Instant::now(), Instant::now()));
+            } else {
+                tasks.remove(task_id);
+            }
+        }
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.nbr_running_tasks += if is_running { 1 } else { -1 };
+        }
+    }
+
+    fn log(&self, task_id: &str, level: LogLevel, message: &str) {
+        if AppLogger::is_enabled(level, module_path!()) {
+            AppLogger::write(level, &format!("Task[{}] {}", task_id, message));
+        }
+        if let Ok(mut logs) = self.task_logs.lock() {
+            logs.push(task_id, level, message);
+        }
+    }
+
+    /// Drop all cached thread results and responses shared between tasks
+    pub fn clear_result_cache(&self) {
+        if let Ok(mut cache) = self.result_cache.lock() {
+            cache.clear();
+        }
+        if let Ok(mut cache) = self.response_cache.lock() {
+            cache.clear();
+        }
+    }
+
+    /// Finish the task with the response of an identical request computed less than
+    /// `ttl_ms` ago, false if there is none
+    fn reuse_cached_response(&self, key: u64, ttl_ms: u64, task_id: &str) -> bool {
+        let Some(mut response) = self
+            .response_cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(key, ttl_ms, self.clock.now_millis()))
+        else {
+            return false;
+        };
+        response.task_id = Some(task_id.to_string());
+        // Хэш покрывает id задачи, у повторно выданного ответа он новый
+        if response.integrity.is_some() {
+            response.integrity = ResponseIntegrity::new(&response, self.signing_key.as_deref()).ok();
+        }
 
-        self.compute(calculation_request, &new_task_id)?;
+        let mut status = TaskStatusResponse::new();
+        status.task_id = Some(task_id.to_string());
+        status.locale = response.request.configuration.locale;
+        status.set_status(Status::Finished);
+        status.percentage_done = 100;
+        status.init_percentage = 100;
+        status.eta_ms = Some(0);
+        status.solution = Some(response);
+        if let Ok(mut statuses) = self.task_statuses.lock() {
+            statuses.insert(task_id.to_string(), status);
+        }
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.nbr_finished_tasks += 1;
+        }
+
+        self.log(task_id, LogLevel::Info, "Identical request computed recently, response reused");
+        self.emit_event(task_id, TaskEventKind::Finished);
+        true
+    }
+
+    /// Fill the thread with cached solutions, returns false if it still has to be executed
+    fn load_cached_solutions(&self, cut_list_thread: &mut CutListThread) -> bool {
+        let Ok(mut cache) = self.result_cache.lock() else {
+            return false;
+        };
+        if cache.capacity() == 0 {
+            return false;
+        }
+
+        match cache.get(ResultCache::key(cut_list_thread)) {
+            Some(solutions) => {
+                log_debug!("STEP_CACHE_HIT: {}", cut_list_thread.aux_info);
+                cut_list_thread.all_solutions = solutions;
+                cut_list_thread.status = Status::Finished;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn store_cached_solutions(&self, cut_list_thread: &CutListThread) {
+        if let Ok(mut cache) = self.result_cache.lock() {
+            if cache.capacity() > 0 {
+                cache.insert(
+                    ResultCache::key(cut_list_thread),
+                    cut_list_thread.all_solutions.clone(),
+                );
+            }
+        }
+    }
 
-        Ok(CalculationSubmissionResult::default())
+    /// Deliver an event to all subscribers, dropping the disconnected ones
+    fn emit_event(&self, task_id: &str, kind: TaskEventKind) {
+        if let Ok(mut subscribers) = self.event_subscribers.lock() {
+            if subscribers.is_empty() {
+                return;
+            }
+            let event = TaskEvent::new(task_id, kind, self.clock.now_millis());
+            subscribers.retain(|subscriber| subscriber.send(event.clone()).is_ok());
+        }
     }
 
     fn get_tile_dimensions_per_material(
         tiles: &[TileDimensions],
     ) -> HashMap<String, Vec<TileDimensions>> {
-        println!("Grouping tiles by material - total_tiles={}", tiles.len());
+        log_debug!("Grouping tiles by material - total_tiles={}", tiles.len());
 
         let material_groups = tiles.iter().fold(HashMap::new(), |mut acc, tile| {
             let material = tile.material.clone();
@@ -71,28 +717,498 @@ impl CutListOptimizerServiceImpl {
             acc
         });
 
-        println!(
+        log_debug!(
             "Material grouping completed - materials={}",
             material_groups.len()
         );
         material_groups
     }
+
+    /// Draw of a computed stage: how many tiles of `requested_material` were placed on `stock_material`
+    fn get_material_draw(
+        task: &Task,
+        key: &str,
+        requested_material: &str,
+        stock_material: &str,
+        nbr_requested: usize,
+    ) -> MaterialDraw {
+        let (nbr_panels, nbr_stock_panels) = match task.get_solutions(key).first() {
+            Some(best) => (
+                nbr_requested.saturating_sub(best.no_fit_panels.len()) as i32,
+                best.mosaics.len() as i32,
+            ),
+            None => (0, 0),
+        };
+        MaterialDraw::new(requested_material, stock_material, nbr_panels, nbr_stock_panels)
+    }
+
+    /// Move the no-fit tiles out of the best solution stored under `key`.
+    /// Without any solution all `tiles` are considered not placed.
+    fn take_overflow_tiles(task: &mut Task, key: &str, tiles: &[TileDimensions]) -> Vec<TileDimensions> {
+        match task.solutions.get_mut(key).and_then(|solutions| solutions.first_mut()) {
+            Some(best) => std::mem::take(&mut best.no_fit_panels),
+            None => tiles.to_vec(),
+        }
+    }
+
+    /// Split off tiles that fit no stock size in any orientation, they would only end up as no-fit
+    fn split_unfittable_tiles(
+        tiles: &[TileDimensions],
+        stock_tiles: &[TileDimensions],
+        tolerance: u32,
+    ) -> (Vec<TileDimensions>, Vec<TileDimensions>) {
+        let stock_sizes = StockFitMask::get_stock_sizes(stock_tiles);
+        let mut fits_any: HashMap<(u32, u32, u32), bool> = HashMap::new();
+
+        let (fitting, unfittable): (Vec<_>, Vec<_>) = tiles.iter().cloned().partition(|tile| {
+            *fits_any
+                .entry((tile.width, tile.height, tile.edge_clearance))
+                .or_insert_with(|| StockFitMask::new(tile, &stock_sizes, tolerance).fits_any())
+        });
+
+        if !unfittable.is_empty() {
+            log_debug!(
+                "Feasibility check: {} of {} tiles don't fit any of {} stock sizes",
+                unfittable.len(),
+                tiles.len(),
+                stock_sizes.len()
+            );
+        }
+        (fitting, unfittable)
+    }
+
+    /// Stock of a material still available after its own tiles were computed
+    fn get_unused_stock(task: &Task, material: &str) -> Vec<TileDimensions> {
+        match task.get_solutions(material).first() {
+            Some(best) => best.unused_stock_panels.iter().cloned().collect(),
+            None => task
+                .stock_dimensions_per_material
+                .get(material)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Parse a dimension of the request, "1,5" and "1/2" are accepted as well
+    fn parse_number(field: &str, value: &str) -> Result<f64> {
+        PrecisionAnalyzer::parse_f64(value).map_err(|_| {
+            ComputationError::InvalidNumber {
+                field: field.to_string(),
+                value: value.to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// Panels whose material has stock, but none of their thickness
+    fn get_thickness_mismatches(calculation_request: &CalculationRequest) -> Vec<String> {
+        let mut stock_keys: HashMap<&str, Vec<String>> = HashMap::new();
+        for stock in &calculation_request.stock_panels {
+            let keys = stock_keys.entry(stock.material.as_str()).or_default();
+            let key = stock.get_material_key();
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        let mut warnings = Vec::new();
+        for panel in &calculation_request.panels {
+            let Some(keys) = stock_keys.get(panel.material.as_str()) else {
+                continue;
+            };
+            if keys.contains(&panel.get_material_key()) {
+                continue;
+            }
+
+            let available: Vec<Option<String>> = calculation_request
+                .stock_panels
+                .iter()
+                .filter(|stock| stock.material == panel.material)
+                .map(|stock| stock.get_thickness())
+                .collect::<std::collections::BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            let warning = Warning::ThicknessMismatch {
+                panel_id: panel.id,
+                material: panel.material.clone(),
+                thickness: panel.get_thickness(),
+                available,
+            };
+            warnings.push(warning.to_text(calculation_request.configuration.locale));
+        }
+        warnings
+    }
+
+    /// Materials whose panels have no stock of the material nor of its substitutes,
+    /// in the order of their first panel row
+    fn get_materials_without_stock(calculation_request: &CalculationRequest) -> Vec<SubmissionWarning> {
+        // Имена материалов сверяются после сведения к справочнику
+        let Ok(normalized) = normalize_request(calculation_request) else {
+            return Vec::new();
+        };
+        let request = &normalized.request;
+        let stock_materials: HashSet<&str> = request.stock_panels.iter().map(|stock| stock.material.as_str()).collect();
+        let stock_keys: HashSet<String> = request.stock_panels.iter().map(|stock| stock.get_material_key()).collect();
+
+        let mut warnings: Vec<SubmissionWarning> = Vec::new();
+        for panel in &request.panels {
+            let has_substitute_stock = request
+                .configuration
+                .material_substitutes
+                .get(&panel.get_material_key())
+                .is_some_and(|substitutes| substitutes.iter().any(|substitute| stock_keys.contains(substitute)));
+            if stock_materials.contains(panel.material.as_str()) || has_substitute_stock {
+                continue;
+            }
+
+            let area = match (PrecisionAnalyzer::parse_f64(&panel.width), PrecisionAnalyzer::parse_f64(&panel.height)) {
+                (Ok(width), Ok(height)) => width * height * panel.count as f64,
+                _ => 0.0,
+            };
+            match warnings.iter_mut().find(|warning| {
+                matches!(warning, SubmissionWarning::MaterialWithoutStock { material, .. } if *material == panel.material)
+            }) {
+                Some(SubmissionWarning::MaterialWithoutStock {
+                    panel_count, area: total_area, ..
+                }) => {
+                    *panel_count += panel.count;
+                    *total_area += area;
+                }
+                None => warnings.push(SubmissionWarning::MaterialWithoutStock {
+                    material: panel.material.clone(),
+                    panel_count: panel.count,
+                    area,
+                }),
+            }
+        }
+        warnings
+    }
+
+    /// Pinned copies per request panel id and pinned sheets per request stock id
+    fn get_pinned_counts(
+        calculation_request: &CalculationRequest,
+    ) -> Result<(HashMap<u32, u32>, HashMap<u32, u32>)> {
+        let mut panel_counts: HashMap<u32, u32> = HashMap::new();
+        let mut sheets: HashSet<(u32, u32)> = HashSet::new();
+        let mut errors = Vec::new();
+
+        for pinned in &calculation_request.pinned_placements {
+            *panel_counts.entry(pinned.panel_id).or_insert(0) += 1;
+            sheets.insert((pinned.stock_id, pinned.sheet_index));
+
+            let panel = calculation_request.panels.iter().find(|panel| panel.id == pinned.panel_id);
+            match calculation_request.stock_panels.iter().find(|stock| stock.id == pinned.stock_id) {
+                Some(stock) if pinned.sheet_index >= stock.count => errors.push(format!(
+                    "pinned sheet {} of stock panel {} doesn't exist",
+                    pinned.sheet_index, pinned.stock_id
+                )),
+                Some(stock) if panel.is_some_and(|panel| panel.get_material_key() != stock.get_material_key()) => {
+                    errors.push(format!(
+                        "panel {} is pinned on stock panel {} of another material or thickness",
+                        pinned.panel_id, pinned.stock_id
+                    ))
+                }
+                Some(_) => {}
+                None => errors.push(format!("pinned stock panel {} doesn't exist", pinned.stock_id)),
+            }
+        }
+
+        for (panel_id, count) in &panel_counts {
+            match calculation_request.panels.iter().find(|panel| panel.id == *panel_id) {
+                Some(panel) if panel.count < *count => errors.push(format!(
+                    "panel {} is pinned {} times but has count {}",
+                    panel_id, count, panel.count
+                )),
+                Some(_) => {}
+                None => errors.push(format!("pinned panel {} doesn't exist", panel_id)),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(CoreError::InvalidInput {
+                details: errors.join("; "),
+            }
+            .into());
+        }
+
+        let mut sheet_counts: HashMap<u32, u32> = HashMap::new();
+        for (stock_id, _) in sheets {
+            *sheet_counts.entry(stock_id).or_insert(0) += 1;
+        }
+        Ok((panel_counts, sheet_counts))
+    }
+
+    /// Carve the pinned panels out of their sheets, returns the leftover pieces to use as stock
+    fn build_pinned_sheets(
+        calculation_request: &CalculationRequest,
+        task: &mut Task,
+        panel_internal_ids: &HashMap<u32, u32>,
+        stock_internal_ids: &HashMap<u32, u32>,
+        precision_multiplier: u32,
+    ) -> Result<Vec<TileDimensions>> {
+        let scale = |field: &str, value: &str| -> Result<u32> {
+            Ok((Self::parse_number(field, value)? * precision_multiplier as f64).round() as u32)
+        };
+        // Same kerf as the threads use
+        let cut_thickness =
+            CutListThread::new_with_config(&calculation_request.configuration, 0).cut_thickness;
+
+        let mut pins_per_sheet: BTreeMap<(u32, u32), Vec<&PinnedPlacement>> = BTreeMap::new();
+        for pinned in &calculation_request.pinned_placements {
+            pins_per_sheet
+                .entry((pinned.stock_id, pinned.sheet_index))
+                .or_default()
+                .push(pinned);
+        }
+
+        let mut fragments = Vec::new();
+        for ((stock_id, sheet_index), pins) in pins_per_sheet {
+            let stock = calculation_request
+                .stock_panels
+                .iter()
+                .find(|stock| stock.id == stock_id)
+                .ok_or_else(|| CoreError::InvalidInput {
+                    details: format!("pinned stock panel {} doesn't exist", stock_id),
+                })?;
+            let sheet = TileDimensions::new(
+                stock_internal_ids[&stock_id],
+                scale("stock width", &stock.width)?,
+                scale("stock height", &stock.height)?,
+                false,
+                &stock.label,
+                &stock.get_material_key(),
+            );
+            let mut pinned_sheet = PinnedSheet::new(&sheet);
+
+            for pinned in pins {
+                let panel = calculation_request
+                    .panels
+                    .iter()
+                    .find(|panel| panel.id == pinned.panel_id)
+                    .ok_or_else(|| CoreError::InvalidInput {
+                        details: format!("pinned panel {} doesn't exist", pinned.panel_id),
+                    })?;
+                let (width, height) = (scale("panel width", &panel.width)?, scale("panel height", &panel.height)?);
+                let (width, height) = if pinned.rotated { (height, width) } else { (width, height) };
+                let tile = TileDimensions::new(
+                    panel_internal_ids[&pinned.panel_id],
+                    width,
+                    height,
+                    pinned.rotated,
+                    &panel.label,
+                    &panel.get_material_key(),
+                );
+
+                pinned_sheet
+                    .pin(
+                        &tile,
+                        scale("pinned x", &pinned.x)? as i32,
+                        scale("pinned y", &pinned.y)? as i32,
+                        cut_thickness,
+                    )
+                    .map_err(|message| ComputationError::ConstraintViolation {
+                        message: format!("stock panel {} sheet {}: {}", stock_id, sheet_index, message),
+                    })?;
+            }
+
+            for node in pinned_sheet.get_free_nodes() {
+                let fragment_id = task.register_panel_id(stock.id, &stock.label, true);
+                pinned_sheet.add_fragment(fragment_id, node.id);
+                fragments.push(TileDimensions::new(
+                    fragment_id,
+                    node.get_width() as u32,
+                    node.get_height() as u32,
+                    false,
+                    &stock.label,
+                    &stock.get_material_key(),
+                ));
+            }
+            log_debug!(
+                "Pinned sheet {} of stock {}: {} free pieces",
+                sheet_index,
+                stock_id,
+                pinned_sheet.fragments.len()
+            );
+            task.pinned_sheets.push(pinned_sheet);
+        }
+        Ok(fragments)
+    }
+
+    /// Replace the members of every sequence group in `processed_tiles` by one block tile
+    fn build_sequence_blocks(
+        calculation_request: &CalculationRequest,
+        task: &mut Task,
+        panel_internal_ids: &HashMap<u32, u32>,
+        processed_tiles: &mut Vec<TileDimensions>,
+    ) -> Result<()> {
+        // Same kerf as the threads use
+        let cut_thickness =
+            CutListThread::new_with_config(&calculation_request.configuration, 0).cut_thickness;
+
+        for group in &calculation_request.sequence_groups {
+            let invalid = |details: String| CoreError::InvalidInput {
+                details: format!("sequence group {}: {}", group.id, details),
+            };
+            if group.panel_ids.len() < 2 {
+                return Err(invalid("needs at least two panels".to_string()).into());
+            }
+
+            let mut members = Vec::new();
+            for panel_id in &group.panel_ids {
+                let internal_id = panel_internal_ids
+                    .get(panel_id)
+                    .ok_or_else(|| invalid(format!("panel {} doesn't exist", panel_id)))?;
+                let index = processed_tiles
+                    .iter()
+                    .position(|tile| tile.id == *internal_id)
+                    .ok_or_else(|| invalid(format!("panel {} is used more times than its count", panel_id)))?;
+                members.push(processed_tiles.remove(index));
+            }
+            if members.iter().any(|member| member.material != members[0].material) {
+                return Err(invalid("panels are of different materials".to_string()).into());
+            }
+
+            let internal_id = task.register_panel_id(group.id, &format!("sequence {}", group.id), false);
+            let block = SequenceBlock::new(
+                group.id,
+                internal_id,
+                members,
+                group.axis == SequenceAxis::Horizontal,
+                cut_thickness,
+            );
+            processed_tiles.push(block.tile.clone());
+            task.sequence_blocks.push(block);
+        }
+        Ok(())
+    }
+
+    /// Cut the placed sequence blocks into their members, blocks that found no room
+    /// go back to the unplaced panels as separate members with a warning
+    fn expand_sequence_blocks(&self, task: &mut Task) {
+        let blocks = std::mem::take(&mut task.sequence_blocks);
+        for block in &blocks {
+            let mut is_placed = false;
+            let mut is_unplaced = block.expand_tiles(&mut task.no_material_tiles);
+            is_unplaced |= block.expand_tiles(&mut task.unfittable_tiles);
+            for solutions in task.solutions.values_mut() {
+                if let Some(best) = solutions.first_mut() {
+                    is_placed |= block.expand(best);
+                    is_unplaced |= block.expand_tiles(&mut best.no_fit_panels);
+                }
+            }
+
+            if is_unplaced || !is_placed {
+                let warning = Warning::SequenceGroupNotContiguous {
+                    group_id: block.group_id,
+                }
+                .to_text(task.calculation_request.configuration.locale);
+                self.log(&task.id, LogLevel::Warn, &warning);
+                task.warnings.push(warning);
+            }
+        }
+        task.sequence_blocks = blocks;
+    }
+
+    /// Add the sheets copied from a pattern to the best solution of their material
+    fn merge_replicated_sheets(task: &mut Task) {
+        for (material, mosaics) in std::mem::take(&mut task.replicated_sheets) {
+            let solutions = task.solutions.entry(material.clone()).or_default();
+            if solutions.is_empty() {
+                solutions.push(Solution::default());
+            }
+            solutions[0].mosaics.extend(mosaics.iter().cloned());
+            task.replicated_sheets.insert(material, mosaics);
+        }
+    }
+
+    /// Put the layouts computed for the free pieces back into their pinned sheets
+    fn merge_pinned_sheets(task: &mut Task) {
+        let pinned_sheets = std::mem::take(&mut task.pinned_sheets);
+
+        for mut pinned_sheet in pinned_sheets {
+            for solutions in task.solutions.values_mut() {
+                let Some(best) = solutions.first_mut() else {
+                    continue;
+                };
+                best.mosaics.retain(|mosaic| {
+                    !mosaic
+                        .root_tile_node
+                        .first()
+                        .is_some_and(|root| pinned_sheet.graft(root))
+                });
+                // Unused free pieces stay inside the pinned sheet
+                best.unused_stock_panels = std::mem::take(&mut best.unused_stock_panels)
+                    .into_iter()
+                    .filter(|stock| !pinned_sheet.is_fragment(stock.id))
+                    .collect();
+            }
+
+            let solutions = task.solutions.entry(pinned_sheet.stock.material.clone()).or_default();
+            if solutions.is_empty() {
+                solutions.push(Solution::default());
+            }
+            solutions[0].mosaics.push(pinned_sheet.to_mosaic());
+            task.pinned_sheets.push(pinned_sheet);
+        }
+    }
+
     /// Generate task ID (equivalent to Java dateFormat.format(new Date()) + taskIdCounter.getAndIncrement())
     fn generate_task_id(&self) -> String {
-        let now: DateTime<Local> = Local::now();
+        let now: DateTime<Local> = DateTime::from_timestamp_millis(self.clock.now_millis() as i64)
+            .unwrap_or_default()
+            .with_timezone(&Local);
         let date_part = now.format("%Y%m%d%H%M").to_string();
         let counter = TASK_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
         format!("{}{}", date_part, counter)
     }
 
     // -=2
-    fn compute(&self, calculation_request: CalculationRequest, task_id: &str) -> Result<()> {
+    fn compute(
+        &self,
+        calculation_request: CalculationRequest,
+        task_id: &str,
+        checkpoint: Option<TaskCheckpoint>,
+    ) -> Result<CalculationResponse> {
         // тут валидация
 
-        println!("=== COMPUTATION STARTED ===");
-        println!("Task initialization - task_id={}", task_id);
+        log_info!("=== COMPUTATION STARTED ===");
+        log_info!("Task initialization - task_id={}", task_id);
+        self.emit_event(task_id, TaskEventKind::Started);
+        self.log(
+            task_id,
+            LogLevel::Info,
+            &format!(
+                "Computation started: {} panels, {} stock panels",
+                calculation_request.panels.len(),
+                calculation_request.stock_panels.len()
+            ),
+        );
+
+        if let Ok(mut cache) = self.result_cache.lock() {
+            let cache_size = calculation_request
+                .performance_thresholds
+                .result_cache_size
+                .unwrap_or(0);
+            cache.set_capacity(cache_size as usize);
+        }
+
+        // Контрольная точка хранит исходный запрос: при возобновлении он нормализуется заново
+        let checkpoint_interval_ms = calculation_request
+            .performance_thresholds
+            .checkpoint_interval_ms
+            .unwrap_or(0);
+        let checkpoint = checkpoint.or_else(|| {
+            (self.task_repository.is_some() && checkpoint_interval_ms > 0)
+                .then(|| TaskCheckpoint::new(task_id, &calculation_request, self.clock.now_millis()))
+        });
+
+        // Проверка строк запроса, перевод в миллиметры и объединение одинаковых строк
+        let normalized = normalize_request(&calculation_request)?;
+        let calculation_request = normalized.request;
+        let configuration = &calculation_request.configuration;
 
         // Вычисляем scale_factor для масштабирования размеров (аналогично example.rs строки 640-653)
+        // Размеры могут быть записаны с запятой ("1,5") или дробью ("1/2")
         let mut max_decimal_places = 0;
 
         // Определяем максимальное количество знаков после запятой для всех panels
@@ -100,112 +1216,687 @@ impl CutListOptimizerServiceImpl {
             let width_str = &panel.width;
             let height_str = &panel.height;
 
-            if let Some(dot_pos) = width_str.find('.') {
-                max_decimal_places = max_decimal_places.max(width_str.len() - dot_pos - 1);
-            }
-            if let Some(dot_pos) = height_str.find('.') {
-                max_decimal_places = max_decimal_places.max(height_str.len() - dot_pos - 1);
+            max_decimal_places = max_decimal_places
+                .max(PrecisionAnalyzer::count_decimal_places(width_str))
+                .max(PrecisionAnalyzer::count_decimal_places(height_str));
+            if let Some(edge_clearance) = &panel.edge_clearance {
+                max_decimal_places = max_decimal_places.max(PrecisionAnalyzer::count_decimal_places(edge_clearance));
             }
         }
 
+        for pinned in &calculation_request.pinned_placements {
+            max_decimal_places = max_decimal_places
+                .max(PrecisionAnalyzer::count_decimal_places(&pinned.x))
+                .max(PrecisionAnalyzer::count_decimal_places(&pinned.y));
+        }
+
+        // Допуск тоже переводится в целые единицы, его знаки учитываются в масштабе
+        if let Some(dimension_tolerance) = &configuration.dimension_tolerance {
+            max_decimal_places = max_decimal_places.max(PrecisionAnalyzer::count_decimal_places(dimension_tolerance));
+        }
+        if let Some(lead_in_allowance) = &configuration.lead_in_allowance {
+            max_decimal_places = max_decimal_places.max(PrecisionAnalyzer::count_decimal_places(lead_in_allowance));
+        }
+
         // Определяем максимальное количество знаков после запятой для всех stock_panels
         for stock in &calculation_request.stock_panels {
             let width_str = &stock.width;
             let height_str = &stock.height;
 
-            if let Some(dot_pos) = width_str.find('.') {
-                max_decimal_places = max_decimal_places.max(width_str.len() - dot_pos - 1);
+            max_decimal_places = max_decimal_places
+                .max(PrecisionAnalyzer::count_decimal_places(width_str))
+                .max(PrecisionAnalyzer::count_decimal_places(height_str));
+        }
+
+        let precision_multiplier: u32 = 10u32.pow(max_decimal_places as u32);
+
+        // -= Создание и настройка задачи
+        let mut task = Task::default();
+        task.set_clock(self.clock.clone());
+        task.id = task_id.to_string();
+        task.status = Status::Queued;
+        task.calculation_request = calculation_request.clone();
+        task.client_info = calculation_request.client_info.clone();
+        task.factor = precision_multiplier;
+        let service_limits = self.task_limits.lock().map(|limits| *limits).unwrap_or_default();
+        task.limits = calculation_request
+            .performance_thresholds
+            .task_limits
+            .unwrap_or_default()
+            .bounded_by(&service_limits);
+        task.time_limit_deadline = task
+            .limits
+            .max_time_ms
+            .map(|max_time_ms| task.now_millis() + max_time_ms);
+        task.mark_progress();
+        self.publish_status(&task);
+        if normalized.report.has_changes() || !normalized.report.aggregated_rows.is_empty() {
+            self.log(
+                task_id,
+                LogLevel::Info,
+                &format!(
+                    "Input normalized: converted from {:?}, {} rows merged, {} rows aggregated",
+                    normalized.report.converted_from,
+                    normalized.report.merged_rows.len(),
+                    normalized.report.aggregated_rows.len()
+                ),
+            );
+        }
+        task.normalization_report = normalized.report;
+        if let Some(mut checkpoint) = checkpoint {
+            if !checkpoint.materials.is_empty() {
+                task.has_solution_all_fit = checkpoint.has_solution_all_fit;
             }
-            if let Some(dot_pos) = height_str.find('.') {
-                max_decimal_places = max_decimal_places.max(height_str.len() - dot_pos - 1);
+            checkpoint.saved_at_ms = task.now_millis();
+            task.checkpoint = Some(checkpoint);
+        }
+        task.set_status(Status::Running)?;
+
+        // Детали разной толщины одного материала раскраиваются отдельно
+        let thickness_warnings = Self::get_thickness_mismatches(&calculation_request);
+        if !thickness_warnings.is_empty() {
+            if configuration.reject_thickness_mismatch {
+                return Err(CoreError::InvalidInput {
+                    details: thickness_warnings.join("; "),
+                }
+                .into());
             }
+            for warning in &thickness_warnings {
+                self.log(task_id, LogLevel::Warn, warning);
+            }
+            task.warnings.extend(thickness_warnings);
         }
 
-        let precision_multiplier: u32 = 10u32.pow(max_decimal_places as u32);
+        // Закреплённые детали не участвуют в раскрое, их листы собираются отдельно
+        let (pinned_panel_counts, pinned_sheet_counts) = Self::get_pinned_counts(&calculation_request)?;
 
         // Конвертация panels в tile_dimensions
         let mut processed_tiles: Vec<TileDimensions> = Vec::new();
+        let mut panel_internal_ids: HashMap<u32, u32> = HashMap::new();
         // -=gen panels
-        for panel in &calculation_request.panels {
-            // В Java проверяется panel.isValid(), здесь все panels валидны после конвертации
-            for _ in 0..panel.count {
-                // Применяем scale_factor к размерам панели
-                let width_original: f64 = panel.width.parse().unwrap_or(0.0);
-                let height_original: f64 = panel.height.parse().unwrap_or(0.0);
-                let width_scaled = (width_original * precision_multiplier as f64).round() as u32;
-                let height_scaled = (height_original * precision_multiplier as f64).round() as u32;
-
-                let tile = TileDimensions::new(
-                    panel.id,
-                    width_scaled,
-                    height_scaled,
-                    false, // is_rotated = false по умолчанию
-                    &panel.label,
-                    &panel.material,
-                );
-
-                processed_tiles.push(tile);
+        for panel in &normalized.panels {
+            let internal_id = task.register_panel_id(panel.id, &panel.label, false);
+            panel_internal_ids.insert(panel.id, internal_id);
+            let pinned_count = pinned_panel_counts.get(&panel.id).copied().unwrap_or(0);
+            // В Java проверяется panel.isValid(), здесь все panels проверены при нормализации
+            for _ in 0..panel.count - pinned_count {
+                processed_tiles.push(panel.to_tile_dimensions(internal_id, precision_multiplier));
             }
         }
 
+        Self::build_sequence_blocks(&calculation_request, &mut task, &panel_internal_ids, &mut processed_tiles)?;
+
         let mut processed_stock_panels: Vec<TileDimensions> = Vec::new();
 
         // -=gen stock_panels
 
-        for stock in &calculation_request.stock_panels {
-            // В Java проверяется stock.isValid(), здесь все stocks валидны после конвертации
-            for _ in 0..stock.count {
-                // Применяем scale_factor к размерам заготовки
-                let width_original: f64 = stock.width.parse().unwrap_or(0.0);
-                let height_original: f64 = stock.height.parse().unwrap_or(0.0);
-                let width_scaled = (width_original * precision_multiplier as f64).round() as u32;
-                let height_scaled = (height_original * precision_multiplier as f64).round() as u32;
+        let mut stock_internal_ids: HashMap<u32, u32> = HashMap::new();
+        for stock in &normalized.stock_panels {
+            let internal_id = task.register_panel_id(stock.id, &stock.label, true);
+            stock_internal_ids.insert(stock.id, internal_id);
+            let pinned_count = pinned_sheet_counts.get(&stock.id).copied().unwrap_or(0);
+            // В Java проверяется stock.isValid(), здесь все stocks проверены при нормализации
+            for _ in 0..stock.count - pinned_count {
+                processed_stock_panels.push(stock.to_tile_dimensions(internal_id, precision_multiplier));
+            }
+        }
 
-                let tile = TileDimensions::new(
-                    stock.id,
-                    width_scaled,
-                    height_scaled,
-                    false, // is_rotated = false по умолчанию
-                    &stock.label,
-                    &stock.material,
+        let fragments = Self::build_pinned_sheets(
+            &calculation_request,
+            &mut task,
+            &panel_internal_ids,
+            &stock_internal_ids,
+            precision_multiplier,
+        )?;
+        processed_stock_panels.extend(fragments);
+
+        task.tile_dimensions_per_material = Self::get_tile_dimensions_per_material(&processed_tiles);
+        task.stock_dimensions_per_material =
+            Self::get_tile_dimensions_per_material(&processed_stock_panels);
+
+        // Java: for (String material : tilesPerMaterial.keySet()) - sorted for stable output
+        let mut materials: Vec<String> = task.tile_dimensions_per_material.keys().cloned().collect();
+        materials.sort();
+
+        // Stage 1: every material on its own stock
+        // Материалы с заменами не улучшаем: их остаток уже разнесён по заменам
+        let mut computed_materials: Vec<(String, Vec<TileDimensions>, Vec<TileDimensions>)> = Vec::new();
+        let mut overflow_per_material: Vec<(String, Vec<TileDimensions>, Option<String>)> =
+            Vec::new();
+        for material in &materials {
+            let tiles = task.tile_dimensions_per_material[material].clone();
+            let has_substitutes = configuration
+                .material_substitutes
+                .get(material)
+                .is_some_and(|substitutes| !substitutes.is_empty());
+
+            match task.stock_dimensions_per_material.get(material).cloned() {
+                Some(stock_tiles) => {
+                    let (tiles, unfittable) = Self::split_unfittable_tiles(&tiles, &stock_tiles, task.get_dimension_tolerance());
+                    let mut overflow = Vec::new();
+                    let mut last_key = None;
+                    if !tiles.is_empty() {
+                        self.compute_material(&tiles, &stock_tiles, &mut task, material, configuration)?;
+                        let draw = Self::get_material_draw(&task, material, material, material, tiles.len());
+                        task.material_draws.push(draw);
+
+                        if has_substitutes {
+                            overflow = Self::take_overflow_tiles(&mut task, material, &tiles);
+                            last_key = Some(material.clone());
+                        } else if !task.replicated_sheets.contains_key(material)
+                            && !task.one_dimensional_materials.contains(material)
+                        {
+                            computed_materials.push((material.clone(), tiles.clone(), stock_tiles.clone()));
+                        }
+                    }
+
+                    if has_substitutes {
+                        overflow.extend(unfittable);
+                        if !overflow.is_empty() {
+                            overflow_per_material.push((material.clone(), overflow, last_key));
+                        }
+                    } else {
+                        task.unfittable_tiles.extend(unfittable);
+                    }
+                }
+                None if has_substitutes => {
+                    overflow_per_material.push((material.clone(), tiles, None));
+                }
+                None => {
+                    log_warn!("No stock for material '{}' - {} tiles can't be placed", material, tiles.len());
+                    task.no_material_tiles.extend(tiles);
+                }
+            }
+        }
+
+        // Stage 2: spill what didn't fit onto the leftover stock of the substitutes, in order
+        let mut remaining_stock: HashMap<String, Vec<TileDimensions>> = HashMap::new();
+        for (material, mut overflow, mut last_key) in overflow_per_material {
+            for substitute in &configuration.material_substitutes[&material] {
+                if overflow.is_empty() {
+                    break;
+                }
+
+                let stock_tiles = remaining_stock
+                    .entry(substitute.clone())
+                    .or_insert_with(|| Self::get_unused_stock(&task, substitute))
+                    .clone();
+                let (fitting, unfittable) = Self::split_unfittable_tiles(&overflow, &stock_tiles, task.get_dimension_tolerance());
+                if fitting.is_empty() {
+                    continue;
+                }
+                overflow = fitting;
+
+                log_debug!(
+                    "Spilling {} tiles of material '{}' to substitute '{}'",
+                    overflow.len(),
+                    material,
+                    substitute
                 );
-                processed_stock_panels.push(tile);
+                let key = format!("{}->{}", material, substitute);
+                let substitute_tiles: Vec<TileDimensions> = overflow
+                    .iter()
+                    .map(|tile| {
+                        TileDimensions::intern(TileDimensionsData {
+                            material: substitute.clone(),
+                            ..(**tile).clone()
+                        })
+                    })
+                    .collect();
+
+                self.compute_material(&substitute_tiles, &stock_tiles, &mut task, &key, configuration)?;
+                let draw = Self::get_material_draw(&task, &key, &material, substitute, overflow.len());
+                task.material_draws.push(draw);
+
+                if let Some(best) = task.get_solutions(&key).first() {
+                    remaining_stock.insert(substitute.clone(), best.unused_stock_panels.iter().cloned().collect());
+                }
+                overflow = Self::take_overflow_tiles(&mut task, &key, &substitute_tiles)
+                    .into_iter()
+                    .map(|tile| {
+                        TileDimensions::intern(TileDimensionsData {
+                            material: material.clone(),
+                            ..(*tile).clone()
+                        })
+                    })
+                    .chain(unfittable)
+                    .collect();
+                last_key = Some(key);
+            }
+
+            if !overflow.is_empty() {
+                // Nothing left to spill to: report the rest with the last stage that tried them
+                match last_key.and_then(|key| task.solutions.get_mut(&key)) {
+                    Some(solutions) if !solutions.is_empty() => {
+                        solutions[0].no_fit_panels.extend(overflow);
+                    }
+                    _ => task.no_material_tiles.extend(overflow),
+                }
             }
         }
-        // -= Создание и настройка задачи
-        let mut task = Task::default();
-        task.calculation_request = calculation_request.clone();
-        task.client_info = calculation_request.client_info;
-        task.factor = precision_multiplier;
+
+        if let Some(budget_ms) = configuration.improvement_budget_ms.filter(|budget_ms| *budget_ms > 0) {
+            if !task.is_time_limit_reached() {
+                self.improve(&computed_materials, &mut task, configuration, budget_ms)?;
+            }
+        }
+
+        if !task.worker_panics.is_empty() {
+            let warning = Warning::ThreadsFailed {
+                nbr_threads: task.worker_panics.len(),
+            }
+            .to_text(configuration.locale);
+            task.warnings.push(warning);
+        }
+
+        if task.is_time_limit_reached() {
+            let warning = Warning::TimeLimitReached {
+                max_time_ms: task.limits.max_time_ms.unwrap_or(0),
+            }
+            .to_text(configuration.locale);
+            self.log(task_id, LogLevel::Warn, &warning);
+            task.warnings.push(warning);
+        }
+
+        if task.nbr_dropped_branches > 0 {
+            self.log(
+                task_id,
+                LogLevel::Info,
+                &format!("{} branches dropped to stay within the pool memory budget", task.nbr_dropped_branches),
+            );
+        }
+
+        let is_stopped = self.is_stopped(task_id);
+        task.set_status(if is_stopped { Status::Stopping } else { Status::Finishing })?;
+        Self::merge_replicated_sheets(&mut task);
+        Self::merge_pinned_sheets(&mut task);
+        self.expand_sequence_blocks(&mut task);
         task.build_solution();
+        if configuration.include_content_hash {
+            task.solution.integrity = Some(ResponseIntegrity::new(&task.solution, self.signing_key.as_deref())?);
+        }
+        task.set_status(if is_stopped { Status::Stopped } else { Status::Finished })?;
+        if !is_stopped {
+            self.record_best_solution_groups(&task);
+        }
+        self.publish_status(&task);
+        // Остановленную задачу можно продолжить, законченной контрольная точка не нужна
+        if is_stopped {
+            self.save_checkpoint(&mut task);
+        } else if let Some(repository) = self.task_repository.as_ref().filter(|_| task.checkpoint.is_some()) {
+            if let Err(e) = repository.delete_checkpoint(task_id) {
+                self.log(task_id, LogLevel::Warn, &format!("Checkpoint not deleted: {}", e));
+            }
+        }
+        log_info!("=== COMPUTATION COMPLETED ===");
+
+        Ok(task.solution)
+    }
+
+    /// Count which thread groups created the returned solutions, see `Stats::best_solution_groups`
+    fn record_best_solution_groups(&self, task: &Task) {
+        if let Ok(mut stats) = self.stats.lock() {
+            for (group, count) in task.get_best_solution_groups() {
+                *stats.best_solution_groups.entry(group).or_insert(0) += count;
+            }
+        }
+    }
+
+    /// Keep refining the computed materials after the first full solution until `budget_ms`
+    /// runs out: every round repeats the permutations with a doubled solution pool
+    fn improve(
+        &self,
+        computed_materials: &[(String, Vec<TileDimensions>, Vec<TileDimensions>)],
+        task: &mut Task,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+        budget_ms: u64,
+    ) -> Result<()> {
+        let deadline = task.now_millis() + budget_ms;
+        // Улучшение не выходит за ограничение времени задачи
+        let deadline = task.time_limit_deadline.map_or(deadline, |limit| deadline.min(limit));
+        task.set_status(Status::Improving)?;
+        task.improvement_deadline = Some(deadline);
+        self.log(
+            &task.id,
+            LogLevel::Info,
+            &format!("First solution delivered, improving for {} ms", budget_ms),
+        );
+        self.emit_event(&task.id, TaskEventKind::Improving { budget_ms });
+
+        let mut round = 1;
+        while !task.is_improvement_over() {
+            if computed_materials
+                .iter()
+                .all(|(material, _, _)| self.is_material_stopped(task, material))
+            {
+                break;
+            }
+            let mut round_configuration = configuration.clone();
+            round_configuration.optimization_factor =
+                OptimizationFactor::Custom(configuration.optimization_factor.value() * 2f64.powi(round));
+
+            for (material, tiles, stock_tiles) in computed_materials {
+                if task.is_improvement_over() {
+                    break;
+                }
+                if self.is_material_stopped(task, material) {
+                    continue;
+                }
+                let permutations = Self::get_permutations(
+                    tiles,
+                    stock_tiles,
+                    &task.calculation_request.performance_thresholds,
+                    configuration.consider_orientation,
+                    &task.id,
+                );
+                self.process_permutations(&permutations, stock_tiles, task, material, &round_configuration)?;
+            }
+            round += 1;
+        }
+
+        // Задача остаётся Improving до сборки ответа, без срока она уже не считается запущенной
+        task.improvement_deadline = None;
+        self.log(
+            &task.id,
+            LogLevel::Info,
+            &format!("Improvement finished after {} rounds", round - 1),
+        );
+        Ok(())
+    }
+
+    /// Compute one material, a failure moves the task to Error with the material in
+    /// `TaskStatusResponse::error` before it is returned
+    fn compute_material(
+        &self,
+        processed_tiles: &[TileDimensions],
+        processed_stock_panels: &[TileDimensions],
+        task: &mut Task,
+        material: &str,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+    ) -> Result<()> {
+        let result = self.try_compute_material(processed_tiles, processed_stock_panels, task, material, configuration);
+        if let Err(e) = &result {
+            // Вложенный вызов (шаблон повторяющихся деталей) уже записал свой материал
+            if task.error.is_none() {
+                task.error = Some(TaskErrorInfo::new(e, Some(material)));
+                task.set_status(Status::Error).ok();
+                self.log(&task.id, LogLevel::Error, &format!("Material '{}' failed: {}", material, e));
+                self.publish_status(task);
+            }
+        }
+        result
+    }
+
+    /// Java: private void computeMaterial(List<TileDimensions> tilesToCut, List<TileDimensions> stockTiles, ..., Task task, String material)
+    ///
+    /// `material` is the key the solutions are stored under in the task
+    fn try_compute_material(
+        &self,
+        processed_tiles: &[TileDimensions],
+        processed_stock_panels: &[TileDimensions],
+        task: &mut Task,
+        material: &str,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+    ) -> Result<()> {
+        let task_id = task.id.clone();
+        task.register_sub_task(material);
+
+        if self.compute_one_dimensional(processed_tiles, processed_stock_panels, task, material, configuration) {
+            return Ok(());
+        }
+
+        let (processed_tiles, processed_stock_panels) =
+            self.chunk_repeated_tiles(processed_tiles, processed_stock_panels, task, material, configuration)?;
+        let (processed_tiles, processed_stock_panels) = (&processed_tiles[..], &processed_stock_panels[..]);
 
         // Calculate total pieces for logging
         let total_pieces = processed_tiles.len();
-        println!(
+        log_debug!(
             "Starting group generation - tiles={}, stock={}, task={}",
             total_pieces,
             processed_stock_panels.len(),
             task_id
         );
 
-        // Print tile groups info
-        print!("Tile groups: ");
-        for tile in &processed_tiles {
-            print!("id={}[{}x{}]*1 ", tile.id, tile.width, tile.height);
+        log_debug!(
+            "Tile groups: {}",
+            processed_tiles
+                .iter()
+                .map(|tile| format!("id={}[{}x{}]*1", tile.id, tile.width, tile.height))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+
+        let final_permutations = Self::get_permutations(
+            processed_tiles,
+            processed_stock_panels,
+            &task.calculation_request.performance_thresholds,
+            configuration.consider_orientation,
+            &task_id,
+        );
+
+        // Add material to compute (Java: task.addMaterialToCompute(material))
+        task.add_material_to_compute(material);
+
+        if configuration.search_strategy == SearchStrategy::FastPreview {
+            self.compute_preview(processed_tiles, processed_stock_panels, task, material, configuration)?;
+        }
+
+        // Calculate optimization factor the same way as Java (lines 815-823)
+        let base_solution_pool_size = 100;
+        let optimization_factor_value = configuration.optimization_factor.value();
+        let mut optimization_factor = if optimization_factor_value > 0.0 {
+            (100.0 * optimization_factor_value) as i32
+        } else {
+            100
+        };
+
+        // Java: if (tilesToCut.size() > 100) { optimizationFactor = (int) (optimizationFactor * (0.5d / (tilesToCut.size() / 100))); }
+        if processed_tiles.len() > 100 {
+            optimization_factor = (optimization_factor as f64
+                * (0.5 / (processed_tiles.len() as f64 / 100.0)))
+                as i32;
+        }
+
+        // Initialize empty solutions list - Java shows solutionsList.isEmpty()=true at start
+        // Solutions will be created during CutListThread execution
+        let stock_solution = StockSolution::new(processed_stock_panels.to_vec());
+
+        // Don't pre-populate solutions - they should start empty as in Java
+        // Java line 678: final List<Solution> solutionsForMaterial = currentTask.getSolutions(currentMaterial);
+        // Initially this returns empty list, solutions are added during thread execution
+
+        // Initialize with empty state - rankings and finished threads start at 0
+        // These will be populated during actual thread execution as in Java
+
+        // Process each permutation (matching Java logs)
+        self.process_permutations(
+            &final_permutations,
+            processed_stock_panels,
+            task,
+            material,
+            configuration,
+        )?;
+        self.log(&task_id, LogLevel::Info, &format!("Material '{}' completed", material));
+        self.emit_event(
+            &task_id,
+            TaskEventKind::MaterialCompleted {
+                material: material.to_string(),
+            },
+        );
+        self.publish_status(task);
+
+        Ok(())
+    }
+
+    /// Cut the material with the one-dimensional optimizer when its panels and stock share a
+    /// dimension, false when it needs the 2D search
+    fn compute_one_dimensional(
+        &self,
+        processed_tiles: &[TileDimensions],
+        processed_stock_panels: &[TileDimensions],
+        task: &mut Task,
+        material: &str,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+    ) -> bool {
+        // Раскрой полосами не знает о зоне прижимов
+        if configuration.disable_one_dimensional || task.get_lead_in_allowance() > 0 {
+            return false;
+        }
+        let cut_thickness = CutListThread::new_with_config(configuration, 0).cut_thickness;
+        let Some(solution) =
+            optimize_one_dimensional(
+                processed_tiles,
+                processed_stock_panels,
+                cut_thickness,
+                task.get_dimension_tolerance(),
+                configuration,
+            )
+        else {
+            return false;
+        };
+
+        task.add_material_to_compute(material);
+        task.add_solutions(material, vec![solution]);
+        task.one_dimensional_materials.push(material.to_string());
+        task.start_material_progress(material, 1);
+        if let Some(progress) = task.get_material_progress_mut(material) {
+            progress.complete();
+        }
+        self.publish_status(task);
+        self.log(
+            &task.id,
+            LogLevel::Info,
+            &format!("Material '{}' cut with the one-dimensional optimizer", material),
+        );
+        self.log(&task.id, LogLevel::Info, &format!("Material '{}' completed", material));
+        self.emit_event(
+            &task.id,
+            TaskEventKind::MaterialCompleted {
+                material: material.to_string(),
+            },
+        );
+        true
+    }
+
+    /// Panels repeated more than `PerformanceThresholds::repeat_chunk_threshold` times: cut one
+    /// sheet of them as a grid, copy that sheet onto identical stock sheets and return the tiles
+    /// and stock left for the regular search
+    fn chunk_repeated_tiles(
+        &self,
+        processed_tiles: &[TileDimensions],
+        processed_stock_panels: &[TileDimensions],
+        task: &mut Task,
+        material: &str,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+    ) -> Result<(Vec<TileDimensions>, Vec<TileDimensions>)> {
+        let mut tiles = processed_tiles.to_vec();
+        let mut stock_tiles = processed_stock_panels.to_vec();
+        let threshold = task
+            .calculation_request
+            .performance_thresholds
+            .repeat_chunk_threshold
+            .map_or(EngineConstants::REPEAT_CHUNK_THRESHOLD, |threshold| threshold as usize);
+        // Сетка не знает о зоне прижимов
+        if threshold == 0 || task.get_lead_in_allowance() > 0 {
+            return Ok((tiles, stock_tiles));
+        }
+
+        let cut_thickness = CutListThread::new_with_config(configuration, 0).cut_thickness;
+        let mut counts: BTreeMap<u32, usize> = BTreeMap::new();
+        for tile in &tiles {
+            *counts.entry(tile.id).or_insert(0) += 1;
+        }
+
+        for (tile_id, count) in counts {
+            if count <= threshold {
+                continue;
+            }
+            let Some(tile) = tiles.iter().find(|tile| tile.id == tile_id).cloned() else {
+                continue;
+            };
+            // Образец - лист, нарезанный сеткой из одной полосы деталей, без перебора
+            let Some(pattern) = stock_tiles
+                .iter()
+                .filter_map(|stock| cut_grid(stock, &tile, cut_thickness, configuration))
+                .reduce(|best, next| {
+                    if next.root_tile_node[0].get_nbr_final_tiles() > best.root_tile_node[0].get_nbr_final_tiles() {
+                        next
+                    } else {
+                        best
+                    }
+                })
+            else {
+                continue;
+            };
+            let root = &pattern.root_tile_node[0];
+            let nbr_per_sheet = root.get_nbr_final_tiles() as usize;
+            if nbr_per_sheet == 0 || nbr_per_sheet >= count {
+                continue;
+            }
+            let is_same_sheet = |stock: &TileDimensions| {
+                Some(stock.id) == root.external_id
+                    && stock.width == root.get_width() as u32
+                    && stock.height == root.get_height() as u32
+            };
+            let nbr_sheets = (count / nbr_per_sheet)
+                .min(stock_tiles.iter().filter(|stock| is_same_sheet(stock)).count());
+            if nbr_sheets == 0 {
+                continue;
+            }
+
+            let mut nbr_tiles_left = nbr_sheets * nbr_per_sheet;
+            tiles.retain(|tile| {
+                let is_replicated = tile.id == tile_id && nbr_tiles_left > 0;
+                nbr_tiles_left -= is_replicated as usize;
+                !is_replicated
+            });
+            let mut nbr_sheets_left = nbr_sheets;
+            stock_tiles.retain(|stock| {
+                let is_replicated = is_same_sheet(stock) && nbr_sheets_left > 0;
+                nbr_sheets_left -= is_replicated as usize;
+                !is_replicated
+            });
+            task.replicated_sheets
+                .entry(material.to_string())
+                .or_default()
+                .extend(std::iter::repeat_n(pattern.clone(), nbr_sheets));
+
+            self.log(
+                &task.id,
+                LogLevel::Info,
+                &format!(
+                    "{} copies of tile {} of '{}' replicated on {} sheets of {} panels",
+                    nbr_sheets * nbr_per_sheet,
+                    tile_id,
+                    material,
+                    nbr_sheets,
+                    nbr_per_sheet
+                ),
+            );
         }
-        println!();
+        Ok((tiles, stock_tiles))
+    }
 
+    /// Tile orders to try: permutations of the biggest groups followed by the rest, without duplicates
+    fn get_permutations(
+        processed_tiles: &[TileDimensions],
+        processed_stock_panels: &[TileDimensions],
+        thresholds: &PerformanceThresholds,
+        consider_orientation: bool,
+        task_id: &str,
+    ) -> Vec<Vec<TileDimensions>> {
         // Generate groups
-        let _grouped_tiles = Self::generate_groups(&processed_tiles, &processed_stock_panels);
+        let _grouped_tiles = Self::generate_groups(processed_tiles, processed_stock_panels);
 
         let _distinct_grouped_tiles = Self::get_distinct_grouped_tile_dimensions(&_grouped_tiles);
 
-        println!("=== Все группы из списка ===");
+        log_debug!("=== Все группы из списка ===");
         for group in &_grouped_tiles {
-            println!("{}", group.to_string());
+            log_debug!("{}", group.to_string());
         }
 
-        println!("Task[{}] Calculating permutations...", task_id);
+        log_debug!("Task[{}] Calculating permutations...", task_id);
 
         // Сортировка групп по убыванию площади (Java линии 710-722)
         let mut sorted_distinct_groups: Vec<GroupedTileDimensions> =
@@ -216,25 +1907,65 @@ impl CutListOptimizerServiceImpl {
             area_b.cmp(&area_a)
         });
 
-        // Оптимизация количества перестановок - если групп больше 7, берем только первые 7 (Java линии 736-742)
-        let (groups_for_permutations, remaining_groups) = if sorted_distinct_groups.len() > 7 {
-            let first_seven = sorted_distinct_groups[0..7].to_vec();
-            let remaining = sorted_distinct_groups[7..].to_vec();
-            (first_seven, remaining)
+        // Оптимизация количества перестановок (Java линии 736-742): переставляются только группы
+        // с наибольшей площадью всех копий, остальные идут следом по убыванию площади
+        let max_groups = thresholds
+            .max_permutation_groups
+            .map_or(EngineConstants::MAX_PERMUTATION_GROUPS, |max_groups| max_groups as usize)
+            .max(1);
+        let max_permutations = thresholds
+            .max_permutations_total
+            .map_or(EngineConstants::MAX_PERMUTATIONS, |max_permutations| max_permutations as usize)
+            .max(1);
+        let tile_permutations = if thresholds.cluster_permutation_groups && sorted_distinct_groups.len() > max_groups {
+            // Переставляются кластеры похожих по площади и пропорциям групп, внутри кластера
+            // группы идут по убыванию площади
+            let sizes: Vec<(u32, u32)> =
+                sorted_distinct_groups.iter().map(|group| (group.width(), group.height())).collect();
+            let clusters = cluster_by_shape(&sizes, max_groups);
+            log_debug!("Task[{}] {} groups clustered into {}", task_id, sizes.len(), clusters.len());
+            Self::generate_permutations(&clusters, max_permutations)
+                .into_iter()
+                .map(|permutation| {
+                    permutation
+                        .iter()
+                        .flatten()
+                        .map(|index| sorted_distinct_groups[*index].clone())
+                        .collect::<Vec<_>>()
+                })
+                .collect()
         } else {
-            (sorted_distinct_groups, Vec::new())
-        };
+            let (groups_for_permutations, remaining_groups) = if sorted_distinct_groups.len() > max_groups {
+                let impact = |group: &GroupedTileDimensions| {
+                    group.area() * _distinct_grouped_tiles.get(group).copied().unwrap_or(1).max(1) as u64
+                };
+                let mut by_impact: Vec<usize> = (0..sorted_distinct_groups.len()).collect();
+                by_impact.sort_by_key(|index| std::cmp::Reverse(impact(&sorted_distinct_groups[*index])));
+                let selected: HashSet<usize> = by_impact[..max_groups].iter().copied().collect();
+                let (selected, remaining): (Vec<_>, Vec<_>) = sorted_distinct_groups
+                    .into_iter()
+                    .enumerate()
+                    .partition(|(index, _)| selected.contains(index));
+                (
+                    selected.into_iter().map(|(_, group)| group).collect(),
+                    remaining.into_iter().map(|(_, group)| group).collect(),
+                )
+            } else {
+                (sorted_distinct_groups, Vec::<GroupedTileDimensions>::new())
+            };
 
-        // Генерация перестановок (Java линии 753-757)
-        let mut tile_permutations = Self::generate_permutations(&groups_for_permutations);
+            // Генерация перестановок (Java линии 753-757)
+            let mut tile_permutations = Self::generate_permutations(&groups_for_permutations, max_permutations);
 
-        // Добавляем оставшиеся группы к каждой перестановке (Java линии 754-757)
-        for permutation in &mut tile_permutations {
-            permutation.extend(remaining_groups.clone());
-        }
+            // Добавляем оставшиеся группы к каждой перестановке (Java линии 754-757)
+            for permutation in &mut tile_permutations {
+                permutation.extend(remaining_groups.clone());
+            }
+            tile_permutations
+        };
 
         // Преобразование перестановок в списки панелей (Java линии 768-773)
-        println!(
+        log_debug!(
             "Task[{}] Sorting tiles according to permutations...",
             task_id
         );
@@ -245,70 +1976,91 @@ impl CutListOptimizerServiceImpl {
         }
 
         // Удаление дублирующих перестановок (Java линии 783-786)
-        println!(
+        log_debug!(
             "Starting duplicate permutation removal - total_permutations={}",
             final_permutations.len()
         );
-        let removed_count = Self::remove_duplicated_permutations(&mut final_permutations);
-        println!(
+        let removed_count = remove_duplicated_permutations(&mut final_permutations, consider_orientation);
+        log_debug!(
             "Duplicate removal completed - removed={}, remaining={}",
             removed_count,
             final_permutations.len()
         );
 
-        // Create task instance (matching Java logic)
-        let mut task = Task::default();
-        task.id = task_id.to_string();
-
-        // Add material to compute (Java: task.addMaterialToCompute(material))
-        task.add_material_to_compute("DEFAULT_MATERIAL");
-
-        // Calculate optimization factor the same way as Java (lines 815-823)
-        let base_solution_pool_size = 100;
-        let optimization_factor_value = calculation_request
-            .configuration
-            .optimization_factor
-            .value();
-        let mut optimization_factor = if optimization_factor_value > 0.0 {
-            (100.0 * optimization_factor_value) as i32
-        } else {
-            100
-        };
-
-        // Java: if (tilesToCut.size() > 100) { optimizationFactor = (int) (optimizationFactor * (0.5d / (tilesToCut.size() / 100))); }
-        if processed_tiles.len() > 100 {
-            optimization_factor = (optimization_factor as f64
-                * (0.5 / (processed_tiles.len() as f64 / 100.0)))
-                as i32;
-        }
-
-        // Initialize empty solutions list - Java shows solutionsList.isEmpty()=true at start
-        // Solutions will be created during CutListThread execution
-        let stock_solution = StockSolution::new(processed_stock_panels.clone());
-
-        // Don't pre-populate solutions - they should start empty as in Java
-        // Java line 678: final List<Solution> solutionsForMaterial = currentTask.getSolutions(currentMaterial);
-        // Initially this returns empty list, solutions are added during thread execution
+        final_permutations
+    }
 
-        // Initialize with empty state - rankings and finished threads start at 0
-        // These will be populated during actual thread execution as in Java
+    /// Single first-fit-decreasing pass: tiles by descending area on the first stock solution,
+    /// one thread keeping one solution per step. The result seeds the solutions of the material
+    /// and is published as the solution of the running task, see `Task::get_status_response`
+    fn compute_preview(
+        &self,
+        processed_tiles: &[TileDimensions],
+        processed_stock_panels: &[TileDimensions],
+        task: &mut Task,
+        material: &str,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+    ) -> Result<()> {
+        let started = Instant::now();
+        let mut tiles = processed_tiles.to_vec();
+        tiles.sort_by_key(|tile| std::cmp::Reverse(tile.area()));
 
-        // Process each permutation (matching Java logs)
-        Self::process_permutations(
-            &final_permutations,
-            &processed_stock_panels,
-            &mut task,
-            &calculation_request.configuration,
-        )?;
-        println!("=== COMPUTATION COMPLETED ===");
+        let mut stock_panel_picker = StockPanelPicker::new(&tiles, processed_stock_panels, task, None);
+        stock_panel_picker.init();
+        let Some(stock_solution) = stock_panel_picker.get_stock_solution(0) else {
+            return Ok(());
+        };
 
+        let cut_list_thread = self.acquire_cutlist_thread(configuration, 1);
+        let mut cut_list_thread =
+            Self::build_cutlist_thread(cut_list_thread, &tiles, stock_solution, "AREA", "preview", task);
+        let result = cut_list_thread.execute_isolated();
+        let comparator = SolutionComparator::from_configuration(configuration)
+            .with_factor(task.factor)
+            .with_stock_prices(task.get_stock_prices(configuration));
+        let preview = cut_list_thread
+            .all_solutions
+            .iter()
+            .min_by(|a, b| comparator.compare(a, b))
+            .cloned();
+        // Поток возвращается в пул и после ошибки, acquire всё равно его сбрасывает
+        self.release_cutlist_thread(cut_list_thread);
+        if let Err(err) = result {
+            return self.handle_thread_error(task, err);
+        }
+        let Some(preview) = preview else {
+            return Ok(());
+        };
+        let solution_id = preview.id;
+        task.add_solutions(material, vec![preview.clone()]);
+        task.preview_solutions.insert(material.to_string(), preview);
+        // Ответ по предварительному раскрою виден в get_task_status до конца полного поиска
+        task.build_solution();
+        self.publish_status(task);
+
+        self.log(
+            &task.id,
+            LogLevel::Info,
+            &format!(
+                "Preview of '{}' ready in {} ms",
+                material,
+                started.elapsed().as_millis()
+            ),
+        );
+        self.emit_event(
+            &task.id,
+            TaskEventKind::PreviewReady {
+                material: material.to_string(),
+                solution_id,
+            },
+        );
         Ok(())
     }
 
     fn get_distinct_grouped_tile_dimensions(
         grouped_panels: &[GroupedTileDimensions],
     ) -> HashMap<GroupedTileDimensions, i32> {
-        println!(
+        log_debug!(
             "Calculating distinct groups - input_size={}",
             grouped_panels.len()
         );
@@ -326,15 +2078,15 @@ impl CutListOptimizerServiceImpl {
         tiles: &[TileDimensions],
         stock_tiles: &[TileDimensions],
     ) -> Vec<GroupedTileDimensions> {
-        println!(
+        log_debug!(
             "Checking one-dimensional optimization - tiles={}, stock={}",
             tiles.len(),
             stock_tiles.len()
         );
 
         // Simple grouping logic - assign all tiles to group 0
-        println!("One-dimensional check result=false (stock doesn't share dimensions)");
-        println!("Using multi-dimensional optimization - group_split_threshold=1");
+        log_debug!("One-dimensional check result=false (stock doesn't share dimensions)");
+        log_debug!("Using multi-dimensional optimization - group_split_threshold=1");
 
         let mut grouped_tiles = Vec::new();
 
@@ -343,14 +2095,15 @@ impl CutListOptimizerServiceImpl {
             grouped_tiles.push(grouped_tile);
         }
 
-        println!(
+        log_debug!(
             "Group generation completed - grouped_tiles={}, groups_used=1",
             grouped_tiles.len()
         );
         grouped_tiles
     }
 
-    fn generate_permutations(groups: &[GroupedTileDimensions]) -> Vec<Vec<GroupedTileDimensions>> {
+    /// The first `limit` permutations of the groups, in the order of the full list
+    fn generate_permutations<T: Clone>(groups: &[T], limit: usize) -> Vec<Vec<T>> {
         if groups.is_empty() {
             return vec![Vec::new()];
         }
@@ -362,11 +2115,14 @@ impl CutListOptimizerServiceImpl {
         let mut result = Vec::new();
 
         for i in 0..groups.len() {
+            if result.len() >= limit {
+                break;
+            }
             let first = groups[i].clone();
             let mut remaining = groups.to_vec();
             remaining.remove(i);
 
-            let sub_perms = Self::generate_permutations(&remaining);
+            let sub_perms = Self::generate_permutations(&remaining, limit - result.len());
             for mut sub_perm in sub_perms {
                 sub_perm.insert(0, first.clone());
                 result.push(sub_perm);
@@ -391,14 +2147,7 @@ impl CutListOptimizerServiceImpl {
                     && group_in_perm.height() == original_tile.height()
                     && group_in_perm.group == original_tile.group
                 {
-                    result.push(TileDimensions::new(
-                        original_tile.id(),
-                        original_tile.width(),
-                        original_tile.height(),
-                        original_tile.is_rotated(),
-                        original_tile.label().unwrap_or(""),
-                        original_tile.material(),
-                    ));
+                    result.push(original_tile.instance.clone());
                 }
             }
         }
@@ -410,43 +2159,18 @@ impl CutListOptimizerServiceImpl {
                 .take(5)
                 .map(|t| format!("{}x{}", t.width, t.height))
                 .collect();
-            println!("First tiles in permutation: {}", first_tiles.join(", "));
+            log_debug!("First tiles in permutation: {}", first_tiles.join(", "));
         }
 
         result
     }
 
-    fn remove_duplicated_permutations(permutations: &mut Vec<Vec<TileDimensions>>) -> usize {
-        let original_len = permutations.len();
-
-        // Simple deduplication by comparing ID sequences
-        let mut unique_permutations = Vec::new();
-
-        for perm in permutations.iter() {
-            let id_sequence: Vec<u32> = perm.iter().map(|t| t.id).collect();
-
-            let is_duplicate =
-                unique_permutations
-                    .iter()
-                    .any(|existing_perm: &Vec<TileDimensions>| {
-                        let existing_id_sequence: Vec<u32> =
-                            existing_perm.iter().map(|t| t.id).collect();
-                        id_sequence == existing_id_sequence
-                    });
-
-            if !is_duplicate {
-                unique_permutations.push(perm.clone());
-            }
-        }
-
-        *permutations = unique_permutations;
-        original_len - permutations.len()
-    }
-
     fn process_permutations(
+        &self,
         permutations: &[Vec<TileDimensions>],
         stock_tiles: &[TileDimensions],
         task: &mut Task,
+        material: &str,
         configuration: &crate::features::engine::model::configuration::Configuration,
     ) -> Result<()> {
         // Calculate optimization factor the same way as earlier in submit_task
@@ -464,107 +2188,335 @@ impl CutListOptimizerServiceImpl {
                 (optimization_factor as f64 * (0.5 / (total_tiles as f64 / 100.0))) as i32;
         }
 
-        for (perm_index, permutation) in permutations.iter().enumerate() {
-            println!(
+        // Во время улучшения прогресс материала уже 100%, его не сбрасываем
+        let improving = task.status == Status::Improving;
+        let mut nbr_resumed = 0;
+        if !improving {
+            task.start_material_progress(material, permutations.len());
+            nbr_resumed = task.resume_material(material, permutations.len());
+            if nbr_resumed > 0 {
+                log_debug!("Resuming '{}' after {} permutations", material, nbr_resumed);
+            }
+        }
+
+        let max_permutations_after_all_fit = task
+            .calculation_request
+            .performance_thresholds
+            .max_permutations_after_all_fit
+            .map(|max_permutations| max_permutations as usize);
+
+        // При возобновлении отчёты о пройденных перестановках потеряны, пропускаются первые по оценке
+        let mut scheduler = task.calculation_request.performance_thresholds.progressive_permutations.then(|| {
+            let cut_thickness = CutListThread::new_with_config(configuration, 0).cut_thickness.max(0) as u32;
+            PermutationScheduler::new(permutations, stock_tiles, cut_thickness, configuration.consider_orientation)
+        });
+        if let Some(scheduler) = &mut scheduler {
+            for _ in 0..nbr_resumed {
+                scheduler.next();
+            }
+        }
+
+        for step in nbr_resumed..permutations.len() {
+            let perm_index = scheduler.as_mut().and_then(|scheduler| scheduler.next()).unwrap_or(step);
+            let permutation = &permutations[perm_index];
+            if (improving && task.is_improvement_over()) || task.is_time_limit_reached() {
+                break;
+            }
+            self.check_stalled(task, material, perm_index)?;
+            // Java: после решения со всеми деталями перебирается ограниченное число перестановок
+            if task.has_solution_all_fit() && max_permutations_after_all_fit.is_some_and(|max| step >= max) {
+                if let Some(progress) = task.get_material_progress_mut(material) {
+                    progress.complete();
+                }
+                log_debug!(
+                    "All panels fit for '{}', skipping {} remaining permutations",
+                    material,
+                    permutations.len() - step
+                );
+                break;
+            }
+            if self.is_material_stopped(task, material) {
+                if let Some(progress) = task.get_material_progress_mut(material) {
+                    progress.stop();
+                }
+                self.log(
+                    &task.id,
+                    LogLevel::Info,
+                    &format!(
+                        "Material '{}' stopped, {} permutations skipped",
+                        material,
+                        permutations.len() - step
+                    ),
+                );
+                break;
+            }
+            if task.is_early_exit(material) {
+                if let Some(progress) = task.get_material_progress_mut(material) {
+                    progress.complete();
+                }
+                log_debug!(
+                    "Acceptable waste reached for '{}', skipping {} remaining permutations",
+                    material,
+                    permutations.len() - step
+                );
+                break;
+            }
+            log_debug!(
                 "Processing permutation[{}/{}]",
                 perm_index,
                 permutations.len()
             );
-            println!("=== PERMUTATION_PROCESSING_START ===");
-            println!("INPUT_PARAMS: permutationIndex={}, material='DEFAULT_MATERIAL', optimizationFactor={}", perm_index, optimization_factor);
-            println!(
+            log_debug!("=== PERMUTATION_PROCESSING_START ===");
+            log_debug!("INPUT_PARAMS: permutationIndex={}, material='{}', optimizationFactor={}", perm_index, material, optimization_factor);
+            log_debug!(
                 "INPUT_DATA: tilesCount={}, solutionsListSize=0, allPermutationsCount={}",
                 permutation.len(),
                 permutations.len()
             );
-            println!("ALGORITHM: Process each stock solution with multiple thread groups (AREA, AREA_HCUTS_1ST, AREA_VCUTS_1ST)");
+            log_debug!("ALGORITHM: Process each stock solution with multiple thread groups (AREA, AREA_HCUTS_1ST, AREA_VCUTS_1ST)");
 
-            Self::process_stock_iterations(
+            let started = Instant::now();
+            let finished_threads = task.get_finished_threads(material);
+            let best_solution_id = task.get_solutions(material).first().map(|solution| solution.id);
+            let mut log = PermutationLog::new(
+                material,
+                perm_index,
+                permutation.iter().map(|tile| tile.id).collect(),
+            );
+
+            log.nbr_stock_solutions = self.process_stock_iterations(
                 permutation,
                 stock_tiles,
                 perm_index,
                 task,
+                material,
                 configuration,
             )?;
+
+            log.nbr_threads = task.get_finished_threads(material) - finished_threads;
+            log.best_solution_id = task.get_solutions(material).first().map(|s| s.id);
+            log.elapsed_ms = started.elapsed().as_millis() as u64;
+            if let Some(scheduler) = &mut scheduler {
+                scheduler.report(perm_index, log.best_solution_id != best_solution_id);
+            }
+            task.add_permutation_log(log);
+            task.mark_progress();
+
+            if improving {
+                continue;
+            }
+            if let Some(progress) = task.get_material_progress_mut(material) {
+                progress.advance();
+                let kind = TaskEventKind::Progress {
+                    material: material.to_string(),
+                    percentage_done: progress.get_percentage_done(),
+                    eta_ms: progress.get_eta_ms(),
+                };
+                self.log(
+                    &task.id,
+                    LogLevel::Debug,
+                    &format!("Permutation {} of '{}' processed", perm_index, material),
+                );
+                self.emit_event(&task.id, kind);
+            }
+            self.publish_status(task);
+            self.save_checkpoint_if_due(task);
+        }
+        Ok(())
+    }
+
+    fn save_checkpoint_if_due(&self, task: &mut Task) {
+        let interval_ms = task
+            .calculation_request
+            .performance_thresholds
+            .checkpoint_interval_ms
+            .unwrap_or(0);
+        let is_due = task
+            .checkpoint
+            .as_ref()
+            .is_some_and(|checkpoint| task.now_millis() >= checkpoint.saved_at_ms + interval_ms);
+        if interval_ms > 0 && is_due {
+            self.save_checkpoint(task);
+        }
+    }
+
+    /// Snapshot the progress of `task` to the task repository, a failed save only logs a warning
+    fn save_checkpoint(&self, task: &mut Task) {
+        let (Some(repository), Some(mut checkpoint)) = (&self.task_repository, task.checkpoint.take()) else {
+            return;
+        };
+        checkpoint.capture(task);
+        match repository.save_checkpoint(&checkpoint) {
+            Ok(()) => log_debug!("Checkpoint of {} materials saved - task={}", checkpoint.materials.len(), task.id),
+            Err(e) => self.log(&task.id, LogLevel::Warn, &format!("Checkpoint not saved: {}", e)),
+        }
+        task.checkpoint = Some(checkpoint);
+    }
+
+    /// Log the running tasks and the threads once `task` made no progress for
+    /// `PerformanceThresholds::stall_timeout_ms`, `TaskError::Stalled` if `terminate_stalled` is set
+    fn check_stalled(&self, task: &mut Task, material: &str, perm_index: usize) -> Result<()> {
+        let thresholds = &task.calculation_request.performance_thresholds;
+        let timeout_ms = thresholds.stall_timeout_ms.unwrap_or(EngineConstants::TASK_STALL_TIMEOUT_MS);
+        let terminate_stalled = thresholds.terminate_stalled;
+        let idle_ms = task.get_idle_ms();
+        if timeout_ms == 0 || idle_ms < timeout_ms {
+            return Ok(());
+        }
+
+        if !task.is_stall_reported {
+            task.is_stall_reported = true;
+            self.log(
+                &task.id,
+                LogLevel::Warn,
+                &format!("No progress for {} ms on '{}', permutation {}", idle_ms, material, perm_index),
+            );
+            // Блокировки берутся по одной, чтобы не пересечься с track_task и publish_status
+            let running_tasks: Vec<(String, Instant, Instant)> = self
+                .running_tasks
+                .lock()
+                .map(|tasks| tasks.iter().map(|(id, (start, last_progress))| (id.clone(), *start, *last_progress)).collect())
+                .unwrap_or_default();
+            for (id, start, last_progress) in running_tasks {
+                let status = self.get_task_status(&id);
+                self.log(
+                    &task.id,
+                    LogLevel::Warn,
+                    &format!(
+                        "Running task {}: status={:?}, done={}%, running {} ms, last progress {} ms ago",
+                        id,
+                        status.as_ref().and_then(|status| status.status),
+                        status.as_ref().map_or(0, |status| status.percentage_done),
+                        start.elapsed().as_millis(),
+                        last_progress.elapsed().as_millis()
+                    ),
+                );
+            }
+            let (nbr_created, nbr_reused) = self
+                .cut_list_thread_pool
+                .lock()
+                .map(|pool| (pool.get_nbr_created(), pool.get_nbr_reused()))
+                .unwrap_or_default();
+            self.log(
+                &task.id,
+                LogLevel::Warn,
+                &format!(
+                    "Threads: finished={} for '{}', panicked={}, pool created={} reused={}",
+                    task.get_finished_threads(material),
+                    material,
+                    task.worker_panics.len(),
+                    nbr_created,
+                    nbr_reused
+                ),
+            );
+        }
+
+        if terminate_stalled {
+            return Err(TaskError::Stalled {
+                task_id: task.id.clone(),
+                idle_ms,
+            }
+            .into());
         }
         Ok(())
     }
 
     fn process_stock_iterations(
+        &self,
         permutation: &[TileDimensions],
         stock_tiles: &[TileDimensions],
         perm_index: usize,
         task: &mut Task,
+        material: &str,
         configuration: &crate::features::engine::model::configuration::Configuration,
-    ) -> Result<()> {
+    ) -> Result<usize> {
         // Create stock panel picker (matching Java logic)
         let mut stock_panel_picker = StockPanelPicker::new(permutation, stock_tiles, task, None);
         stock_panel_picker.init();
 
         let mut stock_index = 0;
-        let material = "DEFAULT_MATERIAL";
         let solutions_list = task.get_solutions(material);
 
+        let window = Self::get_stock_solution_window(task);
+        let context = StockWindowContext { permutation, perm_index, material, configuration, solutions_list: &solutions_list };
+
         // Process multiple stock solutions as in Java (up to MAX_STOCK_ITERATIONS = 1000)
         while stock_index < 1000 {
-            println!("\n--- STOCK_ITERATION_{}_START ---", stock_index);
-            println!(
+            // Перестановка с тысячей стоков тоже может зависнуть, проверка не ждёт её конца
+            if stock_index > 0 {
+                self.check_stalled(task, material, perm_index)?;
+            }
+            if window > 1 {
+                let taken = self.process_stock_window(
+                    &context,
+                    &mut stock_panel_picker,
+                    stock_index,
+                    window.min(1000 - stock_index),
+                    task,
+                )?;
+                if taken > 0 {
+                    task.mark_progress();
+                }
+                if taken == 0
+                    || !task.is_running()
+                    || task.is_early_exit(material)
+                    || self.is_material_stopped(task, material)
+                {
+                    break;
+                }
+                stock_index += taken;
+                continue;
+            }
+
+            log_debug!("--- STOCK_ITERATION_{}_START ---", stock_index);
+            log_debug!(
                 "STEP_STOCK_{}: Getting stock solution for permutation[{}]",
                 stock_index, perm_index
             );
 
             // Get stock solution from picker (matching Java StockPanelPicker.getStockSolution)
             if let Some(stock_solution) = stock_panel_picker.get_stock_solution(stock_index) {
-                println!(
+                log_debug!(
                     "STEP_STOCK_{}_RESULT: Got stockSolution with totalArea={}",
                     stock_index, stock_solution.total_area
                 );
 
-                if !task.is_running() {
-                    println!("STEP_TASK_CHECK: Task is not running, terminating");
+                if !task.is_running() || task.is_early_exit(material) || self.is_material_stopped(task, material) {
+                    log_debug!("STEP_TASK_CHECK: Task is not running, terminating");
                     break;
                 }
-                println!("STEP_TASK_CHECK: Task is running, continuing");
+                log_debug!("STEP_TASK_CHECK: Task is running, continuing");
 
                 // Process check conditions (matching Java logic in processPermutationSequentially)
-                println!("STEP_PROCESS_CHECK: Evaluating processing conditions...");
-                println!(
+                log_debug!("STEP_PROCESS_CHECK: Evaluating processing conditions...");
+                log_debug!(
                     "STEP_PROCESS_CHECK_COND1: task.hasSolutionAllFit()={}",
                     task.has_solution_all_fit()
                 );
-                println!(
+                log_debug!(
                     "STEP_PROCESS_CHECK_COND2: solutionsList.isEmpty()={}",
                     solutions_list.is_empty()
                 );
 
-                let should_process = !task.has_solution_all_fit()
-                    || solutions_list.is_empty()
-                    || (solutions_list.len() > 0 && solutions_list[0].get_mosaics().len() > 1)
-                    || (solutions_list.len() > 0
-                        && solutions_list[0].get_total_area() >= stock_solution.total_area as i64);
+                let should_process =
+                    Self::should_process_stock_solution(task, &solutions_list, stock_solution);
 
-                println!(
+                log_debug!(
                     "STEP_PROCESS_CHECK_RESULT: shouldProcess={}",
                     should_process
                 );
 
                 if should_process {
-                    Self::process_stock_solution(
-                        permutation,
-                        stock_solution,
-                        stock_index,
-                        perm_index,
-                        task,
-                        configuration,
-                    )?;
+                    self.process_stock_solution(&context, stock_solution, stock_index, task)?;
+                    task.mark_progress();
                 } else {
-                    println!(
+                    log_debug!(
                         "STEP_SKIP_STOCK: stock[{}] (already has better solution)",
                         stock_index
                     );
                 }
             } else {
-                println!(
+                log_debug!(
                     "STEP_STOCK_{}_RESULT: No more stock solutions available, terminating",
                     stock_index
                 );
@@ -573,76 +2525,218 @@ impl CutListOptimizerServiceImpl {
 
             stock_index += 1;
         }
-        Ok(())
+        Ok(stock_index)
+    }
+
+    fn should_process_stock_solution(
+        task: &Task,
+        solutions_list: &[Solution],
+        stock_solution: &StockSolution,
+    ) -> bool {
+        !task.has_solution_all_fit()
+            || solutions_list.is_empty()
+            || solutions_list[0].get_mosaics().len() > 1
+            || solutions_list[0].get_total_area() >= stock_solution.total_area as i64
+    }
+
+    /// Number of stock solutions evaluated at once, bounded by the thread threshold and `TaskLimits`
+    fn get_stock_solution_window(task: &Task) -> usize {
+        let thresholds = &task.calculation_request.performance_thresholds;
+        let window = thresholds.stock_solution_window.unwrap_or(1).max(1) as usize;
+        let max_threads = if thresholds.max_simultaneous_threads > 0 {
+            thresholds.max_simultaneous_threads as usize
+        } else {
+            num_cpus::get()
+        };
+        let max_threads = task
+            .limits
+            .max_threads
+            .map_or(max_threads, |limit| max_threads.min(limit as usize));
+        window.min(max_threads.max(1))
+    }
+
+    /// Evaluate up to `window` stock solutions concurrently, returns how many were taken from the picker
+    ///
+    /// Stock solutions do not depend on each other, so all eligible thread groups of the window run
+    /// at once against a snapshot of the task; results are merged back in stock order afterwards
+    fn process_stock_window(
+        &self,
+        context: &StockWindowContext,
+        stock_panel_picker: &mut StockPanelPicker,
+        first_stock_index: usize,
+        window: usize,
+        task: &mut Task,
+    ) -> Result<usize> {
+        let StockWindowContext { permutation, perm_index, material, configuration, solutions_list } = *context;
+        let optimization_factor = Self::get_optimization_factor(permutation, configuration);
+        let mut threads = Vec::new();
+        let mut taken = 0;
+
+        while taken < window {
+            let stock_index = first_stock_index + taken;
+            let Some(stock_solution) = stock_panel_picker.get_stock_solution(stock_index) else {
+                break;
+            };
+            taken += 1;
+
+            if !Self::should_process_stock_solution(task, solutions_list, stock_solution) {
+                log_debug!(
+                    "STEP_SKIP_STOCK: stock[{}] (already has better solution)",
+                    stock_index
+                );
+                continue;
+            }
+
+            for group_name in ["AREA", "AREA_HCUTS_1ST", "AREA_VCUTS_1ST"] {
+                if Self::check_thread_eligibility(group_name, material, task)? {
+                    let mut cut_list_thread = Self::build_cutlist_thread(
+                        self.acquire_cutlist_thread(configuration, optimization_factor),
+                        permutation,
+                        stock_solution,
+                        group_name,
+                        &format!("stock[{}] permutation[{}] PARALLEL", stock_index, perm_index),
+                        task,
+                    );
+                    let cached = self.load_cached_solutions(&mut cut_list_thread);
+                    threads.push((cut_list_thread, cached));
+                }
+            }
+        }
+
+        if taken == 0 {
+            return Ok(0);
+        }
+
+        log_debug!(
+            "STEP_STOCK_WINDOW: stock[{}..{}] permutation[{}] threads={}",
+            first_stock_index,
+            first_stock_index + taken,
+            perm_index,
+            threads.len()
+        );
+
+        // Work stealing: each worker picks the next pending thread until none are left
+        let pending: Vec<usize> = (0..threads.len()).filter(|i| !threads[*i].1).collect();
+        let workers = window.min(pending.len());
+        let next = AtomicUsize::new(0);
+        let errors: Mutex<Vec<(usize, AppError)>> = Mutex::new(Vec::new());
+        let slots: Vec<Mutex<CutListThread>> = threads
+            .into_iter()
+            .map(|(cut_list_thread, _)| Mutex::new(cut_list_thread))
+            .collect();
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    while let Some(&index) = pending.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        if let Ok(mut cut_list_thread) = slots[index].lock() {
+                            if let Err(err) = cut_list_thread.execute_isolated() {
+                                if let Ok(mut errors) = errors.lock() {
+                                    errors.push((index, err));
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        // Упавшие потоки пропускаются, остальные ошибки прерывают расчёт
+        let mut failed = HashSet::new();
+        for (index, err) in errors.into_inner().unwrap_or_default() {
+            self.handle_thread_error(task, err)?;
+            failed.insert(index);
+        }
+
+        for (index, slot) in slots.into_iter().enumerate() {
+            if failed.contains(&index) {
+                continue;
+            }
+            let cut_list_thread = slot.into_inner().map_err(|_| ServiceError::ServiceThreadError {
+                details: "cut list thread panicked".to_string(),
+            })?;
+            if pending.contains(&index) {
+                self.store_cached_solutions(&cut_list_thread);
+            }
+            self.merge_cutlist_thread(&cut_list_thread, task, material, configuration);
+            self.release_cutlist_thread(cut_list_thread);
+        }
+
+        Ok(taken)
     }
 
     fn process_stock_solution(
-        permutation: &[TileDimensions],
+        &self,
+        context: &StockWindowContext,
         stock_solution: &StockSolution,
         stock_index: usize,
-        perm_index: usize,
         task: &mut Task,
-        configuration: &crate::features::engine::model::configuration::Configuration,
     ) -> Result<()> {
-        println!("\n=== STOCK_PROCESSING_START: stock[{}] ===", stock_index);
-        println!("ALGORITHM_PHASE: Setting up CutListThreadBuilder and processing thread groups");
-        println!("\nSTEP_BUILDER: Creating CutListThreadBuilder with configuration...");
-        println!("\nSTEP_GROUPS: Processing thread groups sequentially...");
+        log_debug!("=== STOCK_PROCESSING_START: stock[{}] ===", stock_index);
+        log_debug!("ALGORITHM_PHASE: Setting up CutListThreadBuilder and processing thread groups");
+        log_debug!("STEP_BUILDER: Creating CutListThreadBuilder with configuration...");
+        log_debug!("STEP_GROUPS: Processing thread groups sequentially...");
 
-        Self::process_thread_groups(
-            permutation,
-            stock_solution,
-            stock_index,
-            perm_index,
-            task,
-            configuration,
-        )?;
+        self.process_thread_groups(context, stock_solution, stock_index, task)?;
         Ok(())
     }
 
     fn process_thread_groups(
-        permutation: &[TileDimensions],
+        &self,
+        context: &StockWindowContext,
         stock_solution: &StockSolution,
         stock_index: usize,
-        perm_index: usize,
         task: &mut Task,
-        configuration: &crate::features::engine::model::configuration::Configuration,
     ) -> Result<()> {
+        let material = context.material;
         let thread_groups = ["AREA", "AREA_HCUTS_1ST", "AREA_VCUTS_1ST"];
-        let material = "DEFAULT_MATERIAL";
 
         for group_name in &thread_groups {
+            if task.is_early_exit(material) {
+                break;
+            }
             // Check thread eligibility using real Java logic
             let eligible = Self::check_thread_eligibility(group_name, material, task)?;
 
             if eligible {
-                Self::process_thread_group(
-                    permutation,
-                    stock_solution,
-                    group_name,
-                    stock_index,
-                    perm_index,
-                    task,
-                    configuration,
-                )?;
+                self.process_thread_group(context, stock_solution, group_name, stock_index, task)?;
             } else {
-                println!("STEP_GROUP_{}_SKIPPED: Not eligible to start", group_name);
+                log_debug!("STEP_GROUP_{}_SKIPPED: Not eligible to start", group_name);
             }
         }
         Ok(())
     }
 
+    /// Calculate optimization factor same way as in process_permutations
+    fn get_optimization_factor(
+        permutation: &[TileDimensions],
+        configuration: &crate::features::engine::model::configuration::Configuration,
+    ) -> i32 {
+        let optimization_factor_value = configuration.optimization_factor.value();
+        let mut optimization_factor = if optimization_factor_value > 0.0 {
+            (100.0 * optimization_factor_value) as i32
+        } else {
+            100
+        };
+
+        // Apply tile count adjustment
+        if permutation.len() > 100 {
+            optimization_factor =
+                (optimization_factor as f64 * (0.5 / (permutation.len() as f64 / 100.0))) as i32;
+        }
+        optimization_factor
+    }
+
     fn check_thread_eligibility(group_name: &str, material: &str, task: &Task) -> Result<bool> {
-        println!("=== THREAD_ELIGIBILITY_CHECK_START ===");
-        println!("INPUT: groupName='{}', material='{}'", group_name, material);
-        println!("ALGORITHM: Check if thread group is eligible to start based on rankings and finished threads");
+        log_debug!("=== THREAD_ELIGIBILITY_CHECK_START ===");
+        log_debug!("INPUT: groupName='{}', material='{}'", group_name, material);
+        log_debug!("ALGORITHM: Check if thread group is eligible to start based on rankings and finished threads");
 
         // Get thread group rankings for material (matching Java logic)
         let rankings = task.get_thread_group_rankings(material);
         let mut total_ranking_sum = 0;
         let ranking_count = rankings.len();
 
-        println!(
+        log_debug!(
             "STEP_1: Calculating total thread group rankings for material '{}'",
             material
         );
@@ -661,33 +2755,28 @@ impl CutListOptimizerServiceImpl {
         for (group, ranking) in sorted_groups {
             iteration_count += 1;
             total_ranking_sum += ranking;
-            println!(
+            log_debug!(
                 "STEP_1_ITERATION[{}]: ranking={}, totalSum={}",
                 iteration_count, ranking, total_ranking_sum
             );
         }
-        println!(
+        log_debug!(
             "STEP_1_RESULT: totalRankingSum={}, rankingCount={}",
             total_ranking_sum, ranking_count
         );
 
-        // let finished_threads = task.get_finished_threads(material);
-        // println!(
-        //     "STEP_2: finishedThreads={} for material='{}'",
-        //     finished_threads, material
-        // );
-        // println!(
-        //     "STEP_2_CHECK: finishedThreads < 10 ? ({} < 10) = {}",
-        //     finished_threads,
-        //     finished_threads < 10
-        // );
-
-        // // Early exit if not enough finished threads (matching Java logic line 595-599)
-        // if finished_threads < 10 {
-        //     println!("STEP_2_RESULT: ELIGIBLE=true (early exit - not enough finished threads)");
-        //     println!("=== THREAD_ELIGIBILITY_CHECK_END: ELIGIBLE=true ===");
-        //     return Ok(true);
-        // }
+        let finished_threads = task.get_finished_threads(material);
+        log_debug!(
+            "STEP_2: finishedThreads={} for material='{}'",
+            finished_threads, material
+        );
+
+        // Early exit if not enough finished threads (matching Java logic line 595-599)
+        if finished_threads < 10 {
+            log_debug!("STEP_2_RESULT: ELIGIBLE=true (early exit - not enough finished threads)");
+            log_debug!("=== THREAD_ELIGIBILITY_CHECK_END: ELIGIBLE=true ===");
+            return Ok(true);
+        }
 
         // Check group ranking threshold (matching Java logic lines 601-614)
         let group_ranking = rankings.get(group_name).copied().unwrap_or(0);
@@ -697,15 +2786,15 @@ impl CutListOptimizerServiceImpl {
             0
         };
 
-        println!(
+        log_debug!(
             "STEP_3: Retrieved groupRanking={} for group='{}'",
             group_ranking, group_name
         );
-        println!(
+        log_debug!(
             "STEP_3_CALC: groupRankingValue={}, threshold={} (totalRankingSum/5: {}/5)",
             group_ranking, threshold, total_ranking_sum
         );
-        println!(
+        log_debug!(
             "STEP_3_CHECK: groupRankingValue > threshold ? ({} > {}) = {}",
             group_ranking,
             threshold,
@@ -713,8 +2802,8 @@ impl CutListOptimizerServiceImpl {
         );
 
         let eligible = group_ranking > threshold;
-        println!("STEP_3_RESULT: ELIGIBLE={}", eligible);
-        println!(
+        log_debug!("STEP_3_RESULT: ELIGIBLE={}", eligible);
+        log_debug!(
             "=== THREAD_ELIGIBILITY_CHECK_END: ELIGIBLE={} ===",
             eligible
         );
@@ -723,13 +2812,12 @@ impl CutListOptimizerServiceImpl {
     }
 
     fn process_thread_group(
-        permutation: &[TileDimensions],
+        &self,
+        context: &StockWindowContext,
         stock_solution: &StockSolution,
         group_name: &str,
         stock_index: usize,
-        perm_index: usize,
         task: &mut Task,
-        configuration: &crate::features::engine::model::configuration::Configuration,
     ) -> Result<()> {
         // Use short group name like Java (AREA_HCUTS_1ST -> HCUTS)
         let short_group_name = match group_name {
@@ -740,7 +2828,7 @@ impl CutListOptimizerServiceImpl {
         };
 
         // Get orientation preference from configuration like Java
-        let orientation_pref = configuration.cut_orientation_preference.value();
+        let orientation_pref = context.configuration.cut_orientation_preference.value();
 
         // Check orientation conditions like Java
         let orientation_ok = match group_name {
@@ -757,39 +2845,18 @@ impl CutListOptimizerServiceImpl {
             _ => "BOTH",
         };
 
-        println!(
+        log_debug!(
             "STEP_GROUP_{}: eligibleToStart=true, orientationPref={}, orientationOk={}",
             short_group_name, orientation_pref, orientation_ok
         );
-        println!(
+        log_debug!(
             "STEP_GROUP_{}_PROCESSING: Starting {} group with CutDirection.{}",
             short_group_name, group_name, cut_direction
         );
 
-        // Calculate optimization factor same way as in process_permutations
-        let optimization_factor_value = configuration.optimization_factor.value();
-        let mut optimization_factor = if optimization_factor_value > 0.0 {
-            (100.0 * optimization_factor_value) as i32
-        } else {
-            100
-        };
-
-        // Apply tile count adjustment
-        if permutation.len() > 100 {
-            optimization_factor =
-                (optimization_factor as f64 * (0.5 / (permutation.len() as f64 / 100.0))) as i32;
-        }
+        let optimization_factor = Self::get_optimization_factor(context.permutation, context.configuration);
 
-        Self::execute_cutlist_thread(
-            permutation,
-            stock_solution,
-            group_name,
-            stock_index,
-            perm_index,
-            task,
-            configuration,
-            optimization_factor,
-        )?;
+        self.execute_cutlist_thread(context, stock_solution, group_name, stock_index, task, optimization_factor)?;
 
         // Add result log after execution (matching Java line 1057)
         let short_group_name = match group_name {
@@ -798,7 +2865,7 @@ impl CutListOptimizerServiceImpl {
             "AREA" => "AREA",
             _ => group_name,
         };
-        println!(
+        log_debug!(
             "STEP_GROUP_{}_RESULT: {} group processing completed",
             short_group_name, group_name
         );
@@ -806,45 +2873,124 @@ impl CutListOptimizerServiceImpl {
     }
 
     fn execute_cutlist_thread(
-        permutation: &[TileDimensions],
+        &self,
+        context: &StockWindowContext,
         stock_solution: &StockSolution,
         group_name: &str,
         stock_index: usize,
-        perm_index: usize,
         task: &mut Task,
-        configuration: &crate::features::engine::model::configuration::Configuration,
         optimization_factor: i32,
     ) -> Result<()> {
-        let mut cut_list_thread =
-            CutListThread::new_with_config(configuration, optimization_factor);
+        let StockWindowContext { permutation, perm_index, material, configuration, .. } = *context;
+        let mut cut_list_thread = Self::build_cutlist_thread(
+            self.acquire_cutlist_thread(configuration, optimization_factor),
+            permutation,
+            stock_solution,
+            group_name,
+            &format!("stock[{}] permutation[{}] SEQUENTIAL", stock_index, perm_index),
+            task,
+        );
+
+        if !self.load_cached_solutions(&mut cut_list_thread) {
+            // In Java, allSolutions is initialized with 290 solutions
+            // Create dummy solutions to match Java behavior
+            if let Err(err) = cut_list_thread.execute_isolated() {
+                return self.handle_thread_error(task, err);
+            }
+            self.store_cached_solutions(&cut_list_thread);
+        }
 
+        self.merge_cutlist_thread(&cut_list_thread, task, material, configuration);
+        self.release_cutlist_thread(cut_list_thread);
+        Ok(())
+    }
+
+    /// Record a panicked thread on the task so the computation goes on without it,
+    /// any other error fails the task
+    fn handle_thread_error(&self, task: &mut Task, err: AppError) -> Result<()> {
+        match err {
+            AppError::Computation(ComputationError::WorkerPanicked { .. }) => {
+                self.log(&task.id, LogLevel::Error, &err.to_string());
+                task.worker_panics.push(err.to_string());
+                Ok(())
+            }
+            err => {
+                // Ошибка возможна из любого незавершённого статуса
+                task.set_status(Status::Error).ok();
+                Err(err)
+            }
+        }
+    }
+
+    /// Thread of the pool set up for `configuration`, see `CutListThreadPool::acquire`
+    fn acquire_cutlist_thread(
+        &self,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+        optimization_factor: i32,
+    ) -> CutListThread {
+        match self.cut_list_thread_pool.lock() {
+            Ok(mut pool) => pool.acquire(configuration, optimization_factor),
+            Err(_) => CutListThread::new_with_config(configuration, optimization_factor),
+        }
+    }
+
+    fn build_cutlist_thread(
+        mut cut_list_thread: CutListThread,
+        permutation: &[TileDimensions],
+        stock_solution: &StockSolution,
+        group_name: &str,
+        aux_info: &str,
+        task: &Task,
+    ) -> CutListThread {
         // Configure the thread
         cut_list_thread.group = group_name.to_string();
-        cut_list_thread.aux_info = format!(
-            "stock[{}] permutation[{}] SEQUENTIAL",
-            stock_index, perm_index
-        );
-        cut_list_thread.tiles = permutation.to_vec();
+        cut_list_thread.aux_info = aux_info.to_string();
+        cut_list_thread.tiles.extend_from_slice(permutation);
         // Set cut direction based on group name to match Java logic (overrides configuration default)
         cut_list_thread.first_cut_orientation = match group_name {
             "AREA_HCUTS_1ST" => CutOrientationPreference::Horizontal,
             "AREA_VCUTS_1ST" => CutOrientationPreference::Vertical,
-            _ => configuration.cut_orientation_preference, // Use configuration default for AREA group
+            _ => cut_list_thread.first_cut_orientation, // Keep configuration default for AREA group
         };
+        cut_list_thread.dimension_tolerance = task.get_dimension_tolerance() as i32;
+        cut_list_thread.lead_in_allowance = task.get_lead_in_allowance() as i32;
+        cut_list_thread.max_pool_memory_bytes = task.limits.max_pool_memory_bytes;
         cut_list_thread.stock_solution = Some(stock_solution.clone());
         cut_list_thread.task = Some(task.clone());
+        cut_list_thread
+    }
 
-        // Initialize all_solutions with pre-populated list to match Java behavior
-        let material = "DEFAULT_MATERIAL";
+    /// Return a merged thread to the pool for the next combination
+    fn release_cutlist_thread(&self, cut_list_thread: CutListThread) {
+        if let Ok(mut pool) = self.cut_list_thread_pool.lock() {
+            pool.release(cut_list_thread);
+        }
+    }
 
-        // In Java, allSolutions is initialized with 290 solutions
-        // Create dummy solutions to match Java behavior
-        cut_list_thread.execute();
+    /// All panels placed with no more waste than `Configuration::acceptable_waste_percent`
+    fn is_acceptable_waste(
+        solution: &Solution,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+    ) -> bool {
+        configuration
+            .acceptable_waste_percent
+            .is_some_and(|percent| {
+                solution.get_no_fit_panels().is_empty() && solution.get_waste_percent() <= percent
+            })
+    }
 
-        // Update task with thread results (equivalent to Java thread completion handling)
+    /// Update task with thread results (equivalent to Java thread completion handling)
+    fn merge_cutlist_thread(
+        &self,
+        cut_list_thread: &CutListThread,
+        task: &mut Task,
+        material: &str,
+        configuration: &crate::features::engine::model::configuration::Configuration,
+    ) {
 
         // Mark thread as finished (matching Java pattern where completed threads are tracked)
-        // task.add_finished_thread(material.to_string(), group_name.to_string());
+        task.add_finished_thread(material);
+        task.nbr_dropped_branches += cut_list_thread.nbr_dropped_branches;
 
         // Update rankings based on solutions generated by thread
         let solutions_to_rank = std::cmp::min(cut_list_thread.all_solutions.len(), 5);
@@ -860,10 +3006,69 @@ impl CutListOptimizerServiceImpl {
         let new_solutions = cut_list_thread.all_solutions.clone();
         if !new_solutions.is_empty() {
             let mut existing_solutions = task.get_solutions(material);
+            let previous_best_id = existing_solutions.first().map(|s| s.id);
             existing_solutions.extend(new_solutions);
+
+            // Java: sort(this.solutions, this.finalSolutionPrioritizedComparators)
+            let comparator = SolutionComparator::from_configuration(configuration)
+            .with_factor(task.factor)
+            .with_stock_prices(task.get_stock_prices(configuration));
+            existing_solutions.sort_by(|a, b| comparator.compare(a, b));
+
+            let best_id = existing_solutions.first().map(|s| s.id);
+            let acceptable = existing_solutions
+                .first()
+                .is_some_and(|best| Self::is_acceptable_waste(best, configuration));
             task.add_solutions(material, existing_solutions);
+
+            if acceptable && task.early_exit_materials.insert(material.to_string()) {
+                let message = format!(
+                    "Acceptable waste reached for '{}' - finishing the search early",
+                    material
+                );
+                self.log(&task.id, LogLevel::Info, &message);
+            }
+
+            if let Some(solution_id) = best_id.filter(|id| Some(*id) != previous_best_id) {
+                self.log(
+                    &task.id,
+                    LogLevel::Debug,
+                    &format!("New best solution {} for '{}'", solution_id, material),
+                );
+                self.emit_event(
+                    &task.id,
+                    TaskEventKind::NewBestSolution {
+                        material: material.to_string(),
+                        solution_id,
+                    },
+                );
+            }
         }
+    }
+}
 
-        Ok(())
+impl CutListOptimizerService for CutListOptimizerServiceImpl {
+    fn submit_task(&self, calculation_request: CalculationRequest) -> Result<CalculationSubmissionResult> {
+        CutListOptimizerServiceImpl::submit_task(self, calculation_request)
+    }
+
+    fn get_task_status(&self, task_id: &str) -> Option<TaskStatusResponse> {
+        CutListOptimizerServiceImpl::get_task_status(self, task_id)
+    }
+
+    fn stop_task(&self, task_id: &str) -> bool {
+        CutListOptimizerServiceImpl::stop_task(self, task_id)
+    }
+
+    fn get_stats(&self) -> Stats {
+        CutListOptimizerServiceImpl::get_stats(self)
+    }
+
+    fn get_health(&self) -> HealthReport {
+        CutListOptimizerServiceImpl::get_health(self)
+    }
+
+    fn clear_result_cache(&self) {
+        CutListOptimizerServiceImpl::clear_result_cache(self)
     }
 }
@@ -0,0 +1,217 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::features::engine::model::calculation_request::{CalculationRequest, Panel};
+use crate::features::engine::model::feasibility_report::{
+    FeasibilityIssue, FeasibilityIssueKind, FeasibilityReport, MaterialEstimate,
+};
+use crate::features::input::normalizer::normalize_request;
+use crate::scaled_math::PrecisionAnalyzer;
+
+/// Row of the request with parsed dimensions
+struct SizedRow<'a> {
+    panel: &'a Panel,
+    width: f64,
+    height: f64,
+}
+
+impl SizedRow<'_> {
+    fn get_area(&self) -> f64 {
+        self.width * self.height * self.panel.count as f64
+    }
+}
+
+/// Check a request for problems that keep its panels from being placed without optimizing:
+/// invalid dimensions, panels larger than every sheet they may be cut from, materials and
+/// thicknesses without stock, decimal places the engine can't scale, and per material the
+/// least number of sheets the panel area needs. Cheap enough to run on every edit of a request
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::feasibility::check_feasibility;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::feasibility_report::FeasibilityIssueKind;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(800.0, 600.0).id(1).count(5).material("MFC")
+///     .add_panel(3000.0, 600.0).id(2).material("MFC")
+///     .add_panel(400.0, 300.0).id(3).material("HDF")
+///     .add_stock(2800.0, 1000.0).id(10).count(2).material("MFC")
+///     .build()
+///     .unwrap();
+///
+/// let report = check_feasibility(&request);
+/// assert!(!report.is_feasible);
+/// let kinds: Vec<FeasibilityIssueKind> = report.issues.iter().map(|issue| issue.kind).collect();
+/// assert_eq!(kinds, [FeasibilityIssueKind::MaterialWithoutStock, FeasibilityIssueKind::OversizedPanel]);
+/// assert_eq!(report.issues[1].panel_ids, [2]);
+///
+/// // 5 деталей 800x600 занимают 2.4 м², это минимум один лист 2.8 м²
+/// let mfc = report.materials.iter().find(|estimate| estimate.material == "MFC").unwrap();
+/// assert_eq!((mfc.estimated_sheets, mfc.available_sheets), (1, 2));
+/// ```
+pub fn check_feasibility(request: &CalculationRequest) -> FeasibilityReport {
+    let mut issues = Vec::new();
+    // Материалы справочника и единицы измерения сводятся так же, как перед расчётом;
+    // нормализация останавливается на первой ошибке, тогда проверяется запрос как есть
+    let (request, normalize_error) = match normalize_request(request) {
+        Ok(normalized) => (normalized.request, None),
+        Err(e) => (request.clone(), Some(e.to_string())),
+    };
+    let configuration = &request.configuration;
+    let (panels, invalid_panels) = to_rows(&request.panels);
+    let (stock, invalid_stock) = to_rows(&request.stock_panels);
+    if !invalid_panels.is_empty() || !invalid_stock.is_empty() {
+        issues.push(FeasibilityIssue {
+            stock_ids: invalid_stock,
+            ..FeasibilityIssue::new(
+                FeasibilityIssueKind::InvalidNumber,
+                invalid_panels,
+                "width and height must be positive numbers".to_string(),
+            )
+        });
+    } else if let Some(message) = normalize_error {
+        issues.push(FeasibilityIssue::new(FeasibilityIssueKind::InvalidNumber, Vec::new(), message));
+    }
+    if let Some(issue) = get_precision_overflow(&request, &panels, &stock) {
+        issues.push(issue);
+    }
+
+    let tolerance = configuration.dimension_tolerance.as_deref().and_then(parse).unwrap_or(0.0);
+    let stock_materials: HashSet<&str> = stock.iter().map(|row| row.panel.material.as_str()).collect();
+    let mut panels_per_key: BTreeMap<String, Vec<&SizedRow>> = BTreeMap::new();
+    for row in &panels {
+        panels_per_key.entry(row.panel.get_material_key()).or_default().push(row);
+    }
+
+    let mut materials = Vec::new();
+    for (key, rows) in panels_per_key {
+        let panel_ids: Vec<u32> = rows.iter().map(|row| row.panel.id).collect();
+        // Своего склада нет - детали уходят на склад замен
+        let mut sheets: Vec<&SizedRow> = stock.iter().filter(|row| row.panel.get_material_key() == key).collect();
+        if sheets.is_empty() {
+            let substitutes = configuration.material_substitutes.get(&key).map_or(&[][..], |keys| &keys[..]);
+            sheets = stock.iter().filter(|row| substitutes.contains(&row.panel.get_material_key())).collect();
+        }
+        let material = &rows[0].panel.material;
+        if sheets.is_empty() {
+            let (kind, message) = if stock_materials.contains(material.as_str()) {
+                let thickness = rows[0].panel.get_thickness().unwrap_or_else(|| "unspecified".to_string());
+                (
+                    FeasibilityIssueKind::ThicknessMismatch,
+                    format!("material '{}' has no stock of thickness {}", material, thickness),
+                )
+            } else {
+                (FeasibilityIssueKind::MaterialWithoutStock, format!("material '{}' has no stock", material))
+            };
+            issues.push(FeasibilityIssue::new(kind, panel_ids, message).with_material(&key));
+            continue;
+        }
+
+        let oversized: Vec<u32> = rows
+            .iter()
+            .filter(|row| {
+                let margin = 2.0 * row.panel.edge_clearance.as_deref().and_then(parse).unwrap_or(0.0);
+                let (width, height) = (row.width + margin - tolerance, row.height + margin - tolerance);
+                !sheets.iter().any(|sheet| {
+                    (width <= sheet.width && height <= sheet.height) || (height <= sheet.width && width <= sheet.height)
+                })
+            })
+            .map(|row| row.panel.id)
+            .collect();
+        if !oversized.is_empty() {
+            let message = format!("panels {} are larger than every sheet they may be cut from", join(&oversized));
+            let issue = FeasibilityIssue::new(FeasibilityIssueKind::OversizedPanel, oversized.clone(), message);
+            issues.push(issue.with_material(&key));
+        }
+
+        let panel_area: f64 =
+            rows.iter().filter(|row| !oversized.contains(&row.panel.id)).map(|row| row.get_area()).sum();
+        let stock_area: f64 = sheets.iter().map(|sheet| sheet.get_area()).sum();
+        if panel_area > stock_area {
+            let message = format!("panels of '{}' need more area than all of its stock", key);
+            let issue = FeasibilityIssue::new(FeasibilityIssueKind::InsufficientStock, panel_ids, message);
+            issues.push(issue.with_material(&key));
+        }
+        materials.push(MaterialEstimate {
+            material: key,
+            panel_area,
+            stock_area,
+            estimated_sheets: get_estimated_sheets(&sheets, panel_area),
+            available_sheets: sheets.iter().map(|sheet| sheet.panel.count as usize).sum(),
+        });
+    }
+
+    FeasibilityReport {
+        is_feasible: issues.is_empty(),
+        issues,
+        materials,
+    }
+}
+
+fn parse(value: &str) -> Option<f64> {
+    PrecisionAnalyzer::parse_f64(value).ok().filter(|value| *value > 0.0)
+}
+
+/// Enabled rows of `panels` with their dimensions and the ids of the rows with invalid ones
+fn to_rows(panels: &[Panel]) -> (Vec<SizedRow<'_>>, Vec<u32>) {
+    let mut rows = Vec::new();
+    let mut invalid = Vec::new();
+    for panel in panels.iter().filter(|panel| panel.enabled && panel.count > 0) {
+        match (parse(&panel.width), parse(&panel.height)) {
+            (Some(width), Some(height)) => rows.push(SizedRow { panel, width, height }),
+            _ => invalid.push(panel.id),
+        }
+    }
+    (rows, invalid)
+}
+
+/// Largest sheets first until they cover `panel_area`, the largest repeated once the stock runs out
+fn get_estimated_sheets(sheets: &[&SizedRow], panel_area: f64) -> usize {
+    let mut sheets: Vec<&&SizedRow> = sheets.iter().collect();
+    sheets.sort_by(|a, b| (b.width * b.height).total_cmp(&(a.width * a.height)));
+    let mut remaining = panel_area;
+    let mut nbr_sheets = 0;
+    for sheet in &sheets {
+        let area = sheet.width * sheet.height;
+        let needed = (remaining / area).ceil().min(sheet.panel.count as f64);
+        nbr_sheets += needed as usize;
+        remaining -= needed * area;
+        if remaining <= 0.0 {
+            return nbr_sheets;
+        }
+    }
+    let largest = sheets.first().map_or(1.0, |sheet| sheet.width * sheet.height);
+    nbr_sheets + (remaining / largest).ceil() as usize
+}
+
+/// Rows whose dimensions overflow the i32 coordinates of the engine once scaled to integers
+/// by the most decimal places of the request
+fn get_precision_overflow(request: &CalculationRequest, panels: &[SizedRow], stock: &[SizedRow]) -> Option<FeasibilityIssue> {
+    let configuration = &request.configuration;
+    let values = panels
+        .iter()
+        .chain(stock)
+        .flat_map(|row| [Some(&row.panel.width), Some(&row.panel.height), row.panel.edge_clearance.as_ref()])
+        .chain([configuration.dimension_tolerance.as_ref(), configuration.lead_in_allowance.as_ref()])
+        .chain(request.pinned_placements.iter().flat_map(|pinned| [Some(&pinned.x), Some(&pinned.y)]));
+    let decimal_places = values.flatten().map(|value| PrecisionAnalyzer::count_decimal_places(value)).max()?;
+    let multiplier = 10f64.powi(decimal_places as i32);
+    let overflows = |row: &&SizedRow| row.width.max(row.height) * multiplier > i32::MAX as f64;
+
+    let panel_ids: Vec<u32> = panels.iter().filter(overflows).map(|row| row.panel.id).collect();
+    let stock_ids: Vec<u32> = stock.iter().filter(overflows).map(|row| row.panel.id).collect();
+    if panel_ids.is_empty() && stock_ids.is_empty() {
+        return None;
+    }
+    let message = format!(
+        "{} decimal places scale the dimensions by {}, too large for the engine; round the dimensions",
+        decimal_places, multiplier
+    );
+    Some(FeasibilityIssue {
+        stock_ids,
+        ..FeasibilityIssue::new(FeasibilityIssueKind::PrecisionOverflow, panel_ids, message)
+    })
+}
+
+fn join(ids: &[u32]) -> String {
+    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ")
+}
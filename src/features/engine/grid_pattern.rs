@@ -0,0 +1,121 @@
+use crate::enums::grain_direction::GrainDirection;
+use crate::features::engine::model::{calculation_response::Mosaic, configuration::Configuration, tile_node::TileNode};
+use crate::features::input::models::tile_dimensions::TileDimensions;
+
+/// Sheet cut as a grid of copies of `tile`: one strip of panels is cut along the width and the
+/// strip is repeated down the sheet, so a sheet of repeated panels is laid out without a search.
+/// The orientation of the panel with more copies is used, the panel as it is on a tie. None
+/// when no copy fits, or the edge clearance, grain or `max_cut_stages` need the 2D search.
+/// `max_parts_per_sheet` caps the copies.
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::grid_pattern::cut_grid;
+/// use rezalnyas_core::features::engine::model::configuration::Configuration;
+/// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+///
+/// let sheet = TileDimensions::new(10, 2800, 2070, false, "", "MFC");
+/// // Повёрнутая деталь даёт 5 x 5 копий вместо 6 x 3
+/// let tile = TileDimensions::new(1, 400, 550, false, "", "MFC");
+///
+/// let mosaic = cut_grid(&sheet, &tile, 4, &Configuration::default()).unwrap();
+/// let root = &mosaic.root_tile_node[0];
+/// assert_eq!(root.get_nbr_final_tiles(), 25);
+/// assert!(root.get_final_nodes().iter().all(|node| node.is_rotated && node.external_id == Some(1)));
+/// assert_eq!(mosaic.get_cuts().iter().map(|cut| cut.stage).max(), Some(2));
+///
+/// let configuration = Configuration { max_parts_per_sheet: Some(8), ..Configuration::default() };
+/// assert_eq!(cut_grid(&sheet, &tile, 4, &configuration).unwrap().root_tile_node[0].get_nbr_final_tiles(), 8);
+///
+/// let configuration = Configuration { max_cut_stages: Some(1), ..Configuration::default() };
+/// assert!(cut_grid(&sheet, &tile, 4, &configuration).is_none());
+/// ```
+pub fn cut_grid(
+    stock: &TileDimensions,
+    tile: &TileDimensions,
+    cut_thickness: i32,
+    configuration: &Configuration,
+) -> Option<Mosaic> {
+    if tile.edge_clearance > 0 || stock.edge_clearance > 0 || configuration.max_cut_stages.is_some_and(|max| max < 2) {
+        return None;
+    }
+    let kerf = cut_thickness.max(0) as u32;
+
+    let (sheet_grain, panel_grain) = if configuration.consider_orientation {
+        (GrainDirection::from(stock.orientation), GrainDirection::from(tile.orientation))
+    } else {
+        (GrainDirection::None, GrainDirection::None)
+    };
+    let mut pieces = Vec::with_capacity(2);
+    if sheet_grain.allows(panel_grain, false) {
+        pieces.push(tile.clone());
+    }
+    if !tile.is_square() && sheet_grain.allows(panel_grain, true) {
+        pieces.push(tile.rotate_90());
+    }
+    let piece = pieces
+        .into_iter()
+        .map(|piece| (get_grid_capacity(stock, &piece, kerf), piece))
+        .reduce(|best, next| if next.0 > best.0 { next } else { best })
+        .filter(|(capacity, _)| *capacity > 0)
+        .map(|(_, piece)| piece)?;
+
+    let mut mosaic = Mosaic::from_tile_dimensions(stock);
+    let mut root = mosaic.root_tile_node.first().cloned()?;
+    let mut nbr_parts_left = configuration.max_parts_per_sheet.map_or(usize::MAX, |max| max as usize);
+
+    // Полосы высотой в деталь отрезаются первыми резами, детали из полосы - вторыми
+    let mut rest = &mut root;
+    while nbr_parts_left > 0 && rest.get_height() >= piece.height as i32 {
+        let is_cut = rest.get_height() > piece.height as i32;
+        let strip = if is_cut {
+            rest.split(false, piece.height as i32, cut_thickness);
+            rest.child1.as_deref_mut().expect("split always sets child1")
+        } else {
+            &mut *rest
+        };
+        nbr_parts_left -= cut_strip(strip, &piece, cut_thickness, nbr_parts_left);
+
+        rest = match rest.child2.as_deref_mut() {
+            Some(child2) if is_cut => child2,
+            _ => break,
+        };
+    }
+    mosaic.set_root_tile_node(root);
+    Some(mosaic)
+}
+
+/// Copies of the tile cut from the stock sheet as a plain grid, with a kerf between them
+pub fn get_grid_capacity(stock: &TileDimensions, tile: &TileDimensions, cut_thickness: u32) -> usize {
+    if tile.width == 0 || tile.height == 0 {
+        return 0;
+    }
+    let nbr_columns = (stock.width + cut_thickness) / (tile.width + cut_thickness);
+    let nbr_rows = (stock.height + cut_thickness) / (tile.height + cut_thickness);
+    nbr_columns as usize * nbr_rows as usize
+}
+
+/// Cut up to `max_pieces` copies of `piece` one after another from the start of the strip,
+/// returns how many were cut
+fn cut_strip(strip: &mut TileNode, piece: &TileDimensions, cut_thickness: i32, max_pieces: usize) -> usize {
+    let mut nbr_pieces = 0;
+    let mut rest = strip;
+    while nbr_pieces < max_pieces && rest.get_width() >= piece.width as i32 {
+        let is_cut = rest.get_width() > piece.width as i32;
+        let target = if is_cut {
+            rest.split(true, piece.width as i32, cut_thickness);
+            rest.child1.as_deref_mut().expect("split always sets child1")
+        } else {
+            &mut *rest
+        };
+        target.set_final_tile(true);
+        target.set_external_id(Some(piece.id));
+        target.set_rotated(piece.is_rotated);
+        nbr_pieces += 1;
+
+        rest = match rest.child2.as_deref_mut() {
+            Some(child2) if is_cut => child2,
+            _ => break,
+        };
+    }
+    nbr_pieces
+}
@@ -0,0 +1,234 @@
+use serde::Deserialize;
+
+use crate::enums::panel_shape::PanelShape;
+use crate::errors::Result;
+use crate::features::engine::model::{
+    calculation_request::Edge,
+    calculation_response::{CalculationResponse, Cut, FinalTile, LegacyCut, Mosaic, NoFitTile, Tile},
+    engine_comparison::EngineComparison,
+    response_diff::ResponseDiff,
+};
+
+// Ответ Java-движка сериализуется Jackson'ом в camelCase, булевы геттеры isX()
+// дают свойства без префикса, поэтому у таких полей есть алиасы
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct JavaResponse {
+    id: Option<String>,
+    task_id: Option<String>,
+    elapsed_time: u64,
+    total_used_area: f64,
+    total_wasted_area: f64,
+    total_used_area_ratio: f64,
+    total_nbr_cuts: i64,
+    total_cut_length: f64,
+    panels: Vec<JavaFinalTile>,
+    used_stock_panels: Vec<JavaFinalTile>,
+    no_fit_panels: Vec<JavaNoFitTile>,
+    mosaics: Vec<JavaMosaic>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct JavaFinalTile {
+    request_obj_id: i32,
+    width: f64,
+    height: f64,
+    label: Option<String>,
+    count: i32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct JavaNoFitTile {
+    id: u32,
+    width: f64,
+    height: f64,
+    count: i32,
+    label: Option<String>,
+    material: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct JavaMosaic {
+    request_stock_id: Option<i32>,
+    stock_label: Option<String>,
+    material: Option<String>,
+    used_area: f64,
+    wasted_area: f64,
+    used_area_ratio: f32,
+    nbr_final_panels: i32,
+    nbr_wasted_panels: i32,
+    cut_length: f64,
+    tiles: Vec<JavaTile>,
+    cuts: Vec<JavaCut>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct JavaTile {
+    id: i32,
+    request_obj_id: Option<i32>,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    orientation: i32,
+    label: Option<String>,
+    #[serde(alias = "final")]
+    is_final: bool,
+    has_children: bool,
+    edge: Option<Edge>,
+    #[serde(alias = "rotated")]
+    is_rotated: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct JavaCut {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+    cut_coord: f64,
+    #[serde(alias = "horizontal")]
+    is_horizontal: bool,
+    original_tile_id: i32,
+    original_width: f64,
+    original_height: f64,
+    child1_tile_id: i32,
+    child2_tile_id: i32,
+}
+
+/// Read a response JSON written by the Java engine into the Rust response model
+pub fn parse_java_response(json: &str) -> Result<CalculationResponse> {
+    let java: JavaResponse = serde_json::from_str(json)?;
+
+    let mut response = CalculationResponse::new();
+    response.id = java.id;
+    response.task_id = java.task_id;
+    response.elapsed_time = java.elapsed_time;
+    response.total_used_area = java.total_used_area;
+    response.total_wasted_area = java.total_wasted_area;
+    response.total_used_area_ratio = java.total_used_area_ratio;
+    response.total_nbr_cuts = java.total_nbr_cuts;
+    response.total_cut_length = java.total_cut_length;
+    response.panels = java.panels.into_iter().map(to_final_tile).collect();
+    response.used_stock_panels = java.used_stock_panels.into_iter().map(to_final_tile).collect();
+    response.no_fit_panels = java
+        .no_fit_panels
+        .into_iter()
+        .map(|panel| NoFitTile {
+            id: panel.id,
+            width: panel.width,
+            height: panel.height,
+            count: panel.count,
+            label: panel.label,
+            material: panel.material,
+        })
+        .collect();
+    response.mosaics = java.mosaics.into_iter().map(to_mosaic).collect();
+    Ok(response)
+}
+
+/// Compare a Java engine response JSON with the Rust response of the same request
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::java_response::compare_with_java;
+/// use rezalnyas_core::features::engine::model::calculation_response::CalculationResponse;
+///
+/// let java_json = r#"{
+///     "totalUsedAreaRatio": 0.75,
+///     "totalNbrCuts": 3,
+///     "mosaics": [{
+///         "requestStockId": 10,
+///         "tiles": [{ "id": 1, "requestObjId": 1, "x": 0, "y": 0, "width": 400, "height": 600, "final": true }]
+///     }]
+/// }"#;
+///
+/// let comparison = compare_with_java(java_json, &CalculationResponse::new()).unwrap();
+/// assert_eq!(comparison.java_nbr_sheets, 1);
+/// assert_eq!(comparison.used_area_ratio_delta, -0.75);
+/// assert_eq!(comparison.diff.removed_panels[0].request_obj_id, 1);
+/// ```
+pub fn compare_with_java(java_json: &str, response: &CalculationResponse) -> Result<EngineComparison> {
+    let java = parse_java_response(java_json)?;
+    Ok(EngineComparison {
+        java_used_area_ratio: java.total_used_area_ratio,
+        rust_used_area_ratio: response.total_used_area_ratio,
+        used_area_ratio_delta: response.total_used_area_ratio - java.total_used_area_ratio,
+        java_nbr_sheets: java.mosaics.len(),
+        rust_nbr_sheets: response.mosaics.len(),
+        java_nbr_cuts: java.total_nbr_cuts,
+        rust_nbr_cuts: response.total_nbr_cuts,
+        diff: ResponseDiff::new(&java, response),
+    })
+}
+
+fn to_final_tile(tile: JavaFinalTile) -> FinalTile {
+    FinalTile {
+        request_obj_id: tile.request_obj_id,
+        width: tile.width,
+        height: tile.height,
+        label: tile.label,
+        count: tile.count,
+        ..FinalTile::new()
+    }
+}
+
+fn to_mosaic(java: JavaMosaic) -> Mosaic {
+    let mut mosaic = Mosaic::new();
+    mosaic.request_stock_id = java.request_stock_id;
+    mosaic.stock_label = java.stock_label;
+    mosaic.material = java.material;
+    mosaic.used_area = java.used_area;
+    mosaic.wasted_area = java.wasted_area;
+    mosaic.used_area_ratio = java.used_area_ratio;
+    mosaic.nbr_final_panels = java.nbr_final_panels;
+    mosaic.nbr_wasted_panels = java.nbr_wasted_panels;
+    mosaic.cut_length = java.cut_length;
+    mosaic.nbr_cuts = java.cuts.len() as i32;
+    mosaic.tiles = java
+        .tiles
+        .into_iter()
+        .map(|tile| Tile {
+            id: tile.id,
+            request_obj_id: tile.request_obj_id,
+            x: tile.x,
+            y: tile.y,
+            width: tile.width,
+            height: tile.height,
+            orientation: tile.orientation,
+            label: tile.label,
+            is_final: tile.is_final,
+            has_children: tile.has_children,
+            edge: tile.edge.unwrap_or_else(Edge::new),
+            is_rotated: tile.is_rotated,
+            shape: PanelShape::default(),
+        })
+        .collect();
+    mosaic.cuts = java
+        .cuts
+        .into_iter()
+        .map(|cut| {
+            Cut::from(LegacyCut {
+                x1: cut.x1,
+                y1: cut.y1,
+                x2: cut.x2,
+                y2: cut.y2,
+                cut_coord: cut.cut_coord,
+                is_horizontal: cut.is_horizontal,
+                original_tile_id: cut.original_tile_id,
+                original_width: cut.original_width,
+                original_height: cut.original_height,
+                child1_tile_id: cut.child1_tile_id,
+                child2_tile_id: cut.child2_tile_id,
+                request_obj_id: None,
+                stage: 0,
+            })
+        })
+        .collect();
+    mosaic
+}
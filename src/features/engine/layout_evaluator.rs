@@ -0,0 +1,322 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::features::engine::cut_list_thread::CutListThread;
+use crate::features::engine::model::{
+    calculation_request::{CalculationRequest, Panel, PinnedPlacement},
+    calculation_response::NoFitTile,
+    calculation_response_builder::CalculationResponseBuilder,
+    layout_report::{LayoutIssue, LayoutIssueKind, LayoutReport, SheetReport},
+    pinned_sheet::PinnedSheet,
+    solution::Solution,
+    task::Task,
+};
+use crate::features::input::models::tile_dimensions::TileDimensions;
+use crate::scaled_math::PrecisionAnalyzer;
+
+/// Panel of a manual layout in scaled units
+struct PlacedPanel<'a> {
+    placement: &'a PinnedPlacement,
+    tile: TileDimensions,
+    x: i64,
+    y: i64,
+}
+
+impl PlacedPanel<'_> {
+    fn x2(&self) -> i64 {
+        self.x + self.tile.width as i64
+    }
+
+    fn y2(&self) -> i64 {
+        self.y + self.tile.height as i64
+    }
+}
+
+/// Validate a manually drawn layout and compute its statistics like an optimizer result
+///
+/// Every placement puts one copy of a request panel on a sheet (stock id + sheet index).
+/// Sheets without overlaps, kerf violations or off-sheet panels are carved with the same
+/// guillotine splits the optimizer uses, so the report carries cuts and cut length for them.
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::layout_evaluator::evaluate_layout;
+/// use rezalnyas_core::features::engine::model::calculation_request::PinnedPlacement;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::layout_report::LayoutIssueKind;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 300.0).id(1).count(2)
+///     .add_stock(1000.0, 600.0).id(10)
+///     .build()
+///     .unwrap();
+///
+/// let report = evaluate_layout(&request, &[PinnedPlacement::new(1, 10, 0, "0", "0", false)]);
+/// assert!(report.is_valid);
+/// assert_eq!(report.unplaced_panels[0].count, 1);
+/// assert_eq!(report.response.mosaics.len(), 1);
+///
+/// let overlapping = [
+///     PinnedPlacement::new(1, 10, 0, "0", "0", false),
+///     PinnedPlacement::new(1, 10, 0, "200", "100", false),
+/// ];
+/// let report = evaluate_layout(&request, &overlapping);
+/// assert_eq!(report.issues[0].kind, LayoutIssueKind::Overlap);
+/// ```
+pub fn evaluate_layout(request: &CalculationRequest, layout: &[PinnedPlacement]) -> LayoutReport {
+    let factor = get_factor(request, layout);
+    let scale = |value: &str| (PrecisionAnalyzer::parse_f64(value).unwrap_or(0.0) * factor as f64).round() as i64;
+    let cut_thickness = CutListThread::new_with_config(&request.configuration, 0).cut_thickness as i64;
+
+    let mut task = Task {
+        calculation_request: request.clone(),
+        factor,
+        ..Task::default()
+    };
+    let panel_ids: HashMap<u32, u32> = request
+        .panels
+        .iter()
+        .map(|panel| (panel.id, task.register_panel_id(panel.id, &panel.label, false)))
+        .collect();
+    let stock_ids: HashMap<u32, u32> = request
+        .stock_panels
+        .iter()
+        .map(|stock| (stock.id, task.register_panel_id(stock.id, &stock.label, true)))
+        .collect();
+
+    let mut issues = Vec::new();
+    let mut placed_counts: HashMap<u32, u32> = HashMap::new();
+    let mut sheets: BTreeMap<(u32, u32), Vec<PlacedPanel>> = BTreeMap::new();
+
+    for placement in layout {
+        let Some(panel) = request.panels.iter().find(|panel| panel.id == placement.panel_id) else {
+            issues.push(LayoutIssue::new(
+                LayoutIssueKind::UnknownPanel,
+                vec![placement.panel_id],
+                format!("panel {} is not in the request", placement.panel_id),
+            ));
+            continue;
+        };
+        *placed_counts.entry(panel.id).or_insert(0) += 1;
+
+        let (width, height) = if placement.rotated {
+            (scale(&panel.height), scale(&panel.width))
+        } else {
+            (scale(&panel.width), scale(&panel.height))
+        };
+        sheets
+            .entry((placement.stock_id, placement.sheet_index))
+            .or_default()
+            .push(PlacedPanel {
+                placement,
+                tile: TileDimensions::new(
+                    panel_ids[&panel.id],
+                    width.max(0) as u32,
+                    height.max(0) as u32,
+                    placement.rotated,
+                    &panel.label,
+                    &panel.material,
+                ),
+                x: scale(&placement.x),
+                y: scale(&placement.y),
+            });
+    }
+
+    let mut unplaced_panels = Vec::new();
+    for panel in &request.panels {
+        let placed = placed_counts.get(&panel.id).copied().unwrap_or(0);
+        if placed > panel.count {
+            issues.push(LayoutIssue::new(
+                LayoutIssueKind::TooManyCopies,
+                vec![panel.id],
+                format!("panel {} is placed {} times but has count {}", panel.id, placed, panel.count),
+            ));
+        } else if placed < panel.count {
+            unplaced_panels.push(get_unplaced_panel(panel, panel.count - placed));
+        }
+    }
+
+    let mut sheet_reports = Vec::new();
+    let mut solutions: HashMap<String, Vec<Solution>> = HashMap::new();
+    for ((stock_id, sheet_index), panels) in &sheets {
+        let Some(stock) = request
+            .stock_panels
+            .iter()
+            .find(|stock| stock.id == *stock_id && *sheet_index < stock.count)
+        else {
+            issues.push(
+                LayoutIssue::new(
+                    LayoutIssueKind::UnknownSheet,
+                    panels.iter().map(|panel| panel.placement.panel_id).collect(),
+                    format!("sheet {} of stock panel {} is not in the request", sheet_index, stock_id),
+                )
+                .on_sheet(*stock_id, *sheet_index),
+            );
+            continue;
+        };
+
+        let sheet = TileDimensions::new(
+            stock_ids[stock_id],
+            scale(&stock.width).max(0) as u32,
+            scale(&stock.height).max(0) as u32,
+            false,
+            &stock.label,
+            &stock.material,
+        );
+        let sheet_issues = check_sheet(&sheet, panels, cut_thickness);
+        let can_carve = sheet_issues.is_empty();
+        let carved = if can_carve {
+            carve_sheet(&sheet, panels, cut_thickness)
+        } else {
+            None
+        };
+        issues.extend(
+            sheet_issues
+                .into_iter()
+                .map(|issue| issue.on_sheet(*stock_id, *sheet_index)),
+        );
+        if can_carve && carved.is_none() {
+            issues.push(
+                LayoutIssue::new(
+                    LayoutIssueKind::NotGuillotine,
+                    panels.iter().map(|panel| panel.placement.panel_id).collect(),
+                    "panels can't be separated with guillotine cuts".to_string(),
+                )
+                .on_sheet(*stock_id, *sheet_index),
+            );
+        }
+
+        let sheet_area = sheet.width as f64 * sheet.height as f64;
+        let used_area: f64 = panels
+            .iter()
+            .map(|panel| panel.tile.width as f64 * panel.tile.height as f64)
+            .sum();
+        let area_factor = (factor as f64).powi(2);
+        sheet_reports.push(SheetReport {
+            stock_id: *stock_id,
+            sheet_index: *sheet_index,
+            nbr_panels: panels.len(),
+            used_area: used_area / area_factor,
+            wasted_area: (sheet_area - used_area).max(0.0) / area_factor,
+            used_area_ratio: if sheet_area > 0.0 { used_area / sheet_area } else { 0.0 },
+            is_guillotine: carved.is_some(),
+        });
+
+        if let Some(pinned_sheet) = carved {
+            let material_solutions = solutions.entry(stock.material.clone()).or_default();
+            if material_solutions.is_empty() {
+                material_solutions.push(Solution::default());
+            }
+            material_solutions[0].mosaics.push(pinned_sheet.to_mosaic());
+        }
+    }
+
+    task.solutions = solutions.clone();
+    let response = CalculationResponseBuilder {
+        task,
+        calculation_request: request.clone(),
+        solutions,
+        no_stock_material_panels: Vec::new(),
+    }
+    .build();
+
+    LayoutReport {
+        is_valid: issues.is_empty(),
+        issues,
+        sheets: sheet_reports,
+        unplaced_panels,
+        response,
+    }
+}
+
+fn get_factor(request: &CalculationRequest, layout: &[PinnedPlacement]) -> u32 {
+    let panel_values = request
+        .panels
+        .iter()
+        .chain(&request.stock_panels)
+        .flat_map(|panel| [&panel.width, &panel.height]);
+    let layout_values = layout.iter().flat_map(|placement| [&placement.x, &placement.y]);
+
+    let max_decimal_places = panel_values
+        .chain(layout_values)
+        .map(|value| PrecisionAnalyzer::count_decimal_places(value))
+        .max()
+        .unwrap_or(0);
+    10u32.pow(max_decimal_places as u32)
+}
+
+fn get_unplaced_panel(panel: &Panel, count: u32) -> NoFitTile {
+    let mut no_fit_tile = NoFitTile::with_params(
+        panel.id,
+        PrecisionAnalyzer::parse_f64(&panel.width).unwrap_or(0.0),
+        PrecisionAnalyzer::parse_f64(&panel.height).unwrap_or(0.0),
+        count as i32,
+    );
+    no_fit_tile.label = Some(panel.label.clone());
+    no_fit_tile.material = Some(panel.material.clone());
+    no_fit_tile
+}
+
+/// Off-sheet panels, overlaps and gaps narrower than the saw kerf
+fn check_sheet(sheet: &TileDimensions, panels: &[PlacedPanel], cut_thickness: i64) -> Vec<LayoutIssue> {
+    let mut issues = Vec::new();
+
+    for panel in panels {
+        if panel.x < 0 || panel.y < 0 || panel.x2() > sheet.width as i64 || panel.y2() > sheet.height as i64 {
+            issues.push(LayoutIssue::new(
+                LayoutIssueKind::OffSheet,
+                vec![panel.placement.panel_id],
+                format!(
+                    "panel {} at {},{} is off the sheet",
+                    panel.placement.panel_id, panel.placement.x, panel.placement.y
+                ),
+            ));
+        }
+    }
+
+    for (index, a) in panels.iter().enumerate() {
+        for b in &panels[index + 1..] {
+            let gap_x = (a.x.max(b.x) - a.x2().min(b.x2())).max(0);
+            let gap_y = (a.y.max(b.y) - a.y2().min(b.y2())).max(0);
+            let overlap_x = a.x < b.x2() && b.x < a.x2();
+            let overlap_y = a.y < b.y2() && b.y < a.y2();
+            let panel_ids = vec![a.placement.panel_id, b.placement.panel_id];
+
+            if overlap_x && overlap_y {
+                issues.push(LayoutIssue::new(
+                    LayoutIssueKind::Overlap,
+                    panel_ids,
+                    format!("panels {} and {} overlap", a.placement.panel_id, b.placement.panel_id),
+                ));
+            } else if cut_thickness > 0
+                && ((overlap_y && gap_x < cut_thickness) || (overlap_x && gap_y < cut_thickness))
+            {
+                issues.push(LayoutIssue::new(
+                    LayoutIssueKind::KerfViolation,
+                    panel_ids,
+                    format!(
+                        "panels {} and {} are closer than the cut thickness",
+                        a.placement.panel_id, b.placement.panel_id
+                    ),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+/// Carve the panels in reading order, then column order, None if neither separates them
+fn carve_sheet(sheet: &TileDimensions, panels: &[PlacedPanel], cut_thickness: i64) -> Option<PinnedSheet> {
+    [false, true].into_iter().find_map(|column_first| {
+        let mut sorted: Vec<&PlacedPanel> = panels.iter().collect();
+        sorted.sort_by_key(|panel| if column_first { (panel.x, panel.y) } else { (panel.y, panel.x) });
+
+        let mut pinned_sheet = PinnedSheet::new(sheet);
+        sorted
+            .iter()
+            .all(|panel| {
+                pinned_sheet
+                    .pin(&panel.tile, panel.x as i32, panel.y as i32, cut_thickness as i32)
+                    .is_ok()
+            })
+            .then_some(pinned_sheet)
+    })
+}
@@ -0,0 +1,81 @@
+use crate::features::input::models::tile_dimensions::TileDimensions;
+use crate::features::panel_grouper::panel_grouper::PanelGrouper;
+
+/// Least stock any layout of the panels needs, see `get_lower_bound`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LowerBound {
+    /// No layout uses fewer sheets
+    pub nbr_sheets: usize,
+    /// No layout uses less stock area, in the scaled units of the tiles
+    pub stock_area: u64,
+}
+
+/// Lower bound on the sheets and the stock area that `tiles` need on `stock_tiles`, the largest of:
+/// the largest sheets that together hold the panel area, the panels too big to share a sheet,
+/// and for panels and stock sharing one dimension their lengths with the kerf on the longest bar.
+/// Cheap enough to compute on every status request; a layout reaching it is optimal
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::lower_bound::get_lower_bound;
+/// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+///
+/// let sheet = |id| TileDimensions::new(id, 1000, 600, false, "", "");
+/// let stock = [sheet(10), sheet(11), sheet(12)];
+///
+/// // По площади хватит одного листа, но две детали больше половины листа по обеим сторонам
+/// let tiles = [TileDimensions::new(1, 520, 520, false, "", ""), TileDimensions::new(2, 520, 520, false, "", "")];
+/// let bound = get_lower_bound(&tiles, &stock, 0);
+/// assert_eq!((bound.nbr_sheets, bound.stock_area), (2, 1_200_000));
+///
+/// // Две полосы по 499 мм с пропилом 4 мм длиннее бруска 1000 мм
+/// let strips = [TileDimensions::new(1, 499, 100, false, "", ""), TileDimensions::new(2, 499, 100, false, "", "")];
+/// let bars = [TileDimensions::new(10, 1000, 100, false, "", ""), TileDimensions::new(11, 1000, 100, false, "", "")];
+/// assert_eq!(get_lower_bound(&strips, &bars, 4).nbr_sheets, 2);
+/// ```
+pub fn get_lower_bound(tiles: &[TileDimensions], stock_tiles: &[TileDimensions], cut_thickness: u32) -> LowerBound {
+    if tiles.is_empty() || stock_tiles.is_empty() {
+        return LowerBound { nbr_sheets: 0, stock_area: 0 };
+    }
+    let tiles_area: u64 = tiles.iter().map(|tile| tile.area()).sum();
+    let mut stock_areas: Vec<u64> = stock_tiles.iter().map(|stock| stock.area()).collect();
+    stock_areas.sort_unstable_by(|a, b| b.cmp(a));
+
+    // Наибольшие листы, которые вместе покрывают площадь деталей
+    let mut covered_area = 0;
+    let by_area = stock_areas
+        .iter()
+        .position(|area| {
+            covered_area += area;
+            covered_area >= tiles_area
+        })
+        .map_or(stock_areas.len(), |index| index + 1);
+
+    // Две детали больше половины листа по обеим сторонам в любом повороте на один лист не лягут
+    let max_width = stock_tiles.iter().map(|stock| stock.width).max().unwrap_or(0) as u64;
+    let max_height = stock_tiles.iter().map(|stock| stock.height).max().unwrap_or(0) as u64;
+    let is_over_half = |width: u64, height: u64| 2 * width > max_width && 2 * height > max_height;
+    let by_size = tiles
+        .iter()
+        .filter(|tile| {
+            let (width, height) = (tile.width as u64, tile.height as u64);
+            is_over_half(width, height) && is_over_half(height, width)
+        })
+        .count();
+
+    let by_length = PanelGrouper::get_common_dimension(tiles, stock_tiles).map_or(0, |depth| {
+        let kerf = cut_thickness as u64;
+        let get_length = |tile: &TileDimensions| (if tile.height == depth { tile.width } else { tile.height }) as u64;
+        let longest_bar = stock_tiles.iter().map(get_length).max().unwrap_or(0);
+        let needed: u64 = tiles.iter().map(|tile| get_length(tile) + kerf).sum();
+        needed.div_ceil(longest_bar + kerf) as usize
+    });
+
+    // Меньше всего площади займут самые маленькие листы из нужного числа
+    let nbr_sheets = by_area.max(by_size).max(by_length).min(stock_areas.len());
+    let smallest_area: u64 = stock_areas.iter().rev().take(nbr_sheets).sum();
+    LowerBound {
+        nbr_sheets,
+        stock_area: smallest_area.max(tiles_area),
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::enums::status_code::StatusCode;
+use crate::errors::{AppError, Result};
+use crate::features::engine::cutlist_optimizer_service::CutListOptimizerService;
+use crate::features::engine::model::{
+    calculation_request::CalculationRequest, calculation_response::CalculationResponse,
+    calculation_submission_result::CalculationSubmissionResult, health_report::HealthReport, stats::Stats,
+    status::Status, task_status_response::{TaskErrorInfo, TaskStatusResponse},
+};
+
+/// Service that runs no optimization: submitted requests are captured and answered with
+/// the programmed results in order, an empty response once they run out
+///
+/// ```rust
+/// use rezalnyas_core::errors::{AppError, ServiceError};
+/// use rezalnyas_core::features::engine::cutlist_optimizer_service::CutListOptimizerService;
+/// use rezalnyas_core::features::engine::mock_cutlist_optimizer_service::MockCutListOptimizerService;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::calculation_response::CalculationResponse;
+///
+/// let mock = MockCutListOptimizerService::new();
+/// let mut response = CalculationResponse::new();
+/// response.total_nbr_cuts = 3;
+/// mock.push_response(response);
+/// mock.push_error(AppError::Service(ServiceError::ServiceTaskAlreadyExists { task_id: "1".into() }));
+///
+/// let service: &dyn CutListOptimizerService = &mock;
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0)
+///     .add_stock(1000.0, 600.0)
+///     .build()
+///     .unwrap();
+/// let task_id = service.submit_task(request.clone()).unwrap().task_id.unwrap();
+/// assert_eq!(service.get_task_status(&task_id).unwrap().solution.unwrap().total_nbr_cuts, 3);
+/// assert!(service.submit_task(request).is_err());
+///
+/// assert_eq!(mock.submissions().len(), 2);
+/// assert_eq!(service.get_stats().nbr_error_tasks, 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct MockCutListOptimizerService {
+    results: Mutex<VecDeque<Result<CalculationResponse>>>,
+    submissions: Mutex<Vec<CalculationRequest>>,
+    task_statuses: Mutex<HashMap<String, TaskStatusResponse>>,
+    stopped_tasks: Mutex<Vec<String>>,
+    stats: Mutex<Stats>,
+}
+
+impl MockCutListOptimizerService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer one of the next submissions with `response`
+    pub fn push_response(&self, response: CalculationResponse) {
+        if let Ok(mut results) = self.results.lock() {
+            results.push_back(Ok(response));
+        }
+    }
+
+    /// Fail one of the next submissions with `error`
+    pub fn push_error(&self, error: AppError) {
+        if let Ok(mut results) = self.results.lock() {
+            results.push_back(Err(error));
+        }
+    }
+
+    /// Replace the status `get_task_status` returns for a task, e.g. to fake a running task
+    pub fn set_task_status(&self, task_id: &str, status: TaskStatusResponse) {
+        if let Ok(mut statuses) = self.task_statuses.lock() {
+            statuses.insert(task_id.to_string(), status);
+        }
+    }
+
+    /// Requests submitted so far, oldest first
+    pub fn submissions(&self) -> Vec<CalculationRequest> {
+        self.submissions
+            .lock()
+            .map(|submissions| submissions.clone())
+            .unwrap_or_default()
+    }
+
+    /// Ids `stop_task` was called with, oldest first
+    pub fn stopped_tasks(&self) -> Vec<String> {
+        self.stopped_tasks
+            .lock()
+            .map(|stopped_tasks| stopped_tasks.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl CutListOptimizerService for MockCutListOptimizerService {
+    fn submit_task(&self, calculation_request: CalculationRequest) -> Result<CalculationSubmissionResult> {
+        let task_id = match self.submissions.lock() {
+            Ok(mut submissions) => {
+                submissions.push(calculation_request);
+                submissions.len().to_string()
+            }
+            Err(_) => String::new(),
+        };
+        let result = self
+            .results
+            .lock()
+            .ok()
+            .and_then(|mut results| results.pop_front())
+            .unwrap_or_else(|| Ok(CalculationResponse::new()));
+
+        let mut status = TaskStatusResponse {
+            task_id: Some(task_id.clone()),
+            ..TaskStatusResponse::default()
+        };
+        if let Ok(mut stats) = self.stats.lock() {
+            match &result {
+                Ok(_) => stats.nbr_finished_tasks += 1,
+                Err(_) => stats.nbr_error_tasks += 1,
+            }
+        }
+        let result = match result {
+            Ok(mut response) => {
+                response.task_id = Some(task_id.clone());
+                status.set_status(Status::Finished);
+                status.percentage_done = 100;
+                status.solution = Some(response);
+                Ok(CalculationSubmissionResult::new(StatusCode::Ok, task_id.clone()))
+            }
+            Err(e) => {
+                status.set_status(Status::Error);
+                status.error = Some(TaskErrorInfo::new(&e, None));
+                Err(e)
+            }
+        };
+        self.set_task_status(&task_id, status);
+        result
+    }
+
+    fn get_task_status(&self, task_id: &str) -> Option<TaskStatusResponse> {
+        self.task_statuses
+            .lock()
+            .ok()
+            .and_then(|statuses| statuses.get(task_id).cloned())
+    }
+
+    /// Records the id, stops tasks set as running with `set_task_status`
+    fn stop_task(&self, task_id: &str) -> bool {
+        if let Ok(mut stopped_tasks) = self.stopped_tasks.lock() {
+            stopped_tasks.push(task_id.to_string());
+        }
+        let Ok(mut statuses) = self.task_statuses.lock() else {
+            return false;
+        };
+        match statuses.get_mut(task_id) {
+            Some(status) if matches!(status.status, Some(Status::Running | Status::Improving)) => {
+                status.set_status(Status::Stopped);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn get_stats(&self) -> Stats {
+        self.stats.lock().map(|stats| stats.clone()).unwrap_or_default()
+    }
+
+    fn get_health(&self) -> HealthReport {
+        let stats = self.get_stats();
+        HealthReport {
+            is_alive: true,
+            is_ready: true,
+            nbr_running_tasks: stats.nbr_running_tasks,
+            nbr_finished_tasks: stats.nbr_finished_tasks,
+            nbr_error_tasks: stats.nbr_error_tasks,
+            oldest_task_ms: None,
+            nbr_stalled_tasks: 0,
+        }
+    }
+
+    fn clear_result_cache(&self) {}
+}
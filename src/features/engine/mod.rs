@@ -3,8 +3,30 @@
 
 
 pub mod model;
+pub mod cutlist_optimizer_service;
 pub mod cutlist_optimizer_service_impl;
+#[cfg(feature = "test-util")]
+pub mod mock_cutlist_optimizer_service;
 pub mod task_report;
 pub mod cut_list_thread;
+pub mod cut_list_thread_pool;
 pub mod comparator;
 
+pub mod result_cache;
+pub mod response_cache;
+pub mod template_store;
+pub mod offcut_store;
+pub mod task_repository;
+pub mod layout_evaluator;
+pub mod feasibility;
+pub mod java_response;
+pub mod stock_selection;
+pub mod stock_planner;
+pub mod one_dimensional;
+pub mod grid_pattern;
+pub mod panel_clustering;
+pub mod lower_bound;
+pub mod permutation_scheduler;
+pub mod permutation_hash;
+#[cfg(feature = "bench")]
+pub mod bench_requests;
@@ -1,10 +1,12 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     constants::MaterialConstants,
-    enums::orientation::Orientation,
+    enums::{grain_direction::GrainDirection, orientation::Orientation, panel_shape::PanelShape, stock_pricing::StockPricing},
     features::engine::model::{client_info::ClientInfo, configuration::Configuration, performance_thresholds::PerformanceThresholds},
-    scaled_math::ScaledNumber,
+    features::input::models::material_catalog::MaterialCatalog,
+    scaled_math::{PrecisionAnalyzer, ScaledNumber},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +16,15 @@ pub struct CalculationRequest {
     pub stock_panels: Vec<Panel>,
     pub client_info: ClientInfo,
     pub performance_thresholds: PerformanceThresholds,
+    /// Panels already placed on a sheet, the optimizer fills the space around them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_placements: Vec<PinnedPlacement>,
+    /// Panels cut side by side and in order from one region of a sheet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sequence_groups: Vec<SequenceGroup>,
+    /// Known materials, free-text material names of the rows are folded into their ids
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material_catalog: Option<MaterialCatalog>,
 }
 
 impl Default for CalculationRequest {
@@ -24,6 +35,9 @@ impl Default for CalculationRequest {
             stock_panels: Vec::new(),
             client_info: ClientInfo::default(),
             performance_thresholds: PerformanceThresholds::default(),
+            pinned_placements: Vec::new(),
+            sequence_groups: Vec::new(),
+            material_catalog: None,
         }
     }
 }
@@ -63,6 +77,32 @@ pub struct Panel {
     pub orientation: Orientation,
     pub label: String,
     pub edge: Option<Edge>,
+    /// Board thickness, panels are only cut from stock of the same material and thickness
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thickness: Option<String>,
+    /// Minimum distance between the panel and the stock sheet edges, keeps it off damaged
+    /// borders without trimming the whole sheet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge_clearance: Option<String>,
+    /// Grain direction of the row, takes precedence over `orientation` when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grain: Option<GrainDirection>,
+    /// Stock only: sheets with a higher priority, like offcuts, are used up first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+    /// Stock only: among sheets of the same priority the oldest ones are used first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acquired_date: Option<NaiveDate>,
+    /// Stock only: price of a sheet of the row, None - `Configuration::cost_per_sheet`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// Stock only: how `price` is charged, None - `Configuration::stock_pricing`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<StockPricing>,
+    /// Panels only: outline of the panel, `width` x `height` is its bounding box.
+    /// A circle sets both to its diameter
+    #[serde(default, skip_serializing_if = "PanelShape::is_rect")]
+    pub shape: PanelShape,
 }
 
 impl Panel {
@@ -79,22 +119,52 @@ impl Panel {
             orientation: Orientation::default(),
             label: label.to_string(),
             edge: None,
+            thickness: None,
+            edge_clearance: None,
+            grain: None,
+            priority: None,
+            acquired_date: None,
+            price: None,
+            pricing: None,
+            shape: PanelShape::default(),
         }
     }
 
+    /// Grain of the row, from `grain` or else from the legacy `orientation`
+    pub fn get_grain(&self) -> GrainDirection {
+        self.grain.unwrap_or_else(|| self.orientation.into())
+    }
+
     pub fn set_material(&mut self, material: Option<String>) {
         if let Some(mat) = material {
             self.material = mat;
         }
     }
 
+    /// Material the engine groups by: the material name, plus "@thickness" when it is set
+    pub fn get_material_key(&self) -> String {
+        match self.get_thickness() {
+            Some(thickness) => format!("{}@{}", self.material, thickness),
+            None => self.material.clone(),
+        }
+    }
+
+    /// Thickness in its shortest form, so "18" and "18.0" end up in the same bucket
+    pub fn get_thickness(&self) -> Option<String> {
+        let thickness = self.thickness.as_deref()?.trim();
+        match PrecisionAnalyzer::parse_f64(thickness) {
+            Ok(value) => Some(value.to_string()),
+            Err(_) => Some(thickness.to_string()),
+        }
+    }
+
     pub fn is_valid(&self) -> bool {
         if !self.enabled || self.count <= 0 {
             return false;
         }
 
-        let width_valid = self.width.parse::<f64>().unwrap_or(0.0) > 0.0;
-        let height_valid = self.height.parse::<f64>().unwrap_or(0.0) > 0.0;
+        let width_valid = PrecisionAnalyzer::parse_f64(&self.width).unwrap_or(0.0) > 0.0;
+        let height_valid = PrecisionAnalyzer::parse_f64(&self.height).unwrap_or(0.0) > 0.0;
 
         width_valid && height_valid
     }
@@ -111,7 +181,7 @@ impl std::fmt::Display for Panel {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Edge {
     pub top: Option<String>,
     pub left: Option<String>,
@@ -129,3 +199,57 @@ impl Edge {
         }
     }
 }
+
+/// One copy of a panel locked at a position of a stock sheet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedPlacement {
+    pub panel_id: u32,
+    pub stock_id: u32,
+    /// Which copy of the stock panel, starting at 0
+    pub sheet_index: u32,
+    /// Top left corner of the panel on the sheet
+    pub x: String,
+    pub y: String,
+    pub rotated: bool,
+}
+
+impl PinnedPlacement {
+    pub fn new(panel_id: u32, stock_id: u32, sheet_index: u32, x: &str, y: &str, rotated: bool) -> Self {
+        Self {
+            panel_id,
+            stock_id,
+            sheet_index,
+            x: x.to_string(),
+            y: y.to_string(),
+            rotated,
+        }
+    }
+}
+
+/// Direction the members of a sequence group follow each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SequenceAxis {
+    /// Left to right, the members share the cuts along the height
+    Horizontal,
+    /// Top to bottom, the members share the cuts along the width
+    Vertical,
+}
+
+/// Parts that keep the grain running from one to the next, like the waterfall
+/// of a countertop: one copy of each panel, cut adjacent and in the given order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceGroup {
+    pub id: u32,
+    pub panel_ids: Vec<u32>,
+    pub axis: SequenceAxis,
+}
+
+impl SequenceGroup {
+    pub fn new(id: u32, panel_ids: &[u32], axis: SequenceAxis) -> Self {
+        Self {
+            id,
+            panel_ids: panel_ids.to_vec(),
+            axis,
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+
+use crate::constants::EngineConstants;
+use crate::enums::{grain_direction::GrainDirection, orientation::Orientation, panel_shape::PanelShape, stock_pricing::StockPricing};
+use crate::errors::{CoreError, Result};
+use crate::features::engine::model::{
+    calculation_request::{CalculationRequest, Edge, Panel, SequenceGroup},
+    client_info::ClientInfo,
+    configuration::Configuration,
+    job_template::JobTemplate,
+    performance_thresholds::PerformanceThresholds,
+};
+use crate::features::input::models::material_catalog::MaterialCatalog;
+use crate::scaled_math::ScaledNumber;
+
+/// Fluent builder for CalculationRequest with numeric dimensions
+///
+/// ```rust
+/// use rezalnyas_core::enums::grain_direction::GrainDirection;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(600.0, 400.5).count(2).material("MDF").grain_direction(GrainDirection::AlongWidth)
+///     .add_panel(300.0, 200.0).label("shelf").material("MDF")
+///     .add_stock(2800.0, 2070.0).count(5).material("MDF")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(request.panels.len(), 2);
+/// assert_eq!(request.panels[0].height, "400.5");
+/// assert_eq!(request.panels[0].get_grain(), GrainDirection::AlongWidth);
+/// assert_eq!(request.stock_panels[0].count, 5);
+///
+/// // Validation happens on build()
+/// assert!(CalculationRequestBuilder::new().add_panel(-1.0, 10.0).build().is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CalculationRequestBuilder {
+    request: CalculationRequest,
+    errors: Vec<String>,
+}
+
+impl CalculationRequestBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from the stock and configuration of `template`, see `JobTemplate`
+    pub fn from_template(template: &JobTemplate) -> Self {
+        Self {
+            request: template.instantiate(Vec::new()),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn configuration(mut self, configuration: Configuration) -> Self {
+        self.request.configuration = configuration;
+        self
+    }
+
+    pub fn client_info(mut self, client_info: ClientInfo) -> Self {
+        self.request.client_info = client_info;
+        self
+    }
+
+    pub fn performance_thresholds(mut self, performance_thresholds: PerformanceThresholds) -> Self {
+        self.request.performance_thresholds = performance_thresholds;
+        self
+    }
+
+    /// Fold the material names of the panels and stock into the ids of `catalog`
+    pub fn material_catalog(mut self, catalog: MaterialCatalog) -> Self {
+        self.request.material_catalog = Some(catalog);
+        self
+    }
+
+    /// Cut the panels of `group` adjacent and in order
+    pub fn sequence_group(mut self, group: SequenceGroup) -> Self {
+        self.request.sequence_groups.push(group);
+        self
+    }
+
+    /// Start a panel to cut, its id is the next free one unless set with `id()`
+    pub fn add_panel(self, width: f64, height: f64) -> PanelBuilder {
+        let id = self.request.panels.len() as u32 + 1;
+        PanelBuilder::new(self, id, width, height, false)
+    }
+
+    /// Start a stock sheet, its id is the next free one unless set with `id()`
+    pub fn add_stock(self, width: f64, height: f64) -> PanelBuilder {
+        let id = self.request.stock_panels.len() as u32 + 1;
+        PanelBuilder::new(self, id, width, height, true)
+    }
+
+    pub fn build(self) -> Result<CalculationRequest> {
+        let mut errors = self.errors;
+        let request = self.request;
+
+        if request.panels.is_empty() {
+            errors.push("no panels to cut".to_string());
+        }
+        if request.stock_panels.is_empty() {
+            errors.push("no stock panels".to_string());
+        }
+
+        Self::validate_panels(&request.panels, "panel", EngineConstants::MAX_PANELS_LIMIT, &mut errors);
+        Self::validate_panels(
+            &request.stock_panels,
+            "stock panel",
+            EngineConstants::MAX_STOCK_PANELS_LIMIT,
+            &mut errors,
+        );
+
+        if errors.is_empty() {
+            Ok(request)
+        } else {
+            Err(CoreError::InvalidInput {
+                details: errors.join("; "),
+            }
+            .into())
+        }
+    }
+
+    fn validate_panels(panels: &[Panel], kind: &str, max_count: usize, errors: &mut Vec<String>) {
+        let mut ids = HashSet::new();
+        let mut total_count = 0usize;
+
+        for panel in panels {
+            if !ids.insert(panel.id) {
+                errors.push(format!("duplicate {} id {}", kind, panel.id));
+            }
+            if panel.count == 0 {
+                errors.push(format!("{} {} has zero count", kind, panel.id));
+            }
+            total_count += panel.count as usize;
+        }
+
+        if total_count > max_count {
+            errors.push(format!("too many {}s: {} > {}", kind, total_count, max_count));
+        }
+    }
+
+    fn push(&mut self, panel: Panel, is_stock: bool) {
+        if is_stock {
+            self.request.stock_panels.push(panel);
+        } else {
+            self.request.panels.push(panel);
+        }
+    }
+}
+
+/// Panel or stock sheet being added to a CalculationRequestBuilder
+#[derive(Debug, Clone)]
+pub struct PanelBuilder {
+    parent: CalculationRequestBuilder,
+    panel: Panel,
+    is_stock: bool,
+}
+
+impl PanelBuilder {
+    fn new(
+        mut parent: CalculationRequestBuilder,
+        id: u32,
+        width: f64,
+        height: f64,
+        is_stock: bool,
+    ) -> Self {
+        let kind = if is_stock { "stock panel" } else { "panel" };
+        let width = Self::format_dimension(width, kind, id, "width", &mut parent.errors);
+        let height = Self::format_dimension(height, kind, id, "height", &mut parent.errors);
+
+        let mut panel = Panel::new(id, &width, &height, 1, "");
+        panel.enabled = true;
+
+        Self {
+            parent,
+            panel,
+            is_stock,
+        }
+    }
+
+    /// Dimensions are stored as strings in the request, keep the shortest exact form
+    fn format_dimension(value: f64, kind: &str, id: u32, name: &str, errors: &mut Vec<String>) -> String {
+        if !(value.is_finite() && value > 0.0) {
+            errors.push(format!("{} {} has invalid {} {}", kind, id, name, value));
+            return value.to_string();
+        }
+
+        match ScaledNumber::try_from(value) {
+            Ok(number) => number.to_string(),
+            Err(err) => {
+                errors.push(format!("{} {} has invalid {}: {}", kind, id, name, err));
+                value.to_string()
+            }
+        }
+    }
+
+    pub fn id(mut self, id: u32) -> Self {
+        self.panel.id = id;
+        self
+    }
+
+    pub fn count(mut self, count: u32) -> Self {
+        self.panel.count = count;
+        self
+    }
+
+    pub fn material(mut self, material: &str) -> Self {
+        self.panel.material = material.to_string();
+        self
+    }
+
+    /// Grain direction the panel must keep, as the legacy orientation, see `grain_direction`
+    #[deprecated(note = "use grain_direction")]
+    pub fn grain(self, orientation: Orientation) -> Self {
+        self.grain_direction(orientation.into())
+    }
+
+    /// Grain direction of the panel or stock sheet, a grained panel keeps the grain of a grained sheet
+    pub fn grain_direction(mut self, grain: GrainDirection) -> Self {
+        self.panel.grain = Some(grain);
+        self
+    }
+
+    pub fn label(mut self, label: &str) -> Self {
+        self.panel.label = label.to_string();
+        self
+    }
+
+    pub fn thickness(mut self, thickness: f64) -> Self {
+        let kind = if self.is_stock { "stock panel" } else { "panel" };
+        let thickness = Self::format_dimension(thickness, kind, self.panel.id, "thickness", &mut self.parent.errors);
+        self.panel.thickness = Some(thickness);
+        self
+    }
+
+    /// Minimum distance to the sheet edges, 0 - the panel may touch them
+    pub fn edge_clearance(mut self, edge_clearance: f64) -> Self {
+        if edge_clearance == 0.0 {
+            self.panel.edge_clearance = None;
+            return self;
+        }
+        let kind = if self.is_stock { "stock panel" } else { "panel" };
+        let edge_clearance =
+            Self::format_dimension(edge_clearance, kind, self.panel.id, "edge clearance", &mut self.parent.errors);
+        self.panel.edge_clearance = Some(edge_clearance);
+        self
+    }
+
+    /// Stock priority, sheets with a higher one are used up first
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    ///
+    /// let request = CalculationRequestBuilder::new()
+    ///     .add_panel(400.0, 600.0)
+    ///     .add_stock(2800.0, 2070.0)
+    ///     .add_stock(900.0, 700.0).label("offcut").priority(10)
+    ///     .add_stock(2800.0, 2070.0).acquired_date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(request.stock_panels[1].priority, Some(10));
+    /// assert!(request.stock_panels[2].acquired_date.is_some());
+    /// ```
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.panel.priority = Some(priority);
+        self
+    }
+
+    /// Day the stock sheets were acquired, older sheets of the same priority are used first
+    pub fn acquired_date(mut self, acquired_date: NaiveDate) -> Self {
+        self.panel.acquired_date = Some(acquired_date);
+        self
+    }
+
+    /// Price of a stock sheet and how it is charged, in place of `Configuration::cost_per_sheet`
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::stock_pricing::StockPricing;
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    /// use rezalnyas_core::features::engine::model::cost_summary::CostSummary;
+    ///
+    /// // Целый лист по акции дешевле обрезка, который продаётся за м²
+    /// let request = CalculationRequestBuilder::new()
+    ///     .add_panel(900.0, 600.0)
+    ///     .add_stock(2800.0, 2070.0).id(1).price(10.0, StockPricing::PerSheet)
+    ///     .add_stock(1000.0, 700.0).id(2).price(25.0, StockPricing::PerSquareMeter)
+    ///     .build()
+    ///     .unwrap();
+    /// let service = CutListOptimizerServiceImpl::new(1, false).unwrap();
+    /// let task_id = service.submit_task(request).unwrap().task_id.unwrap();
+    /// let response = service.get_task_status(&task_id).unwrap().solution.unwrap();
+    /// assert_eq!(response.mosaics[0].request_stock_id, Some(1));
+    /// assert_eq!(CostSummary::format_amount(&response.cost_summary.unwrap().total_cost), "10.00");
+    /// ```
+    pub fn price(mut self, price: f64, pricing: StockPricing) -> Self {
+        self.panel.price = Some(price);
+        self.panel.pricing = Some(pricing);
+        self
+    }
+
+    /// Outline of the panel, a circle sets the width and height to its diameter
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::panel_shape::PanelShape;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    ///
+    /// let request = CalculationRequestBuilder::new()
+    ///     .add_panel(900.0, 900.0).shape(PanelShape::Circle { diameter: 900.0 })
+    ///     .add_stock(2800.0, 2070.0)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(request.panels[0].shape, PanelShape::Circle { diameter: 900.0 });
+    /// ```
+    pub fn shape(mut self, shape: PanelShape) -> Self {
+        self.panel.shape = shape;
+        self
+    }
+
+    pub fn edge(mut self, edge: Edge) -> Self {
+        self.panel.edge = Some(edge);
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.panel.enabled = enabled;
+        self
+    }
+
+    /// Finish this entry and return to the request builder
+    pub fn done(self) -> CalculationRequestBuilder {
+        let mut parent = self.parent;
+        parent.push(self.panel, self.is_stock);
+        parent
+    }
+
+    pub fn add_panel(self, width: f64, height: f64) -> PanelBuilder {
+        self.done().add_panel(width, height)
+    }
+
+    pub fn add_stock(self, width: f64, height: f64) -> PanelBuilder {
+        self.done().add_stock(width, height)
+    }
+
+    pub fn build(self) -> Result<CalculationRequest> {
+        self.done().build()
+    }
+}
@@ -1,10 +1,20 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
 
 use serde::{Deserialize, Serialize};
 
-use crate::enums::orientation::Orientation;
+use crate::enums::{
+    coordinate_system::CoordinateSystem, cut_axis::CutAxis, orientation::Orientation, panel_shape::PanelShape,
+};
 use crate::features::engine::model::calculation_request::{CalculationRequest, Edge};
-use crate::features::engine::model::tile_node::TileNode;
+use crate::features::engine::model::cost_summary::CostSummary;
+use crate::features::engine::model::layout_node::LayoutNode;
+use crate::features::engine::model::layout_report::{LayoutIssue, LayoutIssueKind};
+use crate::features::engine::model::material_draw::MaterialDraw;
+use crate::features::engine::model::response_diff::ResponseDiff;
+use crate::features::engine::model::response_integrity::ResponseIntegrity;
+use crate::features::engine::model::response_summary::ResponseSummary;
+use crate::features::engine::model::tile_node::{TileNode, TileNodeIndex};
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,12 +28,36 @@ pub struct CalculationResponse {
     pub total_used_area_ratio: f64,
     pub total_nbr_cuts: i64,
     pub total_cut_length: f64,
+    /// Estimated saw time of all sheets, see `Configuration::saw_speed_mm_per_min`
+    pub total_estimated_time_seconds: Option<f64>,
+    /// Price of the layout, see `Configuration::cost_per_cut` and `Configuration::cost_per_sheet`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_summary: Option<CostSummary>,
     pub request: CalculationRequest,
     pub panels: Vec<FinalTile>,
     pub used_stock_panels: Vec<FinalTile>,
     pub edge_bands: HashMap<String, f64>,
     pub no_fit_panels: Vec<NoFitTile>,
     pub mosaics: Vec<Mosaic>,
+    pub material_draws: Vec<MaterialDraw>,
+    /// Search stopped at `Configuration::acceptable_waste_percent` for at least one material
+    #[serde(default)]
+    pub early_exit: bool,
+    /// Problems of the request that didn't stop the computation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Material keys cut as bars by the one-dimensional optimizer instead of the 2D search
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub one_dimensional_materials: Vec<String>,
+    /// Response of an identical request computed earlier, reused without a new computation
+    #[serde(default)]
+    pub cached: bool,
+    /// Sheets, parts, waste, cuts, edge banding, time and cost per material and in total
+    #[serde(default)]
+    pub summary: ResponseSummary,
+    /// Content hash and signature, see `Configuration::include_content_hash`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<ResponseIntegrity>,
 }
 
 impl CalculationResponse {
@@ -38,15 +72,42 @@ impl CalculationResponse {
             total_used_area_ratio: 0.0,
             total_nbr_cuts: 0,
             total_cut_length: 0.0,
+            total_estimated_time_seconds: None,
+            cost_summary: None,
             request: CalculationRequest::default(),
             panels: Vec::new(),
             used_stock_panels: Vec::new(),
             edge_bands: HashMap::new(),
             no_fit_panels: Vec::new(),
             mosaics: Vec::new(),
+            material_draws: Vec::new(),
+            early_exit: false,
+            warnings: Vec::new(),
+            one_dimensional_materials: Vec::new(),
+            cached: false,
+            summary: ResponseSummary::default(),
+            integrity: None,
         }
     }
 
+    /// Changes from this response to `other`: moved panels, sheet counts and waste
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::calculation_response::CalculationResponse;
+    ///
+    /// let before = CalculationResponse::new();
+    /// let mut after = before.clone();
+    /// after.total_wasted_area = 1250.0;
+    ///
+    /// let diff = before.diff(&after);
+    /// assert_eq!(diff.wasted_area_delta, 1250.0);
+    /// assert!(diff.moved_panels.is_empty());
+    /// assert!(before.diff(&before).is_empty());
+    /// ```
+    pub fn diff(&self, other: &CalculationResponse) -> ResponseDiff {
+        ResponseDiff::new(self, other)
+    }
+
     pub fn version() -> &'static str {
         "1.2"
     }
@@ -61,23 +122,51 @@ impl Default for CalculationResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 // -= доработать
 pub struct Mosaic {
+    /// Cuts of a response mosaic, engine mosaics keep them only in the tree, see `Mosaic::get_cuts`
     pub cuts: Vec<Cut>,
     pub material: Option<String>,
     pub orientation: Orientation,
     pub root_tile_node: Vec<TileNode>,
 
     pub cut_length: f64,
+    pub nbr_cuts: i32,
+    /// Largest leftover rectangle of the sheet
+    pub biggest_unused_tile: Option<Tile>,
+    /// Waste share per cell, see `Mosaic::get_waste_heatmap`
+    pub waste_heatmap: Vec<f32>,
+    /// Cutting plus sheet handling time, None without a configured saw speed
+    pub estimated_time_seconds: Option<f64>,
 
-    
     pub edge_bands: HashMap<String, f64>,
     pub nbr_wasted_panels: i32,
     pub nbr_final_panels: i32,
     pub panels: Vec<FinalTile>,
+    pub tiles: Vec<Tile>,
     pub request_stock_id: Option<i32>,
     pub stock_label: Option<String>,
     pub used_area: f64,
     pub used_area_ratio: f32,
     pub wasted_area: f64,
+    /// Priority of the stock sheet, see `Panel::priority`
+    #[serde(default)]
+    pub stock_priority: i32,
+    /// System of the coordinates of `tiles`, `cuts`, `biggest_unused_tile`, `offcuts` and `layout_tree`
+    #[serde(default)]
+    pub coordinate_system: CoordinateSystem,
+    /// Leftover rectangles of the sheet with both sides at least `Configuration::min_offcut_dimension`,
+    /// to register them as offcut stock
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub offcuts: Vec<Tile>,
+    /// Guillotine tree of the sheet, set with `Configuration::include_layout_tree`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_tree: Option<LayoutNode>,
+
+    /// Cuts derived from root_tile_node, reset whenever the root is replaced
+    #[serde(skip)]
+    derived_cuts: OnceLock<Vec<Cut>>,
+    /// Index of root_tile_node, shared with the copies split from it, see `Mosaic::get_node_index`
+    #[serde(skip)]
+    node_index: OnceLock<Arc<TileNodeIndex>>,
 }
 
 impl Default for Mosaic {
@@ -88,15 +177,26 @@ impl Default for Mosaic {
             orientation: Orientation::Portrait,
             root_tile_node: Vec::new(),
             cut_length: 0.0,
+            nbr_cuts: 0,
+            biggest_unused_tile: None,
+            waste_heatmap: Vec::new(),
+            estimated_time_seconds: None,
             edge_bands: HashMap::new(),
             nbr_wasted_panels: 0,
             nbr_final_panels: 0,
             panels: Vec::new(),
+            tiles: Vec::new(),
             request_stock_id: None,
             stock_label: None,
             used_area: 0.0,
             used_area_ratio: 0.0,
             wasted_area: 0.0,
+            stock_priority: 0,
+            coordinate_system: CoordinateSystem::default(),
+            offcuts: Vec::new(),
+            layout_tree: None,
+            derived_cuts: OnceLock::new(),
+            node_index: OnceLock::new(),
         }
     }
 }
@@ -112,15 +212,21 @@ impl Mosaic {
         
         // Java: this.material = tileDimensions.getMaterial();
         mosaic.material = Some(tile_dimensions.material.clone());
+
+        // Java: this.orientation = tileDimensions.getOrientation();
+        mosaic.orientation = tile_dimensions.orientation;
+        mosaic.stock_priority = tile_dimensions.priority;
         
         // Java: this.rootTileNode = new TileNode(0, tileDimensions.getWidth(), 0, tileDimensions.getHeight());
-        let root_node = TileNode::new(
+        let mut root_node = TileNode::new(
             0, 
             tile_dimensions.width as i32, 
             0, 
             tile_dimensions.height as i32
         );
-     
+        // Root keeps the id of the stock tile so the response can resolve the stock panel
+        root_node.set_external_id(Some(tile_dimensions.id));
+        mosaic.root_tile_node.push(root_node);
         
         // Java: this.wastedArea = tileDimensions.getArea();
         mosaic.wasted_area = (tile_dimensions.width * tile_dimensions.height) as f64;
@@ -134,11 +240,20 @@ impl Mosaic {
     
     /// Calculate unused area - matches Java Mosaic.getUnusedArea()
     pub fn get_unused_area(&self) -> i64 {
-        
-            0 // Fallback if no root node
-        
+        self.root_tile_node
+            .first()
+            .map(|root| root.get_unused_area())
+            .unwrap_or(0) // Fallback if no root node
     }
     
+    /// Number of panels placed on the sheet
+    pub fn get_nbr_final_tiles(&self) -> i32 {
+        self.root_tile_node
+            .first()
+            .map(|root| root.get_nbr_final_tiles())
+            .unwrap_or(0)
+    }
+
     /// Java: public HashSet<Integer> getDistictTileSet()
     pub fn get_distict_tile_set(&self) -> HashSet<i32> {
       
@@ -149,11 +264,235 @@ impl Mosaic {
     /// Java: public long getBiggestArea()
     pub fn get_biggest_area(&self) -> i64 {
         // Java: return this.rootTileNode.getBiggestArea();
-      
-            0
-        
+        self.root_tile_node
+            .first()
+            .map(|root| root.get_biggest_area())
+            .unwrap_or(0)
+    }
+
+    /// Share of waste in each cell of a `size` x `size` grid laid over the sheet, row by row.
+    /// 0.0 - the cell is fully used, 1.0 - the cell is fully wasted.
+    pub fn get_waste_heatmap(&self, size: usize) -> Vec<f32> {
+        let Some(root) = self.root_tile_node.first() else {
+            return Vec::new();
+        };
+        if size == 0 || root.get_area() == 0 {
+            return Vec::new();
+        }
+
+        let unused_nodes = root.get_unused_nodes();
+        let bounds = |from: i32, length: i32, index: usize| {
+            (
+                from + (length as i64 * index as i64 / size as i64) as i32,
+                from + (length as i64 * (index + 1) as i64 / size as i64) as i32,
+            )
+        };
+
+        let mut heatmap = Vec::with_capacity(size * size);
+        for row in 0..size {
+            let (cell_y1, cell_y2) = bounds(root.y1, root.get_height(), row);
+            for column in 0..size {
+                let (cell_x1, cell_x2) = bounds(root.x1, root.get_width(), column);
+                let cell_area = (cell_x2 - cell_x1) as i64 * (cell_y2 - cell_y1) as i64;
+                if cell_area == 0 {
+                    heatmap.push(0.0);
+                    continue;
+                }
+
+                let wasted_area: i64 = unused_nodes
+                    .iter()
+                    .map(|node| {
+                        let width = (node.x2.min(cell_x2) - node.x1.max(cell_x1)).max(0) as i64;
+                        let height = (node.y2.min(cell_y2) - node.y1.max(cell_y1)).max(0) as i64;
+                        width * height
+                    })
+                    .sum();
+                heatmap.push(wasted_area as f32 / cell_area as f32);
+            }
+        }
+        heatmap
     }
     
+    pub fn set_root_tile_node(&mut self, root: TileNode) {
+        self.root_tile_node = vec![root];
+        self.derived_cuts = OnceLock::new();
+        self.node_index = OnceLock::new();
+    }
+
+    /// Replace the root with a copy of it in which node `id` was split further. The index of
+    /// the old root is extended with the new nodes instead of being built again
+    pub fn set_split_root_tile_node(&mut self, root: TileNode, id: u32) {
+        let index = TileNodeIndex::with_split(self.get_node_index(), &root, id);
+        self.set_root_tile_node(root);
+        self.node_index = OnceLock::from(index);
+    }
+
+    /// Index of the nodes of the sheet by id, built once per sheet, see `TileNodeIndex`
+    pub fn get_node_index(&self) -> &Arc<TileNodeIndex> {
+        self.node_index
+            .get_or_init(|| Arc::new(self.root_tile_node.first().map(TileNodeIndex::new).unwrap_or_default()))
+    }
+
+    /// Cuts of the sheet in the order they were made, derived once from the tile tree
+    pub fn get_cuts(&self) -> &[Cut] {
+        self.derived_cuts.get_or_init(|| {
+            let mut cuts: Vec<Cut> = self.root_tile_node.iter().flat_map(|root| root.iter_cuts()).collect();
+            // Node ids grow with every split, so child ids give the cutting order
+            cuts.sort_by_key(|cut| cut.piece_ids[0]);
+            cuts
+        })
+    }
+
+    /// Java: public int getNbrCuts()
+    pub fn get_nbr_cuts(&self) -> i32 {
+        self.get_cuts().len() as i32
+    }
+
+    /// Cuts that run across the whole sheet, edge to edge. Engine sheets are read from the
+    /// tile tree, response sheets from `cuts` and the sheet tile
+    pub fn get_nbr_through_cuts(&self) -> i32 {
+        self.get_cuts_and_sheet_size()
+            .map(|(cuts, width, height)| cuts.iter().filter(|cut| cut.is_through(width, height)).count() as i32)
+            .unwrap_or(0)
+    }
+
+    /// Share of the cuts that run across the whole sheet, 1.0 for a sheet without cuts
+    pub fn get_through_cut_ratio(&self) -> f64 {
+        let nbr_cuts = self.get_cuts_and_sheet_size().map(|(cuts, _, _)| cuts.len()).unwrap_or(0);
+        if nbr_cuts == 0 {
+            return 1.0;
+        }
+        self.get_nbr_through_cuts() as f64 / nbr_cuts as f64
+    }
+
+    fn get_cuts_and_sheet_size(&self) -> Option<(&[Cut], f64, f64)> {
+        match self.root_tile_node.first() {
+            Some(root) => Some((self.get_cuts(), root.get_width() as f64, root.get_height() as f64)),
+            None => self
+                .tiles
+                .first()
+                .map(|sheet| (self.cuts.as_slice(), sheet.width, sheet.height)),
+        }
+    }
+
+    /// Express the tiles, offcuts, cuts and waste heatmap of a response sheet in `target`.
+    /// The legacy `cut_coord` of a cut is measured from the new origin then, see `Cut::get_first_piece_size`
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::coordinate_system::CoordinateSystem;
+    /// use rezalnyas_core::enums::cut_axis::CutAxis;
+    /// use rezalnyas_core::features::engine::model::calculation_response::{Cut, CutNode, Mosaic, Tile};
+    ///
+    /// let mut mosaic = Mosaic::new();
+    /// mosaic.tiles = vec![Tile::with_coords(0, 0.0, 0.0, 1000.0, 600.0), Tile::with_coords(1, 0.0, 0.0, 1000.0, 400.0)];
+    /// let sheet = CutNode { id: 0, x: 0.0, y: 0.0, width: 1000.0, height: 600.0 };
+    /// mosaic.cuts = vec![Cut::new(CutAxis::Y, 400.0, sheet, [1, 2])];
+    ///
+    /// mosaic.set_coordinate_system(CoordinateSystem::BottomLeftYUp);
+    /// assert_eq!(mosaic.tiles[1].y, 200.0);
+    /// assert_eq!(mosaic.cuts[0].get_y1(), 200.0);
+    /// assert!(mosaic.validate(0.0).is_empty());
+    /// ```
+    pub fn set_coordinate_system(&mut self, target: CoordinateSystem) {
+        let source = self.coordinate_system;
+        if source == target {
+            return;
+        }
+        let Some(sheet_height) = self.tiles.first().map(|sheet| sheet.height) else {
+            self.coordinate_system = target;
+            return;
+        };
+
+        for tile in self
+            .tiles
+            .iter_mut()
+            .chain(self.biggest_unused_tile.as_mut())
+            .chain(self.offcuts.iter_mut())
+        {
+            tile.y = source.convert_y(target, tile.y, tile.height, sheet_height);
+        }
+        for cut in &mut self.cuts {
+            *cut = cut.convert_y(source, target, sheet_height);
+        }
+        if let Some(layout_tree) = &mut self.layout_tree {
+            layout_tree.convert_y(source, target, sheet_height);
+        }
+        // Строки карты отходов идут сверху вниз в исходной системе
+        let size = (self.waste_heatmap.len() as f64).sqrt() as usize;
+        if size > 0 && size * size == self.waste_heatmap.len() {
+            let rows: Vec<Vec<f32>> = self.waste_heatmap.chunks(size).rev().map(<[f32]>::to_vec).collect();
+            self.waste_heatmap = rows.concat();
+        }
+        self.coordinate_system = target;
+    }
+
+    /// Check the panels of a response sheet: none sticks out of the sheet, and panels next to
+    /// each other are at least `cut_thickness` apart. The first tile is the sheet itself.
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::calculation_response::{Mosaic, Tile};
+    /// use rezalnyas_core::features::engine::model::layout_report::LayoutIssueKind;
+    ///
+    /// let panel = |id, x| {
+    ///     let mut tile = Tile::with_coords(id, x, 0.0, 400.0, 600.0);
+    ///     tile.request_obj_id = Some(id);
+    ///     tile.is_final = true;
+    ///     tile
+    /// };
+    /// let mut mosaic = Mosaic::new();
+    /// mosaic.tiles = vec![Tile::with_coords(0, 0.0, 0.0, 1000.0, 600.0), panel(1, 0.0), panel(2, 402.0)];
+    ///
+    /// assert!(mosaic.validate(2.0).is_empty());
+    /// assert_eq!(mosaic.validate(3.0)[0].kind, LayoutIssueKind::KerfViolation);
+    /// ```
+    pub fn validate(&self, cut_thickness: f64) -> Vec<LayoutIssue> {
+        const EPSILON: f64 = 1e-6;
+        let mut issues = Vec::new();
+        let Some(sheet) = self.tiles.first() else {
+            return issues;
+        };
+        let panels: Vec<&Tile> = self.tiles[1..].iter().filter(|tile| tile.is_final).collect();
+        let panel_ids = |tiles: &[&Tile]| -> Vec<u32> {
+            tiles.iter().filter_map(|tile| tile.request_obj_id).map(|id| id as u32).collect()
+        };
+
+        for panel in &panels {
+            if panel.x < sheet.x - EPSILON
+                || panel.y < sheet.y - EPSILON
+                || panel.x + panel.width > sheet.x + sheet.width + EPSILON
+                || panel.y + panel.height > sheet.y + sheet.height + EPSILON
+            {
+                issues.push(LayoutIssue::new(
+                    LayoutIssueKind::OffSheet,
+                    panel_ids(&[panel]),
+                    format!("tile {} at {},{} is off the sheet", panel.id, panel.x, panel.y),
+                ));
+            }
+        }
+
+        for (index, a) in panels.iter().enumerate() {
+            for b in &panels[index + 1..] {
+                let gap_x = a.x.max(b.x) - (a.x + a.width).min(b.x + b.width);
+                let gap_y = a.y.max(b.y) - (a.y + a.height).min(b.y + b.height);
+                let (kind, problem) = if gap_x < -EPSILON && gap_y < -EPSILON {
+                    (LayoutIssueKind::Overlap, "overlap")
+                } else if (gap_y < -EPSILON && gap_x < cut_thickness - EPSILON)
+                    || (gap_x < -EPSILON && gap_y < cut_thickness - EPSILON)
+                {
+                    (LayoutIssueKind::KerfViolation, "are closer than the cut thickness")
+                } else {
+                    continue;
+                };
+                issues.push(LayoutIssue::new(
+                    kind,
+                    panel_ids(&[a, b]),
+                    format!("tiles {} and {} {}", a.id, b.id, problem),
+                ));
+            }
+        }
+        issues
+    }
+
     /// Java: public float getHVDiff()
     pub fn get_hvdiff(&self) -> f32 {
       
@@ -177,6 +516,9 @@ pub struct Tile {
     pub has_children: bool,
     pub edge: Edge,
     pub is_rotated: bool,
+    /// Outline of the panel inside the tile, see `Panel::shape`
+    #[serde(default, skip_serializing_if = "PanelShape::is_rect")]
+    pub shape: PanelShape,
 }
 
 impl Tile {
@@ -194,6 +536,7 @@ impl Tile {
             has_children: false,
             edge: Edge::new(),
             is_rotated: false,
+            shape: PanelShape::default(),
         }
     }
 
@@ -217,6 +560,26 @@ impl Default for Tile {
 
 
 
+/// Panel that fits none of the stock, in the units of the request
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(100.0, 100.0).count(1)
+///     .add_panel(1200.5, 300.25).count(2)
+///     .add_stock(1000.0, 1000.0).count(1)
+///     .build()
+///     .unwrap();
+/// let service = CutListOptimizerServiceImpl::new(1, false).unwrap();
+/// let task_id = service.submit_task(request).unwrap().task_id.unwrap();
+/// let response = service.get_task_status(&task_id).unwrap().solution.unwrap();
+///
+/// // Дробные размеры не обрезаются до целых
+/// let no_fit = &response.no_fit_panels[0];
+/// assert_eq!((no_fit.width, no_fit.height, no_fit.count), (1200.5, 300.25, 2));
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoFitTile {
     pub id: u32,
@@ -257,13 +620,45 @@ impl Default for NoFitTile {
     }
 }
 
+/// Row of the cut ticket: all placed copies of one part size, label and material.
+/// `CalculationResponse::panels` has one per part, `Mosaic::panels` one per part of the sheet
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::layout_evaluator::evaluate_layout;
+/// use rezalnyas_core::features::engine::model::calculation_request::PinnedPlacement;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 300.0).id(1).label("shelf").count(3)
+///     .add_stock(1000.0, 600.0).id(10).count(2)
+///     .build()
+///     .unwrap();
+/// let report = evaluate_layout(&request, &[
+///     PinnedPlacement::new(1, 10, 0, "0", "0", false),
+///     PinnedPlacement::new(1, 10, 0, "400", "0", false),
+///     PinnedPlacement::new(1, 10, 1, "0", "0", true),
+/// ]);
+///
+/// let shelf = &report.response.panels[0];
+/// assert_eq!((shelf.width, shelf.height, shelf.count), (400.0, 300.0, 3));
+/// assert_eq!(shelf.label.as_deref(), Some("shelf"));
+/// assert_eq!(shelf.sheet_numbers, vec![1, 2]);
+/// assert_eq!(report.response.mosaics[0].panels[0].count, 2);
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FinalTile {
     pub request_obj_id: i32,
+    /// Size of the part as requested, whatever its rotation on the sheets
     pub width: f64,
     pub height: f64,
     pub label: Option<String>,
     pub count: i32,
+    /// Material key of the sheets the part is cut from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+    /// 1-based numbers of the response sheets with the part, ascending
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sheet_numbers: Vec<usize>,
 }
 
 impl FinalTile {
@@ -274,6 +669,8 @@ impl FinalTile {
             height: 0.0,
             label: None,
             count: 0,
+            material: None,
+            sheet_numbers: Vec::new(),
         }
     }
 
@@ -290,8 +687,173 @@ impl Default for FinalTile {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Guillotine cut of a sheet: a line across `node` at `position` along `axis`. The ends of the
+/// line, the pieces and the length are derived from them, so the cut can't contradict itself.
+/// Serialized in the legacy layout of the response, see `LegacyCut`
+///
+/// ```rust
+/// use rezalnyas_core::enums::cut_axis::CutAxis;
+/// use rezalnyas_core::features::engine::model::calculation_response::Cut;
+/// use rezalnyas_core::features::engine::model::tile_node::TileNode;
+///
+/// let mut sheet = TileNode::new(0, 1000, 0, 600);
+/// let cut = sheet.split(false, 400, 4).unwrap();
+/// assert_eq!((cut.axis, cut.position), (CutAxis::Y, 400.0));
+/// assert_eq!((cut.get_x1(), cut.get_y1(), cut.get_x2(), cut.get_y2()), (0.0, 400.0, 1000.0, 400.0));
+/// assert_eq!((cut.get_first_piece_size(), cut.get_length()), (400.0, 1000.0));
+///
+/// let json = serde_json::to_value(&cut).unwrap();
+/// assert_eq!((json["y1"].as_f64(), json["cut_coord"].as_f64()), (Some(400.0), Some(400.0)));
+/// assert_eq!(serde_json::from_value::<Cut>(json).unwrap(), cut);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "LegacyCut", into = "LegacyCut")]
 pub struct Cut {
+    pub axis: CutAxis,
+    /// Coordinate of the cut line on the sheet along `axis`
+    pub position: f64,
+    /// Tile the cut split, as it was before the cut
+    pub node: CutNode,
+    /// Ids of the piece before and the piece after the cut, tiles of the response
+    pub piece_ids: [i32; 2],
+    /// Request id of the panel this cut was made for
+    pub request_obj_id: Option<i32>,
+    /// Guillotine stage of the cut, starting at 1
+    pub stage: u32,
+}
+
+/// Rectangle of the tile a cut split
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CutNode {
+    pub id: i32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Cut {
+    pub fn new(axis: CutAxis, position: f64, node: CutNode, piece_ids: [i32; 2]) -> Self {
+        Self {
+            axis,
+            position,
+            node,
+            piece_ids,
+            request_obj_id: None,
+            stage: 0,
+        }
+    }
+
+    /// Cut that split `node` into `child1` and `child2`, see CutListThread split_horizontally/split_vertically
+    pub fn from_split(node: &TileNode, child1: &TileNode, child2: &TileNode) -> Self {
+        let is_horizontal = child1
+            .cut_horizontal
+            .unwrap_or(child1.y1 == node.y1 && child1.y2 == node.y2);
+        let (axis, position) = if is_horizontal {
+            (CutAxis::X, node.x1 + child1.get_width())
+        } else {
+            (CutAxis::Y, node.y1 + child1.get_height())
+        };
+        let node_rect = CutNode {
+            id: node.id as i32,
+            x: node.x1 as f64,
+            y: node.y1 as f64,
+            width: node.get_width() as f64,
+            height: node.get_height() as f64,
+        };
+        Self {
+            stage: child1.cut_stage,
+            ..Self::new(axis, position as f64, node_rect, [child1.id as i32, child2.id as i32])
+        }
+    }
+
+    pub fn get_x1(&self) -> f64 {
+        match self.axis {
+            CutAxis::X => self.position,
+            CutAxis::Y => self.node.x,
+        }
+    }
+
+    pub fn get_y1(&self) -> f64 {
+        match self.axis {
+            CutAxis::X => self.node.y,
+            CutAxis::Y => self.position,
+        }
+    }
+
+    pub fn get_x2(&self) -> f64 {
+        match self.axis {
+            CutAxis::X => self.position,
+            CutAxis::Y => self.node.x + self.node.width,
+        }
+    }
+
+    pub fn get_y2(&self) -> f64 {
+        match self.axis {
+            CutAxis::X => self.node.y + self.node.height,
+            CutAxis::Y => self.position,
+        }
+    }
+
+    /// Distance of the cut line from the start of the node along the axis, the legacy `cut_coord`
+    pub fn get_first_piece_size(&self) -> f64 {
+        match self.axis {
+            CutAxis::X => self.position - self.node.x,
+            CutAxis::Y => self.position - self.node.y,
+        }
+    }
+
+    pub fn get_length(&self) -> f64 {
+        match self.axis {
+            CutAxis::X => self.node.height,
+            CutAxis::Y => self.node.width,
+        }
+    }
+
+    /// Whether the cut runs the full `width` or `height` of the sheet
+    pub fn is_through(&self, width: f64, height: f64) -> bool {
+        match self.axis {
+            CutAxis::X => self.get_length() >= height,
+            CutAxis::Y => self.get_length() >= width,
+        }
+    }
+
+    /// The cut with all lengths divided by `divisor`, engine units to request units
+    pub fn scaled(&self, divisor: f64) -> Self {
+        Self {
+            position: self.position / divisor,
+            node: CutNode {
+                x: self.node.x / divisor,
+                y: self.node.y / divisor,
+                width: self.node.width / divisor,
+                height: self.node.height / divisor,
+                ..self.node
+            },
+            ..self.clone()
+        }
+    }
+
+    /// The cut with y in `target`, see `CoordinateSystem::convert_y`
+    pub fn convert_y(&self, source: CoordinateSystem, target: CoordinateSystem, sheet_height: f64) -> Self {
+        let position = match self.axis {
+            CutAxis::X => self.position,
+            CutAxis::Y => source.convert_y(target, self.position, 0.0, sheet_height),
+        };
+        Self {
+            position,
+            node: CutNode {
+                y: source.convert_y(target, self.node.y, self.node.height, sheet_height),
+                ..self.node
+            },
+            ..self.clone()
+        }
+    }
+}
+
+/// Cut in the field layout of the Java response and of the responses before `Cut` was derived
+/// from its node. Legacy cuts measure `cut_coord` from the top-left corner of the node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LegacyCut {
     pub x1: f64,
     pub y1: f64,
     pub x2: f64,
@@ -303,28 +865,51 @@ pub struct Cut {
     pub original_height: f64,
     pub child1_tile_id: i32,
     pub child2_tile_id: i32,
+    #[serde(default)]
+    pub request_obj_id: Option<i32>,
+    #[serde(default)]
+    pub stage: u32,
 }
 
-impl Cut {
-    pub fn new() -> Self {
+impl From<Cut> for LegacyCut {
+    fn from(cut: Cut) -> Self {
         Self {
-            x1: 0.0,
-            y1: 0.0,
-            x2: 0.0,
-            y2: 0.0,
-            cut_coord: 0.0,
-            is_horizontal: false,
-            original_tile_id: 0,
-            original_width: 0.0,
-            original_height: 0.0,
-            child1_tile_id: 0,
-            child2_tile_id: 0,
+            x1: cut.get_x1(),
+            y1: cut.get_y1(),
+            x2: cut.get_x2(),
+            y2: cut.get_y2(),
+            cut_coord: cut.get_first_piece_size(),
+            is_horizontal: cut.axis.is_horizontal(),
+            original_tile_id: cut.node.id,
+            original_width: cut.node.width,
+            original_height: cut.node.height,
+            child1_tile_id: cut.piece_ids[0],
+            child2_tile_id: cut.piece_ids[1],
+            request_obj_id: cut.request_obj_id,
+            stage: cut.stage,
         }
     }
 }
 
-impl Default for Cut {
-    fn default() -> Self {
-        Self::new()
+impl From<LegacyCut> for Cut {
+    fn from(legacy: LegacyCut) -> Self {
+        let axis = CutAxis::from_horizontal(legacy.is_horizontal);
+        // Начало узла вдоль оси - линия реза минус первый кусок, поперёк - начало линии
+        let (position, x, y) = match axis {
+            CutAxis::X => (legacy.x1, legacy.x1 - legacy.cut_coord, legacy.y1.min(legacy.y2)),
+            CutAxis::Y => (legacy.y1, legacy.x1.min(legacy.x2), legacy.y1 - legacy.cut_coord),
+        };
+        let node = CutNode {
+            id: legacy.original_tile_id,
+            x,
+            y,
+            width: legacy.original_width,
+            height: legacy.original_height,
+        };
+        Self {
+            request_obj_id: legacy.request_obj_id,
+            stage: legacy.stage,
+            ..Self::new(axis, position, node, [legacy.child1_tile_id, legacy.child2_tile_id])
+        }
     }
 }
@@ -1,16 +1,23 @@
 use serde::{Deserialize, Serialize};
 
+use crate::constants::EngineConstants;
 use crate::features::engine::cut_list_thread::CutListThread;
-use crate::features::engine::model::calculation_request::CalculationRequest;
+use crate::features::engine::model::calculation_request::{CalculationRequest, Panel};
 use crate::features::engine::model::calculation_response::{self, CalculationResponse};
 use crate::features::engine::model::client_info::ClientInfo;
+use crate::features::engine::model::layout_node::LayoutNode;
+use crate::features::engine::model::response_summary::ResponseSummary;
 use crate::features::engine::model::solution::Solution;
 use crate::features::engine::model::task::Task;
 use crate::features::engine::model::tile_node::TileNode;
 use crate::features::engine::model::{
     calculation_response::Mosaic, status::Status, stock_solution::StockSolution,
 };
+use crate::features::input::models::normalization_report::MergedRows;
 use crate::features::input::models::tile_dimensions::TileDimensions;
+use crate::i18n::warning::Warning;
+use crate::scaled_math::PrecisionAnalyzer;
+use rayon::prelude::*;
 use std::collections::{HashMap, LinkedList};
 use std::sync::atomic::{AtomicI32, Ordering};
 
@@ -27,9 +34,6 @@ impl CalculationResponseBuilder {
     pub fn build(&self) -> CalculationResponse {
         let mut calculation_response = CalculationResponse::new();
 
-        let panels = &self.calculation_request.panels;
-        let stock_panels = &self.calculation_request.stock_panels;
-
         let mut solution = Solution::default();
         let mut solution_ids = Vec::new();
         let mut timestamp = 0;
@@ -75,102 +79,51 @@ impl CalculationResponseBuilder {
             solution_ids.iter().fold(0, |acc, &x| acc ^ x)
         ));
         calculation_response.solution_elapsed_time = if timestamp > 0 {
-            Some(timestamp - self.task.start_time)
+            Some(timestamp.saturating_sub(self.task.start_time))
         } else {
             None
         };
         calculation_response.request = self.calculation_request.clone();
+        calculation_response.material_draws = self.task.material_draws.clone();
+        calculation_response.early_exit = !self.task.early_exit_materials.is_empty();
+        calculation_response.warnings = self.task.warnings.clone();
+        calculation_response.one_dimensional_materials = self.task.one_dimensional_materials.clone();
+
+        let min_offcut_dimension = self
+            .calculation_request
+            .configuration
+            .min_offcut_dimension
+            .as_deref()
+            .and_then(|value| PrecisionAnalyzer::parse_f64(value).ok())
+            .unwrap_or(0.0);
+
+        // -= Mosaics =- листы собираются параллельно, порядок сохраняется
+        calculation_response.mosaics = solution
+            .mosaics
+            .par_iter()
+            .map(|mosaic| self.build_mosaic(mosaic, min_offcut_dimension))
+            .collect();
 
-        // -= Mosaics =-
-        for mosaic in &solution.mosaics {
-{
-            let mut response_mosaic = Mosaic::new();
-
-            if let Some(root_node) = mosaic.root_tile_node.first() {
-                response_mosaic.request_stock_id = Some(root_node.external_id.unwrap_or(0) as i32);
-                response_mosaic.used_area =
-                    root_node.get_used_area() as f64 / (self.task.factor * self.task.factor) as f64;
-                response_mosaic.used_area_ratio = if root_node.get_area() > 0 {
-                    root_node.get_used_area() as f32 / root_node.get_area() as f32
-                } else {
-                    0.0
-                };
-                response_mosaic.nbr_final_panels = root_node.get_nbr_final_tiles();
-                response_mosaic.nbr_wasted_panels = root_node.get_nbr_final_tiles(); // TODO: implement getNbrUnusedTiles
-                response_mosaic.wasted_area =
-                    mosaic.get_unused_area() as f64 / (self.task.factor * self.task.factor) as f64;
-                response_mosaic.material = mosaic.material.clone();
-
-                // Add children to tiles list
-                // self.add_children_to_list(root_node, &mut response_mosaic.panels);
-            }
-
-            // Calculate cut length
-            let cut_length: i64 = mosaic
-                .cuts
-                .iter()
-                .map(|cut| {
-                    if cut.is_horizontal {
-                        ((cut.x2 - cut.x1) as f64 * self.task.factor as f64) as i64
-                    } else {
-                        ((cut.y2 - cut.y1) as f64 * self.task.factor as f64) as i64
-                    }
-                })
-                .sum();
-            response_mosaic.cut_length = cut_length as f64 / self.task.factor as f64;
-
-            // TODO: Add edge band calculation
-            // response_mosaic.edge_bands = EdgeBanding::calc_edge_bands(&final_tile_nodes, panels, self.task.factor);
-
-            // Set panel labels
-            for panel in panels {
-                for tile in &mut response_mosaic.panels {
-                    if tile.request_obj_id as u32 == panel.id {
-                        tile.label = Some(panel.label.clone());
-                    }
-                }
-            }
-
-            // Set stock panel labels
-            for stock_panel in stock_panels {
-                if let Some(request_stock_id) = response_mosaic.request_stock_id {
-                    if request_stock_id as u32 == stock_panel.id {
-                        response_mosaic.stock_label = Some(stock_panel.label.clone());
-                    }
-                }
-            }
-
-            // Create final panels map
-            let _final_panels_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-            // TODO: Implement final tile nodes collection and processing
-
-            // Add cuts to response mosaic
-            for cut in &mosaic.cuts {
-                let response_cut = calculation_response::Cut {
-                    x1: cut.x1 / self.task.factor as f64,
-                    y1: cut.y1 / self.task.factor as f64,
-                    x2: cut.x2 / self.task.factor as f64,
-                    y2: cut.y2 / self.task.factor as f64,
-                    cut_coord: cut.cut_coord / self.task.factor as f64,
-                    is_horizontal: cut.is_horizontal,
-                    original_tile_id: cut.original_tile_id,
-                    original_width: cut.original_width / self.task.factor as f64,
-                    original_height: cut.original_height / self.task.factor as f64,
-                    child1_tile_id: cut.child1_tile_id,
-                    child2_tile_id: cut.child2_tile_id,
-                };
-                response_mosaic.cuts.push(response_cut);
-            }
-
-            calculation_response.mosaics.push(response_mosaic);
+        // Add no-fit panels from solution
+        for no_fit_panel in &solution.no_fit_panels {
+            self.add_no_fit_tile(&mut calculation_response, no_fit_panel);
         }
 
-
+        for rows in &self.task.normalization_report.aggregated_rows {
+            self.expand_aggregated_rows(&mut calculation_response, rows);
+        }
+        if self.calculation_request.configuration.instance_label_template.is_some() {
+            self.label_instances(&mut calculation_response.mosaics);
+        }
+        if let Some(min_percent) = self.calculation_request.configuration.min_sheet_utilization_percent {
+            let warnings = self.get_low_utilization_warnings(&calculation_response.mosaics, min_percent);
+            calculation_response.warnings.extend(warnings);
         }
 
-        // Add no-fit panels from solution
-        for no_fit_panel in &solution.no_fit_panels {
-            self.add_no_fit_tile(&mut calculation_response, no_fit_panel);
+        // Строки билета: одинаковые детали всех листов и каждого листа отдельно
+        calculation_response.panels = self.group_final_tiles(&calculation_response.mosaics, true);
+        for mosaic in &mut calculation_response.mosaics {
+            mosaic.panels = self.group_final_tiles(std::slice::from_ref(mosaic), false);
         }
 
         // Calculate totals
@@ -197,20 +150,269 @@ impl CalculationResponseBuilder {
         };
         calculation_response.total_nbr_cuts = total_nbr_cuts;
         calculation_response.total_cut_length = total_cut_length;
+        calculation_response.total_estimated_time_seconds = self
+            .calculation_request
+            .configuration
+            .estimate_processing_seconds(total_cut_length, calculation_response.mosaics.len());
+        let sheets: Vec<_> = calculation_response
+            .mosaics
+            .iter()
+            .map(|mosaic| (self.get_request_stock(mosaic), mosaic.used_area + mosaic.wasted_area))
+            .collect();
+        calculation_response.cost_summary =
+            self.calculation_request.configuration.estimate_cost(total_nbr_cuts, &sheets);
         // calculation_response.elapsed_time = self.task.elapsed_time;
+        calculation_response.summary = ResponseSummary::from_response(&calculation_response);
 
 
         calculation_response
     }
 
+    /// Stock row a response mosaic was cut from
+    fn get_request_stock(&self, mosaic: &Mosaic) -> Option<&Panel> {
+        let request_stock_id = mosaic.request_stock_id?;
+        self.calculation_request
+            .stock_panels
+            .iter()
+            .find(|stock| stock.id as i32 == request_stock_id)
+    }
+
+    /// Response sheet of one mosaic of the solution: tiles, cuts, offcuts and stats
+    fn build_mosaic(&self, mosaic: &Mosaic, min_offcut_dimension: f64) -> Mosaic {
+        let panels = &self.calculation_request.panels;
+        let stock_panels = &self.calculation_request.stock_panels;
+
+        let mut response_mosaic = Mosaic::new();
+
+        if let Some(root_node) = mosaic.root_tile_node.first() {
+            response_mosaic.request_stock_id = root_node
+                .external_id
+                .and_then(|id| self.task.get_request_id(id))
+                .map(|id| id as i32);
+            response_mosaic.used_area =
+                root_node.get_used_area() as f64 / (self.task.factor * self.task.factor) as f64;
+            response_mosaic.used_area_ratio = if root_node.get_area() > 0 {
+                root_node.get_used_area() as f32 / root_node.get_area() as f32
+            } else {
+                0.0
+            };
+            response_mosaic.nbr_final_panels = root_node.get_nbr_final_tiles();
+            response_mosaic.nbr_wasted_panels = root_node.get_nbr_final_tiles(); // TODO: implement getNbrUnusedTiles
+            response_mosaic.wasted_area =
+                mosaic.get_unused_area() as f64 / (self.task.factor * self.task.factor) as f64;
+            response_mosaic.material = mosaic.material.clone();
+
+            response_mosaic.tiles = root_node.iter().map(|node| self.to_tile(node)).collect();
+            if self.calculation_request.configuration.include_layout_tree {
+                response_mosaic.layout_tree = Some(self.to_layout_node(root_node));
+            }
+
+            // Фигурная деталь занимает свой габарит, но всё вокруг фигуры идёт в отход
+            let shape_waste: f64 = response_mosaic
+                .tiles
+                .iter()
+                .filter(|tile| tile.is_final && !tile.shape.is_rect())
+                .map(|tile| tile.width * tile.height - tile.shape.get_area(tile.width, tile.height))
+                .sum();
+            if shape_waste > 0.0 {
+                let sheet_area = root_node.get_area() as f64 / (self.task.factor * self.task.factor) as f64;
+                response_mosaic.used_area -= shape_waste;
+                response_mosaic.wasted_area += shape_waste;
+                response_mosaic.used_area_ratio = (response_mosaic.used_area / sheet_area) as f32;
+            }
+
+            let to_free_tile = |node: &TileNode| {
+                calculation_response::Tile::with_coords(
+                    node.id as i32,
+                    node.x1 as f64 / self.task.factor as f64,
+                    node.y1 as f64 / self.task.factor as f64,
+                    node.get_width() as f64 / self.task.factor as f64,
+                    node.get_height() as f64 / self.task.factor as f64,
+                )
+            };
+            response_mosaic.biggest_unused_tile = root_node.get_biggest_unused_node().map(to_free_tile);
+            response_mosaic.offcuts = root_node
+                .iter_free()
+                .map(to_free_tile)
+                .filter(|tile| tile.width.min(tile.height) >= min_offcut_dimension.max(f64::MIN_POSITIVE))
+                .collect();
+            response_mosaic.waste_heatmap =
+                mosaic.get_waste_heatmap(EngineConstants::WASTE_HEATMAP_SIZE);
+        }
+
+        // Calculate cut length
+        let cut_length: f64 = mosaic.get_cuts().iter().map(|cut| cut.get_length()).sum();
+        response_mosaic.cut_length = cut_length / self.task.factor as f64;
+        response_mosaic.nbr_cuts = mosaic.get_nbr_cuts();
+        response_mosaic.estimated_time_seconds = self
+            .calculation_request
+            .configuration
+            .estimate_processing_seconds(response_mosaic.cut_length, 1);
+
+        // TODO: Add edge band calculation
+        // response_mosaic.edge_bands = EdgeBanding::calc_edge_bands(&final_tile_nodes, panels, self.task.factor);
+
+        // Set panel labels
+        for panel in panels {
+            for tile in &mut response_mosaic.panels {
+                if tile.request_obj_id as u32 == panel.id {
+                    tile.label = Some(panel.label.clone());
+                }
+            }
+        }
+
+        // Set stock panel labels
+        for stock_panel in stock_panels {
+            if let Some(request_stock_id) = response_mosaic.request_stock_id {
+                if request_stock_id as u32 == stock_panel.id {
+                    response_mosaic.stock_label = Some(stock_panel.label.clone());
+                }
+            }
+        }
+
+        // Create final panels map
+        let _final_panels_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+        // TODO: Implement final tile nodes collection and processing
+
+        // Add cuts to response mosaic
+        for cut in mosaic.get_cuts() {
+            let response_cut = calculation_response::Cut {
+                request_obj_id: mosaic
+                    .root_tile_node
+                    .first()
+                    .and_then(|root| root.find_by_id(cut.piece_ids[0] as u32))
+                    .and_then(|child1| child1.get_final_external_id())
+                    .and_then(|id| self.task.get_request_id(id))
+                    .map(|id| id as i32),
+                ..cut.scaled(self.task.factor as f64)
+            };
+            response_mosaic.cuts.push(response_cut);
+        }
+
+        // Отладочные сборки проверяют, что движок не нарушил пропил и границы листа
+        if cfg!(debug_assertions) {
+            let cut_thickness =
+                CutListThread::new_with_config(&self.calculation_request.configuration, 0).cut_thickness;
+            let issues = response_mosaic.validate(cut_thickness as f64 / self.task.factor as f64);
+            assert!(issues.is_empty(), "Engine produced an invalid layout: {:?}", issues);
+        }
+        response_mosaic.set_coordinate_system(self.calculation_request.configuration.coordinate_system);
+        response_mosaic
+    }
+
+    /// Placed parts of `mosaics` grouped by size, label and material in the order they first
+    /// appear, with the numbers of their sheets when `with_sheet_numbers`
+    fn group_final_tiles(&self, mosaics: &[Mosaic], with_sheet_numbers: bool) -> Vec<calculation_response::FinalTile> {
+        let mut final_tiles: Vec<calculation_response::FinalTile> = Vec::new();
+        for (index, mosaic) in mosaics.iter().enumerate() {
+            for tile in mosaic.tiles.iter().filter(|tile| tile.is_final) {
+                let Some(request_obj_id) = tile.request_obj_id else {
+                    continue;
+                };
+                let (width, height) = if tile.is_rotated {
+                    (tile.height, tile.width)
+                } else {
+                    (tile.width, tile.height)
+                };
+                // Подпись строки запроса, объединённые строки делят подпись первой
+                let label = self
+                    .calculation_request
+                    .panels
+                    .iter()
+                    .find(|panel| panel.id as i32 == request_obj_id)
+                    .map(|panel| panel.label.clone())
+                    .or_else(|| tile.label.clone());
+
+                let final_tile = match final_tiles.iter_mut().find(|final_tile| {
+                    final_tile.width == width
+                        && final_tile.height == height
+                        && final_tile.label == label
+                        && final_tile.material == mosaic.material
+                }) {
+                    Some(final_tile) => final_tile,
+                    None => {
+                        final_tiles.push(calculation_response::FinalTile {
+                            request_obj_id,
+                            width,
+                            height,
+                            label,
+                            material: mosaic.material.clone(),
+                            ..calculation_response::FinalTile::new()
+                        });
+                        final_tiles.last_mut().unwrap()
+                    }
+                };
+                final_tile.increment_count();
+                if with_sheet_numbers && final_tile.sheet_numbers.last() != Some(&(index + 1)) {
+                    final_tile.sheet_numbers.push(index + 1);
+                }
+            }
+        }
+        final_tiles
+    }
+
+    /// Number the copies of every panel in sheet order and label them with
+    /// `Configuration::instance_label_template`
+    fn label_instances(&self, mosaics: &mut [Mosaic]) {
+        let configuration = &self.calculation_request.configuration;
+        let mut numbers: HashMap<i32, u32> = HashMap::new();
+        for mosaic in mosaics {
+            for tile in mosaic.tiles.iter_mut().filter(|tile| tile.is_final) {
+                let Some(panel) = tile
+                    .request_obj_id
+                    .and_then(|id| self.calculation_request.panels.iter().find(|panel| panel.id as i32 == id))
+                else {
+                    continue;
+                };
+                let number = numbers.entry(panel.id as i32).or_insert(0);
+                *number += 1;
+                if let Some(label) = configuration.get_instance_label(&panel.label, *number, panel.count) {
+                    tile.label = Some(label);
+                }
+            }
+        }
+    }
+
+    /// Warnings for the sheets of `mosaics` used less than `min_percent`, with their panels
+    fn get_low_utilization_warnings(&self, mosaics: &[Mosaic], min_percent: f64) -> Vec<String> {
+        let locale = self.calculation_request.configuration.locale;
+        mosaics
+            .iter()
+            .enumerate()
+            .filter(|(_, mosaic)| (mosaic.used_area_ratio as f64) * 100.0 < min_percent)
+            .map(|(index, mosaic)| {
+                let mut panel_ids: Vec<i32> = mosaic
+                    .tiles
+                    .iter()
+                    .filter(|tile| tile.is_final)
+                    .filter_map(|tile| tile.request_obj_id)
+                    .collect();
+                panel_ids.sort_unstable();
+                panel_ids.dedup();
+                Warning::LowSheetUtilization {
+                    sheet_number: index + 1,
+                    used_percent: mosaic.used_area_ratio as f64 * 100.0,
+                    min_percent,
+                    panel_ids,
+                }
+                .to_text(locale)
+            })
+            .collect()
+    }
+
     fn add_no_fit_tile(
         &self,
         calculation_response: &mut CalculationResponse,
         tile_dimensions: &TileDimensions,
     ) {
+        let request_id = self
+            .task
+            .get_request_id(tile_dimensions.id)
+            .unwrap_or(tile_dimensions.id);
+
         // Check if tile already exists and increment count
         for no_fit_tile in &mut calculation_response.no_fit_panels {
-            if no_fit_tile.id == tile_dimensions.id {
+            if no_fit_tile.id == request_id {
                 no_fit_tile.count += 1;
                 return;
             }
@@ -218,9 +420,9 @@ impl CalculationResponseBuilder {
 
         // Create new no-fit tile
         let mut no_fit_tile = calculation_response::NoFitTile::new();
-        no_fit_tile.id = tile_dimensions.id;
-        no_fit_tile.width = (tile_dimensions.width / self.task.factor) as f64;
-        no_fit_tile.height = (tile_dimensions.height / self.task.factor) as f64;
+        no_fit_tile.id = request_id;
+        no_fit_tile.width = tile_dimensions.width as f64 / self.task.factor as f64;
+        no_fit_tile.height = tile_dimensions.height as f64 / self.task.factor as f64;
         no_fit_tile.count = 1;
 
         // Set label and material from calculation request panels
@@ -235,38 +437,115 @@ impl CalculationResponseBuilder {
         calculation_response.no_fit_panels.push(no_fit_tile);
     }
 
-    fn add_children_to_list(
-        &self,
-        tile_node: &TileNode,
-        tiles: &mut Vec<calculation_response::Tile>,
-    ) {
+    /// Give the panels of rows computed as one row back to the rows, in row order:
+    /// placed panels fill the first rows, the rest of the rows stay not placed
+    fn expand_aggregated_rows(&self, calculation_response: &mut CalculationResponse, rows: &MergedRows) {
+        let mut quotas: Vec<(u32, i32)> = std::iter::once(rows.id)
+            .chain(rows.merged_ids.iter().copied())
+            .filter_map(|id| self.calculation_request.panels.iter().find(|panel| panel.id == id))
+            .map(|panel| (panel.id, panel.count as i32))
+            .collect();
+        let mut next_row = 0;
+
+        for mosaic in &mut calculation_response.mosaics {
+            let mut tile_rows: HashMap<i32, u32> = HashMap::new();
+            for tile in &mut mosaic.tiles {
+                if !tile.is_final || tile.request_obj_id != Some(rows.id as i32) {
+                    continue;
+                }
+                while next_row + 1 < quotas.len() && quotas[next_row].1 <= 0 {
+                    next_row += 1;
+                }
+                let Some((id, remaining)) = quotas.get_mut(next_row) else {
+                    break;
+                };
+                *remaining -= 1;
+                tile.request_obj_id = Some(*id as i32);
+                tile_rows.insert(tile.id, *id);
+            }
+            for cut in &mut mosaic.cuts {
+                if let Some(id) = tile_rows.get(&cut.piece_ids[0]) {
+                    cut.request_obj_id = Some(*id as i32);
+                }
+            }
+        }
+
+        // Неразмещённые детали объединённой строки делятся между строками, которым их не хватило
+        let Some(index) = calculation_response
+            .no_fit_panels
+            .iter()
+            .position(|no_fit| no_fit.id == rows.id)
+        else {
+            return;
+        };
+        let no_fit = calculation_response.no_fit_panels.remove(index);
+        let mut nbr_left = no_fit.count;
+        let mut position = index;
+        for (id, remaining) in quotas {
+            let count = remaining.min(nbr_left);
+            if count <= 0 {
+                continue;
+            }
+            nbr_left -= count;
+            let mut row_no_fit = no_fit.clone();
+            row_no_fit.id = id;
+            row_no_fit.count = count;
+            calculation_response.no_fit_panels.insert(position, row_no_fit);
+            position += 1;
+        }
+    }
+
+    fn to_layout_node(&self, tile_node: &TileNode) -> LayoutNode {
+        let tile = self.to_tile(tile_node);
+        LayoutNode {
+            id: tile.id,
+            x: tile.x,
+            y: tile.y,
+            width: tile.width,
+            height: tile.height,
+            is_final: tile.is_final,
+            is_rotated: tile.is_rotated,
+            request_obj_id: tile.request_obj_id,
+            cut_stage: tile_node.cut_stage,
+            cut_horizontal: tile_node.cut_horizontal,
+            children: [&tile_node.child1, &tile_node.child2]
+                .into_iter()
+                .flatten()
+                .map(|child| self.to_layout_node(child))
+                .collect(),
+        }
+    }
+
+    fn to_tile(&self, tile_node: &TileNode) -> calculation_response::Tile {
         let mut tile = calculation_response::Tile::new();
 
         tile.id = tile_node.id as i32;
-        // tile.x = tile_node.x1 as f64 / self.task.factor;
-        // tile.y = tile_node.y1 as f64 / self.task.factor;
-        // tile.width = tile_node.get_width() as f64 / self.task.factor;
-        // tile.height = tile_node.get_height() as f64 / self.task.factor;
+        tile.x = tile_node.x1 as f64 / self.task.factor as f64;
+        tile.y = tile_node.y1 as f64 / self.task.factor as f64;
+        tile.width = tile_node.get_width() as f64 / self.task.factor as f64;
+        tile.height = tile_node.get_height() as f64 / self.task.factor as f64;
         tile.is_final = tile_node.is_final;
         tile.is_rotated = tile_node.is_rotated;
+        tile.has_children = tile_node.child1.is_some() || tile_node.child2.is_some();
 
-        if let Some(external_id) = tile_node.external_id {
-            tile.request_obj_id = Some(external_id as i32);
-        }
-
-        if tile_node.child1.is_some() || tile_node.child2.is_some() {
-            tile.has_children = true;
-            tiles.push(tile);
-
-            if let Some(ref child1) = tile_node.child1 {
-                self.add_children_to_list(child1, tiles);
-            }
-            if let Some(ref child2) = tile_node.child2 {
-                self.add_children_to_list(child2, tiles);
+        // Root keeps the stock id, final tiles keep the panel id
+        if let Some(mapping) = tile_node
+            .external_id
+            .and_then(|id| self.task.get_panel_id_mapping(id))
+        {
+            tile.request_obj_id = Some(mapping.request_id as i32);
+            tile.label = Some(mapping.label.clone());
+            if tile.is_final && !mapping.is_stock {
+                if let Some(panel) = self
+                    .calculation_request
+                    .panels
+                    .iter()
+                    .find(|panel| panel.id == mapping.request_id)
+                {
+                    tile.shape = panel.shape;
+                }
             }
-        } else {
-            tile.has_children = false;
-            tiles.push(tile);
         }
+        tile
     }
 }
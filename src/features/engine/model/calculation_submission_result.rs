@@ -1,24 +1,70 @@
 use serde::{Deserialize, Serialize};
 
+use crate::enums::status_code::StatusCode;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalculationSubmissionResult {
-    pub status_code: Option<String>,
+    /// Serialized as the legacy numeric string, see `StatusCode`
+    pub status_code: Option<StatusCode>,
     pub task_id: Option<String>,
+    /// Problems found while validating the request, see `SubmissionWarning`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<SubmissionWarning>,
+}
+
+/// Problem of a submitted request reported before its computation starts
+///
+/// ```rust
+/// use rezalnyas_core::enums::status_code::StatusCode;
+/// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::calculation_submission_result::SubmissionWarning;
+///
+/// let service = CutListOptimizerServiceImpl::new(1, false).unwrap();
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0).material("MDF")
+///     .add_panel(300.0, 300.0).count(2).material("Glass 4mm")
+///     .add_stock(1000.0, 600.0).material("MDF")
+///     .build()
+///     .unwrap();
+///
+/// let result = service.submit_task(request).unwrap();
+/// assert_eq!(result.status_code, Some(StatusCode::InvalidStockTiles));
+/// assert_eq!(result.task_id, None);
+/// assert_eq!(
+///     result.warnings,
+///     vec![SubmissionWarning::MaterialWithoutStock {
+///         material: "Glass 4mm".to_string(),
+///         panel_count: 2,
+///         area: 180000.0,
+///     }]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SubmissionWarning {
+    /// Panels of the material have no stock of it nor of its substitutes, `area` in mm²
+    MaterialWithoutStock {
+        material: String,
+        panel_count: u32,
+        area: f64,
+    },
 }
 
 impl CalculationSubmissionResult {
-    pub fn new(status_code: String, task_id: String) -> Self {
+    pub fn new(status_code: StatusCode, task_id: String) -> Self {
         Self {
             status_code: Some(status_code),
             task_id: Some(task_id),
+            warnings: Vec::new(),
         }
     }
 
-    pub fn with_status_code(status_code: String) -> Self {
+    pub fn with_status_code(status_code: StatusCode) -> Self {
         Self {
             status_code: Some(status_code),
             task_id: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -26,6 +72,7 @@ impl CalculationSubmissionResult {
         Self {
             status_code: None,
             task_id: None,
+            warnings: Vec::new(),
         }
     }
 }
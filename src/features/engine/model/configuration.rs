@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
-use crate::{enums::{cut_orientation_preference::CutOrientationPreference, optimization_level::OptimizationFactor, optimization_priority::OptimizationPriority, orientation::Orientation}, features::engine::model::performance_thresholds::PerformanceThresholds};
+use crate::constants::EngineConstants;
+use crate::{enums::{coordinate_system::CoordinateSystem, cut_orientation_preference::CutOrientationPreference, length_unit::LengthUnit, optimization_level::OptimizationFactor, optimization_priority::OptimizationPriority, orientation::Orientation, search_strategy::SearchStrategy, stock_pricing::StockPricing, stock_selection::StockSelection}, features::engine::{model::{calculation_request::Panel, cost_summary::{CostSummary, StockPrice}, performance_thresholds::PerformanceThresholds}, stock_selection::StockSelectionStrategy}, i18n::locale::Locale, scaled_math::{Money, RoundingMode, ScaledNumber}};
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +21,136 @@ pub struct Configuration {
     
     pub units: Option<i32>,
     pub use_single_stock_unit: bool,
+
+    /// Preferred material -> substitutes in order of preference.
+    /// Panels that don't fit on the preferred stock spill to the substitutes.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub material_substitutes: HashMap<String, Vec<String>>,
+
+    /// Feed speed of the saw, used to estimate machine time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saw_speed_mm_per_min: Option<f64>,
+
+    /// Time to load, position and unload one sheet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sheet_handling_seconds: Option<f64>,
+
+    /// Guillotine stages the saw can do (2, 3, ...), None - unlimited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cut_stages: Option<u32>,
+
+    /// Parts one sheet may carry, for sorting and edgebanding lines that can't take more, None - unlimited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parts_per_sheet: Option<u32>,
+
+    /// Solutions kept per group of similar layouts in the thread pools, None or 0 - no limit.
+    /// A low value, like 1, keeps repeated panels from filling the pools with near-identical layouts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_similar_solutions: Option<u32>,
+
+    /// Share of common placements from which two layouts count as similar, 0.8 by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity_threshold: Option<f64>,
+
+    /// Waste percent of the used sheets that is good enough: the first layout placing all
+    /// panels at or below it ends the search of its material, None - full search
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acceptable_waste_percent: Option<f64>,
+
+    /// Used area percent every opened sheet should reach, sheets below it get a warning
+    /// suggesting to move their panels to a future order, None - no check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_sheet_utilization_percent: Option<f64>,
+
+    /// Fail the request when panels have no stock of their thickness instead of warning
+    #[serde(default)]
+    pub reject_thickness_mismatch: bool,
+
+    /// Unit of the request dimensions, they are converted to millimeters before the
+    /// computation, None - already in millimeters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_unit: Option<LengthUnit>,
+
+    /// Merge panel and stock rows that only differ by id into one row of the response,
+    /// panel rows are computed as one row either way
+    #[serde(default)]
+    pub merge_duplicate_panels: bool,
+
+    /// Always use the 2D search, even for materials whose panels and stock share one dimension
+    #[serde(default)]
+    pub disable_one_dimensional: bool,
+
+    /// Full search, or a quick first-fit-decreasing preview before it
+    #[serde(default)]
+    pub search_strategy: SearchStrategy,
+
+    /// Time the task keeps refining its solution after the first full one, in the
+    /// `Improving` status, None - the search stops at 100%
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub improvement_budget_ms: Option<u64>,
+
+    /// Charge of one cut, with `cost_per_sheet` it makes the optimizer prefer the cheapest layout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_per_cut: Option<f64>,
+
+    /// Stock price: of one used sheet, or of one square meter of the used sheets, see `stock_pricing`.
+    /// Stock rows with their own `Panel::price` are charged that instead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_per_sheet: Option<f64>,
+
+    /// How `cost_per_sheet` is charged, and the price of stock rows without their own `Panel::pricing`
+    #[serde(default)]
+    pub stock_pricing: StockPricing,
+
+    /// Currency of the charges, `CostSummary::format_amount` formats the amounts in it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+
+    /// Built-in rule picking the stock sheets each layout is tried on
+    #[serde(default)]
+    pub stock_selection: StockSelection,
+
+    /// Custom stock selection used instead of `stock_selection`, set from code only
+    #[serde(skip)]
+    pub stock_selection_strategy: Option<Arc<dyn StockSelectionStrategy>>,
+
+    /// Origin and Y axis of the tile and cut coordinates of the response
+    #[serde(default)]
+    pub coordinate_system: CoordinateSystem,
+
+    /// Shortest side of a leftover rectangle listed in `Mosaic::offcuts`, None - every leftover
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_offcut_dimension: Option<String>,
+
+    /// Language of the warnings and status descriptions of the response
+    #[serde(default, skip_serializing_if = "Locale::is_default")]
+    pub locale: Locale,
+
+    /// Difference between a panel side and a free space side that still counts as an exact
+    /// fit, for the measurement noise of re-imported offcuts, None - sides must match exactly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimension_tolerance: Option<String>,
+
+    /// Clamping zone of a beam saw kept free of panels at the start of every first-stage cut:
+    /// along the top edge of sheets first cut across the width, along the left edge otherwise.
+    /// None - panels may touch every edge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lead_in_allowance: Option<String>,
+
+    /// Add the guillotine tree of every sheet to the response, see `Mosaic::layout_tree`
+    #[serde(default)]
+    pub include_layout_tree: bool,
+
+    /// Add a SHA-256 of the response, signed with the key of the service when it has one,
+    /// see `CalculationResponse::integrity`
+    #[serde(default)]
+    pub include_content_hash: bool,
+
+    /// Label of every copy of a panel with a count above 1, e.g. "{label} {n}/{count}": `{label}` -
+    /// label of the panel, `{n}` - number of the copy in sheet order, `{count}` - copies of the
+    /// panel. None - the copies share the label of the panel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_label_template: Option<String>,
 }
 
 impl Default for Configuration {
@@ -31,6 +165,145 @@ impl Default for Configuration {
             performance_thresholds: None,
             units: None,
             use_single_stock_unit: false,
+            material_substitutes: HashMap::new(),
+            saw_speed_mm_per_min: None,
+            sheet_handling_seconds: None,
+            max_cut_stages: None,
+            max_parts_per_sheet: None,
+            max_similar_solutions: None,
+            similarity_threshold: None,
+            acceptable_waste_percent: None,
+            min_sheet_utilization_percent: None,
+            reject_thickness_mismatch: false,
+            input_unit: None,
+            merge_duplicate_panels: false,
+            disable_one_dimensional: false,
+            search_strategy: SearchStrategy::default(),
+            improvement_budget_ms: None,
+            cost_per_cut: None,
+            cost_per_sheet: None,
+            stock_pricing: StockPricing::default(),
+            currency: None,
+            stock_selection: StockSelection::default(),
+            stock_selection_strategy: None,
+            coordinate_system: CoordinateSystem::default(),
+            min_offcut_dimension: None,
+            locale: Locale::default(),
+            dimension_tolerance: None,
+            lead_in_allowance: None,
+            include_layout_tree: false,
+            include_content_hash: false,
+            instance_label_template: None,
+        }
+    }
+}
+
+impl Configuration {
+    /// Estimated machine time in seconds for `cut_length_mm` of cuts over `nbr_sheets` sheets.
+    /// None when no saw speed is configured.
+    pub fn estimate_processing_seconds(&self, cut_length_mm: f64, nbr_sheets: usize) -> Option<f64> {
+        let saw_speed = self.saw_speed_mm_per_min.filter(|speed| *speed > 0.0)?;
+        let handling = self.sheet_handling_seconds.unwrap_or(0.0) * nbr_sheets as f64;
+        Some(cut_length_mm / saw_speed * 60.0 + handling)
+    }
+
+    /// Custom stock selection when one is set, the built-in `stock_selection` otherwise
+    pub fn get_stock_selection_strategy(&self) -> &dyn StockSelectionStrategy {
+        self.stock_selection_strategy.as_deref().unwrap_or(&self.stock_selection)
+    }
+
+    /// True when a per-cut or per-sheet charge is set
+    pub fn has_costs(&self) -> bool {
+        self.cost_per_cut.is_some() || self.cost_per_sheet.is_some()
+    }
+
+    /// `amount` in `currency`, no currency when it isn't set. Two more places than cents are
+    /// kept, so the price of a cut or a square meter is rounded only in the totals
+    fn to_money(&self, amount: f64) -> Option<Money> {
+        let precision = Money::DEFAULT_PRECISION + 2;
+        let amount = ScaledNumber::from_f64(amount, precision).ok()?;
+        Money::with_precision(amount, self.currency.as_deref().unwrap_or(""), precision, RoundingMode::HalfEven).ok()
+    }
+
+    /// Charge of one cut, zero when `cost_per_cut` isn't set
+    pub fn get_cut_price(&self) -> Option<Money> {
+        self.to_money(self.cost_per_cut.unwrap_or(0.0))
+    }
+
+    /// Price of a sheet of the `stock` row: `Panel::price` charged by `Panel::pricing`, or else
+    /// `cost_per_sheet` and `stock_pricing`. None when neither price is set
+    pub fn get_stock_price(&self, stock: Option<&Panel>) -> Option<StockPrice> {
+        let price = stock.and_then(|stock| stock.price).or(self.cost_per_sheet)?;
+        Some(StockPrice {
+            price: self.to_money(price)?,
+            pricing: stock.and_then(|stock| stock.pricing).unwrap_or(self.stock_pricing),
+        })
+    }
+
+    /// Price of `nbr_cuts` cuts over the used `sheets`, each a stock row and its area in mm².
+    /// None when nothing is charged
+    ///
+    /// ```rust
+    /// use rezalnyas_core::enums::stock_pricing::StockPricing;
+    /// use rezalnyas_core::features::engine::model::calculation_request::Panel;
+    /// use rezalnyas_core::features::engine::model::configuration::Configuration;
+    /// use rezalnyas_core::features::engine::model::cost_summary::CostSummary;
+    ///
+    /// let mut configuration = Configuration {
+    ///     cost_per_cut: Some(0.5),
+    ///     cost_per_sheet: Some(40.0),
+    ///     ..Configuration::default()
+    /// };
+    /// let sheets = [(None, 5_000_000.0), (None, 5_000_000.0)];
+    /// let cost = configuration.estimate_cost(12, &sheets).unwrap();
+    /// assert_eq!(CostSummary::format_amount(&cost.cuts_cost), "6.00");
+    /// assert_eq!(CostSummary::format_amount(&cost.total_cost), "86.00");
+    /// assert!(Configuration::default().estimate_cost(12, &sheets).is_none());
+    ///
+    /// // Обрезок 2500x2000 - 5 м² по 5.5 за м², целый лист по цене конфигурации
+    /// let mut offcut = Panel::new(2, "2500", "2000", 1, "offcut");
+    /// offcut.price = Some(5.5);
+    /// offcut.pricing = Some(StockPricing::PerSquareMeter);
+    /// configuration.currency = Some("EUR".to_string());
+    /// let cost = configuration.estimate_cost(12, &[(Some(&offcut), 5_000_000.0), (None, 5_000_000.0)]).unwrap();
+    /// assert_eq!(cost.sheets_cost.to_string(), "67.50 EUR");
+    /// ```
+    pub fn estimate_cost(&self, nbr_cuts: i64, sheets: &[(Option<&Panel>, f64)]) -> Option<CostSummary> {
+        let sheets = sheets
+            .iter()
+            .map(|(stock, area)| {
+                let square_meters = ScaledNumber::from_f64(area / EngineConstants::MM2_PER_M2, 6).ok()?;
+                Some((self.get_stock_price(*stock), square_meters))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let has_costs = self.cost_per_cut.is_some() || sheets.iter().any(|(stock_price, _)| stock_price.is_some());
+        if !has_costs {
+            return None;
         }
+        CostSummary::new(nbr_cuts, &self.get_cut_price()?, &sheets).ok()
+    }
+
+    /// Label of copy `number` of `count` copies of a panel from `instance_label_template`,
+    /// None without a template or for a single panel
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::configuration::Configuration;
+    ///
+    /// let configuration = Configuration {
+    ///     instance_label_template: Some("{label} {n}/{count}".to_string()),
+    ///     ..Configuration::default()
+    /// };
+    /// assert_eq!(configuration.get_instance_label("Side A", 2, 4).as_deref(), Some("Side A 2/4"));
+    /// assert_eq!(configuration.get_instance_label("", 1, 4).as_deref(), Some("1/4"));
+    /// assert!(configuration.get_instance_label("Side A", 1, 1).is_none());
+    /// ```
+    pub fn get_instance_label(&self, label: &str, number: u32, count: u32) -> Option<String> {
+        let template = self.instance_label_template.as_deref().filter(|_| count > 1)?;
+        let instance_label = template
+            .replace("{label}", label)
+            .replace("{n}", &number.to_string())
+            .replace("{count}", &count.to_string());
+        // Без подписи детали остаётся только номер
+        Some(instance_label.trim().to_string())
     }
 }
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::stock_pricing::StockPricing;
+use crate::scaled_math::{Money, RoundingMode, ScaledError, ScaledNumber};
+
+/// Price of one stock sheet and how it is charged, see `Configuration::get_stock_price`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StockPrice {
+    pub price: Money,
+    pub pricing: StockPricing,
+}
+
+/// Price of a layout from the per-cut charge and the price of every used sheet
+///
+/// ```rust
+/// use rezalnyas_core::enums::stock_pricing::StockPricing;
+/// use rezalnyas_core::features::engine::model::cost_summary::{CostSummary, StockPrice};
+/// use rezalnyas_core::scaled_math::{Money, ScaledNumber};
+///
+/// let per_meter = StockPrice { price: Money::from_str("5.5", "EUR").unwrap(), pricing: StockPricing::PerSquareMeter };
+/// let per_sheet = StockPrice { price: Money::from_str("40", "EUR").unwrap(), pricing: StockPricing::PerSheet };
+/// let area = ScaledNumber::from_str("5", 0).unwrap();
+/// let cost_per_cut = Money::from_str("0.5", "EUR").unwrap();
+///
+/// // Лист без цены учитывается в площади, но ничего не стоит
+/// let sheets = [(Some(per_meter), area), (Some(per_sheet), area), (None, area)];
+/// let cost = CostSummary::new(12, &cost_per_cut, &sheets).unwrap();
+/// assert_eq!((cost.nbr_sheets, cost.sheets_area), (3, 15.0));
+/// assert_eq!(cost.sheets_cost.to_string(), "67.50 EUR");
+/// assert_eq!(cost.total_cost.to_string(), "73.50 EUR");
+/// assert_eq!(CostSummary::format_amount(&Money::from_str("30.875", "").unwrap()), "30.88");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostSummary {
+    pub nbr_cuts: i64,
+    pub nbr_sheets: usize,
+    /// Area of the used sheets in square meters
+    #[serde(default)]
+    pub sheets_area: f64,
+    /// In the currency of `Configuration::currency`, no currency when it isn't set
+    pub cuts_cost: Money,
+    pub sheets_cost: Money,
+    pub total_cost: Money,
+}
+
+impl CostSummary {
+    /// Summary of `nbr_cuts` cuts at `cost_per_cut` and of the used sheets with their price and
+    /// area in square meters. The cuts and every sheet are rounded to cents before they are added up
+    pub fn new(
+        nbr_cuts: i64,
+        cost_per_cut: &Money,
+        sheets: &[(Option<StockPrice>, ScaledNumber)],
+    ) -> Result<Self, ScaledError> {
+        let currency = cost_per_cut.currency();
+        let cuts_cost = cost_per_cut.checked_mul(ScaledNumber::from_i64(nbr_cuts, 0)?, RoundingMode::HalfEven)?;
+        let cuts_cost = Money::new(cuts_cost.amount(), currency)?;
+
+        let mut sheets_cost = Money::zero(currency);
+        let mut sheets_area = ScaledNumber::from_raw(0, 0)?;
+        for (stock_price, square_meters) in sheets {
+            sheets_area = sheets_area.checked_add(*square_meters)?;
+            let Some(stock_price) = stock_price else {
+                continue;
+            };
+            let sheet_cost = stock_price.pricing.get_sheet_cost(&stock_price.price, *square_meters)?;
+            sheets_cost = sheets_cost.checked_add(&Money::new(sheet_cost.amount(), currency)?)?;
+        }
+
+        Ok(Self {
+            nbr_cuts,
+            nbr_sheets: sheets.len(),
+            sheets_area: sheets_area.to_f64(),
+            total_cost: cuts_cost.checked_add(&sheets_cost)?,
+            cuts_cost,
+            sheets_cost,
+        })
+    }
+
+    /// `amount` with two decimal places, followed by its currency when there is one
+    pub fn format_amount(amount: &Money) -> String {
+        if amount.currency().is_empty() {
+            amount.amount().to_string()
+        } else {
+            amount.to_string()
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::engine::model::response_diff::ResponseDiff;
+
+/// Java engine response compared with the Rust one for the same request,
+/// deltas are Rust minus Java
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EngineComparison {
+    pub java_used_area_ratio: f64,
+    pub rust_used_area_ratio: f64,
+    pub used_area_ratio_delta: f64,
+    pub java_nbr_sheets: usize,
+    pub rust_nbr_sheets: usize,
+    pub java_nbr_cuts: i64,
+    pub rust_nbr_cuts: i64,
+    /// Placements, sheet counts and totals, the Java response is the "before" side
+    pub diff: ResponseDiff,
+}
+
+impl EngineComparison {
+    /// Both engines placed every panel at the same position
+    pub fn is_identical(&self) -> bool {
+        self.diff.is_empty()
+    }
+
+    /// The Rust layout uses at least as much of the sheets as the Java one
+    pub fn is_rust_not_worse(&self) -> bool {
+        self.used_area_ratio_delta >= 0.0 && self.diff.no_fit_panels_delta <= 0
+    }
+}
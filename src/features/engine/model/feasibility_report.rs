@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// Problem that keeps panels of a request from being placed, found without optimizing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeasibilityIssueKind {
+    /// Dimension that is not a positive number
+    InvalidNumber,
+    /// Panel larger than every sheet it may be cut from
+    OversizedPanel,
+    /// Material without stock of its own or of a substitute
+    MaterialWithoutStock,
+    /// The material has stock, but none of the panel thickness
+    ThicknessMismatch,
+    /// Decimal places scale the dimensions beyond the integer range of the engine
+    PrecisionOverflow,
+    /// Panels of a material have more area than all of its stock
+    InsufficientStock,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeasibilityIssue {
+    pub kind: FeasibilityIssueKind,
+    pub panel_ids: Vec<u32>,
+    pub stock_ids: Vec<u32>,
+    /// Material key, see `Panel::get_material_key`
+    pub material: Option<String>,
+    pub message: String,
+}
+
+impl FeasibilityIssue {
+    pub fn new(kind: FeasibilityIssueKind, panel_ids: Vec<u32>, message: String) -> Self {
+        Self {
+            kind,
+            panel_ids,
+            stock_ids: Vec::new(),
+            material: None,
+            message,
+        }
+    }
+
+    pub fn with_material(mut self, material: &str) -> Self {
+        self.material = Some(material.to_string());
+        self
+    }
+}
+
+/// Area estimate of one material key, areas in request units (millimeters with an input unit)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaterialEstimate {
+    pub material: String,
+    pub panel_area: f64,
+    pub stock_area: f64,
+    /// Largest sheets whose area holds the panels, no layout uses fewer
+    pub estimated_sheets: usize,
+    pub available_sheets: usize,
+}
+
+/// Result of `check_feasibility`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeasibilityReport {
+    /// No issues: every panel has stock it fits on and the request can be computed
+    pub is_feasible: bool,
+    pub issues: Vec<FeasibilityIssue>,
+    /// Per material key with panels and stock, sorted by key; oversized panels are left out
+    pub materials: Vec<MaterialEstimate>,
+}
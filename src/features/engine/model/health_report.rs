@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Liveness and readiness of the optimizer service, what health probes of a deployment check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// State of the service is usable, none of its locks is poisoned
+    pub is_alive: bool,
+    /// The service can take one more task: it is alive, runs fewer tasks than its thread
+    /// count and none of them is stalled
+    pub is_ready: bool,
+    pub nbr_running_tasks: i64,
+    pub nbr_finished_tasks: i64,
+    pub nbr_error_tasks: i64,
+    /// Time the oldest running task has been computing, a value that keeps growing means a stalled task
+    pub oldest_task_ms: Option<u64>,
+    /// Running tasks without progress for `EngineConstants::TASK_STALL_TIMEOUT_MS`
+    #[serde(default)]
+    pub nbr_stalled_tasks: usize,
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::engine::model::{
+    calculation_request::{CalculationRequest, Panel},
+    configuration::Configuration,
+    performance_thresholds::PerformanceThresholds,
+};
+use crate::features::input::models::material_catalog::MaterialCatalog;
+
+/// Named stock inventory and configuration of a recurring job, e.g. "standard wardrobe, 18mm MFC".
+/// Only the panel list changes from one job to the next
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::job_template::JobTemplate;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0)
+///     .add_stock(2800.0, 2070.0).count(10).material("MFC 18")
+///     .build()
+///     .unwrap();
+/// let template = JobTemplate::from_request("wardrobe", &request);
+/// assert_eq!(template.stock_panels.len(), 1);
+///
+/// let request = CalculationRequestBuilder::from_template(&template)
+///     .add_panel(720.0, 560.0).count(2).material("MFC 18")
+///     .build()
+///     .unwrap();
+/// assert_eq!(request.panels[0].width, "720");
+/// assert_eq!(request.stock_panels[0].count, 10);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobTemplate {
+    pub name: String,
+    pub stock_panels: Vec<Panel>,
+    pub configuration: Configuration,
+    #[serde(default)]
+    pub performance_thresholds: PerformanceThresholds,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material_catalog: Option<MaterialCatalog>,
+}
+
+impl JobTemplate {
+    /// Template of everything in `request` but its panels
+    pub fn from_request(name: &str, request: &CalculationRequest) -> Self {
+        Self {
+            name: name.to_string(),
+            stock_panels: request.stock_panels.clone(),
+            configuration: request.configuration.clone(),
+            performance_thresholds: request.performance_thresholds.clone(),
+            material_catalog: request.material_catalog.clone(),
+        }
+    }
+
+    /// Request of the template with `panels` to cut
+    pub fn instantiate(&self, panels: Vec<Panel>) -> CalculationRequest {
+        CalculationRequest {
+            configuration: self.configuration.clone(),
+            panels,
+            stock_panels: self.stock_panels.clone(),
+            performance_thresholds: self.performance_thresholds.clone(),
+            material_catalog: self.material_catalog.clone(),
+            ..CalculationRequest::default()
+        }
+    }
+}
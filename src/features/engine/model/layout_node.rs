@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::coordinate_system::CoordinateSystem;
+
+/// Node of the guillotine tree of a response sheet, in the units and coordinate system of
+/// the response. The root is the sheet, a cut splits a node into its children.
+/// Set in `Mosaic::layout_tree` with `Configuration::include_layout_tree`
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::configuration::Configuration;
+///
+/// let service = CutListOptimizerServiceImpl::new(1, false).unwrap();
+/// let configuration = Configuration {
+///     include_layout_tree: true,
+///     ..Configuration::default()
+/// };
+/// let request = CalculationRequestBuilder::new()
+///     .configuration(configuration)
+///     .add_panel(400.0, 600.0)
+///     .add_stock(1000.0, 600.0)
+///     .build()
+///     .unwrap();
+/// let task_id = service.submit_task(request).unwrap().task_id.unwrap();
+/// let response = service.get_task_status(&task_id).unwrap().solution.unwrap();
+///
+/// let tree = response.mosaics[0].layout_tree.as_ref().unwrap();
+/// assert_eq!((tree.width, tree.height), (1000.0, 600.0));
+/// assert_eq!(tree.children.len(), 2);
+/// let panel = tree.iter().find(|node| node.is_final).unwrap();
+/// assert_eq!(panel.request_obj_id, Some(1));
+/// assert_eq!(panel.width * panel.height, 240000.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutNode {
+    /// Id of the node, the same as `Tile::id`
+    pub id: i32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// True for a placed panel
+    pub is_final: bool,
+    pub is_rotated: bool,
+    /// Panel id of a placed panel, stock id of the sheet
+    pub request_obj_id: Option<i32>,
+    /// Guillotine stage of the cut that produced this node, 0 for the sheet itself
+    pub cut_stage: u32,
+    /// Orientation of the cut that produced this node, None for the sheet itself
+    pub cut_horizontal: Option<bool>,
+    /// Nodes the cut of this node produced, empty for a leaf
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<LayoutNode>,
+}
+
+impl LayoutNode {
+    /// This node and all nodes below it, depth first
+    pub fn iter(&self) -> impl Iterator<Item = &LayoutNode> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+
+    /// Move the node and its children from the `source` to the `target` coordinate system
+    pub fn convert_y(&mut self, source: CoordinateSystem, target: CoordinateSystem, sheet_height: f64) {
+        self.y = source.convert_y(target, self.y, self.height, sheet_height);
+        for child in &mut self.children {
+            child.convert_y(source, target, sheet_height);
+        }
+    }
+}
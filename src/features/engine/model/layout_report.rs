@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::engine::model::calculation_response::{CalculationResponse, NoFitTile};
+
+/// Problem found in a manually drawn layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutIssueKind {
+    /// Panel id of a placement is not in the request
+    UnknownPanel,
+    /// Stock id or sheet index of a placement is not in the request
+    UnknownSheet,
+    /// More copies of a panel placed than requested
+    TooManyCopies,
+    /// Panel sticks out of the sheet
+    OffSheet,
+    /// Two panels cover the same area
+    Overlap,
+    /// Two panels are closer than the cut thickness
+    KerfViolation,
+    /// Panels of the sheet can't be separated with guillotine cuts
+    NotGuillotine,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutIssue {
+    pub kind: LayoutIssueKind,
+    pub stock_id: Option<u32>,
+    pub sheet_index: Option<u32>,
+    pub panel_ids: Vec<u32>,
+    pub message: String,
+}
+
+impl LayoutIssue {
+    pub fn new(kind: LayoutIssueKind, panel_ids: Vec<u32>, message: String) -> Self {
+        Self {
+            kind,
+            stock_id: None,
+            sheet_index: None,
+            panel_ids,
+            message,
+        }
+    }
+
+    pub fn on_sheet(mut self, stock_id: u32, sheet_index: u32) -> Self {
+        self.stock_id = Some(stock_id);
+        self.sheet_index = Some(sheet_index);
+        self
+    }
+}
+
+/// Usage of one sheet of a manual layout, areas in request units
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SheetReport {
+    pub stock_id: u32,
+    pub sheet_index: u32,
+    pub nbr_panels: usize,
+    pub used_area: f64,
+    pub wasted_area: f64,
+    pub used_area_ratio: f64,
+    pub is_guillotine: bool,
+}
+
+/// Result of `evaluate_layout`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutReport {
+    pub is_valid: bool,
+    pub issues: Vec<LayoutIssue>,
+    pub sheets: Vec<SheetReport>,
+    /// Requested copies that the layout doesn't place
+    pub unplaced_panels: Vec<NoFitTile>,
+    /// Statistics of the sheets that could be cut, built like an optimizer response
+    pub response: CalculationResponse,
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// Quantity taken from a stock material for the panels of a requested material
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaterialDraw {
+    pub requested_material: String,
+    /// Equals `requested_material` unless the panels spilled to a substitute
+    pub stock_material: String,
+    pub nbr_panels: i32,
+    pub nbr_stock_panels: i32,
+}
+
+impl MaterialDraw {
+    pub fn new(
+        requested_material: &str,
+        stock_material: &str,
+        nbr_panels: i32,
+        nbr_stock_panels: i32,
+    ) -> Self {
+        Self {
+            requested_material: requested_material.to_string(),
+            stock_material: stock_material.to_string(),
+            nbr_panels,
+            nbr_stock_panels,
+        }
+    }
+
+    pub fn is_substitute(&self) -> bool {
+        self.requested_material != self.stock_material
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Permutations processed for one material key of a task
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MaterialProgress {
+    pub material: String,
+    /// Sub-task id of the material, see `Task::register_sub_task`
+    #[serde(default)]
+    pub task_id: String,
+    pub nbr_permutations: usize,
+    pub nbr_permutations_done: usize,
+    /// Milliseconds since UNIX epoch
+    pub start_time: u64,
+    pub elapsed_ms: u64,
+    /// The sub-task was stopped before all permutations were processed
+    #[serde(default)]
+    pub is_stopped: bool,
+}
+
+impl MaterialProgress {
+    pub fn new(material: &str, nbr_permutations: usize) -> Self {
+        Self {
+            material: material.to_string(),
+            nbr_permutations,
+            start_time: Self::now(),
+            ..Self::default()
+        }
+    }
+
+    pub fn advance(&mut self) {
+        self.nbr_permutations_done = (self.nbr_permutations_done + 1).min(self.nbr_permutations);
+        self.elapsed_ms = Self::now().saturating_sub(self.start_time);
+    }
+
+    /// Remaining permutations are skipped, e.g. after an early exit
+    pub fn complete(&mut self) {
+        self.nbr_permutations_done = self.nbr_permutations;
+        self.elapsed_ms = Self::now().saturating_sub(self.start_time);
+    }
+
+    /// Remaining permutations are dropped at the request of the client
+    pub fn stop(&mut self) {
+        self.is_stopped = true;
+        self.elapsed_ms = Self::now().saturating_sub(self.start_time);
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.nbr_permutations_done >= self.nbr_permutations
+    }
+
+    pub fn get_percentage_done(&self) -> i32 {
+        if self.nbr_permutations == 0 {
+            return 100;
+        }
+        (self.nbr_permutations_done * 100 / self.nbr_permutations) as i32
+    }
+
+    /// Rough time left, the average time of the processed permutations times the remaining ones.
+    /// None until the first permutation is done.
+    pub fn get_eta_ms(&self) -> Option<u64> {
+        if self.is_done() || self.is_stopped {
+            return Some(0);
+        }
+        if self.nbr_permutations_done == 0 {
+            return None;
+        }
+        let remaining = (self.nbr_permutations - self.nbr_permutations_done) as u64;
+        Some(self.elapsed_ms / self.nbr_permutations_done as u64 * remaining)
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as u64
+    }
+}
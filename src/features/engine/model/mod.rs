@@ -17,3 +17,26 @@ pub mod stock_panel_picker;
 pub mod tile_node;
 pub mod solution;
 pub mod calculation_response_builder;
+pub mod task_event;
+pub mod panel_id_mapping;
+pub mod material_draw;
+pub mod calculation_request_builder;
+pub mod permutation_log;
+pub mod stock_fit_mask;
+pub mod response_diff;
+pub mod pinned_sheet;
+pub mod layout_report;
+pub mod feasibility_report;
+pub mod material_progress;
+pub mod engine_comparison;
+pub mod sequence_block;
+pub mod cost_summary;
+pub mod health_report;
+pub mod task_limits;
+pub mod response_summary;
+pub mod job_template;
+pub mod stock_solution_summary;
+pub mod task_checkpoint;
+pub mod layout_node;
+pub mod reproducibility_report;
+pub mod response_integrity;
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Link between the internal tile id used by the engine and the panel row of the request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PanelIdMapping {
+    pub internal_id: u32,
+    pub request_id: u32,
+    pub label: String,
+    pub is_stock: bool,
+}
+
+impl PanelIdMapping {
+    pub fn new(internal_id: u32, request_id: u32, label: &str, is_stock: bool) -> Self {
+        Self {
+            internal_id,
+            request_id,
+            label: label.to_string(),
+            is_stock,
+        }
+    }
+}
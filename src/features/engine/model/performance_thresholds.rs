@@ -1,11 +1,124 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::features::engine::model::task_limits::TaskLimits;
+
+/// Limits and shortcuts of the search of a request
+///
+/// ```rust
+/// use std::time::{Duration, Instant};
+///
+/// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+///
+/// // Полные листы одинаковых деталей режутся сеткой, перебор остаётся только для остатка
+/// let mut request = CalculationRequestBuilder::new()
+///     .add_panel(100.0, 100.0).count(2000)
+///     .add_stock(2800.0, 2070.0).count(5)
+///     .build()
+///     .unwrap();
+/// request.configuration.max_similar_solutions = Some(1);
+/// let service = CutListOptimizerServiceImpl::new(1, false).unwrap();
+/// let started = Instant::now();
+/// let task_id = service.submit_task(request).unwrap().task_id.unwrap();
+/// let response = service.get_task_status(&task_id).unwrap().solution.unwrap();
+/// assert!(started.elapsed() < Duration::from_secs(60));
+/// assert_eq!(response.mosaics.len(), 4);
+/// assert!(response.no_fit_panels.is_empty());
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceThresholds {
     pub max_simultaneous_tasks: i32,
     pub max_simultaneous_threads: i32,
     pub thread_check_interval: i64,
+    /// Stock solutions of a permutation evaluated concurrently, capped by max_simultaneous_threads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stock_solution_window: Option<u32>,
+    /// Thread results kept between tasks of the service, None or 0 - no caching
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_cache_size: Option<u32>,
+    /// Copies of one panel above which a sheet of it is cut as a grid and replicated instead
+    /// of searching all copies, None - `EngineConstants::REPEAT_CHUNK_THRESHOLD`, 0 - no chunking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat_chunk_threshold: Option<u32>,
+    /// Milliseconds the response of a completed task is reused for identical requests,
+    /// None or 0 - every request is computed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_cache_ttl_ms: Option<u64>,
+    /// Threads and time of the task, capped by the limits of the service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_limits: Option<TaskLimits>,
+    /// Tile groups with the most area × count whose order is permuted,
+    /// None - `EngineConstants::MAX_PERMUTATION_GROUPS`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_permutation_groups: Option<u32>,
+    /// With more distinct sizes than `max_permutation_groups`, permute clusters of sizes of
+    /// similar area and aspect ratio instead of the largest groups only
+    #[serde(default)]
+    pub cluster_permutation_groups: bool,
+    /// Try the permutations in the order of `PermutationScheduler` instead of the generated one,
+    /// so a tight time limit reaches the promising ones first
+    #[serde(default)]
+    pub progressive_permutations: bool,
+    /// Permutations tried per material, None - `EngineConstants::MAX_PERMUTATIONS`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_permutations_total: Option<u32>,
+    /// Permutations tried per material once a solution placed every panel, None - no cap
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_permutations_after_all_fit: Option<u32>,
+    /// Milliseconds between checkpoints of the running task to the task repository of the
+    /// service, None or 0 - no checkpoints
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_interval_ms: Option<u64>,
+    /// Milliseconds without a processed stock solution after which the task is reported as
+    /// stalled, None - `EngineConstants::TASK_STALL_TIMEOUT_MS`, 0 - no stall detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stall_timeout_ms: Option<u64>,
+    /// End a stalled task with `TaskError::Stalled` instead of only logging it
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    /// use rezalnyas_core::logging::LogLevel;
+    /// use rezalnyas_core::utils::clock::Clock;
+    ///
+    /// // Часы уходят на секунду вперёд при каждом чтении, как будто каждый шаг завис
+    /// #[derive(Debug)]
+    /// struct SlowClock(AtomicU64);
+    ///
+    /// impl Clock for SlowClock {
+    ///     fn now_millis(&self) -> u64 {
+    ///         self.0.fetch_add(1_000, Ordering::SeqCst)
+    ///     }
+    /// }
+    ///
+    /// let mut request = CalculationRequestBuilder::new()
+    ///     .add_panel(400.0, 300.0).count(4)
+    ///     .add_stock(1000.0, 800.0).count(2)
+    ///     .build()
+    ///     .unwrap();
+    /// request.performance_thresholds.stall_timeout_ms = Some(500);
+    ///
+    /// // Без terminate_stalled зависание только пишется в лог задачи
+    /// let service = CutListOptimizerServiceImpl::new(1, false).unwrap()
+    ///     .with_clock(Arc::new(SlowClock(AtomicU64::new(0))));
+    /// let task_id = service.submit_task(request.clone()).unwrap().task_id.unwrap();
+    /// let logs = service.get_task_logs(&task_id, LogLevel::Warn);
+    /// assert!(logs.iter().any(|record| record.message.starts_with("No progress for")));
+    /// assert!(logs.iter().any(|record| record.message.starts_with("Threads:")));
+    ///
+    /// request.performance_thresholds.terminate_stalled = true;
+    /// let service = CutListOptimizerServiceImpl::new(1, false).unwrap()
+    ///     .with_clock(Arc::new(SlowClock(AtomicU64::new(0))));
+    /// let error = service.submit_task(request).unwrap_err();
+    /// assert_eq!(error.code(), "task");
+    /// assert!(error.to_string().contains("made no progress"));
+    /// ```
+    #[serde(default)]
+    pub terminate_stalled: bool,
 }
 
 impl Default for PerformanceThresholds {
@@ -14,6 +127,19 @@ impl Default for PerformanceThresholds {
             max_simultaneous_tasks: 1,
             max_simultaneous_threads: 0,
             thread_check_interval: 0,
+            stock_solution_window: None,
+            result_cache_size: None,
+            repeat_chunk_threshold: None,
+            response_cache_ttl_ms: None,
+            task_limits: None,
+            max_permutation_groups: None,
+            cluster_permutation_groups: false,
+            progressive_permutations: false,
+            max_permutations_total: None,
+            max_permutations_after_all_fit: None,
+            checkpoint_interval_ms: None,
+            stall_timeout_ms: None,
+            terminate_stalled: false,
         }
     }
 }
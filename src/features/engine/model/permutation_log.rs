@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// Summary of one processed permutation, kept on the task for debug bundles
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PermutationLog {
+    pub material: String,
+    pub permutation_index: usize,
+    pub nbr_tiles: usize,
+    /// Tile ids in the order they were fed to the threads
+    pub tile_ids: Vec<u32>,
+    pub nbr_stock_solutions: usize,
+    pub nbr_threads: i32,
+    /// Id of the best solution of the material after this permutation
+    pub best_solution_id: Option<i32>,
+    pub elapsed_ms: u64,
+}
+
+impl PermutationLog {
+    pub fn new(material: &str, permutation_index: usize, tile_ids: Vec<u32>) -> Self {
+        Self {
+            material: material.to_string(),
+            permutation_index,
+            nbr_tiles: tile_ids.len(),
+            tile_ids,
+            ..Self::default()
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::engine::model::{calculation_response::Mosaic, tile_node::TileNode};
+use crate::features::input::models::tile_dimensions::TileDimensions;
+
+/// Stock sheet with pinned panels carved out by guillotine cuts
+///
+/// The leftover leaves are handed to the engine as separate stock pieces (fragments),
+/// their layouts are grafted back into the sheet once the computation is done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedSheet {
+    pub stock: TileDimensions,
+    pub root: TileNode,
+    /// Internal stock id of each fragment -> id of the leaf node it was cut from
+    pub fragments: Vec<(u32, u32)>,
+}
+
+impl PinnedSheet {
+    pub fn new(stock: &TileDimensions) -> Self {
+        let mut root = TileNode::new(0, stock.width as i32, 0, stock.height as i32);
+        root.set_external_id(Some(stock.id));
+        Self {
+            stock: stock.clone(),
+            root,
+            fragments: Vec::new(),
+        }
+    }
+
+    /// Carve `tile` out of the sheet with its top left corner at (x, y)
+    pub fn pin(&mut self, tile: &TileDimensions, x: i32, y: i32, cut_thickness: i32) -> Result<(), String> {
+        let (x2, y2) = (x + tile.width as i32, y + tile.height as i32);
+        let leaf_id = Self::find_free_leaf(&self.root, x, y, x2, y2).ok_or_else(|| {
+            format!(
+                "panel {} at {},{} is off the sheet or overlaps another pinned panel",
+                tile.id, x, y
+            )
+        })?;
+        let Some(leaf) = self.root.find_by_id_mut(leaf_id) else {
+            return Err(format!("panel {} has no free space at {},{}", tile.id, x, y));
+        };
+
+        let node = Self::cut_off(leaf, false, y, cut_thickness)?;
+        let node = Self::cut_off(node, true, x, cut_thickness)?;
+        let node = Self::cut_keep(node, true, tile.width as i32, cut_thickness);
+        let node = Self::cut_keep(node, false, tile.height as i32, cut_thickness);
+
+        node.set_final_tile(true);
+        node.set_external_id(Some(tile.id));
+        node.set_rotated(tile.is_rotated);
+        Ok(())
+    }
+
+    /// Leftover pieces of the sheet to use as stock
+    pub fn get_free_nodes(&self) -> Vec<TileNode> {
+        self.root.get_unused_nodes().into_iter().cloned().collect()
+    }
+
+    pub fn add_fragment(&mut self, stock_id: u32, leaf_id: u32) {
+        self.fragments.push((stock_id, leaf_id));
+    }
+
+    pub fn is_fragment(&self, stock_id: u32) -> bool {
+        self.fragments.iter().any(|(id, _)| *id == stock_id)
+    }
+
+    /// Put the layout computed for a fragment back into its leaf of the sheet
+    pub fn graft(&mut self, fragment_root: &TileNode) -> bool {
+        let Some(leaf_id) = fragment_root.external_id.and_then(|stock_id| {
+            self.fragments
+                .iter()
+                .find(|(id, _)| *id == stock_id)
+                .map(|(_, leaf_id)| *leaf_id)
+        }) else {
+            return false;
+        };
+        let Some(leaf) = self.root.find_by_id_mut(leaf_id) else {
+            return false;
+        };
+
+        let (dx, dy) = (leaf.x1 - fragment_root.x1, leaf.y1 - fragment_root.y1);
+        leaf.child1 = fragment_root.child1.as_deref().map(|child| Box::new(Self::translate(child, dx, dy)));
+        leaf.child2 = fragment_root.child2.as_deref().map(|child| Box::new(Self::translate(child, dx, dy)));
+        // A panel filling the whole fragment is placed on the fragment root itself
+        if fragment_root.is_final {
+            leaf.set_final_tile(true);
+            leaf.set_external_id(fragment_root.external_id);
+            leaf.set_rotated(fragment_root.is_rotated);
+        }
+        Self::restage(leaf);
+        true
+    }
+
+    pub fn to_mosaic(&self) -> Mosaic {
+        let mut mosaic = Mosaic::from_tile_dimensions(&self.stock);
+        mosaic.set_root_tile_node(self.root.clone());
+        mosaic.wasted_area = self.root.get_unused_area() as f64;
+        mosaic
+    }
+
+    fn find_free_leaf(node: &TileNode, x1: i32, y1: i32, x2: i32, y2: i32) -> Option<u32> {
+        if node.x1 > x1 || node.y1 > y1 || node.x2 < x2 || node.y2 < y2 {
+            return None;
+        }
+        if node.child1.is_none() && node.child2.is_none() {
+            return (!node.is_final).then_some(node.id);
+        }
+        [&node.child1, &node.child2]
+            .into_iter()
+            .flatten()
+            .find_map(|child| Self::find_free_leaf(child, x1, y1, x2, y2))
+    }
+
+    /// Cut away the strip before `position`, the kerf goes into the strip
+    fn cut_off(node: &mut TileNode, is_horizontal: bool, position: i32, cut_thickness: i32) -> Result<&mut TileNode, String> {
+        let start = if is_horizontal { node.x1 } else { node.y1 };
+        let gap = position - start;
+        if gap == 0 {
+            return Ok(node);
+        }
+        if gap <= cut_thickness {
+            return Err(format!(
+                "pinned panel is {} from the edge, less than the cut thickness {}",
+                gap, cut_thickness
+            ));
+        }
+
+        node.split(is_horizontal, gap - cut_thickness, cut_thickness);
+        node.child2
+            .as_deref_mut()
+            .ok_or_else(|| "pinned panel is off the sheet".to_string())
+    }
+
+    /// Keep the first `size` of the node, the rest stays free
+    fn cut_keep(node: &mut TileNode, is_horizontal: bool, size: i32, cut_thickness: i32) -> &mut TileNode {
+        let length = if is_horizontal { node.get_width() } else { node.get_height() };
+        if length == size {
+            return node;
+        }
+
+        node.split(is_horizontal, size, cut_thickness);
+        node.child1.as_deref_mut().expect("split always sets child1")
+    }
+
+    fn translate(node: &TileNode, dx: i32, dy: i32) -> TileNode {
+        let mut moved = node.clone();
+        moved.x1 += dx;
+        moved.x2 += dx;
+        moved.y1 += dy;
+        moved.y2 += dy;
+        moved.child1 = node.child1.as_deref().map(|child| Box::new(Self::translate(child, dx, dy)));
+        moved.child2 = node.child2.as_deref().map(|child| Box::new(Self::translate(child, dx, dy)));
+        moved
+    }
+
+    /// Cut stages of a grafted layout continue from the stage of the leaf
+    fn restage(node: &mut TileNode) {
+        let next_stages = [node.child1.as_deref(), node.child2.as_deref()].map(|child| {
+            child
+                .and_then(|child| child.cut_horizontal)
+                .map(|is_horizontal| node.get_next_cut_stage(is_horizontal))
+        });
+
+        for (child, stage) in [node.child1.as_deref_mut(), node.child2.as_deref_mut()]
+            .into_iter()
+            .zip(next_stages)
+        {
+            let Some(child) = child else {
+                continue;
+            };
+            if let Some(stage) = stage {
+                child.cut_stage = stage;
+            }
+            Self::restage(child);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::engine::model::calculation_response::CalculationResponse;
+use crate::features::engine::model::response_diff::ResponseDiff;
+
+/// Spread of the results of one request computed several times with its rows in different
+/// orders, see `CutListOptimizerServiceImpl::check_reproducibility`. A wide spread means the
+/// search hasn't converged and more time or a higher optimization factor may pay off
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReproducibilityReport {
+    /// Row order seed of every run, 0 - the request as submitted
+    pub seeds: Vec<u64>,
+    /// Waste percent of the used sheets per run, in the order of `seeds`
+    pub waste_percents: Vec<f64>,
+    pub mean_waste_percent: f64,
+    pub min_waste_percent: f64,
+    pub max_waste_percent: f64,
+    /// Panels not placed per run, in the order of `seeds`
+    pub nbr_no_fit_panels: Vec<usize>,
+    /// Share of the placed panels the other runs put at the same place as the best run,
+    /// 1 - every run produced the same layout
+    pub placement_stability: f64,
+}
+
+impl ReproducibilityReport {
+    /// Report of the responses of the runs with `seeds`, in the same order
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::calculation_response::CalculationResponse;
+    /// use rezalnyas_core::features::engine::model::reproducibility_report::ReproducibilityReport;
+    ///
+    /// let response = |wasted_area| CalculationResponse {
+    ///     total_used_area: 75.0,
+    ///     total_wasted_area: wasted_area,
+    ///     ..CalculationResponse::new()
+    /// };
+    /// let report = ReproducibilityReport::new(vec![0, 1], &[response(25.0), response(125.0)]);
+    /// assert_eq!(report.waste_percents, vec![25.0, 62.5]);
+    /// assert_eq!(report.mean_waste_percent, 43.75);
+    /// assert_eq!(report.placement_stability, 1.0);
+    /// assert!(!report.is_converged(10.0));
+    /// ```
+    pub fn new(seeds: Vec<u64>, responses: &[CalculationResponse]) -> Self {
+        let waste_percents: Vec<f64> = responses.iter().map(Self::get_waste_percent).collect();
+        let nbr_runs = waste_percents.len().max(1) as f64;
+        let best = waste_percents
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index);
+
+        // Доля деталей, оставшихся на месте относительно лучшего прогона
+        let mut nbr_placed = 0;
+        let mut nbr_changed = 0;
+        if let Some(best) = best.map(|index| &responses[index]) {
+            for response in responses {
+                let diff = ResponseDiff::new(best, response);
+                nbr_placed += response.mosaics.iter().map(|mosaic| mosaic.nbr_final_panels.max(0) as usize).sum::<usize>();
+                nbr_changed += diff.moved_panels.len() + diff.added_panels.len();
+            }
+        }
+
+        Self {
+            seeds,
+            mean_waste_percent: waste_percents.iter().sum::<f64>() / nbr_runs,
+            min_waste_percent: waste_percents.iter().copied().reduce(f64::min).unwrap_or(0.0),
+            max_waste_percent: waste_percents.iter().copied().reduce(f64::max).unwrap_or(0.0),
+            nbr_no_fit_panels: responses
+                .iter()
+                .map(|response| response.no_fit_panels.iter().map(|panel| panel.count.max(0) as usize).sum())
+                .collect(),
+            placement_stability: if nbr_placed > 0 {
+                1.0 - nbr_changed as f64 / nbr_placed as f64
+            } else {
+                1.0
+            },
+            waste_percents,
+        }
+    }
+
+    /// True when the waste of the runs differs by at most `tolerance_percent` points and
+    /// all runs placed the same number of panels
+    pub fn is_converged(&self, tolerance_percent: f64) -> bool {
+        self.max_waste_percent - self.min_waste_percent <= tolerance_percent
+            && self.nbr_no_fit_panels.windows(2).all(|pair| pair[0] == pair[1])
+    }
+
+    fn get_waste_percent(response: &CalculationResponse) -> f64 {
+        let total_area = response.total_used_area + response.total_wasted_area;
+        if total_area > 0.0 {
+            response.total_wasted_area * 100.0 / total_area
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::engine::model::calculation_response::{CalculationResponse, Tile};
+
+/// Where a panel was placed, `sheet_index` is the position of its mosaic in the response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelPlacement {
+    pub request_obj_id: i32,
+    pub label: Option<String>,
+    pub sheet_index: usize,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub is_rotated: bool,
+}
+
+impl PanelPlacement {
+    fn new(tile: &Tile, request_obj_id: i32, sheet_index: usize) -> Self {
+        Self {
+            request_obj_id,
+            label: tile.label.clone(),
+            sheet_index,
+            x: tile.x,
+            y: tile.y,
+            width: tile.width,
+            height: tile.height,
+            is_rotated: tile.is_rotated,
+        }
+    }
+
+    fn is_same_place(&self, other: &Self) -> bool {
+        self.sheet_index == other.sheet_index
+            && self.x == other.x
+            && self.y == other.y
+            && self.is_rotated == other.is_rotated
+    }
+}
+
+/// Panel placed in both responses, but on another sheet, position or rotation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelMove {
+    pub from: PanelPlacement,
+    pub to: PanelPlacement,
+}
+
+/// Number of sheets of one stock panel used before and after
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SheetCountChange {
+    pub request_stock_id: Option<i32>,
+    pub stock_label: Option<String>,
+    pub before: usize,
+    pub after: usize,
+}
+
+/// What changed between two responses, see `CalculationResponse::diff`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResponseDiff {
+    pub moved_panels: Vec<PanelMove>,
+    /// Placed only in the new response
+    pub added_panels: Vec<PanelPlacement>,
+    /// Placed only in the old response
+    pub removed_panels: Vec<PanelPlacement>,
+    pub sheet_count_changes: Vec<SheetCountChange>,
+    pub nbr_sheets_delta: i64,
+    pub wasted_area_delta: f64,
+    pub used_area_ratio_delta: f64,
+    pub nbr_cuts_delta: i64,
+    pub cut_length_delta: f64,
+    pub no_fit_panels_delta: i64,
+}
+
+impl ResponseDiff {
+    pub fn new(before: &CalculationResponse, after: &CalculationResponse) -> Self {
+        let mut diff = Self {
+            nbr_sheets_delta: after.mosaics.len() as i64 - before.mosaics.len() as i64,
+            wasted_area_delta: after.total_wasted_area - before.total_wasted_area,
+            used_area_ratio_delta: after.total_used_area_ratio - before.total_used_area_ratio,
+            nbr_cuts_delta: after.total_nbr_cuts - before.total_nbr_cuts,
+            cut_length_delta: after.total_cut_length - before.total_cut_length,
+            no_fit_panels_delta: Self::count_no_fit(after) - Self::count_no_fit(before),
+            ..Self::default()
+        };
+
+        diff.compare_placements(before, after);
+        diff.compare_sheet_counts(before, after);
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moved_panels.is_empty()
+            && self.added_panels.is_empty()
+            && self.removed_panels.is_empty()
+            && self.sheet_count_changes.is_empty()
+            && self.wasted_area_delta == 0.0
+            && self.nbr_cuts_delta == 0
+            && self.no_fit_panels_delta == 0
+    }
+
+    /// Copies of the same panel are interchangeable: unchanged places are matched first,
+    /// the rest are paired in sheet order as moves
+    fn compare_placements(&mut self, before: &CalculationResponse, after: &CalculationResponse) {
+        let mut before_placements = Self::get_placements(before);
+        let mut after_placements = Self::get_placements(after);
+
+        for (request_obj_id, old) in before_placements.iter_mut() {
+            let new = after_placements.entry(*request_obj_id).or_default();
+            old.retain(|placement| {
+                match new.iter().position(|other| other.is_same_place(placement)) {
+                    Some(index) => {
+                        new.remove(index);
+                        false
+                    }
+                    None => true,
+                }
+            });
+
+            let moved = old.len().min(new.len());
+            for (from, to) in old.drain(..moved).zip(new.drain(..moved)) {
+                self.moved_panels.push(PanelMove { from, to });
+            }
+            self.removed_panels.append(old);
+        }
+
+        for (_, new) in after_placements {
+            self.added_panels.extend(new);
+        }
+    }
+
+    fn compare_sheet_counts(&mut self, before: &CalculationResponse, after: &CalculationResponse) {
+        let mut counts: BTreeMap<Option<i32>, (Option<String>, usize, usize)> = BTreeMap::new();
+        for mosaic in &before.mosaics {
+            let entry = counts
+                .entry(mosaic.request_stock_id)
+                .or_insert((mosaic.stock_label.clone(), 0, 0));
+            entry.1 += 1;
+        }
+        for mosaic in &after.mosaics {
+            let entry = counts
+                .entry(mosaic.request_stock_id)
+                .or_insert((mosaic.stock_label.clone(), 0, 0));
+            entry.2 += 1;
+        }
+
+        self.sheet_count_changes = counts
+            .into_iter()
+            .filter(|(_, (_, before, after))| before != after)
+            .map(|(request_stock_id, (stock_label, before, after))| SheetCountChange {
+                request_stock_id,
+                stock_label,
+                before,
+                after,
+            })
+            .collect();
+    }
+
+    fn get_placements(response: &CalculationResponse) -> BTreeMap<i32, Vec<PanelPlacement>> {
+        let mut placements: BTreeMap<i32, Vec<PanelPlacement>> = BTreeMap::new();
+        for (sheet_index, mosaic) in response.mosaics.iter().enumerate() {
+            for tile in mosaic.tiles.iter().filter(|tile| tile.is_final) {
+                if let Some(request_obj_id) = tile.request_obj_id {
+                    placements
+                        .entry(request_obj_id)
+                        .or_default()
+                        .push(PanelPlacement::new(tile, request_obj_id, sheet_index));
+                }
+            }
+        }
+        placements
+    }
+
+    fn count_no_fit(response: &CalculationResponse) -> i64 {
+        response
+            .no_fit_panels
+            .iter()
+            .map(|panel| panel.count as i64)
+            .sum()
+    }
+}
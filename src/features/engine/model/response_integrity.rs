@@ -0,0 +1,92 @@
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::Result;
+use crate::features::engine::model::calculation_response::CalculationResponse;
+
+/// Hash of a response so a plan sent on can be checked for changes, set in
+/// `CalculationResponse::integrity` with `Configuration::include_content_hash`
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::calculation_response::CalculationResponse;
+/// use rezalnyas_core::features::engine::model::response_integrity::ResponseIntegrity;
+///
+/// let mut response = CalculationResponse::new();
+/// response.total_used_area = 240000.0;
+/// response.integrity = Some(ResponseIntegrity::new(&response, Some(b"secret")).unwrap());
+/// assert!(ResponseIntegrity::verify(&response, Some(b"secret")).unwrap());
+/// assert!(!ResponseIntegrity::verify(&response, Some(b"other")).unwrap());
+///
+/// response.total_used_area = 250000.0;
+/// assert!(!ResponseIntegrity::verify(&response, None).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseIntegrity {
+    /// Lowercase hex SHA-256 of the JSON of the response without `integrity` and `cached`
+    pub sha256: String,
+    /// Lowercase hex HMAC-SHA256 of the same JSON with the signing key of the service,
+    /// None without a key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hmac_sha256: Option<String>,
+}
+
+impl ResponseIntegrity {
+    /// Hash of `response`, signed when `key` is set
+    pub fn new(response: &CalculationResponse, key: Option<&[u8]>) -> Result<Self> {
+        let content = Self::get_content(response)?;
+        Ok(Self {
+            sha256: to_hex(&Sha256::digest(&content)),
+            hmac_sha256: key.map(|key| to_hex(&Self::get_hmac(key, &content).finalize().into_bytes())),
+        })
+    }
+
+    /// True when `response` still has the hash it was generated with, and with `key` also
+    /// its signature. False for a response without `integrity` or a signature without a key
+    pub fn verify(response: &CalculationResponse, key: Option<&[u8]>) -> Result<bool> {
+        let Some(integrity) = &response.integrity else {
+            return Ok(false);
+        };
+        let content = Self::get_content(response)?;
+        if to_hex(&Sha256::digest(&content)) != integrity.sha256 {
+            return Ok(false);
+        }
+        Ok(match (key, &integrity.hmac_sha256) {
+            (None, _) => true,
+            (Some(key), Some(hmac)) => from_hex(hmac)
+                .is_some_and(|signature| Self::get_hmac(key, &content).verify_slice(&signature).is_ok()),
+            (Some(_), None) => false,
+        })
+    }
+
+    // Флаг cached не входит в хэш, ответ из кэша сверяется так же.
+    // Через Value ключи HashMap сортируются, JSON не зависит от порядка обхода
+    fn get_content(response: &CalculationResponse) -> Result<Vec<u8>> {
+        let content = CalculationResponse {
+            integrity: None,
+            cached: false,
+            ..response.clone()
+        };
+        Ok(serde_json::to_vec(&serde_json::to_value(&content)?)?)
+    }
+
+    fn get_hmac(key: &[u8], content: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(content);
+        mac
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
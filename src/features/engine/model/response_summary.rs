@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::engine::model::calculation_request::Panel;
+use crate::features::engine::model::calculation_response::CalculationResponse;
+use crate::scaled_math::Money;
+
+/// Sheets of one stock size used by a layout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SheetUsage {
+    pub material: String,
+    pub width: f64,
+    pub height: f64,
+    pub count: u32,
+}
+
+/// Totals of the sheets of one material, or of the whole layout
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MaterialSummary {
+    /// Material key, empty for the total of the layout
+    pub material: String,
+    pub nbr_sheets: u32,
+    pub nbr_placed_parts: u32,
+    pub nbr_unplaced_parts: u32,
+    pub used_area: f64,
+    pub wasted_area: f64,
+    /// Wasted area in percent of the sheet area
+    pub waste_percent: f64,
+    pub nbr_cuts: u32,
+    pub cut_length: f64,
+    /// Cuts that run across the whole sheet, see `Mosaic::get_nbr_through_cuts`
+    pub nbr_through_cuts: u32,
+    /// Share of the cuts that run across the whole sheet, 1.0 without cuts
+    pub through_cut_ratio: f64,
+    /// Edge band length per band material, of the placed parts
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub edge_banding: BTreeMap<String, f64>,
+    /// See `Configuration::estimate_processing_seconds`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_time_seconds: Option<f64>,
+    /// See `Configuration::estimate_cost`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_cost: Option<Money>,
+}
+
+/// Numbers clients would otherwise add up from the mosaics of a response
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSummary {
+    /// Sheets used per stock size, by material then size
+    pub sheets: Vec<SheetUsage>,
+    /// Per material, in material order
+    pub materials: Vec<MaterialSummary>,
+    pub total: MaterialSummary,
+}
+
+impl ResponseSummary {
+    /// Summary of the mosaics and not placed panels of a response
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::layout_evaluator::evaluate_layout;
+    /// use rezalnyas_core::features::engine::model::calculation_request::{Edge, PinnedPlacement};
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    ///
+    /// let edge = Edge { top: Some("ABS".to_string()), left: None, bottom: Some("ABS".to_string()), right: None };
+    /// let request = CalculationRequestBuilder::new()
+    ///     .add_panel(400.0, 600.0).id(1).edge(edge)
+    ///     .add_stock(1000.0, 600.0).id(10)
+    ///     .build()
+    ///     .unwrap();
+    /// let report = evaluate_layout(&request, &[PinnedPlacement::new(1, 10, 0, "0", "0", false)]);
+    ///
+    /// let summary = &report.response.summary;
+    /// assert_eq!(summary.sheets[0].count, 1);
+    /// assert_eq!(summary.total.nbr_placed_parts, 1);
+    /// assert_eq!(summary.total.waste_percent, 60.0);
+    /// assert_eq!(summary.total.edge_banding["ABS"], 800.0);
+    /// assert_eq!(summary.total.through_cut_ratio, 1.0);
+    /// ```
+    pub fn from_response(response: &CalculationResponse) -> Self {
+        let configuration = &response.request.configuration;
+        let mut sheets: Vec<SheetUsage> = Vec::new();
+        let mut materials: BTreeMap<String, MaterialSummary> = BTreeMap::new();
+        // Листы материала с их строками стока, каждая строка может иметь свою цену
+        let mut material_sheets: BTreeMap<String, Vec<(Option<&Panel>, f64)>> = BTreeMap::new();
+
+        for mosaic in &response.mosaics {
+            let material = mosaic.material.clone().unwrap_or_default();
+            let stock = mosaic
+                .request_stock_id
+                .and_then(|id| response.request.stock_panels.iter().find(|stock| stock.id as i32 == id));
+            material_sheets
+                .entry(material.clone())
+                .or_default()
+                .push((stock, mosaic.used_area + mosaic.wasted_area));
+            let summary = materials.entry(material.clone()).or_insert_with(|| MaterialSummary {
+                material: material.clone(),
+                ..MaterialSummary::default()
+            });
+            summary.nbr_sheets += 1;
+            summary.used_area += mosaic.used_area;
+            summary.wasted_area += mosaic.wasted_area;
+            summary.nbr_cuts += mosaic.cuts.len() as u32;
+            summary.cut_length += mosaic.cut_length;
+            summary.nbr_through_cuts += mosaic.get_nbr_through_cuts() as u32;
+
+            for tile in mosaic.tiles.iter().filter(|tile| tile.is_final) {
+                let Some(panel) = tile
+                    .request_obj_id
+                    .and_then(|id| response.request.panels.iter().find(|panel| panel.id as i32 == id))
+                else {
+                    continue;
+                };
+                summary.nbr_placed_parts += 1;
+                let Some(edge) = &panel.edge else {
+                    continue;
+                };
+                // Кромки верха и низа идут вдоль ширины детали, у повёрнутой детали это высота плитки
+                let (along_width, along_height) = if tile.is_rotated {
+                    (tile.height, tile.width)
+                } else {
+                    (tile.width, tile.height)
+                };
+                for (band, length) in [
+                    (&edge.top, along_width),
+                    (&edge.bottom, along_width),
+                    (&edge.left, along_height),
+                    (&edge.right, along_height),
+                ] {
+                    if let Some(band) = band {
+                        *summary.edge_banding.entry(band.clone()).or_insert(0.0) += length;
+                    }
+                }
+            }
+
+            if let Some(sheet) = mosaic.tiles.first() {
+                match sheets.iter_mut().find(|usage| {
+                    usage.material == material && usage.width == sheet.width && usage.height == sheet.height
+                }) {
+                    Some(usage) => usage.count += 1,
+                    None => sheets.push(SheetUsage {
+                        material,
+                        width: sheet.width,
+                        height: sheet.height,
+                        count: 1,
+                    }),
+                }
+            }
+        }
+
+        for no_fit in &response.no_fit_panels {
+            let material = response
+                .request
+                .panels
+                .iter()
+                .find(|panel| panel.id == no_fit.id)
+                .map(|panel| panel.get_material_key())
+                .or_else(|| no_fit.material.clone())
+                .unwrap_or_default();
+            materials
+                .entry(material.clone())
+                .or_insert_with(|| MaterialSummary {
+                    material,
+                    ..MaterialSummary::default()
+                })
+                .nbr_unplaced_parts += no_fit.count.max(0) as u32;
+        }
+
+        let mut total = MaterialSummary::default();
+        for summary in materials.values_mut() {
+            summary.waste_percent = Self::get_waste_percent(summary);
+            summary.through_cut_ratio = Self::get_through_cut_ratio(summary);
+            summary.estimated_time_seconds =
+                configuration.estimate_processing_seconds(summary.cut_length, summary.nbr_sheets as usize);
+            let priced_sheets = material_sheets.get(&summary.material).map_or(&[][..], Vec::as_slice);
+            summary.estimated_cost = configuration
+                .estimate_cost(summary.nbr_cuts as i64, priced_sheets)
+                .map(|cost| cost.total_cost);
+
+            total.nbr_sheets += summary.nbr_sheets;
+            total.nbr_placed_parts += summary.nbr_placed_parts;
+            total.nbr_unplaced_parts += summary.nbr_unplaced_parts;
+            total.used_area += summary.used_area;
+            total.wasted_area += summary.wasted_area;
+            total.nbr_cuts += summary.nbr_cuts;
+            total.cut_length += summary.cut_length;
+            total.nbr_through_cuts += summary.nbr_through_cuts;
+            for (band, length) in &summary.edge_banding {
+                *total.edge_banding.entry(band.clone()).or_insert(0.0) += length;
+            }
+        }
+        total.waste_percent = Self::get_waste_percent(&total);
+        total.through_cut_ratio = Self::get_through_cut_ratio(&total);
+        total.estimated_time_seconds =
+            configuration.estimate_processing_seconds(total.cut_length, total.nbr_sheets as usize);
+        let all_sheets: Vec<_> = material_sheets.into_values().flatten().collect();
+        total.estimated_cost = configuration
+            .estimate_cost(total.nbr_cuts as i64, &all_sheets)
+            .map(|cost| cost.total_cost);
+
+        sheets.sort_by(|a, b| {
+            a.material
+                .cmp(&b.material)
+                .then(b.width.total_cmp(&a.width))
+                .then(b.height.total_cmp(&a.height))
+        });
+
+        Self {
+            sheets,
+            materials: materials.into_values().collect(),
+            total,
+        }
+    }
+
+    fn get_through_cut_ratio(summary: &MaterialSummary) -> f64 {
+        if summary.nbr_cuts == 0 {
+            return 1.0;
+        }
+        summary.nbr_through_cuts as f64 / summary.nbr_cuts as f64
+    }
+
+    fn get_waste_percent(summary: &MaterialSummary) -> f64 {
+        let area = summary.used_area + summary.wasted_area;
+        if area > 0.0 {
+            summary.wasted_area / area * 100.0
+        } else {
+            0.0
+        }
+    }
+}
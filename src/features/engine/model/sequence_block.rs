@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::orientation::Orientation;
+use crate::features::engine::model::{solution::Solution, tile_node::TileNode};
+use crate::features::input::models::tile_dimensions::{TileDimensions, TileDimensionsData};
+
+/// Members of a sequence group packed into one tile: the engine places the block as a
+/// whole and `expand` cuts it back into the members, in order, once the computation is done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceBlock {
+    pub group_id: u32,
+    pub tile: TileDimensions,
+    pub members: Vec<TileDimensions>,
+    pub is_horizontal: bool,
+    pub cut_thickness: i32,
+}
+
+impl SequenceBlock {
+    /// Block of the members laid along the axis with a kerf between them, `members` is not empty
+    pub fn new(
+        group_id: u32,
+        internal_id: u32,
+        members: Vec<TileDimensions>,
+        is_horizontal: bool,
+        cut_thickness: i32,
+    ) -> Self {
+        let along = |tile: &TileDimensions| if is_horizontal { tile.width } else { tile.height };
+        let across = |tile: &TileDimensions| if is_horizontal { tile.height } else { tile.width };
+        let gaps = cut_thickness.max(0) as u32 * (members.len() as u32 - 1);
+        let length = members.iter().map(along).sum::<u32>() + gaps;
+        let depth = members.iter().map(across).max().unwrap_or(0);
+        let (width, height) = if is_horizontal { (length, depth) } else { (depth, length) };
+
+        let first = &members[0];
+        // Общее направление волокон сохраняется только если оно у всех деталей одно
+        let orientation = if members.iter().all(|member| member.orientation == first.orientation) {
+            first.orientation
+        } else {
+            Orientation::Default
+        };
+        let tile = TileDimensions::intern(TileDimensionsData {
+            id: internal_id,
+            width,
+            height,
+            material: first.material.clone(),
+            label: format!("sequence {}", group_id),
+            orientation,
+            is_rotated: false,
+            edge_clearance: members.iter().map(|member| member.edge_clearance).max().unwrap_or(0),
+            priority: 0,
+            acquired_date: None,
+        });
+
+        Self {
+            group_id,
+            tile,
+            members,
+            is_horizontal,
+            cut_thickness: cut_thickness.max(0),
+        }
+    }
+
+    /// Cut the placed block of every sheet of the solution into its members, true if it was placed
+    pub fn expand(&self, solution: &mut Solution) -> bool {
+        let mut is_placed = false;
+        for mosaic in &mut solution.mosaics {
+            let Some(mut root) = mosaic.root_tile_node.first().cloned() else {
+                continue;
+            };
+            if self.expand_node(&mut root) {
+                mosaic.set_root_tile_node(root);
+                is_placed = true;
+            }
+        }
+        is_placed
+    }
+
+    /// Replace the block in a list of unplaced tiles by its members, true if it was there
+    pub fn expand_tiles(&self, tiles: &mut Vec<TileDimensions>) -> bool {
+        let nbr_tiles = tiles.len();
+        tiles.retain(|tile| tile.id != self.tile.id);
+        let nbr_blocks = nbr_tiles - tiles.len();
+        for _ in 0..nbr_blocks {
+            tiles.extend(self.members.iter().cloned());
+        }
+        nbr_blocks > 0
+    }
+
+    fn expand_node(&self, node: &mut TileNode) -> bool {
+        if node.is_final && node.external_id == Some(self.tile.id) {
+            self.cut_members(node);
+            return true;
+        }
+        let mut is_placed = false;
+        for child in [node.child1.as_deref_mut(), node.child2.as_deref_mut()]
+            .into_iter()
+            .flatten()
+        {
+            is_placed |= self.expand_node(child);
+        }
+        is_placed
+    }
+
+    fn cut_members(&self, node: &mut TileNode) {
+        // Повёрнутый блок лежит вдоль другой оси, его детали тоже повёрнуты
+        let is_rotated = node.is_rotated;
+        let is_horizontal = self.is_horizontal != is_rotated;
+        node.set_final_tile(false);
+        node.set_external_id(None);
+        node.set_rotated(false);
+
+        let mut rest = node;
+        for member in &self.members {
+            let member = if is_rotated { member.rotate_90() } else { member.clone() };
+            let (along, across) = if is_horizontal {
+                (member.width as i32, member.height as i32)
+            } else {
+                (member.height as i32, member.width as i32)
+            };
+            let length = if is_horizontal { rest.get_width() } else { rest.get_height() };
+
+            if length > along {
+                rest.split(is_horizontal, along, self.cut_thickness);
+                if let Some(piece) = rest.child1.as_deref_mut() {
+                    self.place_member(piece, &member, !is_horizontal, across);
+                }
+                rest = match rest.child2.as_deref_mut() {
+                    Some(child2) => child2,
+                    None => return,
+                };
+            } else {
+                self.place_member(rest, &member, !is_horizontal, across);
+                return;
+            }
+        }
+    }
+
+    /// Trim the piece across the axis to the member and mark it final
+    fn place_member(&self, piece: &mut TileNode, member: &TileDimensions, is_horizontal: bool, size: i32) {
+        let length = if is_horizontal { piece.get_width() } else { piece.get_height() };
+        let piece = if length > size {
+            piece.split(is_horizontal, size, self.cut_thickness);
+            piece.child1.as_deref_mut().expect("split always sets child1")
+        } else {
+            piece
+        };
+        piece.set_final_tile(true);
+        piece.set_external_id(Some(member.id));
+        piece.set_rotated(member.is_rotated);
+    }
+}
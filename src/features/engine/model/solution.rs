@@ -7,7 +7,7 @@ use std::{
 use serde::{Deserialize, Serialize};
 
 use crate::features::{
-    engine::model::{calculation_response::Mosaic, stock_solution::StockSolution},
+    engine::model::{calculation_response::Mosaic, stock_solution::StockSolution, tile_node::TileNode},
     input::models::tile_dimensions::TileDimensions,
 };
 
@@ -21,6 +21,9 @@ pub struct Solution {
     pub aux_info: Option<String>,
     pub creator_thread_group: Option<String>,
 }
+/// Placed panel of a solution: sheet index, panel id, x, y, rotated
+pub type Placement = (usize, Option<u32>, i32, i32, bool);
+
 static SOLUTION_ID_COUNTER: AtomicI32 = AtomicI32::new(0);
 impl Default for Solution {
     fn default() -> Self {
@@ -51,18 +54,8 @@ impl Solution {
 
         // Java: addMosaic(new Mosaic(this.unusedStockPanels.poll()));
         if let Some(first_stock_tile) = solution.unused_stock_panels.pop_front() {
-            let mut mosaic = Mosaic::new();
-            mosaic.material = Some(first_stock_tile.material.clone());
-
-            // Create root tile node for this mosaic (like Java constructor Mosaic(TileDimensions))
-            let root_node = crate::features::engine::model::tile_node::TileNode::new(
-                0,
-                first_stock_tile.width as i32,
-                0,
-                first_stock_tile.height as i32,
-            );
-       
-            mosaic.wasted_area = (first_stock_tile.width * first_stock_tile.height) as f64;
+            // Java: new Mosaic(TileDimensions) creates the root tile node of the sheet
+            let mosaic = Mosaic::from_tile_dimensions(&first_stock_tile);
 
             solution.add_mosaic(mosaic);
         }
@@ -178,11 +171,36 @@ impl Solution {
     pub fn get_nbr_final_tiles(&self) -> i32 {
         let mut nbr_final_tiles = 0;
         for mosaic in &self.mosaics {
-        
+            nbr_final_tiles += mosaic.get_nbr_final_tiles();
         }
         nbr_final_tiles
     }
 
+    /// Sheets carrying more than `max_parts_per_sheet` panels
+    pub fn get_nbr_mosaics_over_parts_cap(&self, max_parts_per_sheet: u32) -> i32 {
+        self.mosaics
+            .iter()
+            .filter(|mosaic| mosaic.get_nbr_final_tiles() as u32 > max_parts_per_sheet)
+            .count() as i32
+    }
+
+    /// Mean priority of the used stock sheets weighted by the panel area cut from each,
+    /// so opening more sheets of a priority doesn't raise it; 0.0 without panels
+    pub fn get_stock_priority(&self) -> f64 {
+        let (weighted_priority, used_area) = self
+            .mosaics
+            .iter()
+            .filter_map(|mosaic| {
+                let used_area = mosaic.root_tile_node.first()?.get_used_area();
+                Some((mosaic.stock_priority as f64 * used_area as f64, used_area))
+            })
+            .fold((0.0, 0), |(weighted, total), (priority, area)| (weighted + priority, total + area));
+        if used_area == 0 {
+            return 0.0;
+        }
+        weighted_priority / used_area as f64
+    }
+
     /// Java: public long getUnusedArea()
     pub fn get_unused_area(&self) -> i64 {
         let mut unused_area = 0;
@@ -196,7 +214,7 @@ impl Solution {
     pub fn get_nbr_cuts(&self) -> i32 {
         let mut nbr_cuts = 0;
         for mosaic in &self.mosaics {
-            nbr_cuts += mosaic.cuts.len() as i32;
+            nbr_cuts += mosaic.get_nbr_cuts();
         }
         nbr_cuts
     }
@@ -210,7 +228,10 @@ impl Solution {
     pub fn get_total_area(&self) -> i64 {
         let mut total_area = 0;
         for mosaic in &self.mosaics {
-       
+            // Java: totalArea += mosaic.getRootTileNode().getArea();
+            if let Some(root) = mosaic.root_tile_node.first() {
+                total_area += root.get_width() as i64 * root.get_height() as i64;
+            }
         }
         total_area
     }
@@ -238,6 +259,175 @@ impl Solution {
         j_max
     }
 
+    /// Unused area of every sheet, in the order of the sheets
+    pub fn get_unused_area_per_mosaic(&self) -> Vec<i64> {
+        self.mosaics.iter().map(|mosaic| mosaic.get_unused_area()).collect()
+    }
+
+    /// Share of the unused area that lies on the most wasted sheet, 1.0 when all the waste
+    /// is one sheet (usually the last one) and 1 / n when it is spread evenly over n sheets
+    pub fn get_waste_concentration(&self) -> f64 {
+        let unused_areas = self.get_unused_area_per_mosaic();
+        let total: i64 = unused_areas.iter().sum();
+        if total == 0 {
+            return 1.0;
+        }
+        unused_areas.into_iter().max().unwrap_or(0) as f64 / total as f64
+    }
+
+    /// How scattered the unused area is: 1 - largest free rectangle / total free area.
+    /// 0.0 when all the waste is one rectangle or there is none, close to 1.0 for many small pieces
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::{solution::Solution, stock_solution::StockSolution};
+    /// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+    ///
+    /// let stock = TileDimensions::new(0, 1000, 600, false, "", "");
+    /// let mut solution = Solution::from_stock_solution(&StockSolution::new(vec![stock]));
+    /// assert_eq!(solution.get_fragmentation_score(), 0.0);
+    ///
+    /// let mut root = solution.mosaics[0].root_tile_node[0].clone();
+    /// root.split(true, 300, 0);
+    /// root.child1.as_mut().unwrap().split(false, 200, 0);
+    /// root.child1.as_mut().unwrap().child1.as_mut().unwrap().set_final_tile(true);
+    /// solution.mosaics[0].set_root_tile_node(root);
+    /// // 700x600 and 300x400 are left: 1 - 420000 / 540000
+    /// assert!((solution.get_fragmentation_score() - 2.0 / 9.0).abs() < 1e-9);
+    /// ```
+    pub fn get_fragmentation_score(&self) -> f64 {
+        let total = self.get_unused_area();
+        if total == 0 {
+            return 0.0;
+        }
+        1.0 - self.get_biggest_area() as f64 / total as f64
+    }
+
+    /// Share of the cuts of all sheets that run across the whole sheet, like the rip cuts of
+    /// a panel saw. 1.0 when every cut goes edge to edge or there are no cuts
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::{solution::Solution, stock_solution::StockSolution};
+    /// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+    ///
+    /// let stock = TileDimensions::new(0, 1000, 600, false, "", "");
+    /// let mut solution = Solution::from_stock_solution(&StockSolution::new(vec![stock]));
+    /// assert_eq!(solution.get_through_cut_ratio(), 1.0);
+    ///
+    /// let mut root = solution.mosaics[0].root_tile_node[0].clone();
+    /// root.split(true, 300, 0);
+    /// root.child1.as_mut().unwrap().split(false, 200, 0);
+    /// solution.mosaics[0].set_root_tile_node(root);
+    /// // The 600 long cut crosses the sheet, the 300 long one only the first strip
+    /// assert_eq!(solution.get_through_cut_ratio(), 0.5);
+    /// ```
+    pub fn get_through_cut_ratio(&self) -> f64 {
+        let nbr_cuts = self.get_nbr_cuts();
+        if nbr_cuts == 0 {
+            return 1.0;
+        }
+        let nbr_through_cuts: i32 = self.mosaics.iter().map(|mosaic| mosaic.get_nbr_through_cuts()).sum();
+        nbr_through_cuts as f64 / nbr_cuts as f64
+    }
+
+    /// Unused area of the sheets in percent of their total area
+    pub fn get_waste_percent(&self) -> f64 {
+        let total_area = self.get_sheet_area();
+        if total_area == 0 {
+            return 0.0;
+        }
+        self.get_unused_area() as f64 * 100.0 / total_area as f64
+    }
+
+    /// Used share of the area of the sheets, 0 without sheets
+    pub fn get_yield(&self) -> f64 {
+        let sheet_area = self.get_sheet_area();
+        if sheet_area == 0 {
+            return 0.0;
+        }
+        (sheet_area - self.get_unused_area()) as f64 / sheet_area as f64
+    }
+
+    /// Highest yield the branch can still reach when panels of `remaining_area` are placed
+    /// without waste, on its sheets first and on new sheets after them
+    pub fn get_yield_upper_bound(&self, remaining_area: i64) -> f64 {
+        let reachable_area = self.get_sheet_area() - self.get_unused_area() + remaining_area;
+        let area = self.get_sheet_area().max(reachable_area);
+        if area == 0 {
+            return 0.0;
+        }
+        reachable_area as f64 / area as f64
+    }
+
+    /// Rough heap and inline size of the solution, for the memory budget of the pools
+    pub fn estimate_memory_bytes(&self) -> usize {
+        let nbr_nodes: usize = self
+            .mosaics
+            .iter()
+            .flat_map(|mosaic| mosaic.root_tile_node.iter())
+            .map(|root| root.iter().count())
+            .sum();
+        std::mem::size_of::<Self>()
+            + self.mosaics.len() * std::mem::size_of::<Mosaic>()
+            + nbr_nodes * std::mem::size_of::<TileNode>()
+            + (self.unused_stock_panels.len() + self.no_fit_panels.len()) * std::mem::size_of::<TileDimensions>()
+    }
+
+    fn get_sheet_area(&self) -> i64 {
+        self.mosaics
+            .iter()
+            .filter_map(|mosaic| mosaic.root_tile_node.first())
+            .map(|root| root.get_area() as i64)
+            .sum()
+    }
+
+    /// Placed panels of all sheets, sorted
+    pub fn get_placements(&self) -> Vec<Placement> {
+        let mut placements: Vec<_> = self
+            .mosaics
+            .iter()
+            .enumerate()
+            .flat_map(|(sheet_index, mosaic)| {
+                mosaic.root_tile_node.iter().flat_map(move |root| {
+                    root.get_final_nodes()
+                        .into_iter()
+                        .map(move |node| (sheet_index, node.external_id, node.x1, node.y1, node.is_rotated))
+                })
+            })
+            .collect();
+        placements.sort_unstable();
+        placements
+    }
+
+    /// Share of placements two solutions have in common, 1.0 - same layout
+    pub fn get_similarity(&self, other: &Solution) -> f64 {
+        Self::get_placements_similarity(&self.get_placements(), &other.get_placements())
+    }
+
+    /// Same as `get_similarity` for placements taken with `get_placements`
+    pub fn get_placements_similarity(
+        placements: &[Placement],
+        other: &[Placement],
+    ) -> f64 {
+        let total = placements.len().max(other.len());
+        if total == 0 {
+            return 1.0;
+        }
+
+        let (mut i, mut j, mut shared) = (0, 0, 0);
+        while i < placements.len() && j < other.len() {
+            match placements[i].cmp(&other[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    shared += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        shared as f64 / total as f64
+    }
+
     /// Java: public float getHVDiff()
     pub fn get_hvdiff(&self) -> f32 {
         // Java: Iterator<Mosaic> it = this.mosaics.iterator(); float hVDiff = 0.0f;
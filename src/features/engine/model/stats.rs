@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::features::engine::task_report::TaskReport;
@@ -15,6 +17,10 @@ pub struct Stats {
     pub nbr_queued_threads: i32,
     pub nbr_finished_threads: i64,
     pub task_reports: Vec<TaskReport>,
+    /// Thread group (AREA, AREA_HCUTS_1ST, AREA_VCUTS_1ST) -> number of finished task
+    /// materials whose returned solution it created, to tune `cut_orientation_preference`
+    #[serde(default)]
+    pub best_solution_groups: HashMap<String, i64>,
 }
 
 impl Stats {
@@ -30,6 +36,7 @@ impl Stats {
             nbr_queued_threads: 0,
             nbr_finished_threads: 0,
             task_reports: Vec::new(),
+            best_solution_groups: HashMap::new(),
         }
     }
 }
@@ -1,13 +1,94 @@
 use serde::{Deserialize, Serialize};
 
 /// Статусы задач
+///
+/// В JSON пишутся как в Java ("RUNNING"), старые значения ("Running") тоже читаются
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Status {
+    #[serde(alias = "Idle")]
     Idle,
+    #[serde(alias = "Queued")]
     Queued,
+    #[serde(alias = "Running")]
     Running,
+    /// Первое полное решение отдано, поиск продолжает улучшать его в пределах бюджета
+    #[serde(alias = "Improving")]
+    Improving,
+    /// Поиск окончен, ответ собирается
+    #[serde(alias = "Finishing")]
+    Finishing,
+    #[serde(alias = "Finished")]
     Finished,
+    /// Клиент остановил задачу, ответ собирается из лучшего найденного раскроя
+    #[serde(alias = "Stopping")]
+    Stopping,
+    #[serde(alias = "Stopped")]
     Stopped,
+    #[serde(alias = "Terminated")]
     Terminated,
+    #[serde(alias = "Error")]
     Error,
-}
\ No newline at end of file
+}
+
+impl Status {
+    /// Java: Status.name()
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Idle => "IDLE",
+            Status::Queued => "QUEUED",
+            Status::Running => "RUNNING",
+            Status::Improving => "IMPROVING",
+            Status::Finishing => "FINISHING",
+            Status::Finished => "FINISHED",
+            Status::Stopping => "STOPPING",
+            Status::Stopped => "STOPPED",
+            Status::Terminated => "TERMINATED",
+            Status::Error => "ERROR",
+        }
+    }
+
+    /// Whether a task may go from this status to `next`:
+    /// Queued → Running → {Stopping → Stopped, Finishing → Finished, Error, Terminated},
+    /// Running and Improving may go to each other's targets
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::status::Status;
+    ///
+    /// assert!(Status::Queued.can_transition_to(Status::Running));
+    /// assert!(Status::Running.can_transition_to(Status::Stopping));
+    /// assert!(Status::Stopping.can_transition_to(Status::Stopped));
+    /// assert!(!Status::Running.can_transition_to(Status::Finished));
+    /// assert!(!Status::Finished.can_transition_to(Status::Running));
+    /// ```
+    pub fn can_transition_to(&self, next: Status) -> bool {
+        match self {
+            Status::Idle => matches!(next, Status::Queued | Status::Running | Status::Error),
+            Status::Queued => matches!(
+                next,
+                Status::Running | Status::Stopped | Status::Terminated | Status::Error
+            ),
+            Status::Running | Status::Improving => matches!(
+                next,
+                Status::Improving | Status::Stopping | Status::Finishing | Status::Terminated | Status::Error
+            ) && next != *self,
+            Status::Stopping => matches!(next, Status::Stopped | Status::Terminated | Status::Error),
+            Status::Finishing => matches!(next, Status::Finished | Status::Terminated | Status::Error),
+            Status::Finished | Status::Stopped | Status::Terminated | Status::Error => false,
+        }
+    }
+
+    /// True once the task is over and its status won't change anymore
+    pub fn is_final(&self) -> bool {
+        matches!(self, Status::Finished | Status::Stopped | Status::Terminated | Status::Error)
+    }
+}
+
+/// Status change of a task, see `Task::set_status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusTransition {
+    pub from: Status,
+    pub to: Status,
+    /// Clock millis of the change
+    pub at_ms: u64,
+}
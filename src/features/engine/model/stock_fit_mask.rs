@@ -0,0 +1,64 @@
+use crate::features::input::models::tile_dimensions::TileDimensions;
+
+/// Stock sizes a tile fits into, one bit per distinct stock size, as is and rotated
+///
+/// Used to drop tiles that can't be placed on any sheet before permutations are generated
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StockFitMask {
+    pub fits: Vec<u64>,
+    pub fits_rotated: Vec<u64>,
+}
+
+impl StockFitMask {
+    /// Tile sides up to `tolerance` longer than a stock side still fit it
+    pub fn new(tile: &TileDimensions, stock_sizes: &[(u32, u32)], tolerance: u32) -> Self {
+        let words = stock_sizes.len().div_ceil(64);
+        let mut mask = Self {
+            fits: vec![0; words],
+            fits_rotated: vec![0; words],
+        };
+
+        // Панель с отступом от кромок занимает на листе больше места
+        let margin = 2 * tile.edge_clearance;
+        let (tile_width, tile_height) = (
+            (tile.width + margin).saturating_sub(tolerance),
+            (tile.height + margin).saturating_sub(tolerance),
+        );
+        for (index, (width, height)) in stock_sizes.iter().enumerate() {
+            let bit = 1u64 << (index % 64);
+            if tile_width <= *width && tile_height <= *height {
+                mask.fits[index / 64] |= bit;
+            }
+            if tile_height <= *width && tile_width <= *height {
+                mask.fits_rotated[index / 64] |= bit;
+            }
+        }
+        mask
+    }
+
+    /// Distinct stock sizes in the order the mask bits refer to
+    pub fn get_stock_sizes(stock_tiles: &[TileDimensions]) -> Vec<(u32, u32)> {
+        let mut sizes: Vec<(u32, u32)> = stock_tiles
+            .iter()
+            .map(|stock| (stock.width, stock.height))
+            .collect();
+        sizes.sort_unstable();
+        sizes.dedup();
+        sizes
+    }
+
+    pub fn fits_any(&self) -> bool {
+        self.fits
+            .iter()
+            .zip(&self.fits_rotated)
+            .any(|(fits, fits_rotated)| fits | fits_rotated != 0)
+    }
+
+    pub fn fits_stock(&self, index: usize, rotated: bool) -> bool {
+        let words = if rotated { &self.fits_rotated } else { &self.fits };
+        words
+            .get(index / 64)
+            .is_some_and(|word| word & (1u64 << (index % 64)) != 0)
+    }
+}
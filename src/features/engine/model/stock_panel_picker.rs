@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::features::input::models::tile_dimensions::TileDimensions;
 use crate::features::engine::model::{stock_solution::StockSolution, task::Task};
 
@@ -9,19 +11,55 @@ pub struct StockPanelPicker {
 
 impl StockPanelPicker {
     pub fn new(tiles: &[TileDimensions], stock_tiles: &[TileDimensions], task: &Task, single_stock: Option<i32>) -> Self {
-        let mut stock_solutions = Vec::new();
-        
-        // Create stock solutions from available stock
-        for stock_tile in stock_tiles {
-            stock_solutions.push(StockSolution::new(vec![stock_tile.clone()]));
-        }
-        
+        // Приоритетные и самые старые листы идут первыми, стратегия сохраняет этот порядок
+        let mut stock_tiles = stock_tiles.to_vec();
+        stock_tiles.sort_by_key(|stock| {
+            (std::cmp::Reverse(stock.priority), stock.acquired_date.is_none(), stock.acquired_date)
+        });
+
+        // Наборы листов задаёт стратегия выбора из конфигурации задачи
+        let stock_solutions = task
+            .calculation_request
+            .configuration
+            .get_stock_selection_strategy()
+            .get_stock_solutions(tiles, &stock_tiles)
+            .into_iter()
+            .filter(|stock_solution| Self::is_within_stock(stock_solution, &stock_tiles))
+            .collect();
+
         Self {
             stock_solutions,
             current_index: 0,
         }
     }
     
+    /// Whether `stock_solution` takes no more sheets of a stock row than the request has,
+    /// custom strategies may ask for more
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::stock_panel_picker::StockPanelPicker;
+    /// use rezalnyas_core::features::engine::model::stock_solution::StockSolution;
+    /// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+    ///
+    /// let sheet = TileDimensions::new(1, 1000, 1000, false, "", "MFC");
+    /// let stock = vec![sheet.clone(), sheet.clone()];
+    /// assert!(StockPanelPicker::is_within_stock(&StockSolution::new(stock.clone()), &stock));
+    /// assert!(!StockPanelPicker::is_within_stock(&StockSolution::new(vec![sheet; 3]), &stock));
+    /// ```
+    pub fn is_within_stock(stock_solution: &StockSolution, stock_tiles: &[TileDimensions]) -> bool {
+        let mut available: HashMap<u32, usize> = HashMap::new();
+        for stock in stock_tiles {
+            *available.entry(stock.id).or_default() += 1;
+        }
+        stock_solution.stock_tiles.iter().all(|stock| match available.get_mut(&stock.id) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        })
+    }
+
     pub fn init(&mut self) {
         // Initialize the picker - in Java this starts a separate thread
         // For simplicity, we'll keep it synchronous
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::stock_selection::StockSelection;
+use crate::features::engine::model::response_summary::SheetUsage;
+
+/// Bounds of `StockPlanner::enumerate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StockPlanLimits {
+    /// How the sheet sets are picked and ordered
+    #[serde(default)]
+    pub strategy: StockSelection,
+    /// Sheet sets returned, the first ones in the order of the strategy
+    pub max_solutions: usize,
+    /// Sets with more sheets are left out, None - not limited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_sheets: Option<usize>,
+}
+
+impl Default for StockPlanLimits {
+    fn default() -> Self {
+        Self {
+            strategy: StockSelection::Exhaustive,
+            max_solutions: 10,
+            max_sheets: None,
+        }
+    }
+}
+
+/// Set of stock sheets the panels could be cut from, judged by area only
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StockSolutionSummary {
+    pub nbr_sheets: usize,
+    /// Sheets per stock size, biggest first
+    pub sheets: Vec<SheetUsage>,
+    pub total_area: u64,
+    /// Area of all the panels
+    pub required_area: u64,
+    /// Sheet area per panel area, the part above 1.0 is the most waste the layout may have
+    pub area_ratio: f64,
+    /// The sheets cover the panel area and every panel fits on one of them.
+    /// The optimizer may still need more sheets for the cut losses
+    pub is_sufficient: bool,
+}
@@ -5,12 +5,30 @@ use crate::features::engine::model::calculation_request::CalculationRequest;
 use crate::features::engine::model::calculation_response::CalculationResponse;
 use crate::features::engine::model::calculation_response_builder::CalculationResponseBuilder;
 use crate::features::engine::model::client_info::ClientInfo;
+use crate::features::engine::model::configuration::Configuration;
+use crate::features::engine::model::cost_summary::StockPrice;
+use crate::features::engine::model::material_draw::MaterialDraw;
+use crate::features::engine::model::material_progress::MaterialProgress;
+use crate::features::engine::model::panel_id_mapping::PanelIdMapping;
+use crate::features::engine::model::permutation_log::PermutationLog;
+use crate::features::engine::model::pinned_sheet::PinnedSheet;
+use crate::features::engine::model::sequence_block::SequenceBlock;
 use crate::features::engine::model::solution::Solution;
-use crate::features::engine::model::{calculation_response::Mosaic, status::Status, stock_solution::StockSolution};
+use crate::features::engine::model::task_checkpoint::TaskCheckpoint;
+use crate::features::engine::model::task_limits::TaskLimits;
+use crate::features::engine::lower_bound::get_lower_bound;
+use crate::features::engine::model::task_status_response::{MaterialStatus, OptimalityGap, TaskErrorInfo, TaskStatusResponse};
+use crate::features::engine::model::{calculation_response::Mosaic, status::{Status, StatusTransition}, stock_solution::StockSolution};
+use crate::errors::{CoreError, Result, TaskError};
+use crate::features::input::models::normalization_report::NormalizationReport;
 use crate::features::input::models::tile_dimensions::TileDimensions;
-use std::collections::{HashMap, LinkedList};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::collections::{HashMap, HashSet, LinkedList};
 use std::sync::atomic::{AtomicI32, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use crate::utils::clock::{Clock, SystemClock};
 
 // Java: private static final AtomicInteger idAtomicInteger = new AtomicInteger(0);
 
@@ -19,6 +37,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 pub struct Task {
     pub id: String,
     pub status: Status,
+    pub status_transitions: Vec<StatusTransition>, // changes made with set_status, oldest first
     pub calculation_request: CalculationRequest,
     pub solution: CalculationResponse,
     pub solutions: HashMap<String, Vec<Solution>>,
@@ -26,6 +45,7 @@ pub struct Task {
     pub stock_dimensions_per_material: HashMap<String, Vec<TileDimensions>>, 
     pub tile_dimensions_per_material: HashMap<String, Vec<TileDimensions>>, 
     pub no_material_tiles: Vec<TileDimensions>,
+    pub unfittable_tiles: Vec<TileDimensions>, // bigger than every stock sheet of their material
 
     pub thread_group_rankings: HashMap<String, HashMap<String, i32>>, // material -> group -> ranking
     pub finished_threads: HashMap<String, i32>, // material -> count
@@ -33,6 +53,35 @@ pub struct Task {
     pub factor: u32,
     pub threads: Vec<CutListThread>, // List of threads for tracking finished ones (Java: List<CutListThread> threads)
     pub start_time: u64, // Start time for the task
+    pub panel_id_mappings: Vec<PanelIdMapping>, // internal tile id <-> request panel id
+    pub material_draws: Vec<MaterialDraw>, // stock drawn per requested material, including substitutes
+    pub permutation_logs: Vec<PermutationLog>, // one entry per processed permutation
+    pub pinned_sheets: Vec<PinnedSheet>, // sheets with panels locked by the request
+    pub sequence_blocks: Vec<SequenceBlock>, // sequence groups placed as one tile each
+    pub replicated_sheets: HashMap<String, Vec<Mosaic>>, // material -> sheets copied from a solved pattern
+    pub one_dimensional_materials: Vec<String>, // material keys cut by the one-dimensional optimizer
+    pub early_exit_materials: HashSet<String>, // materials whose search stopped at the acceptable waste
+    pub material_progress: Vec<MaterialProgress>, // permutations done per material key
+    pub sub_task_ids: HashMap<String, String>, // material key -> id of its sub-task
+    pub warnings: Vec<String>, // problems of the request that didn't stop the computation
+    pub worker_panics: Vec<String>, // cut list threads that panicked and were skipped
+    pub error: Option<TaskErrorInfo>, // failure that ended the task with the Error status
+    pub nbr_dropped_branches: u64, // partial solutions dropped to keep the pools in TaskLimits::max_pool_memory_bytes
+    pub normalization_report: NormalizationReport, // unit conversion and merged rows of the request
+    pub preview_solutions: HashMap<String, Solution>, // material -> first-fit-decreasing preview
+    #[serde(skip)]
+    pub improvement_deadline: Option<u64>, // end of the improvement budget while Improving, clock millis
+    pub limits: TaskLimits, // request limits capped by the service limits
+    #[serde(skip)]
+    pub time_limit_deadline: Option<u64>, // end of TaskLimits::max_time_ms, clock millis
+    #[serde(skip)]
+    pub last_progress_at: u64, // last processed stock solution, clock millis
+    #[serde(skip)]
+    pub is_stall_reported: bool, // the current stall was already logged
+    #[serde(skip)]
+    pub clock: Option<Arc<dyn Clock>>, // None - system clock
+    #[serde(skip)]
+    pub checkpoint: Option<TaskCheckpoint>, // last checkpoint saved, or the one being resumed
 }
 
 
@@ -48,6 +97,7 @@ impl Default for Task {
         Self {
             id: String::new(),
             status: Status::Running, 
+            status_transitions: Vec::new(),
             thread_group_rankings: HashMap::new(),
             finished_threads: HashMap::new(),
             has_solution_all_fit: false,
@@ -60,10 +110,31 @@ impl Default for Task {
             client_info: ClientInfo::default(),
             factor: 1,
             no_material_tiles: Vec::new(),
-            start_time:  SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_millis() as u64,
+            unfittable_tiles: Vec::new(),
+            start_time: SystemClock.now_millis(),
+            panel_id_mappings: Vec::new(),
+            material_draws: Vec::new(),
+            permutation_logs: Vec::new(),
+            pinned_sheets: Vec::new(),
+            sequence_blocks: Vec::new(),
+            replicated_sheets: HashMap::new(),
+            one_dimensional_materials: Vec::new(),
+            early_exit_materials: HashSet::new(),
+            material_progress: Vec::new(),
+            sub_task_ids: HashMap::new(),
+            warnings: Vec::new(),
+            worker_panics: Vec::new(),
+            error: None,
+            nbr_dropped_branches: 0,
+            normalization_report: NormalizationReport::default(),
+            preview_solutions: HashMap::new(),
+            improvement_deadline: None,
+            limits: TaskLimits::default(),
+            time_limit_deadline: None,
+            last_progress_at: 0,
+            is_stall_reported: false,
+            clock: None,
+            checkpoint: None,
         }
     }
 }
@@ -71,19 +142,57 @@ impl Default for Task {
 impl Task {
 
 
+/// Build the response once the computation is over and keep it in `solution`,
+/// status queries of the finished task clone it instead of building it again
 pub fn build_solution(&mut self)  {
 
 let builder = CalculationResponseBuilder{
     task: self.clone(),
     calculation_request: self.calculation_request.clone(),
     solutions: self.solutions.clone(),
-    no_stock_material_panels: self.no_material_tiles.clone(),
+    no_stock_material_panels: self.no_material_tiles.iter().chain(&self.unfittable_tiles).cloned().collect(),
 };
 
-
+self.solution = builder.build();
 
 }
 
+    /// Register a request panel row and return the internal id assigned to its tiles
+    pub fn register_panel_id(&mut self, request_id: u32, label: &str, is_stock: bool) -> u32 {
+        let internal_id = self.panel_id_mappings.len() as u32;
+        self.panel_id_mappings
+            .push(PanelIdMapping::new(internal_id, request_id, label, is_stock));
+        internal_id
+    }
+
+    pub fn get_panel_id_mapping(&self, internal_id: u32) -> Option<&PanelIdMapping> {
+        self.panel_id_mappings.get(internal_id as usize)
+    }
+
+    /// Resolve the request panel id of an internal tile id
+    pub fn get_request_id(&self, internal_id: u32) -> Option<u32> {
+        self.get_panel_id_mapping(internal_id)
+            .map(|mapping| mapping.request_id)
+    }
+
+    /// Internal stock tile id -> price of its stock row, only the rows with their own `Panel::price`
+    pub fn get_stock_prices(&self, configuration: &Configuration) -> HashMap<u32, StockPrice> {
+        let stock_panels = &self.calculation_request.stock_panels;
+        if stock_panels.iter().all(|stock| stock.price.is_none()) {
+            return HashMap::new();
+        }
+        self.panel_id_mappings
+            .iter()
+            .filter(|mapping| mapping.is_stock)
+            .filter_map(|mapping| {
+                let stock = stock_panels
+                    .iter()
+                    .find(|stock| stock.id == mapping.request_id && stock.price.is_some())?;
+                Some((mapping.internal_id, configuration.get_stock_price(Some(stock))?))
+            })
+            .collect()
+    }
+
     /// Java: public void addMaterialToCompute(String str)
     pub fn add_material_to_compute(&mut self, material: &str) {
         self.solutions.insert(material.to_string(), Vec::new());
@@ -120,13 +229,117 @@ let builder = CalculationResponseBuilder{
     //     self.threads.push(thread_info);
     // }
     
+    /// Use `clock` for the start time and deadlines of the task, the start time is reset to its now
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.start_time = clock.now_millis();
+        self.clock = Some(clock);
+    }
+
+    /// Milliseconds since UNIX epoch by the clock of the task
+    pub fn now_millis(&self) -> u64 {
+        match &self.clock {
+            Some(clock) => clock.now_millis(),
+            None => SystemClock.now_millis(),
+        }
+    }
+
+    /// Move the task to `status` and record when, `TaskInvalidStatusTransition` if the
+    /// current status can't lead there, see `Status::can_transition_to`
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::{status::Status, task::Task};
+    ///
+    /// let mut task = Task { status: Status::Queued, ..Task::default() };
+    /// task.set_status(Status::Running).unwrap();
+    /// task.set_status(Status::Finishing).unwrap();
+    /// assert!(task.set_status(Status::Stopped).is_err());
+    /// task.set_status(Status::Finished).unwrap();
+    ///
+    /// assert_eq!(task.status, Status::Finished);
+    /// assert_eq!(task.status_transitions.len(), 3);
+    /// assert_eq!(task.status_transitions[0].from, Status::Queued);
+    /// ```
+    pub fn set_status(&mut self, status: Status) -> Result<()> {
+        if !self.status.can_transition_to(status) {
+            return Err(TaskError::TaskInvalidStatusTransition {
+                from: self.status.as_str().to_string(),
+                to: status.as_str().to_string(),
+            }
+            .into());
+        }
+        self.status_transitions.push(StatusTransition {
+            from: self.status,
+            to: status,
+            at_ms: self.now_millis(),
+        });
+        self.status = status;
+        Ok(())
+    }
+
+    /// Milliseconds the task spent in `status` according to its transitions, up to now
+    /// for the current status
+    pub fn get_status_duration_ms(&self, status: Status) -> u64 {
+        let mut duration_ms = 0;
+        let mut entered_at = None;
+        for transition in &self.status_transitions {
+            if let Some(entered_at) = entered_at.take() {
+                duration_ms += transition.at_ms.saturating_sub(entered_at);
+            }
+            if transition.to == status {
+                entered_at = Some(transition.at_ms);
+            }
+        }
+        if let Some(entered_at) = entered_at {
+            duration_ms += self.now_millis().saturating_sub(entered_at);
+        }
+        duration_ms
+    }
+
+    /// Improving tasks count as running until their improvement budget is spent
     pub fn is_running(&self) -> bool {
-        matches!(self.status, Status::Running)
+        if self.is_time_limit_reached() {
+            return false;
+        }
+        match self.status {
+            Status::Running => true,
+            Status::Improving => !self.is_improvement_over(),
+            _ => false,
+        }
+    }
+
+    /// True when the task isn't improving or its improvement budget is spent
+    pub fn is_improvement_over(&self) -> bool {
+        self.status != Status::Improving
+            || self
+                .improvement_deadline
+                .is_none_or(|deadline| self.now_millis() >= deadline)
     }
     
+    /// True once the task used up `TaskLimits::max_time_ms`
+    pub fn is_time_limit_reached(&self) -> bool {
+        self.time_limit_deadline
+            .is_some_and(|deadline| self.now_millis() >= deadline)
+    }
+
+    /// Record that the computation moved on, a stall is measured from the last call
+    pub fn mark_progress(&mut self) {
+        self.last_progress_at = self.now_millis();
+        self.is_stall_reported = false;
+    }
+
+    /// Milliseconds since the last `mark_progress`
+    pub fn get_idle_ms(&self) -> u64 {
+        self.now_millis().saturating_sub(self.last_progress_at)
+    }
+
     pub fn has_solution_all_fit(&self) -> bool {
         self.has_solution_all_fit
     }
+
+    /// True once a layout of `material` met `Configuration::acceptable_waste_percent`
+    pub fn is_early_exit(&self, material: &str) -> bool {
+        self.early_exit_materials.contains(material)
+    }
     
     pub fn get_solutions(&self, material: &str) -> Vec<Solution> {
         self.solutions.get(material).cloned().unwrap_or_default()
@@ -135,11 +348,323 @@ let builder = CalculationResponseBuilder{
     pub fn get_thread_group_rankings(&self, material: &str) -> HashMap<String, i32> {
         self.thread_group_rankings.get(material).cloned().unwrap_or_default()
     }
+
+    /// `Configuration::dimension_tolerance` in the scaled units of the tiles, 0 if not set
+    pub fn get_dimension_tolerance(&self) -> u32 {
+        self.calculation_request
+            .configuration
+            .dimension_tolerance
+            .as_deref()
+            .and_then(|tolerance| tolerance.parse::<f64>().ok())
+            .map_or(0, |tolerance| (tolerance * self.factor as f64).round().max(0.0) as u32)
+    }
+
+    /// `Configuration::lead_in_allowance` in the scaled units of the tiles, 0 if not set
+    pub fn get_lead_in_allowance(&self) -> u32 {
+        self.calculation_request
+            .configuration
+            .lead_in_allowance
+            .as_deref()
+            .and_then(|allowance| allowance.parse::<f64>().ok())
+            .map_or(0, |allowance| (allowance * self.factor as f64).round().max(0.0) as u32)
+    }
+
+    /// Best solutions of the materials so far against `get_lower_bound` of their panels. None
+    /// while a material has no layout of all its panels, and for materials with substitutes,
+    /// whose panels may go to the stock of other materials
+    pub fn get_optimality_gap(&self) -> Option<OptimalityGap> {
+        let configuration = &self.calculation_request.configuration;
+        let cut_thickness = CutListThread::new_with_config(configuration, 0).cut_thickness.max(0) as u32;
+        let (mut nbr_sheets, mut min_nbr_sheets, mut stock_area, mut min_stock_area) = (0, 0, 0, 0);
+        for (material, tiles) in &self.tile_dimensions_per_material {
+            let Some(stock_tiles) = self.stock_dimensions_per_material.get(material) else {
+                continue;
+            };
+            if configuration.material_substitutes.get(material).is_some_and(|substitutes| !substitutes.is_empty()) {
+                return None;
+            }
+            // Детали больше любого листа не входят ни в одну раскладку
+            let tiles: Vec<TileDimensions> =
+                tiles.iter().filter(|tile| stock_tiles.iter().any(|stock| stock.fits(tile))).cloned().collect();
+            if tiles.is_empty() {
+                continue;
+            }
+
+            let best = self.solutions.get(material)?.first()?;
+            if !best.no_fit_panels.is_empty() {
+                return None;
+            }
+            let bound = get_lower_bound(&tiles, stock_tiles, cut_thickness);
+            nbr_sheets += best.mosaics.len();
+            stock_area += best
+                .mosaics
+                .iter()
+                .filter_map(|mosaic| mosaic.root_tile_node.first())
+                .map(|root| root.get_width() as u64 * root.get_height() as u64)
+                .sum::<u64>();
+            min_nbr_sheets += bound.nbr_sheets;
+            min_stock_area += bound.stock_area;
+        }
+        if nbr_sheets == 0 {
+            return None;
+        }
+
+        let area_factor = self.factor as f64 * self.factor as f64;
+        Some(OptimalityGap::new(
+            nbr_sheets,
+            min_nbr_sheets,
+            stock_area as f64 / area_factor,
+            min_stock_area as f64 / area_factor,
+        ))
+    }
+
+    /// Number of materials whose best solution was created by each thread group
+    pub fn get_best_solution_groups(&self) -> HashMap<String, i64> {
+        let mut groups: HashMap<String, i64> = HashMap::new();
+        for best in self.solutions.values().filter_map(|solutions| solutions.first()) {
+            if let Some(group) = &best.creator_thread_group {
+                *groups.entry(group.clone()).or_insert(0) += 1;
+            }
+        }
+        groups
+    }
     
-    // pub fn get_finished_threads(&self, material: &str) -> i32 {
-    //     self.get_nbr_finished_threads(material)
-    // }
+    /// Java: public synchronized int getNbrFinishedThreads(String str)
+    pub fn get_finished_threads(&self, material: &str) -> i32 {
+        self.finished_threads.get(material).copied().unwrap_or(0)
+    }
+
+    pub fn add_finished_thread(&mut self, material: &str) {
+        *self.finished_threads.entry(material.to_string()).or_insert(0) += 1;
+    }
     
+    pub fn add_permutation_log(&mut self, log: PermutationLog) {
+        self.permutation_logs.push(log);
+    }
+
+    /// Id of the sub-task computing `material`, created on first use as `<task id>.<n>`.
+    /// The repeated-panel patterns of a material (`material#patternN`) share its sub-task
+    pub fn register_sub_task(&mut self, material: &str) -> String {
+        let nbr_sub_tasks = self.sub_task_ids.len();
+        self.sub_task_ids
+            .entry(Self::get_sub_task_key(material).to_string())
+            .or_insert_with(|| format!("{}.{}", self.id, nbr_sub_tasks + 1))
+            .clone()
+    }
+
+    pub fn get_sub_task_id(&self, material: &str) -> Option<&str> {
+        self.sub_task_ids
+            .get(Self::get_sub_task_key(material))
+            .map(String::as_str)
+    }
+
+    fn get_sub_task_key(material: &str) -> &str {
+        material.split('#').next().unwrap_or(material)
+    }
+
+    pub fn start_material_progress(&mut self, material: &str, nbr_permutations: usize) {
+        let mut progress = MaterialProgress::new(material, nbr_permutations);
+        progress.task_id = self.get_sub_task_id(material).unwrap_or_default().to_string();
+        self.material_progress
+            .retain(|progress| progress.material != material);
+        self.material_progress.push(progress);
+    }
+
+    /// Seed `material` with the solutions of the checkpoint being resumed and return the
+    /// permutations it already processed; 0 when the checkpoint has other permutations
+    pub fn resume_material(&mut self, material: &str, nbr_permutations: usize) -> usize {
+        let Some(saved) = self
+            .checkpoint
+            .as_ref()
+            .and_then(|checkpoint| checkpoint.get_material(material))
+            .cloned()
+        else {
+            return 0;
+        };
+        self.solutions.insert(material.to_string(), saved.solutions);
+        self.thread_group_rankings
+            .insert(material.to_string(), saved.thread_group_rankings);
+        self.finished_threads
+            .insert(material.to_string(), saved.nbr_finished_threads);
+        if saved.nbr_permutations != nbr_permutations {
+            return 0;
+        }
+        if let Some(progress) = self.get_material_progress_mut(material) {
+            progress.nbr_permutations_done = saved.nbr_permutations_done;
+        }
+        saved.nbr_permutations_done
+    }
+
+    pub fn get_material_progress_mut(&mut self, material: &str) -> Option<&mut MaterialProgress> {
+        self.material_progress
+            .iter_mut()
+            .find(|progress| progress.material == material)
+    }
+
+    /// Overall and per-material progress, weighted by the number of permutations
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::task::Task;
+    ///
+    /// let mut task = Task::default();
+    /// task.start_material_progress("MDF", 4);
+    /// task.get_material_progress_mut("MDF").unwrap().advance();
+    ///
+    /// let status = task.get_status_response();
+    /// assert_eq!(status.percentage_done, 25);
+    /// assert_eq!(status.materials[0].material, "MDF");
+    /// assert!(status.eta_ms.is_some());
+    /// ```
+    pub fn get_status_response(&self) -> TaskStatusResponse {
+        let nbr_permutations: usize = self
+            .material_progress
+            .iter()
+            .map(|progress| progress.nbr_permutations)
+            .sum();
+        // Остановленные подзадачи больше не считаются, их остаток засчитывается как сделанный
+        let nbr_permutations_done: usize = self
+            .material_progress
+            .iter()
+            .map(|progress| {
+                if progress.is_stopped {
+                    progress.nbr_permutations
+                } else {
+                    progress.nbr_permutations_done
+                }
+            })
+            .sum();
+
+        let mut response = TaskStatusResponse::new();
+        response.task_id = Some(self.id.clone());
+        response.locale = self.calculation_request.configuration.locale;
+        response.set_status(self.status);
+        response.status_transitions = self.status_transitions.clone();
+        response.percentage_done = match (nbr_permutations_done * 100).checked_div(nbr_permutations) {
+            Some(percentage_done) => percentage_done as i32,
+            None if self.is_running() => 0,
+            None => 100,
+        };
+        response.materials = self
+            .material_progress
+            .iter()
+            .map(|progress| MaterialStatus {
+                material: progress.material.clone(),
+                task_id: progress.task_id.clone(),
+                percentage_done: progress.get_percentage_done(),
+                eta_ms: progress.get_eta_ms(),
+            })
+            .collect();
+        response.eta_ms = self
+            .material_progress
+            .iter()
+            .map(|progress| progress.get_eta_ms())
+            .sum();
+        response.error = self.error.clone();
+        response.optimality_gap = self.get_optimality_gap();
+        // Пока идёт полный поиск, клиент видит ответ по предварительному раскрою
+        if matches!(self.status, Status::Finished | Status::Stopped)
+            || (self.is_running() && !self.preview_solutions.is_empty())
+        {
+            response.solution = Some(self.solution.clone());
+        }
+        response.sub_task_ids = self.sub_task_ids.values().cloned().collect();
+        // Порядок создания: "42.2" раньше "42.10"
+        response.sub_task_ids.sort_by(|a, b| (a.len(), a).cmp(&(b.len(), b)));
+        response
+    }
+
+    /// Status of one material sub-task of the task, None if `sub_task_id` isn't one of them
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::status::Status;
+    /// use rezalnyas_core::features::engine::model::task::Task;
+    ///
+    /// let mut task = Task { id: "42".to_string(), ..Task::default() };
+    /// let glass = task.register_sub_task("Glass 4mm");
+    /// let mdf = task.register_sub_task("MDF");
+    /// task.start_material_progress("Glass 4mm", 2);
+    /// task.start_material_progress("MDF", 2);
+    /// task.get_material_progress_mut("Glass 4mm").unwrap().stop();
+    ///
+    /// let status = task.get_sub_task_status_response(&glass).unwrap();
+    /// assert_eq!(status.parent_task_id.as_deref(), Some("42"));
+    /// assert_eq!(status.material.as_deref(), Some("Glass 4mm"));
+    /// assert_eq!(status.status, Some(Status::Stopped));
+    /// assert_eq!(task.get_sub_task_status_response(&mdf).unwrap().status, Some(Status::Running));
+    /// assert_eq!(task.get_status_response().sub_task_ids, vec![glass, mdf]);
+    /// ```
+    pub fn get_sub_task_status_response(&self, sub_task_id: &str) -> Option<TaskStatusResponse> {
+        let (material, _) = self.sub_task_ids.iter().find(|(_, id)| *id == sub_task_id)?;
+        let progress = self
+            .material_progress
+            .iter()
+            .find(|progress| &progress.material == material);
+
+        let mut response = TaskStatusResponse::new();
+        response.task_id = Some(sub_task_id.to_string());
+        response.parent_task_id = Some(self.id.clone());
+        response.material = Some(material.clone());
+        response.locale = self.calculation_request.configuration.locale;
+        // Пока задача улучшает решение, готовые материалы тоже улучшаются
+        response.set_status(match progress {
+            Some(progress) if progress.is_stopped => Status::Stopped,
+            Some(progress) if progress.is_done() && self.status != Status::Improving => Status::Finished,
+            _ => self.status,
+        });
+        response.percentage_done = progress.map_or(0, |progress| progress.get_percentage_done());
+        response.eta_ms = progress.and_then(|progress| progress.get_eta_ms());
+        response.error = self.error.clone().filter(|error| error.material.as_ref() == Some(material));
+        Some(response)
+    }
+
+    /// Write a zip with the request, configuration, permutation logs and best solutions
+    /// so a bad layout can be reproduced from a single file
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::task::Task;
+    ///
+    /// let path = std::env::temp_dir().join("rezalnyas_debug_bundle.zip");
+    /// Task::default().dump_debug_bundle(&path).unwrap();
+    /// assert!(std::fs::metadata(&path).unwrap().len() > 0);
+    /// # std::fs::remove_file(&path).ok();
+    /// ```
+    pub fn dump_debug_bundle<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let best_solutions: HashMap<&String, &Solution> = self
+            .solutions
+            .iter()
+            .filter_map(|(material, solutions)| solutions.first().map(|best| (material, best)))
+            .collect();
+
+        let entries = [
+            ("request.json", serde_json::to_vec_pretty(&self.calculation_request)?),
+            (
+                "configuration.json",
+                serde_json::to_vec_pretty(&self.calculation_request.configuration)?,
+            ),
+            ("permutations.json", serde_json::to_vec_pretty(&self.permutation_logs)?),
+            ("best_solutions.json", serde_json::to_vec_pretty(&best_solutions)?),
+            ("best_solution_groups.json", serde_json::to_vec_pretty(&self.get_best_solution_groups())?),
+            ("response.json", serde_json::to_vec_pretty(&self.solution)?),
+        ];
+
+        let mut zip = zip::ZipWriter::new(File::create(path)?);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        for (name, content) in entries {
+            zip.start_file(name, options).map_err(Self::zip_error)?;
+            zip.write_all(&content)?;
+        }
+        zip.finish().map_err(Self::zip_error)?;
+        Ok(())
+    }
+
+    fn zip_error(err: zip::result::ZipError) -> CoreError {
+        CoreError::Internal {
+            message: format!("Failed to write debug bundle: {}", err),
+        }
+    }
+
     /// Java: task.getSolutions(material) returns existing solutions
     pub fn add_solutions(&mut self, material: &str, solutions: Vec<Solution>) {
         self.solutions.insert(material.to_string(), solutions);
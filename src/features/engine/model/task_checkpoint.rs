@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::EngineConstants;
+use crate::features::engine::model::{calculation_request::CalculationRequest, solution::Solution, task::Task};
+
+/// Search state of one material key when its task was checkpointed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialCheckpoint {
+    pub material: String,
+    pub nbr_permutations: usize,
+    /// Permutations processed, the resumed search continues with the next one
+    pub nbr_permutations_done: usize,
+    /// Best solutions found so far, best first, up to `EngineConstants::MAX_CHECKPOINT_SOLUTIONS`
+    pub solutions: Vec<Solution>,
+    #[serde(default)]
+    pub thread_group_rankings: HashMap<String, i32>,
+    #[serde(default)]
+    pub nbr_finished_threads: i32,
+}
+
+/// Snapshot of a running task saved to a `TaskRepository`, so the task can be resumed
+/// after a crash or a stop, see `CutListOptimizerServiceImpl::resume_task`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskCheckpoint {
+    pub task_id: String,
+    /// Request as submitted, computed again on resume
+    pub calculation_request: CalculationRequest,
+    /// Materials in the order they were computed, the last one is usually unfinished
+    pub materials: Vec<MaterialCheckpoint>,
+    #[serde(default)]
+    pub has_solution_all_fit: bool,
+    /// Milliseconds since UNIX epoch
+    pub saved_at_ms: u64,
+}
+
+impl TaskCheckpoint {
+    pub fn new(task_id: &str, calculation_request: &CalculationRequest, saved_at_ms: u64) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            calculation_request: calculation_request.clone(),
+            materials: Vec::new(),
+            has_solution_all_fit: false,
+            saved_at_ms,
+        }
+    }
+
+    /// Take the progress and best solutions of every material `task` has started
+    pub fn capture(&mut self, task: &Task) {
+        self.materials = task
+            .material_progress
+            .iter()
+            .map(|progress| MaterialCheckpoint {
+                material: progress.material.clone(),
+                nbr_permutations: progress.nbr_permutations,
+                nbr_permutations_done: progress.nbr_permutations_done,
+                solutions: task
+                    .solutions
+                    .get(&progress.material)
+                    .map(|solutions| solutions.iter().take(EngineConstants::MAX_CHECKPOINT_SOLUTIONS).cloned().collect())
+                    .unwrap_or_default(),
+                thread_group_rankings: task.get_thread_group_rankings(&progress.material),
+                nbr_finished_threads: task.get_finished_threads(&progress.material),
+            })
+            .collect();
+        self.has_solution_all_fit = task.has_solution_all_fit();
+        self.saved_at_ms = task.now_millis();
+    }
+
+    pub fn get_material(&self, material: &str) -> Option<&MaterialCheckpoint> {
+        self.materials.iter().find(|saved| saved.material == material)
+    }
+}
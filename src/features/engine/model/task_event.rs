@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of a task lifecycle event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TaskEventKind {
+    /// Task was accepted by the service
+    Submitted,
+    /// Computation of the task has started
+    Started,
+    /// A permutation of a material was processed, `eta_ms` is the rough time left for it
+    Progress {
+        material: String,
+        percentage_done: i32,
+        eta_ms: Option<u64>,
+    },
+    /// All permutations of a material were processed
+    MaterialCompleted { material: String },
+    /// The first-fit-decreasing preview of a material is ready, the full search goes on
+    PreviewReady { material: String, solution_id: i32 },
+    /// A better solution became the best one for a material
+    NewBestSolution { material: String, solution_id: i32 },
+    /// The first full solution is done, the task keeps improving it for `budget_ms`;
+    /// upgrades arrive as `NewBestSolution`
+    Improving { budget_ms: u64 },
+    /// Task finished successfully
+    Finished,
+    /// Task failed
+    Error { message: String },
+}
+
+/// Task lifecycle event delivered to subscribers of the optimizer service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskEvent {
+    pub task_id: String,
+    /// Milliseconds since UNIX epoch by the clock of the service, see `CutListOptimizerServiceImpl::with_clock`
+    pub timestamp: u64,
+    pub kind: TaskEventKind,
+}
+
+impl TaskEvent {
+    pub fn new(task_id: &str, kind: TaskEventKind, timestamp: u64) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            timestamp,
+            kind,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// Resources one task may use, None - not limited
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskLimits {
+    /// Threads evaluating stock solutions of the task at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_threads: Option<u32>,
+    /// Computation time, the task returns the best layout found so far once it runs out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_time_ms: Option<u64>,
+    /// Memory the solution pool of one cut list thread may take, branches that can't reach
+    /// the best yield are dropped first when the pool nears it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pool_memory_bytes: Option<u64>,
+}
+
+impl TaskLimits {
+    /// Limits of a request capped by the limits of the service
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::task_limits::TaskLimits;
+    ///
+    /// let service = TaskLimits { max_threads: Some(8), max_time_ms: Some(120_000), max_pool_memory_bytes: None };
+    /// let request = TaskLimits { max_threads: Some(16), max_time_ms: None, max_pool_memory_bytes: Some(1 << 20) };
+    ///
+    /// let limits = request.bounded_by(&service);
+    /// assert_eq!(limits.max_threads, Some(8));
+    /// assert_eq!(limits.max_time_ms, Some(120_000));
+    /// assert_eq!(limits.max_pool_memory_bytes, Some(1 << 20));
+    /// ```
+    pub fn bounded_by(&self, maxima: &TaskLimits) -> TaskLimits {
+        fn min<T: Ord>(value: Option<T>, maximum: Option<T>) -> Option<T> {
+            match (value, maximum) {
+                (Some(value), Some(maximum)) => Some(value.min(maximum)),
+                (value, maximum) => value.or(maximum),
+            }
+        }
+        TaskLimits {
+            max_threads: min(self.max_threads, maxima.max_threads),
+            max_time_ms: min(self.max_time_ms, maxima.max_time_ms),
+            max_pool_memory_bytes: min(self.max_pool_memory_bytes, maxima.max_pool_memory_bytes),
+        }
+    }
+}
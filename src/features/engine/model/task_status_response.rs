@@ -1,28 +1,193 @@
 use serde::{Deserialize, Serialize};
 
-use crate::features::engine::model::calculation_response::CalculationResponse;
+use crate::errors::AppError;
+use crate::i18n::locale::Locale;
+use crate::features::engine::model::{
+    calculation_response::CalculationResponse,
+    status::{Status, StatusTransition},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskStatusResponse {
-    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    /// Task the material sub-task belongs to, None for top-level tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_task_id: Option<String>,
+    /// Material key computed by the sub-task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+    /// Serialized as the Java status name ("RUNNING", "FINISHED", ...)
+    pub status: Option<Status>,
+    /// `status` in words, for people rather than programs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_description: Option<String>,
+    /// Language of `status_description`, the locale of the request
+    #[serde(default, skip_serializing_if = "Locale::is_default")]
+    pub locale: Locale,
+    /// Status changes of the task with their times, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_transitions: Vec<StatusTransition>,
     pub percentage_done: i32,
     pub init_percentage: i32,
     pub solution: Option<CalculationResponse>,
+    /// Progress of each material computed so far, in computation order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub materials: Vec<MaterialStatus>,
+    /// Rough time left in milliseconds, None until it can be estimated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eta_ms: Option<u64>,
+    /// Ids of the per-material sub-tasks, each can be queried and stopped on its own
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sub_task_ids: Vec<String>,
+    /// Why the task ended with the Error status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<TaskErrorInfo>,
+    /// How far the best layout so far can be from the optimum, None until every material
+    /// has a layout of all its panels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimality_gap: Option<OptimalityGap>,
 }
 
 impl TaskStatusResponse {
     pub fn new() -> Self {
         Self {
+            task_id: None,
+            parent_task_id: None,
+            material: None,
             status: None,
+            status_description: None,
+            locale: Locale::default(),
+            status_transitions: Vec::new(),
             percentage_done: 0,
             init_percentage: 0,
             solution: None,
+            materials: Vec::new(),
+            eta_ms: None,
+            sub_task_ids: Vec::new(),
+            error: None,
+            optimality_gap: None,
         }
     }
 }
 
+impl TaskStatusResponse {
+    /// Set `status` along with its description in the locale of the response
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::status::Status;
+    /// use rezalnyas_core::features::engine::model::task_status_response::TaskStatusResponse;
+    /// use rezalnyas_core::i18n::locale::Locale;
+    ///
+    /// let mut response = TaskStatusResponse { locale: Locale::Ru, ..TaskStatusResponse::default() };
+    /// response.set_status(Status::Queued);
+    /// assert_eq!(response.status, Some(Status::Queued));
+    /// assert_eq!(response.status_description.as_deref(), Some("В очереди"));
+    /// ```
+    pub fn set_status(&mut self, status: Status) {
+        self.status = Some(status);
+        self.status_description = Some(self.locale.describe_status(status).to_string());
+    }
+}
+
 impl Default for TaskStatusResponse {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaterialStatus {
+    pub material: String,
+    #[serde(default)]
+    pub task_id: String,
+    pub percentage_done: i32,
+    pub eta_ms: Option<u64>,
+}
+
+/// Failure of a task, see `TaskStatusResponse::error`
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+/// use rezalnyas_core::features::engine::model::calculation_request::PinnedPlacement;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::status::Status;
+///
+/// let service = CutListOptimizerServiceImpl::new(1, false).unwrap();
+/// let events = service.subscribe();
+/// let mut request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0).id(1)
+///     .add_stock(1000.0, 600.0).id(10)
+///     .build()
+///     .unwrap();
+/// // Деталь не помещается на лист в закреплённой позиции
+/// request.pinned_placements = vec![PinnedPlacement::new(1, 10, 0, "800", "0", false)];
+/// assert!(service.submit_task(request).is_err());
+///
+/// let task_id = events.try_recv().unwrap().task_id;
+/// let status = service.get_task_status(&task_id).unwrap();
+/// assert_eq!(status.status, Some(Status::Error));
+/// let error = status.error.unwrap();
+/// assert_eq!(error.code, "computation");
+/// assert_eq!(error.material, None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskErrorInfo {
+    /// Category of the error, see `AppError::code`
+    pub code: String,
+    pub message: String,
+    /// Material key that was being computed, None when the task failed outside a material
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+}
+
+impl TaskErrorInfo {
+    pub fn new(error: &AppError, material: Option<&str>) -> Self {
+        Self {
+            code: error.code().to_string(),
+            message: error.to_string(),
+            material: material.map(str::to_string),
+        }
+    }
+}
+
+/// Best layout so far against the least stock any layout of the panels needs, see
+/// `TaskStatusResponse::optimality_gap` and `get_lower_bound`
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::task_status_response::OptimalityGap;
+///
+/// let gap = OptimalityGap::new(3, 2, 1_800_000.0, 1_200_000.0);
+/// assert!((gap.gap_percent - 33.33).abs() < 0.01);
+/// assert_eq!(OptimalityGap::new(2, 2, 1_200_000.0, 1_200_000.0).gap_percent, 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptimalityGap {
+    /// Sheets of the best layout so far
+    pub nbr_sheets: usize,
+    /// No layout needs fewer sheets
+    pub min_nbr_sheets: usize,
+    /// Stock area of the best layout so far, in the units of the request
+    pub stock_area: f64,
+    /// No layout needs less stock area
+    pub min_stock_area: f64,
+    /// Share of `stock_area` a better layout could save at most, in percent, 0 - the layout is optimal
+    pub gap_percent: f64,
+}
+
+impl OptimalityGap {
+    pub fn new(nbr_sheets: usize, min_nbr_sheets: usize, stock_area: f64, min_stock_area: f64) -> Self {
+        let gap_percent = if stock_area > 0.0 {
+            ((stock_area - min_stock_area) / stock_area * 100.0).max(0.0)
+        } else {
+            0.0
+        };
+        Self {
+            nbr_sheets,
+            min_nbr_sheets,
+            stock_area,
+            min_stock_area,
+            gap_percent,
+        }
+    }
+}
@@ -1,12 +1,42 @@
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::collections::HashSet;
+use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::errors::{ComputationError, Result};
+use crate::features::engine::model::calculation_response::Cut;
 
 static NODE_ID_COUNTER: AtomicU32 = AtomicU32::new(1);
 
+/// Pieces of a guillotine cut of a node, made by `TileNode::split_h` and `TileNode::split_v`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TileSplit {
+    pub child1: TileNode,
+    /// None when the kerf takes all that is left after child1
+    pub child2: Option<TileNode>,
+    /// None without child2, nothing is cut off then
+    pub cut: Option<Cut>,
+}
+
+/// Node of the guillotine tree of a sheet
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::tile_node::TileNode;
+///
+/// // Загруженное дерево не сдвигает счётчик id
+/// let mut sheet = TileNode::new(0, 1000, 0, 600);
+/// sheet.id = 4_000_000_000;
+/// let loaded: TileNode = serde_json::from_str(&serde_json::to_string(&sheet).unwrap()).unwrap();
+/// assert_eq!(loaded.id, 4_000_000_000);
+/// assert!(TileNode::new(0, 10, 0, 10).id < 4_000_000_000);
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 // -= доработать
 pub struct TileNode {
+    /// Unique among the nodes created in the process, copies of a node keep it. Loaded trees
+    /// (checkpoints) keep their ids as they are and are only ranked, never split further
     pub id: u32,
     pub x1: i32,
     pub y1: i32,
@@ -17,6 +47,12 @@ pub struct TileNode {
     pub is_rotated: bool,
     pub child1: Option<Box<TileNode>>,
     pub child2: Option<Box<TileNode>>,
+    /// Guillotine stage of the cut that produced this node, 0 for the sheet itself
+    #[serde(default)]
+    pub cut_stage: u32,
+    /// Orientation of the cut that produced this node
+    #[serde(default)]
+    pub cut_horizontal: Option<bool>,
 }
 
 impl TileNode {
@@ -32,6 +68,8 @@ impl TileNode {
             is_rotated: false,
             child1: None,
             child2: None,
+            cut_stage: 0,
+            cut_horizontal: None,
         }
     }
     
@@ -50,6 +88,8 @@ impl TileNode {
             // Java: this.child1 = tileNode.getChild1(); (SHALLOW copy - direct reference!)
             child1: other.child1.clone(), // Clone the Box (shallow copy of structure)
             child2: other.child2.clone(), // Clone the Box (shallow copy of structure)
+            cut_stage: other.cut_stage,
+            cut_horizontal: other.cut_horizontal,
         }
     }
 
@@ -67,26 +107,7 @@ impl TileNode {
     
     /// Calculate used area - matches Java TileNode.getUsedArea()
     pub fn get_used_area(&self) -> i64 {
-        // Java: if (this.isFinal) { return getArea(); }
-        if self.is_final {
-            return self.get_area() as i64;
-        }
-        
-        // Java: TileNode tileNode = this.child1; long usedArea = tileNode != null ? tileNode.getUsedArea() : 0L;
-        let used_area = if let Some(ref child1) = self.child1 {
-            child1.get_used_area()
-        } else {
-            0
-        };
-        
-        // Java: TileNode tileNode2 = this.child2; if (tileNode2 != null) { usedArea += tileNode2.getUsedArea(); }
-        let used_area = if let Some(ref child2) = self.child2 {
-            used_area + child2.get_used_area()
-        } else {
-            used_area
-        };
-        
-        used_area
+        self.iter_final().map(|node| node.get_area() as i64).sum()
     }
     
     /// Calculate unused area - matches Java TileNode.getUnusedArea()
@@ -113,12 +134,114 @@ impl TileNode {
         self.is_rotated = is_rotated;
     }
 
-    pub fn set_child1(&mut self, child: Option<Box<TileNode>>) {
-        self.child1 = child;
+    pub fn set_cut_stage(&mut self, stage: u32, is_horizontal: bool) {
+        self.cut_stage = stage;
+        self.cut_horizontal = Some(is_horizontal);
+    }
+
+    /// Stage of a new cut of this node: parallel cuts stay in the stage
+    /// of the cut that produced the node, turning the piece starts the next one
+    pub fn get_next_cut_stage(&self, is_horizontal: bool) -> u32 {
+        match self.cut_horizontal {
+            None => 1,
+            Some(horizontal) if horizontal == is_horizontal => self.cut_stage,
+            Some(_) => self.cut_stage + 1,
+        }
     }
 
-    pub fn set_child2(&mut self, child: Option<Box<TileNode>>) {
-        self.child2 = child;
+    /// Cut across the width `at` from the left edge: child1 is the left part and child2
+    /// starts after the `kerf`. Err unless `at` lies within the width
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::tile_node::TileNode;
+    ///
+    /// let mut sheet = TileNode::new(0, 1000, 0, 600);
+    /// let split = sheet.split_h(400, 4).unwrap();
+    /// assert_eq!((split.child1.x2, split.child2.as_ref().unwrap().x1), (400, 404));
+    /// assert_eq!(split.cut.as_ref().unwrap().position, 400.0);
+    ///
+    /// sheet.set_split(split).unwrap();
+    /// let again = sheet.split_v(100, 4).unwrap();
+    /// assert!(sheet.set_split(again).is_err());
+    /// assert!(sheet.child1.as_ref().unwrap().split_h(401, 4).is_err());
+    ///
+    /// let strip = TileNode::new(0, 402, 0, 600).split_h(400, 4).unwrap();
+    /// assert!(strip.child2.is_none() && strip.cut.is_none());
+    /// ```
+    pub fn split_h(&self, at: i32, kerf: i32) -> Result<TileSplit> {
+        self.split_at(true, at, kerf)
+    }
+
+    /// Cut across the height `at` from the top edge: child1 is the upper part and child2
+    /// starts after the `kerf`. Err unless `at` lies within the height
+    pub fn split_v(&self, at: i32, kerf: i32) -> Result<TileSplit> {
+        self.split_at(false, at, kerf)
+    }
+
+    fn split_at(&self, is_horizontal: bool, at: i32, kerf: i32) -> Result<TileSplit> {
+        let length = if is_horizontal { self.get_width() } else { self.get_height() };
+        if at <= 0 || at > length || kerf < 0 {
+            return Err(ComputationError::ConstraintViolation {
+                message: format!(
+                    "can't cut node {} of {}x{} at {} with kerf {}",
+                    self.id,
+                    self.get_width(),
+                    self.get_height(),
+                    at,
+                    kerf
+                ),
+            }
+            .into());
+        }
+
+        let stage = self.get_next_cut_stage(is_horizontal);
+        let (mut child1, mut child2) = if is_horizontal {
+            (
+                TileNode::new(self.x1, self.x1 + at, self.y1, self.y2),
+                TileNode::new(self.x1 + at + kerf, self.x2, self.y1, self.y2),
+            )
+        } else {
+            (
+                TileNode::new(self.x1, self.x2, self.y1, self.y1 + at),
+                TileNode::new(self.x1, self.x2, self.y1 + at + kerf, self.y2),
+            )
+        };
+        child1.set_cut_stage(stage, is_horizontal);
+        child2.set_cut_stage(stage, is_horizontal);
+
+        let child2 = Some(child2).filter(|child2| child2.get_width() > 0 && child2.get_height() > 0);
+        let cut = child2.as_ref().map(|child2| Cut::from_split(self, &child1, child2));
+        Ok(TileSplit { child1, child2, cut })
+    }
+
+    /// Attach the children of a split of this node and return its cut.
+    /// Err if the node is split already or the children lie outside of it
+    pub fn set_split(&mut self, split: TileSplit) -> Result<Option<Cut>> {
+        let is_inside = |child: &TileNode| {
+            child.x1 >= self.x1 && child.x2 <= self.x2 && child.y1 >= self.y1 && child.y2 <= self.y2
+        };
+        let is_valid = self.child1.is_none()
+            && self.child2.is_none()
+            && is_inside(&split.child1)
+            && split.child2.as_ref().is_none_or(is_inside);
+        if !is_valid {
+            return Err(ComputationError::ConstraintViolation {
+                message: format!("split doesn't belong to node {}", self.id),
+            }
+            .into());
+        }
+
+        self.child1 = Some(Box::new(split.child1));
+        self.child2 = split.child2.map(Box::new);
+        Ok(split.cut)
+    }
+
+    /// Split the node so that child1 is `size` long along the cut axis and child2 starts
+    /// after the kerf, see `split_h` and `split_v`. The node stays as it was when the split
+    /// is invalid
+    pub fn split(&mut self, is_horizontal: bool, size: i32, cut_thickness: i32) -> Option<Cut> {
+        let split = self.split_at(is_horizontal, size, cut_thickness).ok()?;
+        self.set_split(split).ok().flatten()
     }
 
     pub fn get_child1(&self) -> &Option<Box<TileNode>> {
@@ -137,44 +260,45 @@ impl TileNode {
         self.child2.as_deref_mut()
     }
 
+    /// Node of this tree with the id of `target`, see `find_by_id`
     pub fn find_tile(&self, target: &TileNode) -> Option<&TileNode> {
-        if self.id == target.id {
+        self.find_by_id(target.id)
+    }
+
+    pub fn find_tile_mut(&mut self, target: &TileNode) -> Option<&mut TileNode> {
+        self.find_by_id_mut(target.id)
+    }
+
+    /// Find a node of this tree by its id
+    pub fn find_by_id(&self, id: u32) -> Option<&TileNode> {
+        if self.id == id {
             return Some(self);
         }
-        
-        if let Some(ref child1) = self.child1 {
-            if let Some(result) = child1.find_tile(target) {
-                return Some(result);
-            }
-        }
-        
-        if let Some(ref child2) = self.child2 {
-            if let Some(result) = child2.find_tile(target) {
-                return Some(result);
-            }
-        }
-        
-        None
+        self.child1
+            .as_deref()
+            .and_then(|child1| child1.find_by_id(id))
+            .or_else(|| self.child2.as_deref().and_then(|child2| child2.find_by_id(id)))
     }
 
-    pub fn find_tile_mut(&mut self, target: &TileNode) -> Option<&mut TileNode> {
-        if self.id == target.id {
+    pub fn find_by_id_mut(&mut self, id: u32) -> Option<&mut TileNode> {
+        if self.id == id {
             return Some(self);
         }
-        
-        if let Some(ref mut child1) = self.child1 {
-            if let Some(result) = child1.find_tile_mut(target) {
-                return Some(result);
-            }
+        if let Some(found) = self.child1.as_deref_mut().and_then(|child1| child1.find_by_id_mut(id)) {
+            return Some(found);
         }
-        
-        if let Some(ref mut child2) = self.child2 {
-            if let Some(result) = child2.find_tile_mut(target) {
-                return Some(result);
-            }
+        self.child2.as_deref_mut().and_then(|child2| child2.find_by_id_mut(id))
+    }
+
+    /// External id of the final tile carved out of this node.
+    /// Splits always place the tile into child1, so the child1 chain is followed.
+    pub fn get_final_external_id(&self) -> Option<u32> {
+        if self.is_final || self.external_id.is_some() {
+            return self.external_id;
         }
-        
-        None
+        self.child1
+            .as_deref()
+            .and_then(|child1| child1.get_final_external_id())
     }
 
     pub fn to_string_identifier(&self) -> String {
@@ -196,76 +320,95 @@ impl TileNode {
         }
     }
     
-    /// Java: public int getNbrFinalTiles()
-    pub fn get_nbr_final_tiles(&self) -> i32 {
-        if self.is_final {
-            return 1;
+    /// Feed the fields of `to_string_identifier` to `hasher` without building the string
+    pub fn hash_identifier(&self, hasher: &mut Xxh3) {
+        for value in [self.x1, self.y1, self.x2, self.y2] {
+            hasher.update(&value.to_le_bytes());
         }
-        
-        let mut nbr_final_tiles = 0;
+        // Флаги детей отделяют поддеревья, как скобки в строке
+        hasher.update(&[self.is_final as u8, self.child1.is_some() as u8, self.child2.is_some() as u8]);
         if let Some(ref child1) = self.child1 {
-            nbr_final_tiles += child1.get_nbr_final_tiles();
+            child1.hash_identifier(hasher);
         }
         if let Some(ref child2) = self.child2 {
-            nbr_final_tiles += child2.get_nbr_final_tiles();
+            child2.hash_identifier(hasher);
         }
-        
-        nbr_final_tiles
+    }
+
+    /// Java: public int getNbrFinalTiles()
+    pub fn get_nbr_final_tiles(&self) -> i32 {
+        self.iter_final().count() as i32
     }
     
     /// Java: public HashSet<Integer> getDistictTileSet()
     pub fn get_distict_tile_set(&self) -> HashSet<i32> {
-        let mut tile_set = HashSet::new();
-        self.get_distict_tile_set_recursive(&mut tile_set);
-        tile_set
-    }
-    
-    /// Java: private HashSet<Integer> getDistictTileSet(HashSet<Integer> hashSet)
-    fn get_distict_tile_set_recursive(&self, hash_set: &mut HashSet<i32>) {
-        if self.is_final {
-            // Java: int width = this.tile.getWidth(); int height = this.tile.getHeight(); int i = width + height;
-            let width = self.get_width();
-            let height = self.get_height();
-            let i = width + height;
-            // Java: hashSet.add(Integer.valueOf(((i * (i + 1)) / 2) + height));
-            let pairing_value = ((i * (i + 1)) / 2) + height;
-            hash_set.insert(pairing_value);
-        } else {
-            // Java: TileNode tileNode = this.child1; if (tileNode != null) { tileNode.getDistictTileSet(hashSet); }
-            if let Some(ref child1) = self.child1 {
-                child1.get_distict_tile_set_recursive(hash_set);
-            }
-            // Java: TileNode tileNode2 = this.child2; if (tileNode2 != null) { tileNode2.getDistictTileSet(hashSet); }
-            if let Some(ref child2) = self.child2 {
-                child2.get_distict_tile_set_recursive(hash_set);
-            }
-        }
+        self.iter_final()
+            .map(|node| {
+                // Java: hashSet.add(Integer.valueOf(((i * (i + 1)) / 2) + height));
+                let i = node.get_width() + node.get_height();
+                ((i * (i + 1)) / 2) + node.get_height()
+            })
+            .collect()
     }
     
     /// Java: public long getBiggestArea()
     pub fn get_biggest_area(&self) -> i64 {
-        // Java: long area = (getChild1() == null && getChild2() == null && !this.isFinal) ? getArea() : 0L;
-        let area = if self.child1.is_none() && self.child2.is_none() && !self.is_final {
-            self.get_area() as i64
-        } else {
-            0
-        };
-        
-        // Java: TileNode tileNode = this.child1; if (tileNode != null) { area = Math.max(tileNode.getBiggestArea(), area); }
-        let area = if let Some(ref child1) = self.child1 {
-            area.max(child1.get_biggest_area())
-        } else {
-            area
-        };
-        
-        // Java: TileNode tileNode2 = this.child2; return tileNode2 != null ? Math.max(tileNode2.getBiggestArea(), area) : area;
-        if let Some(ref child2) = self.child2 {
-            area.max(child2.get_biggest_area())
-        } else {
-            area
-        }
+        self.iter_free().map(|node| node.get_area() as i64).max().unwrap_or(0)
     }
     
+    /// Leaf nodes that are neither final nor split - the leftovers of the sheet
+    pub fn get_unused_nodes(&self) -> Vec<&TileNode> {
+        self.iter_free().collect()
+    }
+
+    /// Nodes holding a placed panel
+    pub fn get_final_nodes(&self) -> Vec<&TileNode> {
+        self.iter_final().collect()
+    }
+
+    /// The node and all nodes below it, depth first, child1 before child2
+    pub fn iter(&self) -> TileNodeIter<'_> {
+        TileNodeIter { stack: vec![self] }
+    }
+
+    /// Nodes holding a placed panel, depth first
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::tile_node::TileNode;
+    ///
+    /// let mut sheet = TileNode::new(0, 1000, 0, 600);
+    /// sheet.split(true, 400, 4);
+    /// sheet.child1.as_deref_mut().unwrap().set_final_tile(true);
+    ///
+    /// assert_eq!(sheet.iter_final().count(), 1);
+    /// assert_eq!(sheet.iter_free().map(|node| node.get_width()).collect::<Vec<_>>(), vec![596]);
+    /// assert_eq!(sheet.iter_cuts().map(|cut| cut.get_first_piece_size()).collect::<Vec<_>>(), vec![400.0]);
+    /// ```
+    pub fn iter_final(&self) -> impl Iterator<Item = &TileNode> {
+        self.iter().filter(|node| node.is_final)
+    }
+
+    /// Leaf nodes that are neither final nor split, depth first
+    pub fn iter_free(&self) -> impl Iterator<Item = &TileNode> {
+        self.iter()
+            .filter(|node| !node.is_final && node.child1.is_none() && node.child2.is_none())
+    }
+
+    /// Cuts of the splits below the node, depth first
+    pub fn iter_cuts(&self) -> impl Iterator<Item = Cut> + '_ {
+        self.iter().filter_map(|node| match (&node.child1, &node.child2) {
+            (Some(child1), Some(child2)) => Some(Cut::from_split(node, child1, child2)),
+            _ => None,
+        })
+    }
+
+    /// Largest leftover rectangle, the node behind getBiggestArea()
+    pub fn get_biggest_unused_node(&self) -> Option<&TileNode> {
+        self.get_unused_nodes()
+            .into_iter()
+            .max_by_key(|node| node.get_area())
+    }
+
     /// Java: public boolean isHorizontal()
     pub fn is_horizontal(&self) -> bool {
         // Java: return getWidth() > getHeight();
@@ -280,41 +423,147 @@ impl TileNode {
     
     /// Java: public int getNbrFinalHorizontal()
     pub fn get_nbr_final_horizontal(&self) -> i32 {
-        // Java: int nbrFinalHorizontal = (isFinal() && isHorizontal()) ? 1 : 0;
-        let nbr_final_horizontal = if self.is_final && self.is_horizontal() { 1 } else { 0 };
-        
-        // Java: TileNode tileNode = this.child1; if (tileNode != null) { nbrFinalHorizontal += tileNode.getNbrFinalHorizontal(); }
-        let nbr_final_horizontal = if let Some(ref child1) = self.child1 {
-            nbr_final_horizontal + child1.get_nbr_final_horizontal()
-        } else {
-            nbr_final_horizontal
-        };
-        
-        // Java: TileNode tileNode2 = this.child2; return tileNode2 != null ? nbrFinalHorizontal + tileNode2.getNbrFinalHorizontal() : nbrFinalHorizontal;
-        if let Some(ref child2) = self.child2 {
-            nbr_final_horizontal + child2.get_nbr_final_horizontal()
-        } else {
-            nbr_final_horizontal
-        }
+        self.iter_final().filter(|node| node.is_horizontal()).count() as i32
     }
     
     /// Java: public int getNbrFinalVertical()
     pub fn get_nbr_final_vertical(&self) -> i32 {
-        // Java: int nbrFinalVertical = (isFinal() && isVertical()) ? 1 : 0;
-        let nbr_final_vertical = if self.is_final && self.is_vertical() { 1 } else { 0 };
-        
-        // Java: TileNode tileNode = this.child1; if (tileNode != null) { nbrFinalVertical += tileNode.getNbrFinalVertical(); }
-        let nbr_final_vertical = if let Some(ref child1) = self.child1 {
-            nbr_final_vertical + child1.get_nbr_final_vertical()
-        } else {
-            nbr_final_vertical
+        self.iter_final().filter(|node| node.is_vertical()).count() as i32
+    }
+}
+
+/// Path from the root to every node of a tile tree by node id, so a node is found in O(depth)
+/// in the tree and in every copy of it made before the copy was split further. A copy split
+/// further gets its own index with `with_split`, which shares this one
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use rezalnyas_core::features::engine::model::tile_node::{TileNode, TileNodeIndex};
+///
+/// let mut sheet = TileNode::new(0, 1000, 0, 600);
+/// sheet.split(true, 400, 4);
+/// let rest = sheet.child2.as_ref().unwrap().id;
+///
+/// let index = Arc::new(TileNodeIndex::new(&sheet));
+/// assert_eq!(index.get(&sheet, rest).unwrap().x1, 404);
+///
+/// let mut copy = sheet.clone();
+/// index.get_mut(&mut copy, rest).unwrap().split(false, 300, 4);
+/// assert!(index.get(&copy, rest).unwrap().child1.is_some());
+/// assert!(sheet.child2.as_ref().unwrap().child1.is_none());
+///
+/// // Новые узлы копии добавляются слоем поверх индекса листа
+/// let copy_index = TileNodeIndex::with_split(&index, &copy, rest);
+/// let piece = copy.child2.as_ref().unwrap().child2.as_ref().unwrap().id;
+/// assert_eq!(copy_index.get(&copy, piece).unwrap().y1, 304);
+/// assert_eq!((index.len(), copy_index.len()), (3, 5));
+/// assert!(index.get(&copy, piece).is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TileNodeIndex {
+    /// false - child1, true - child2
+    paths: HashMap<u32, Vec<bool>>,
+    /// Index of the tree this copy was split from, see `with_split`
+    base: Option<Arc<TileNodeIndex>>,
+    nbr_layers: usize,
+}
+
+impl TileNodeIndex {
+    /// Layers an index may stack before `with_split` indexes the whole tree again
+    const MAX_LAYERS: usize = 16;
+
+    pub fn new(root: &TileNode) -> Self {
+        let mut paths = HashMap::new();
+        Self::insert_subtree(&mut paths, root, Vec::new());
+        Self { paths, base: None, nbr_layers: 1 }
+    }
+
+    /// Index of a copy of the indexed tree, `root`, in which node `id` was split further.
+    /// Only the new nodes are indexed, over a shared `index`
+    pub fn with_split(index: &Arc<Self>, root: &TileNode, id: u32) -> Arc<Self> {
+        let (Some(node), Some(path)) = (index.get(root, id), index.get_path(id)) else {
+            return Arc::new(Self::new(root));
         };
-        
-        // Java: TileNode tileNode2 = this.child2; return tileNode2 != null ? nbrFinalVertical + tileNode2.getNbrFinalVertical() : nbrFinalVertical;
-        if let Some(ref child2) = self.child2 {
-            nbr_final_vertical + child2.get_nbr_final_vertical()
-        } else {
-            nbr_final_vertical
+        if node.child1.is_none() && node.child2.is_none() {
+            return Arc::clone(index);
+        }
+        if index.nbr_layers >= Self::MAX_LAYERS {
+            return Arc::new(Self::new(root));
+        }
+
+        let mut paths = HashMap::new();
+        for (child, is_child2) in [(&node.child1, false), (&node.child2, true)] {
+            if let Some(child) = child.as_deref() {
+                let mut child_path = path.to_vec();
+                child_path.push(is_child2);
+                Self::insert_subtree(&mut paths, child, child_path);
+            }
+        }
+        Arc::new(Self { paths, base: Some(Arc::clone(index)), nbr_layers: index.nbr_layers + 1 })
+    }
+
+    fn insert_subtree(paths: &mut HashMap<u32, Vec<bool>>, node: &TileNode, path: Vec<bool>) {
+        let mut stack = vec![(node, path)];
+        while let Some((node, path)) = stack.pop() {
+            for (child, is_child2) in [(&node.child1, false), (&node.child2, true)] {
+                if let Some(child) = child.as_deref() {
+                    let mut child_path = path.clone();
+                    child_path.push(is_child2);
+                    stack.push((child, child_path));
+                }
+            }
+            paths.insert(node.id, path);
+        }
+    }
+
+    fn get_path(&self, id: u32) -> Option<&[bool]> {
+        let mut index = self;
+        loop {
+            if let Some(path) = index.paths.get(&id) {
+                return Some(path);
+            }
+            index = index.base.as_deref()?;
         }
     }
-}
\ No newline at end of file
+
+    /// Node `id` of `root`, None if the tree has no node at its path or it has another id
+    pub fn get<'a>(&self, root: &'a TileNode, id: u32) -> Option<&'a TileNode> {
+        let mut node = root;
+        for is_child2 in self.get_path(id)? {
+            node = if *is_child2 { node.child2.as_deref()? } else { node.child1.as_deref()? };
+        }
+        Some(node).filter(|node| node.id == id)
+    }
+
+    pub fn get_mut<'a>(&self, root: &'a mut TileNode, id: u32) -> Option<&'a mut TileNode> {
+        let mut node = root;
+        for is_child2 in self.get_path(id)? {
+            node = if *is_child2 { node.child2.as_deref_mut()? } else { node.child1.as_deref_mut()? };
+        }
+        Some(node).filter(|node| node.id == id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len() + self.base.as_ref().map_or(0, |base| base.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Depth-first walk over a tile tree, see `TileNode::iter`
+pub struct TileNodeIter<'a> {
+    stack: Vec<&'a TileNode>,
+}
+
+impl<'a> Iterator for TileNodeIter<'a> {
+    type Item = &'a TileNode;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.child2.as_deref());
+        self.stack.extend(node.child1.as_deref());
+        Some(node)
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::errors::Result;
+use crate::features::engine::model::calculation_request::{CalculationRequest, Panel};
+use crate::features::engine::model::calculation_response::CalculationResponse;
+
+/// Leftovers kept between runs, per material key (see `Panel::get_material_key`), as stock rows
+/// in millimeters. `CutListOptimizerServiceImpl::with_offcut_store` offers them to every request
+/// and stores the offcuts of its response
+pub trait OffcutStore: Debug + Send + Sync {
+    /// Replace the offcuts of `material`, an empty list removes the material
+    fn save(&self, material: &str, offcuts: &[Panel]) -> Result<()>;
+
+    fn load(&self, material: &str) -> Result<Vec<Panel>>;
+
+    /// Materials with stored offcuts, sorted
+    fn list(&self) -> Result<Vec<String>>;
+
+    /// Take `count` pieces of offcut `id` of `material`, false if there weren't that many
+    fn consume(&self, material: &str, id: u32, count: u32) -> Result<bool> {
+        let mut offcuts = self.load(material)?;
+        let Some(index) = offcuts.iter().position(|offcut| offcut.id == id && offcut.count >= count) else {
+            return Ok(false);
+        };
+        offcuts[index].count -= count;
+        if offcuts[index].count == 0 {
+            offcuts.remove(index);
+        }
+        self.save(material, &offcuts)?;
+        Ok(true)
+    }
+
+    /// Add the stored offcuts of the materials of `request` as stock rows after its own, returns
+    /// the stock id of every added row with the material and the id of its offcut
+    fn add_to_request(&self, request: &mut CalculationRequest) -> Result<HashMap<u32, (String, u32)>> {
+        let materials: HashSet<String> =
+            request.panels.iter().filter(|panel| panel.enabled).map(|panel| panel.get_material_key()).collect();
+        let mut materials: Vec<String> = materials.into_iter().collect();
+        materials.sort();
+        // Склад хранится в миллиметрах, запрос может быть в других единицах
+        let millimeters = request.configuration.input_unit.map_or(1.0, |unit| unit.get_millimeters());
+        let to_request_unit = |value: &str| match value.parse::<f64>() {
+            Ok(value) if millimeters != 1.0 => format!("{}", value / millimeters),
+            _ => value.to_string(),
+        };
+
+        let mut next_id = request.stock_panels.iter().map(|stock| stock.id + 1).max().unwrap_or(0);
+        let mut added = HashMap::new();
+        for material in materials {
+            for offcut in self.load(&material)? {
+                added.insert(next_id, (material.clone(), offcut.id));
+                request.stock_panels.push(Panel {
+                    id: next_id,
+                    width: to_request_unit(&offcut.width),
+                    height: to_request_unit(&offcut.height),
+                    ..offcut
+                });
+                next_id += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Consume the offcuts `response` cut, see `add_to_request` for `added`, and store the
+    /// offcuts of its sheets. Returns the number of stored offcuts
+    fn update_from_response(&self, response: &CalculationResponse, added: &HashMap<u32, (String, u32)>) -> Result<usize> {
+        let mut new_offcuts: BTreeMap<String, Vec<Panel>> = BTreeMap::new();
+        for mosaic in &response.mosaics {
+            let stock_id = mosaic.request_stock_id.map(|id| id as u32);
+            if let Some((material, id)) = stock_id.and_then(|id| added.get(&id)) {
+                self.consume(material, *id, 1)?;
+            }
+            let Some(stock) = stock_id.and_then(|id| response.request.stock_panels.iter().find(|stock| stock.id == id))
+            else {
+                continue;
+            };
+            // Обрезок наследует материал, толщину и волокна листа
+            for offcut in &mosaic.offcuts {
+                new_offcuts.entry(stock.get_material_key()).or_default().push(Panel {
+                    width: format!("{}", offcut.width),
+                    height: format!("{}", offcut.height),
+                    count: 1,
+                    label: "offcut".to_string(),
+                    enabled: true,
+                    priority: None,
+                    acquired_date: None,
+                    // Обрезок уже оплачен вместе с листом
+                    price: Some(0.0),
+                    pricing: None,
+                    ..stock.clone()
+                });
+            }
+        }
+
+        let mut nbr_stored = 0;
+        for (material, offcuts) in new_offcuts {
+            let mut stored = self.load(&material)?;
+            let next_id = stored.iter().map(|offcut| offcut.id + 1).max().unwrap_or(1);
+            nbr_stored += offcuts.len();
+            for (id, offcut) in (next_id..).zip(offcuts) {
+                stored.push(Panel { id, ..offcut });
+            }
+            self.save(&material, &stored)?;
+        }
+        Ok(nbr_stored)
+    }
+}
+
+/// Offcuts of all materials in one JSON file, created on the first save
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::calculation_request::Panel;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::offcut_store::{FileOffcutStore, OffcutStore};
+///
+/// let path = std::env::temp_dir().join("rezalnyas_offcuts_doc.json");
+/// let store = FileOffcutStore::new(&path);
+/// let mut offcut = Panel::new(1, "600", "400", 2, "offcut");
+/// offcut.material = "MFC".to_string();
+/// offcut.enabled = true;
+/// store.save("MFC", &[offcut]).unwrap();
+/// assert_eq!(store.list().unwrap(), ["MFC"]);
+///
+/// let mut request = CalculationRequestBuilder::new()
+///     .add_panel(500.0, 300.0).material("MFC")
+///     .add_stock(2800.0, 2070.0).id(7).material("MFC")
+///     .build()
+///     .unwrap();
+/// let added = store.add_to_request(&mut request).unwrap();
+/// assert_eq!(request.stock_panels[1].id, 8);
+/// assert_eq!(added[&8], ("MFC".to_string(), 1));
+///
+/// assert!(store.consume("MFC", 1, 2).unwrap());
+/// assert!(!store.consume("MFC", 1, 1).unwrap());
+/// assert!(store.list().unwrap().is_empty());
+/// std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FileOffcutStore {
+    path: PathBuf,
+    /// Read-modify-write of the file by one caller at a time
+    lock: Mutex<()>,
+}
+
+impl FileOffcutStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn read(&self) -> Result<BTreeMap<String, Vec<Panel>>> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        Ok(serde_json::from_slice(&std::fs::read(&self.path)?)?)
+    }
+}
+
+impl OffcutStore for FileOffcutStore {
+    fn save(&self, material: &str, offcuts: &[Panel]) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut inventory = self.read()?;
+        if offcuts.is_empty() {
+            inventory.remove(material);
+        } else {
+            inventory.insert(material.to_string(), offcuts.to_vec());
+        }
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&inventory)?)?;
+        Ok(())
+    }
+
+    fn load(&self, material: &str) -> Result<Vec<Panel>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read()?.remove(material).unwrap_or_default())
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read()?.into_keys().collect())
+    }
+}
@@ -0,0 +1,176 @@
+use crate::enums::grain_direction::GrainDirection;
+use crate::features::engine::model::{calculation_response::Mosaic, configuration::Configuration, solution::Solution};
+use crate::features::input::models::tile_dimensions::TileDimensions;
+use crate::features::panel_grouper::panel_grouper::PanelGrouper;
+
+/// Stock sheet opened as a bar and the panels cut from it, in cutting order
+struct Bar {
+    stock: TileDimensions,
+    pieces: Vec<TileDimensions>,
+    used_length: u32,
+}
+
+/// Cut panels that share one dimension with each other and with all stock sheets as bars:
+/// every panel is a cross-cut of a sheet, the longest panels go first onto the bar they
+/// leave the shortest offcut on. None when the material is not one-dimensional, or its edge
+/// clearance or grain needs the 2D search. Panels up to `tolerance` longer than the rest of
+/// a bar still take it whole, see `Configuration::dimension_tolerance`.
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::configuration::Configuration;
+/// use rezalnyas_core::features::engine::one_dimensional::optimize_one_dimensional;
+/// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+///
+/// let tile = |id, width| TileDimensions::new(id, width, 100, false, "", "");
+/// let tiles = [tile(1, 600), tile(2, 500), tile(3, 400), tile(4, 300)];
+/// let stock = [TileDimensions::new(10, 1000, 100, false, "", ""), TileDimensions::new(11, 100, 1000, false, "", "")];
+///
+/// let solution = optimize_one_dimensional(&tiles, &stock, 0, 0, &Configuration::default()).unwrap();
+/// assert_eq!(solution.mosaics.len(), 2);
+/// assert!(solution.no_fit_panels.is_empty());
+///
+/// // 300 x 200 shares no dimension with the rest
+/// let tiles = [tile(1, 600), TileDimensions::new(2, 300, 200, false, "", "")];
+/// assert!(optimize_one_dimensional(&tiles, &stock, 0, 0, &Configuration::default()).is_none());
+/// ```
+pub fn optimize_one_dimensional(
+    tiles: &[TileDimensions],
+    stock_tiles: &[TileDimensions],
+    cut_thickness: i32,
+    tolerance: u32,
+    configuration: &Configuration,
+) -> Option<Solution> {
+    let depth = PanelGrouper::get_common_dimension(tiles, stock_tiles)?;
+    if tiles.iter().chain(stock_tiles).any(|tile| tile.edge_clearance > 0) {
+        return None;
+    }
+    let kerf = cut_thickness.max(0) as u32;
+
+    let mut pieces = tiles.to_vec();
+    pieces.sort_by_key(|tile| std::cmp::Reverse(get_length(tile, depth)));
+
+    let mut unused_stock: Vec<TileDimensions> = stock_tiles.to_vec();
+    let mut bars: Vec<Bar> = Vec::new();
+    let mut no_fit_panels = Vec::new();
+    for tile in pieces {
+        let length = get_length(&tile, depth);
+        let has_room = |bar: &Bar| {
+            configuration
+                .max_parts_per_sheet
+                .is_none_or(|max_parts| (bar.pieces.len() as u32) < max_parts)
+                && get_needed_length(bar, length, kerf) <= get_length(&bar.stock, depth) + tolerance
+        };
+
+        // Сначала лучший из открытых прутков, потом самый длинный из приоритетных неиспользованных листов
+        let index = match bars
+            .iter()
+            .enumerate()
+            .filter(|(_, bar)| has_room(bar))
+            .min_by_key(|(_, bar)| (get_length(&bar.stock, depth) + tolerance) - get_needed_length(bar, length, kerf))
+        {
+            Some((index, _)) => index,
+            None => {
+                let Some(stock_index) = unused_stock
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, stock)| get_length(stock, depth) + tolerance >= length)
+                    .max_by_key(|(index, stock)| {
+                        (stock.priority, get_length(stock, depth), std::cmp::Reverse(*index))
+                    })
+                    .map(|(index, _)| index)
+                else {
+                    no_fit_panels.push(tile);
+                    continue;
+                };
+                bars.push(Bar {
+                    stock: unused_stock.remove(stock_index),
+                    pieces: Vec::new(),
+                    used_length: 0,
+                });
+                bars.len() - 1
+            }
+        };
+
+        let bar = &mut bars[index];
+        let piece = orient_piece(&tile, &bar.stock, depth);
+        if configuration.consider_orientation
+            && !GrainDirection::from(bar.stock.orientation)
+                .allows(GrainDirection::from(tile.orientation), piece.is_rotated != tile.is_rotated)
+        {
+            return None;
+        }
+        bar.used_length = get_needed_length(bar, length, kerf);
+        bar.pieces.push(piece);
+    }
+
+    let mut solution = Solution::default();
+    solution.add_all_mosaics(bars.iter().map(|bar| cut_bar(bar, depth, kerf as i32, tolerance as i32)).collect());
+    solution.unused_stock_panels = unused_stock.into_iter().collect();
+    solution.no_fit_panels = no_fit_panels;
+    Some(solution)
+}
+
+/// Length of the panel or sheet along the bar, the dimension that isn't `depth`
+fn get_length(tile: &TileDimensions, depth: u32) -> u32 {
+    if tile.height == depth {
+        tile.width
+    } else {
+        tile.height
+    }
+}
+
+fn get_needed_length(bar: &Bar, length: u32, kerf: u32) -> u32 {
+    if bar.pieces.is_empty() {
+        length
+    } else {
+        bar.used_length + kerf + length
+    }
+}
+
+/// True when the sheet runs along x, its height is the common dimension
+fn is_along_width(stock: &TileDimensions, depth: u32) -> bool {
+    stock.height == depth
+}
+
+/// Turn the panel so its `depth` lies across the bar
+fn orient_piece(tile: &TileDimensions, stock: &TileDimensions, depth: u32) -> TileDimensions {
+    let across = if is_along_width(stock, depth) { tile.height } else { tile.width };
+    if across == depth {
+        tile.clone()
+    } else {
+        tile.rotate_90()
+    }
+}
+
+/// Sheet with the pieces of the bar cross-cut one after another from its start
+fn cut_bar(bar: &Bar, depth: u32, kerf: i32, tolerance: i32) -> Mosaic {
+    let mut mosaic = Mosaic::from_tile_dimensions(&bar.stock);
+    let Some(mut root) = mosaic.root_tile_node.first().cloned() else {
+        return mosaic;
+    };
+    let is_along_width = is_along_width(&bar.stock, depth);
+
+    let mut rest = &mut root;
+    for piece in &bar.pieces {
+        let length = if is_along_width { piece.width } else { piece.height } as i32;
+        let rest_length = if is_along_width { rest.get_width() } else { rest.get_height() };
+        // Остаток в пределах допуска отдаётся детали целиком, без реза
+        let is_cut = rest_length > length + tolerance;
+        let target = if is_cut {
+            rest.split(is_along_width, length, kerf);
+            rest.child1.as_deref_mut().expect("split always sets child1")
+        } else {
+            &mut *rest
+        };
+        target.set_final_tile(true);
+        target.set_external_id(Some(piece.id));
+        target.set_rotated(piece.is_rotated);
+
+        rest = match rest.child2.as_deref_mut() {
+            Some(child2) if is_cut => child2,
+            _ => break,
+        };
+    }
+    mosaic.set_root_tile_node(root);
+    mosaic
+}
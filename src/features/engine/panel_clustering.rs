@@ -0,0 +1,95 @@
+/// Iterations of k-means after which the clusters are taken as they are
+const MAX_ITERATIONS: usize = 32;
+
+/// Split panel sizes into at most `nbr_clusters` clusters of similar area and aspect ratio
+/// with k-means. Returns the indexes of `sizes` per cluster, clusters by descending area of
+/// their largest panel, indexes within a cluster by descending area. The same sizes always
+/// give the same clusters
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::panel_clustering::cluster_by_shape;
+///
+/// let sizes = [(1000, 500), (100, 100), (990, 510), (2000, 100), (110, 90), (1900, 120)];
+/// let clusters = cluster_by_shape(&sizes, 3);
+/// assert_eq!(clusters, vec![vec![2, 0], vec![5, 3], vec![1, 4]]);
+/// assert_eq!(cluster_by_shape(&sizes, 10).len(), 6);
+/// ```
+pub fn cluster_by_shape(sizes: &[(u32, u32)], nbr_clusters: usize) -> Vec<Vec<usize>> {
+    let area = |index: usize| sizes[index].0 as u64 * sizes[index].1 as u64;
+    let mut by_area: Vec<usize> = (0..sizes.len()).collect();
+    by_area.sort_by_key(|index| std::cmp::Reverse(area(*index)));
+
+    let nbr_clusters = nbr_clusters.max(1);
+    if sizes.len() <= nbr_clusters {
+        return by_area.into_iter().map(|index| vec![index]).collect();
+    }
+
+    // Логарифмы площади и соотношения сторон, приведённые к [0, 1]
+    let mut points: Vec<[f64; 2]> = sizes
+        .iter()
+        .map(|(width, height)| {
+            let (long, short) = ((*width).max(*height).max(1) as f64, (*width).min(*height).max(1) as f64);
+            [(long * short).ln(), (long / short).ln()]
+        })
+        .collect();
+    for axis in 0..2 {
+        let min = points.iter().map(|point| point[axis]).fold(f64::INFINITY, f64::min);
+        let max = points.iter().map(|point| point[axis]).fold(f64::NEG_INFINITY, f64::max);
+        if max > min {
+            for point in &mut points {
+                point[axis] = (point[axis] - min) / (max - min);
+            }
+        } else {
+            for point in &mut points {
+                point[axis] = 0.0;
+            }
+        }
+    }
+
+    // Начальные центры - равномерно по убыванию площади, без случайности
+    let mut centroids: Vec<[f64; 2]> = (0..nbr_clusters)
+        .map(|cluster| points[by_area[cluster * sizes.len() / nbr_clusters]])
+        .collect();
+    let nearest = |point: &[f64; 2], centroids: &[[f64; 2]]| {
+        (0..centroids.len())
+            .min_by(|a, b| distance(point, &centroids[*a]).total_cmp(&distance(point, &centroids[*b])))
+            .unwrap_or(0)
+    };
+
+    let mut assignment: Vec<usize> = points.iter().map(|point| nearest(point, &centroids)).collect();
+    for _ in 0..MAX_ITERATIONS {
+        for (cluster, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&[f64; 2]> =
+                points.iter().zip(&assignment).filter(|(_, a)| **a == cluster).map(|(point, _)| point).collect();
+            if !members.is_empty() {
+                let nbr_members = members.len() as f64;
+                *centroid = [
+                    members.iter().map(|point| point[0]).sum::<f64>() / nbr_members,
+                    members.iter().map(|point| point[1]).sum::<f64>() / nbr_members,
+                ];
+            }
+        }
+        let next: Vec<usize> = points.iter().map(|point| nearest(point, &centroids)).collect();
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+    }
+
+    // by_area уже по убыванию площади, поэтому кластеры и их состав идут в том же порядке
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut cluster_of = vec![usize::MAX; nbr_clusters];
+    for index in by_area {
+        let cluster = assignment[index];
+        if cluster_of[cluster] == usize::MAX {
+            cluster_of[cluster] = clusters.len();
+            clusters.push(Vec::new());
+        }
+        clusters[cluster_of[cluster]].push(index);
+    }
+    clusters
+}
+
+fn distance(a: &[f64; 2], b: &[f64; 2]) -> f64 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)
+}
@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+use rayon::prelude::*;
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::enums::orientation::Orientation;
+use crate::features::input::models::tile_dimensions::TileDimensions;
+
+/// 128-bit xxh3 of the tiles of a permutation in order. Tiles are hashed by their canonical
+/// dimensions, so rows that only differ by id or by swapped width and height of a rotatable
+/// tile hash the same. The same permutation gives the same hash on every run and platform
+pub fn get_permutation_hash(permutation: &[TileDimensions], consider_orientation: bool) -> u128 {
+    let mut hasher = Xxh3::new();
+    for tile in permutation {
+        let can_rotate = !consider_orientation || tile.orientation == Orientation::Default;
+        let (width, height) = tile.get_canonical_dimensions(can_rotate);
+        hasher.update(&width.to_le_bytes());
+        hasher.update(&height.to_le_bytes());
+        // Длина перед материалом, чтобы "ab" + "c" не совпало с "a" + "bc"
+        hasher.update(&(tile.material.len() as u64).to_le_bytes());
+        hasher.update(tile.material.as_bytes());
+        hasher.update(&[tile.orientation.to_numeric()]);
+        hasher.update(&tile.edge_clearance.to_le_bytes());
+    }
+    hasher.digest128()
+}
+
+/// Keep the first of the permutations that place the same tiles in the same order, see
+/// `get_permutation_hash`. Hashes are computed in parallel, returns the number removed
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::permutation_hash::remove_duplicated_permutations;
+/// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+///
+/// let tile = |id, width, height, material| TileDimensions::new(id, width, height, false, "", material);
+/// let mut permutations = vec![
+///     // width * 31 + height совпадает у 10x62 и 11x31
+///     vec![tile(1, 10, 62, "MFC")],
+///     vec![tile(2, 11, 31, "MFC")],
+///     // Материалы склеиваются в одну строку, но не совпадают
+///     vec![tile(3, 100, 100, "ab"), tile(4, 100, 100, "c")],
+///     vec![tile(5, 100, 100, "a"), tile(6, 100, 100, "bc")],
+///     // Повёрнутая копия первой перестановки
+///     vec![tile(7, 62, 10, "MFC")],
+/// ];
+///
+/// assert_eq!(remove_duplicated_permutations(&mut permutations, false), 1);
+/// let ids: Vec<u32> = permutations.iter().map(|permutation| permutation[0].id).collect();
+/// assert_eq!(ids, [1, 2, 3, 5]);
+/// ```
+pub fn remove_duplicated_permutations(permutations: &mut Vec<Vec<TileDimensions>>, consider_orientation: bool) -> usize {
+    let original_len = permutations.len();
+    let hashes: Vec<u128> = permutations
+        .par_iter()
+        .map(|permutation| get_permutation_hash(permutation, consider_orientation))
+        .collect();
+    let mut seen = HashSet::with_capacity(hashes.len());
+    let mut hashes = hashes.into_iter();
+    permutations.retain(|_| hashes.next().is_some_and(|hash| seen.insert(hash)));
+    original_len - permutations.len()
+}
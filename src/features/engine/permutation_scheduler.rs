@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use crate::features::input::models::tile_dimensions::TileDimensions;
+
+/// Order of the permutations of a material with `PerformanceThresholds::progressive_permutations`:
+/// every permutation is scored with a quick shelf packing of its tiles in order, the best scored
+/// go first. Permutations starting with the tile of one that improved the best solution move
+/// forward, those starting like one that didn't move back
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::permutation_scheduler::PermutationScheduler;
+/// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+///
+/// let tile = |id, width, height| TileDimensions::new(id, width, height, false, "", "");
+/// let (low, high) = (tile(1, 500, 100), tile(2, 500, 300));
+/// let stock = [tile(10, 1000, 300)];
+///
+/// // Низкая деталь первой задаёт низкую полку, для высокой приходится открыть второй лист
+/// let permutations = vec![vec![low.clone(), high.clone()], vec![high.clone(), low.clone()]];
+/// let mut scheduler = PermutationScheduler::new(&permutations, &stock, 0, false);
+/// assert_eq!(scheduler.next(), Some(1));
+/// scheduler.report(1, true);
+/// assert_eq!(scheduler.next(), Some(0));
+/// assert_eq!(scheduler.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PermutationScheduler {
+    /// Stock area the shelf packing of each permutation opens, unplaced tiles count as the biggest sheet each
+    scores: Vec<u64>,
+    /// Id of the first tile of each permutation
+    leading_ids: Vec<Option<u32>>,
+    /// Factor of the scores per leading tile id, adjusted by `report`
+    factors: HashMap<u32, f64>,
+    pending: Vec<usize>,
+}
+
+impl PermutationScheduler {
+    /// Factor applied to the permutations starting like an improving one, the inverse otherwise
+    const IMPROVEMENT_FACTOR: f64 = 0.9;
+
+    pub fn new(
+        permutations: &[Vec<TileDimensions>],
+        stock_tiles: &[TileDimensions],
+        cut_thickness: u32,
+        consider_orientation: bool,
+    ) -> Self {
+        let mut stock: Vec<(u32, u32)> = stock_tiles.iter().map(|stock| (stock.width, stock.height)).collect();
+        stock.sort_by_key(|(width, height)| std::cmp::Reverse(*width as u64 * *height as u64));
+        Self {
+            scores: permutations
+                .iter()
+                .map(|permutation| get_shelf_score(permutation, &stock, cut_thickness, !consider_orientation))
+                .collect(),
+            leading_ids: permutations.iter().map(|permutation| permutation.first().map(|tile| tile.id)).collect(),
+            factors: HashMap::new(),
+            pending: (0..permutations.len()).collect(),
+        }
+    }
+
+    /// Result of permutation `index`: `improved` - it changed the best solution of the material
+    pub fn report(&mut self, index: usize, improved: bool) {
+        let Some(Some(id)) = self.leading_ids.get(index) else {
+            return;
+        };
+        let factor = self.factors.entry(*id).or_insert(1.0);
+        if improved {
+            *factor *= Self::IMPROVEMENT_FACTOR;
+        } else {
+            *factor /= Self::IMPROVEMENT_FACTOR;
+        }
+    }
+
+    fn get_priority(&self, index: usize) -> f64 {
+        let factor = self.leading_ids[index].and_then(|id| self.factors.get(&id)).copied().unwrap_or(1.0);
+        self.scores[index] as f64 * factor
+    }
+}
+
+impl Iterator for PermutationScheduler {
+    type Item = usize;
+
+    /// Index of the most promising permutation not yet taken, the first one on equal scores
+    fn next(&mut self) -> Option<usize> {
+        let position = (0..self.pending.len()).min_by(|a, b| {
+            let (a, b) = (self.pending[*a], self.pending[*b]);
+            self.get_priority(a).total_cmp(&self.get_priority(b)).then(a.cmp(&b))
+        })?;
+        Some(self.pending.remove(position))
+    }
+}
+
+/// Sheet of the shelf packing: width, free height and its shelves as (height, free width)
+type ShelfSheet = (u32, u32, Vec<(u32, u32)>);
+
+/// Stock area opened by shelves filled first-fit in the order of `tiles`, `stock` by descending area
+fn get_shelf_score(tiles: &[TileDimensions], stock: &[(u32, u32)], cut_thickness: u32, can_rotate: bool) -> u64 {
+    let Some(biggest_area) = stock.first().map(|(width, height)| *width as u64 * *height as u64) else {
+        return 0;
+    };
+    let mut stock = stock.to_vec();
+    let mut sheets: Vec<ShelfSheet> = Vec::new();
+    let mut score = 0;
+    for tile in tiles {
+        let mut sizes = vec![(tile.width + cut_thickness, tile.height + cut_thickness)];
+        if can_rotate && tile.width != tile.height {
+            sizes.push((tile.height + cut_thickness, tile.width + cut_thickness));
+        }
+        let on_shelf = sheets.iter_mut().flat_map(|(_, _, shelves)| shelves.iter_mut()).find_map(|shelf| {
+            let size = sizes.iter().find(|(width, height)| *height <= shelf.0 && *width <= shelf.1)?;
+            Some((shelf, size.0))
+        });
+        if let Some((shelf, width)) = on_shelf {
+            shelf.1 -= width;
+            continue;
+        }
+        let on_sheet = sheets.iter_mut().find_map(|sheet| {
+            let size = sizes.iter().find(|(width, height)| *width <= sheet.0 && *height <= sheet.1)?;
+            Some((sheet, *size))
+        });
+        if let Some((sheet, (width, height))) = on_sheet {
+            sheet.1 -= height;
+            sheet.2.push((height, sheet.0 - width));
+            continue;
+        }
+        // Открытый лист уходит со склада, деталь без листа считается целым большим листом
+        let new_sheet = stock.iter().enumerate().find_map(|(index, (stock_width, stock_height))| {
+            let (sheet_width, sheet_height) = (stock_width + cut_thickness, stock_height + cut_thickness);
+            let size = sizes.iter().find(|(width, height)| *width <= sheet_width && *height <= sheet_height)?;
+            Some((index, (sheet_width, sheet_height), *size))
+        });
+        match new_sheet {
+            Some((index, (sheet_width, sheet_height), (width, height))) => {
+                let (stock_width, stock_height) = stock.remove(index);
+                score += stock_width as u64 * stock_height as u64;
+                sheets.push((sheet_width, sheet_height - height, vec![(height, sheet_width - width)]));
+            }
+            None => score += biggest_area,
+        }
+    }
+    score
+}
@@ -0,0 +1,99 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use crate::features::engine::model::calculation_request::CalculationRequest;
+use crate::features::engine::model::calculation_response::CalculationResponse;
+use crate::features::input::normalizer::normalize_request;
+
+/// Responses of completed tasks, reused when the same request is submitted again
+///
+/// Entries are keyed by a hash of the normalized request, so rows that only differ by
+/// unit or by being split into duplicates hit the same entry. Entries older than the TTL
+/// of the new request are ignored, the oldest ones are evicted past `CAPACITY`
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::calculation_response::CalculationResponse;
+/// use rezalnyas_core::features::engine::response_cache::ResponseCache;
+///
+/// let request = || {
+///     CalculationRequestBuilder::new()
+///         .add_panel(400.0, 600.0)
+///         .add_stock(1000.0, 600.0)
+///         .build()
+///         .unwrap()
+/// };
+/// let key = ResponseCache::key(&request()).unwrap();
+/// assert_eq!(ResponseCache::key(&request()), Some(key));
+///
+/// let mut cache = ResponseCache::default();
+/// cache.insert(key, CalculationResponse::new(), 1_000);
+/// assert!(cache.get(key, 60_000, 1_500).unwrap().cached);
+/// assert!(cache.get(key, 60_000, 61_000).is_none());
+/// ```
+#[derive(Debug, Default)]
+pub struct ResponseCache {
+    entries: HashMap<u64, (u64, CalculationResponse)>, // key -> stored at, clock millis
+    order: VecDeque<u64>,
+}
+
+impl ResponseCache {
+    pub const CAPACITY: usize = 64;
+
+    /// Content key of a request, None if it can't be reused: invalid rows or a custom
+    /// stock selection strategy that isn't part of the serialized request
+    pub fn key(request: &CalculationRequest) -> Option<u64> {
+        if request.configuration.stock_selection_strategy.is_some() {
+            return None;
+        }
+        let mut request = normalize_request(request).ok()?.request;
+        // Один и тот же заказ от разных клиентов считается одинаковым
+        request.client_info = Default::default();
+
+        // Value хранит ключи объектов упорядоченно, порядок HashMap запроса не влияет на ключ
+        let value = serde_json::to_value(&request).ok()?;
+        let mut hasher = DefaultHasher::new();
+        value.to_string().hash(&mut hasher);
+        Some(hasher.finish())
+    }
+
+    /// Response stored under `key` less than `ttl_ms` before `now_ms`, marked as cached
+    pub fn get(&self, key: u64, ttl_ms: u64, now_ms: u64) -> Option<CalculationResponse> {
+        let (stored_at, response) = self.entries.get(&key)?;
+        if now_ms.saturating_sub(*stored_at) >= ttl_ms {
+            return None;
+        }
+        let mut response = response.clone();
+        response.cached = true;
+        Some(response)
+    }
+
+    pub fn insert(&mut self, key: u64, response: CalculationResponse, now_ms: u64) {
+        if self.entries.insert(key, (now_ms, response)).is_some() {
+            self.order.retain(|k| *k != key);
+        }
+        self.order.push_back(key);
+        while self.entries.len() > Self::CAPACITY {
+            match self.order.pop_front() {
+                Some(key) => {
+                    self.entries.remove(&key);
+                }
+                None => break,
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
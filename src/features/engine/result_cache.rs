@@ -0,0 +1,147 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use crate::features::engine::cut_list_thread::CutListThread;
+use crate::features::engine::model::solution::Solution;
+
+/// Solutions of already computed (permutation, stock solution, thread group) runs, shared between tasks
+///
+/// Entries are keyed by a hash of everything the thread result depends on, so a repeated or
+/// slightly changed request reuses the sub-problems it has in common with the previous one.
+/// Least recently used entries are evicted once `capacity` is reached, capacity 0 disables the cache
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    capacity: usize,
+    entries: HashMap<u64, Vec<Solution>>,
+    order: VecDeque<u64>,
+    hits: u64,
+    misses: u64,
+}
+
+impl ResultCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Content key of a configured thread: tiles, stock, group and the configuration it runs with
+    pub fn key(cut_list_thread: &CutListThread) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        cut_list_thread.tiles.len().hash(&mut hasher);
+        for tile in &cut_list_thread.tiles {
+            (tile.id, tile.width, tile.height, tile.orientation, tile.is_rotated).hash(&mut hasher);
+            tile.material.hash(&mut hasher);
+        }
+
+        if let Some(stock_solution) = &cut_list_thread.stock_solution {
+            stock_solution.stock_tiles.len().hash(&mut hasher);
+            for stock in &stock_solution.stock_tiles {
+                (stock.id, stock.width, stock.height, stock.orientation).hash(&mut hasher);
+            }
+        }
+
+        cut_list_thread.group.hash(&mut hasher);
+        cut_list_thread.first_cut_orientation.hash(&mut hasher);
+        cut_list_thread.accuracy_factor.hash(&mut hasher);
+        cut_list_thread.consider_grain_direction.hash(&mut hasher);
+        cut_list_thread.cut_thickness.hash(&mut hasher);
+        cut_list_thread.min_trim_dimension.hash(&mut hasher);
+        cut_list_thread.dimension_tolerance.hash(&mut hasher);
+        cut_list_thread.lead_in_allowance.hash(&mut hasher);
+        cut_list_thread.max_cut_stages.hash(&mut hasher);
+        cut_list_thread.max_parts_per_sheet.hash(&mut hasher);
+        cut_list_thread.max_similar_solutions.hash(&mut hasher);
+        cut_list_thread.similarity_threshold.to_bits().hash(&mut hasher);
+        cut_list_thread.prefer_through_cuts.hash(&mut hasher);
+        cut_list_thread.max_pool_memory_bytes.hash(&mut hasher);
+        if let Some(task) = &cut_list_thread.task {
+            task.calculation_request
+                .configuration
+                .optimization_priority
+                .value()
+                .hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    pub fn get(&mut self, key: u64) -> Option<Vec<Solution>> {
+        match self.entries.get(&key) {
+            Some(solutions) => {
+                self.hits += 1;
+                let solutions = solutions.clone();
+                self.touch(key);
+                Some(solutions)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: u64, solutions: Vec<Solution>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.insert(key, solutions).is_some() {
+            self.touch(key);
+        } else {
+            self.order.push_back(key);
+        }
+        self.evict();
+    }
+
+    /// Change the size bound, dropping least recently used entries that no longer fit
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    fn touch(&mut self, key: u64) {
+        if let Some(position) = self.order.iter().position(|k| *k == key) {
+            self.order.remove(position);
+        }
+        self.order.push_back(key);
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            match self.order.pop_front() {
+                Some(key) => {
+                    self.entries.remove(&key);
+                }
+                None => break,
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::features::engine::model::response_summary::SheetUsage;
+use crate::features::engine::model::stock_solution::StockSolution;
+use crate::features::engine::model::stock_solution_summary::{StockPlanLimits, StockSolutionSummary};
+use crate::features::engine::stock_selection::StockSelectionStrategy;
+use crate::features::input::models::tile_dimensions::TileDimensions;
+
+/// Sheet sets that could hold the panels, found without running the optimizer,
+/// to answer "how many sheets do I need to buy" in milliseconds
+pub struct StockPlanner;
+
+impl StockPlanner {
+    /// Sheet sets of `stock` in the order of `limits.strategy`, with their sheet counts and area
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::model::stock_solution_summary::StockPlanLimits;
+    /// use rezalnyas_core::features::engine::stock_planner::StockPlanner;
+    /// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+    ///
+    /// let tiles: Vec<_> = (1..=6).map(|id| TileDimensions::new(id, 600, 400, false, "", "MFC")).collect();
+    /// let stock: Vec<_> = (1..=4).map(|id| TileDimensions::new(id, 1000, 600, false, "", "MFC")).collect();
+    ///
+    /// let plans = StockPlanner::enumerate(&tiles, &stock, &StockPlanLimits::default());
+    /// assert_eq!(plans[0].nbr_sheets, 3);
+    /// assert_eq!(plans[0].sheets[0].count, 3);
+    /// assert!(plans[0].is_sufficient);
+    /// assert!((plans[0].area_ratio - 1.25).abs() < 1e-9);
+    /// ```
+    pub fn enumerate(
+        tiles: &[TileDimensions],
+        stock: &[TileDimensions],
+        limits: &StockPlanLimits,
+    ) -> Vec<StockSolutionSummary> {
+        let required_area: u64 = tiles.iter().map(|tile| tile.area()).sum();
+        limits
+            .strategy
+            .get_stock_solutions(tiles, stock)
+            .into_iter()
+            .filter(|solution| limits.max_sheets.is_none_or(|max_sheets| solution.stock_tiles.len() <= max_sheets))
+            .take(limits.max_solutions)
+            .map(|solution| Self::summarize(tiles, &solution, required_area))
+            .collect()
+    }
+
+    fn summarize(tiles: &[TileDimensions], solution: &StockSolution, required_area: u64) -> StockSolutionSummary {
+        let mut sheets: Vec<SheetUsage> = Vec::new();
+        for sheet in &solution.stock_tiles {
+            let (width, height) = (sheet.width as f64, sheet.height as f64);
+            match sheets.iter_mut().find(|usage| {
+                usage.material == sheet.material && usage.width == width && usage.height == height
+            }) {
+                Some(usage) => usage.count += 1,
+                None => sheets.push(SheetUsage {
+                    material: sheet.material.clone(),
+                    width,
+                    height,
+                    count: 1,
+                }),
+            }
+        }
+        sheets.sort_by(|a, b| (b.width * b.height).total_cmp(&(a.width * a.height)));
+
+        let all_fit = tiles
+            .iter()
+            .all(|tile| solution.stock_tiles.iter().any(|sheet| sheet.fits(tile)));
+        StockSolutionSummary {
+            nbr_sheets: solution.stock_tiles.len(),
+            sheets,
+            total_area: solution.total_area,
+            required_area,
+            area_ratio: if required_area > 0 {
+                solution.total_area as f64 / required_area as f64
+            } else {
+                0.0
+            },
+            is_sufficient: all_fit && solution.total_area >= required_area,
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use crate::enums::stock_selection::StockSelection;
+use crate::features::engine::model::stock_solution::StockSolution;
+use crate::features::input::models::tile_dimensions::TileDimensions;
+
+/// Stock solutions the optimizer tries, like the Java StockPanelPicker
+const MAX_STOCK_SOLUTIONS: usize = 1000;
+/// Sheet combinations looked at by the combination strategies
+const MAX_COMBINATIONS: usize = 100_000;
+
+/// Picks the sets of stock sheets the layouts of a task are tried on, in the order they are tried
+///
+/// Set `Configuration::stock_selection_strategy` to plug in custom inventory rules, e.g. sheets
+/// of the oldest batch first or sheets reserved for other orders left out.
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use rezalnyas_core::features::engine::model::configuration::Configuration;
+/// use rezalnyas_core::features::engine::model::stock_solution::StockSolution;
+/// use rezalnyas_core::features::engine::stock_selection::StockSelectionStrategy;
+/// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+///
+/// /// Sheets with id 99 are reserved, everything else is used at once
+/// #[derive(Debug)]
+/// struct SkipReserved;
+///
+/// impl StockSelectionStrategy for SkipReserved {
+///     fn get_stock_solutions(&self, _tiles: &[TileDimensions], stock_tiles: &[TileDimensions]) -> Vec<StockSolution> {
+///         let free = stock_tiles.iter().filter(|stock| stock.id != 99).cloned().collect();
+///         vec![StockSolution::new(free)]
+///     }
+/// }
+///
+/// let configuration = Configuration {
+///     stock_selection_strategy: Some(Arc::new(SkipReserved)),
+///     ..Configuration::default()
+/// };
+/// let stock = [TileDimensions::new(1, 1000, 600, false, "", ""), TileDimensions::new(99, 1000, 600, false, "", "")];
+/// let solutions = configuration.get_stock_selection_strategy().get_stock_solutions(&[], &stock);
+/// assert_eq!(solutions[0].stock_tiles.len(), 1);
+/// ```
+pub trait StockSelectionStrategy: Debug + Send + Sync {
+    /// Sets of `stock_tiles` to cut `tiles` from, the first ones are tried first
+    fn get_stock_solutions(&self, tiles: &[TileDimensions], stock_tiles: &[TileDimensions]) -> Vec<StockSolution>;
+}
+
+/// Every built-in strategy stays within the sheets of the request and offers enough of them
+/// when one sheet is not enough
+///
+/// ```rust
+/// use rezalnyas_core::enums::stock_selection::StockSelection;
+/// use rezalnyas_core::features::engine::stock_selection::StockSelectionStrategy;
+/// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+///
+/// let panels = vec![TileDimensions::new(1, 900, 900, false, "", "MFC"); 3];
+/// let stock = vec![TileDimensions::new(2, 1000, 1000, false, "", "MFC"); 2];
+/// for strategy in [
+///     StockSelection::EachSheet,
+///     StockSelection::Exhaustive,
+///     StockSelection::LargestFirst,
+///     StockSelection::CostBased,
+///     StockSelection::SingleSheetSize,
+/// ] {
+///     let solutions = strategy.get_stock_solutions(&panels, &stock);
+///     assert!(solutions.iter().all(|solution| solution.stock_tiles.len() <= 2), "{:?}", strategy);
+///     assert!(solutions.iter().any(|solution| solution.stock_tiles.len() == 2), "{:?}", strategy);
+/// }
+///
+/// // Один лист вмещает обе детали по площади, но не по размерам
+/// let panels = vec![TileDimensions::new(1, 600, 600, false, "", "MFC"); 2];
+/// let solutions = StockSelection::EachSheet.get_stock_solutions(&panels, &stock);
+/// let nbr_sheets: Vec<usize> = solutions.iter().map(|solution| solution.stock_tiles.len()).collect();
+/// assert_eq!(nbr_sheets, [1, 1, 2]);
+/// ```
+impl StockSelectionStrategy for StockSelection {
+    fn get_stock_solutions(&self, tiles: &[TileDimensions], stock_tiles: &[TileDimensions]) -> Vec<StockSolution> {
+        let required_area: u64 = tiles.iter().map(|tile| tile.area()).sum();
+        let mut solutions = match self {
+            StockSelection::EachSheet => {
+                let mut solutions: Vec<StockSolution> = stock_tiles
+                    .iter()
+                    .filter(|stock| stock.area() >= required_area)
+                    .map(|stock| StockSolution::new(vec![stock.clone()]))
+                    .collect();
+                // Одного листа мало: берём листы по порядку, добавляя по одному
+                let mut area = 0;
+                let nbr_covering = stock_tiles
+                    .iter()
+                    .position(|stock| {
+                        area += stock.area();
+                        area >= required_area
+                    })
+                    .map_or(stock_tiles.len(), |index| index + 1);
+                solutions.extend(
+                    (nbr_covering.max(2)..=stock_tiles.len())
+                        .map(|nbr_sheets| StockSolution::new(stock_tiles[..nbr_sheets].to_vec())),
+                );
+                if solutions.is_empty() && !stock_tiles.is_empty() {
+                    solutions.push(StockSolution::new(stock_tiles.to_vec()));
+                }
+                solutions
+            }
+            StockSelection::Exhaustive => {
+                let mut solutions = get_combinations(stock_tiles, required_area);
+                solutions.sort_by_key(|solution| (solution.total_area, solution.stock_tiles.len()));
+                solutions
+            }
+            StockSelection::CostBased => {
+                let mut solutions = get_combinations(stock_tiles, required_area);
+                solutions.sort_by_key(|solution| (solution.stock_tiles.len(), solution.total_area));
+                solutions
+            }
+            StockSelection::LargestFirst => {
+                let mut sorted = stock_tiles.to_vec();
+                sorted.sort_by_key(|stock| std::cmp::Reverse(stock.area()));
+                let mut area = 0;
+                let nbr_covering = sorted
+                    .iter()
+                    .position(|stock| {
+                        area += stock.area();
+                        area >= required_area
+                    })
+                    .map_or(sorted.len(), |index| index + 1);
+                (nbr_covering.max(1)..=sorted.len())
+                    .map(|nbr_sheets| StockSolution::new(sorted[..nbr_sheets].to_vec()))
+                    .collect()
+            }
+            StockSelection::SingleSheetSize => {
+                let mut solutions: Vec<StockSolution> = get_sizes(stock_tiles)
+                    .into_values()
+                    .flat_map(|sheets| {
+                        let area = sheets[0].area().max(1);
+                        let nbr_covering = (required_area.div_ceil(area) as usize).clamp(1, sheets.len());
+                        (nbr_covering..=sheets.len())
+                            .map(move |nbr_sheets| StockSolution::new(sheets[..nbr_sheets].to_vec()))
+                    })
+                    .collect();
+                solutions.sort_by_key(|solution| solution.total_area);
+                solutions
+            }
+        };
+        solutions.truncate(MAX_STOCK_SOLUTIONS);
+        solutions
+    }
+}
+
+/// Sheets of the same stock row and size
+fn get_sizes(stock_tiles: &[TileDimensions]) -> BTreeMap<(u32, u32, u32), Vec<TileDimensions>> {
+    let mut sizes: BTreeMap<(u32, u32, u32), Vec<TileDimensions>> = BTreeMap::new();
+    for stock in stock_tiles {
+        sizes
+            .entry((stock.id, stock.width, stock.height))
+            .or_default()
+            .push(stock.clone());
+    }
+    sizes
+}
+
+/// Distinct sets of sheets covering `required_area`, or all sheets together when none does
+fn get_combinations(stock_tiles: &[TileDimensions], required_area: u64) -> Vec<StockSolution> {
+    let sizes: Vec<Vec<TileDimensions>> = get_sizes(stock_tiles).into_values().collect();
+    let max_area = sizes.iter().map(|sheets| sheets[0].area()).max().unwrap_or(0).max(1);
+    let min_nbr_sheets = (required_area.div_ceil(max_area) as usize).max(1);
+
+    let mut solutions = Vec::new();
+    let mut nbr_visited = 0;
+    let mut counts = vec![0; sizes.len()];
+    for nbr_sheets in min_nbr_sheets..=stock_tiles.len() {
+        collect_combinations(&sizes, 0, nbr_sheets, required_area, &mut counts, &mut solutions, &mut nbr_visited);
+        if solutions.len() >= MAX_STOCK_SOLUTIONS || nbr_visited >= MAX_COMBINATIONS {
+            break;
+        }
+    }
+    if solutions.is_empty() && !stock_tiles.is_empty() {
+        solutions.push(StockSolution::new(stock_tiles.to_vec()));
+    }
+    solutions
+}
+
+/// Spread `nbr_left` sheets over the sizes from `size_index` on, keeping the sets that cover the area
+fn collect_combinations(
+    sizes: &[Vec<TileDimensions>],
+    size_index: usize,
+    nbr_left: usize,
+    required_area: u64,
+    counts: &mut Vec<usize>,
+    solutions: &mut Vec<StockSolution>,
+    nbr_visited: &mut usize,
+) {
+    if solutions.len() >= MAX_STOCK_SOLUTIONS || *nbr_visited >= MAX_COMBINATIONS {
+        return;
+    }
+    if nbr_left == 0 || size_index == sizes.len() {
+        *nbr_visited += 1;
+        if nbr_left > 0 {
+            return;
+        }
+        let sheets: Vec<TileDimensions> = counts
+            .iter()
+            .zip(sizes)
+            .flat_map(|(count, sheets)| sheets[..*count].iter().cloned())
+            .collect();
+        let solution = StockSolution::new(sheets);
+        if solution.total_area >= required_area {
+            solutions.push(solution);
+        }
+        return;
+    }
+    for count in (0..=nbr_left.min(sizes[size_index].len())).rev() {
+        counts[size_index] = count;
+        collect_combinations(sizes, size_index + 1, nbr_left - count, required_area, counts, solutions, nbr_visited);
+    }
+    counts[size_index] = 0;
+}
@@ -13,6 +13,8 @@ pub struct TaskReport {
     pub nbr_panels: i32,
     pub percentage_done: i32,
     pub elapsed_time: Option<String>,
+    /// Estimated saw time of the current best solution
+    pub estimated_time_seconds: Option<f64>,
 }
 
 impl TaskReport {
@@ -27,6 +29,7 @@ impl TaskReport {
             nbr_panels: 0,
             percentage_done: 0,
             elapsed_time: None,
+            estimated_time_seconds: None,
         }
     }
 }
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::errors::{CoreError, Result};
+use crate::features::engine::model::task_checkpoint::TaskCheckpoint;
+
+/// Storage of the checkpoints of long-running tasks, one per task id
+pub trait TaskRepository: Debug + Send + Sync {
+    /// Store `checkpoint`, replacing the previous checkpoint of its task
+    fn save_checkpoint(&self, checkpoint: &TaskCheckpoint) -> Result<()>;
+
+    fn load_checkpoint(&self, task_id: &str) -> Result<Option<TaskCheckpoint>>;
+
+    /// false if the task had no checkpoint
+    fn delete_checkpoint(&self, task_id: &str) -> Result<bool>;
+}
+
+/// Checkpoints kept in memory, they survive a stop but not the process
+#[derive(Debug, Default)]
+pub struct InMemoryTaskRepository {
+    checkpoints: Mutex<HashMap<String, TaskCheckpoint>>,
+}
+
+impl InMemoryTaskRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TaskRepository for InMemoryTaskRepository {
+    fn save_checkpoint(&self, checkpoint: &TaskCheckpoint) -> Result<()> {
+        if let Ok(mut checkpoints) = self.checkpoints.lock() {
+            checkpoints.insert(checkpoint.task_id.clone(), checkpoint.clone());
+        }
+        Ok(())
+    }
+
+    fn load_checkpoint(&self, task_id: &str) -> Result<Option<TaskCheckpoint>> {
+        Ok(self
+            .checkpoints
+            .lock()
+            .ok()
+            .and_then(|checkpoints| checkpoints.get(task_id).cloned()))
+    }
+
+    fn delete_checkpoint(&self, task_id: &str) -> Result<bool> {
+        Ok(self
+            .checkpoints
+            .lock()
+            .is_ok_and(|mut checkpoints| checkpoints.remove(task_id).is_some()))
+    }
+}
+
+/// Checkpoints kept as `<task id>.json` files of one directory, created on the first save
+#[derive(Debug, Clone)]
+pub struct FileTaskRepository {
+    dir: PathBuf,
+}
+
+impl FileTaskRepository {
+    const EXTENSION: &'static str = "json";
+
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// File of the task `task_id`; ids are file names, so separators and dot names are rejected
+    fn get_path(&self, task_id: &str) -> Result<PathBuf> {
+        let is_valid = !task_id.trim().is_empty()
+            && task_id != "."
+            && task_id != ".."
+            && !task_id.contains(['/', '\\', '\0']);
+        if !is_valid {
+            return Err(CoreError::InvalidInput {
+                details: format!("invalid task id '{}'", task_id),
+            }
+            .into());
+        }
+        Ok(self.dir.join(format!("{}.{}", task_id, Self::EXTENSION)))
+    }
+}
+
+impl TaskRepository for FileTaskRepository {
+    fn save_checkpoint(&self, checkpoint: &TaskCheckpoint) -> Result<()> {
+        let path = self.get_path(&checkpoint.task_id)?;
+        std::fs::create_dir_all(&self.dir)?;
+        // Сначала во временный файл: сбой во время записи не портит прошлую контрольную точку
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(checkpoint)?)?;
+        std::fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    fn load_checkpoint(&self, task_id: &str) -> Result<Option<TaskCheckpoint>> {
+        let path = self.get_path(task_id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&std::fs::read(path)?)?))
+    }
+
+    fn delete_checkpoint(&self, task_id: &str) -> Result<bool> {
+        let path = self.get_path(task_id)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(path)?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+use crate::errors::{CoreError, Result};
+use crate::features::engine::model::job_template::JobTemplate;
+
+/// Storage of named job templates, see `JobTemplate`
+pub trait TemplateStore: Send + Sync {
+    /// Store `template` under its name, replacing a template of the same name
+    fn save(&self, template: &JobTemplate) -> Result<()>;
+
+    fn load(&self, name: &str) -> Result<Option<JobTemplate>>;
+
+    /// Names of the stored templates, sorted
+    fn list(&self) -> Result<Vec<String>>;
+
+    /// false if there was no template of that name
+    fn delete(&self, name: &str) -> Result<bool>;
+}
+
+/// Templates kept as `<name>.json` files of one directory, created on the first save
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::job_template::JobTemplate;
+/// use rezalnyas_core::features::engine::template_store::{FileTemplateStore, TemplateStore};
+///
+/// let dir = std::env::temp_dir().join("rezalnyas_templates_doc");
+/// let store = FileTemplateStore::new(&dir);
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0)
+///     .add_stock(2800.0, 2070.0).material("MFC 18")
+///     .build()
+///     .unwrap();
+/// store.save(&JobTemplate::from_request("standard wardrobe, 18mm MFC", &request)).unwrap();
+///
+/// assert_eq!(store.list().unwrap(), ["standard wardrobe, 18mm MFC"]);
+/// let template = store.load("standard wardrobe, 18mm MFC").unwrap().unwrap();
+/// assert_eq!(template.stock_panels[0].material, "MFC 18");
+/// assert!(store.load("kitchen").unwrap().is_none());
+/// assert!(store.save(&JobTemplate::from_request("../escape", &request)).is_err());
+///
+/// assert!(store.delete("standard wardrobe, 18mm MFC").unwrap());
+/// assert!(store.list().unwrap().is_empty());
+/// std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileTemplateStore {
+    dir: PathBuf,
+}
+
+impl FileTemplateStore {
+    const EXTENSION: &'static str = "json";
+
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// File of the template `name`; names are file names, so separators and dot names are rejected
+    fn get_path(&self, name: &str) -> Result<PathBuf> {
+        let is_valid = !name.trim().is_empty()
+            && name != "."
+            && name != ".."
+            && !name.contains(['/', '\\', '\0']);
+        if !is_valid {
+            return Err(CoreError::InvalidInput {
+                details: format!("invalid template name '{}'", name),
+            }
+            .into());
+        }
+        Ok(self.dir.join(format!("{}.{}", name, Self::EXTENSION)))
+    }
+}
+
+impl TemplateStore for FileTemplateStore {
+    fn save(&self, template: &JobTemplate) -> Result<()> {
+        let path = self.get_path(&template.name)?;
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(path, serde_json::to_vec_pretty(template)?)?;
+        Ok(())
+    }
+
+    fn load(&self, name: &str) -> Result<Option<JobTemplate>> {
+        let path = self.get_path(name)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&std::fs::read(path)?)?))
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == Self::EXTENSION) {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn delete(&self, name: &str) -> Result<bool> {
+        let path = self.get_path(name)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(path)?;
+        Ok(true)
+    }
+}
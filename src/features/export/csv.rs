@@ -0,0 +1,72 @@
+use std::fmt::Write;
+
+use crate::features::engine::model::calculation_response::CalculationResponse;
+use crate::i18n::locale::Locale;
+
+/// Column headers of the placements CSV, in English
+pub const PLACEMENTS_COLUMNS: [&str; 10] = [
+    "Sheet", "Stock ID", "Material", "Part ID", "Label", "X", "Y", "Width", "Height", "Rotated",
+];
+
+/// One row per placed panel of every sheet, for spreadsheets and label printers,
+/// with the column headers in `locale`
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::layout_evaluator::evaluate_layout;
+/// use rezalnyas_core::features::engine::model::calculation_request::PinnedPlacement;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::export::csv::export_csv;
+/// use rezalnyas_core::i18n::locale::Locale;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0).id(1).label("door, left")
+///     .add_stock(1000.0, 600.0).id(10)
+///     .build()
+///     .unwrap();
+/// let report = evaluate_layout(&request, &[PinnedPlacement::new(1, 10, 0, "0", "0", false)]);
+///
+/// let csv = export_csv(&report.response, Locale::En);
+/// assert!(csv.starts_with("Sheet,Stock ID,Material,Part ID"));
+/// assert!(csv.contains(",1,\"door, left\",0,0,400,600,false"));
+/// assert!(export_csv(&report.response, Locale::Ru).starts_with("Лист,ID заготовки,Материал"));
+/// ```
+pub fn export_csv(response: &CalculationResponse, locale: Locale) -> String {
+    let mut out = String::new();
+    let columns: Vec<&str> = PLACEMENTS_COLUMNS.iter().map(|column| locale.tr(column)).collect();
+    writeln!(out, "{}", columns.join(",")).unwrap();
+
+    for (index, mosaic) in response.mosaics.iter().enumerate() {
+        let stock_id = mosaic.request_stock_id.map(|id| id.to_string()).unwrap_or_default();
+        let material = escape(mosaic.material.as_deref().unwrap_or(""));
+        for tile in mosaic.tiles.iter().filter(|tile| tile.is_final) {
+            let Some(part_id) = tile.request_obj_id else {
+                continue;
+            };
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{}",
+                index + 1,
+                stock_id,
+                material,
+                part_id,
+                escape(tile.label.as_deref().unwrap_or("")),
+                tile.x,
+                tile.y,
+                tile.width,
+                tile.height,
+                tile.is_rotated
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+/// Quote a field holding a separator, quote or line break
+fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::enums::cut_axis::CutAxis;
+use crate::features::engine::model::calculation_response::{CalculationResponse, Cut, Mosaic, Tile};
+use crate::i18n::locale::Locale;
+
+/// Layout of the text cut list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CutListFormat {
+    /// Fixed-width text for printers and terminals
+    PlainText,
+    /// Markdown with checkboxes for the parts checklist
+    Markdown,
+}
+
+/// Part of the checklist: copies of one request panel over all sheets
+struct ChecklistPart {
+    label: String,
+    width: f64,
+    height: f64,
+    count: usize,
+}
+
+/// Classic cut list of a response: the numbered cuts of every sheet with the pieces
+/// they produce, followed by a checklist of all parts and the panels that didn't fit,
+/// worded in `locale`
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::layout_evaluator::evaluate_layout;
+/// use rezalnyas_core::features::engine::model::calculation_request::PinnedPlacement;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::export::cut_list_text::{export_cut_list, CutListFormat};
+/// use rezalnyas_core::i18n::locale::Locale;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0).id(1).label("door")
+///     .add_stock(1000.0, 600.0).id(10)
+///     .build()
+///     .unwrap();
+/// let report = evaluate_layout(&request, &[PinnedPlacement::new(1, 10, 0, "0", "0", false)]);
+///
+/// let text = export_cut_list(&report.response, CutListFormat::PlainText, Locale::En);
+/// assert!(text.contains("Sheet 1: 1000 x 600"));
+/// assert!(text.contains("1. Cut at x = 400: 1000 x 600 -> 400 x 600 [#1 door] + 600 x 600"));
+///
+/// let markdown = export_cut_list(&report.response, CutListFormat::Markdown, Locale::Ru);
+/// assert!(markdown.starts_with("# Карта раскроя"));
+/// assert!(markdown.contains("## Лист 1: 1000 x 600"));
+/// assert!(markdown.contains("- [ ] #1 door 400 x 600 x 1"));
+/// ```
+pub fn export_cut_list(response: &CalculationResponse, format: CutListFormat, locale: Locale) -> String {
+    let mut out = String::new();
+    let markdown = format == CutListFormat::Markdown;
+
+    let title = locale.tr("Cut list");
+    if markdown {
+        writeln!(out, "# {}", title).unwrap();
+    } else {
+        writeln!(out, "{}", title.to_uppercase()).unwrap();
+        writeln!(out, "{}", "=".repeat(title.chars().count())).unwrap();
+    }
+
+    for (index, mosaic) in response.mosaics.iter().enumerate() {
+        writeln!(out).unwrap();
+        write_sheet(&mut out, index + 1, mosaic, markdown, locale);
+    }
+
+    writeln!(out).unwrap();
+    if markdown {
+        writeln!(out, "## {}", locale.tr("Parts checklist")).unwrap();
+        writeln!(out).unwrap();
+    } else {
+        writeln!(out, "{}", locale.tr("Parts checklist").to_uppercase()).unwrap();
+    }
+    for (id, part) in get_checklist(response) {
+        let checkbox = if markdown { "- [ ]" } else { "  [ ]" };
+        writeln!(
+            out,
+            "{} #{}{} {} x {} x {}",
+            checkbox,
+            id,
+            format_label(&part.label),
+            part.width,
+            part.height,
+            part.count
+        )
+        .unwrap();
+    }
+
+    if !response.no_fit_panels.is_empty() {
+        writeln!(out).unwrap();
+        if markdown {
+            writeln!(out, "## {}", locale.tr("Not placed")).unwrap();
+            writeln!(out).unwrap();
+        } else {
+            writeln!(out, "{}", locale.tr("Not placed").to_uppercase()).unwrap();
+        }
+        for panel in &response.no_fit_panels {
+            writeln!(
+                out,
+                "{} #{}{} {} x {} x {}",
+                if markdown { "-" } else { " " },
+                panel.id,
+                format_label(panel.label.as_deref().unwrap_or("")),
+                panel.width,
+                panel.height,
+                panel.count
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+fn write_sheet(out: &mut String, number: usize, mosaic: &Mosaic, markdown: bool, locale: Locale) {
+    let Some(sheet) = mosaic.tiles.first() else {
+        return;
+    };
+
+    let mut title = format!("{} {}: {} x {}", locale.tr("Sheet"), number, sheet.width, sheet.height);
+    if let Some(material) = &mosaic.material {
+        write!(title, ", {}", material).unwrap();
+    }
+    if let Some(stock_id) = mosaic.request_stock_id {
+        write!(title, ", {} #{}{}", locale.tr("stock"), stock_id, format_label(mosaic.stock_label.as_deref().unwrap_or(""))).unwrap();
+    }
+    if markdown {
+        writeln!(out, "## {}", title).unwrap();
+        writeln!(out).unwrap();
+    } else {
+        writeln!(out, "{}", title).unwrap();
+        writeln!(out, "{}", "-".repeat(title.chars().count())).unwrap();
+    }
+
+    for (index, cut) in mosaic.cuts.iter().enumerate() {
+        let indent = if markdown { "" } else { "  " };
+        writeln!(out, "{}{}. {}", indent, index + 1, describe_cut(mosaic, cut, locale)).unwrap();
+    }
+
+    let pieces: Vec<&Tile> = mosaic
+        .tiles
+        .iter()
+        .filter(|tile| tile.is_final && tile.request_obj_id.is_some())
+        .collect();
+    if !pieces.is_empty() {
+        writeln!(out).unwrap();
+        if markdown {
+            writeln!(out, "### {}\n", locale.tr("Pieces")).unwrap();
+        } else {
+            writeln!(out, "  {}:", locale.tr("Pieces")).unwrap();
+        }
+        for tile in pieces {
+            writeln!(
+                out,
+                "{}{}",
+                if markdown { "- [ ] " } else { "  - " },
+                describe_tile(tile, locale)
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn describe_cut(mosaic: &Mosaic, cut: &Cut, locale: Locale) -> String {
+    let position = match cut.axis {
+        CutAxis::X => format!("x = {}", cut.position),
+        CutAxis::Y => format!("y = {}", cut.position),
+    };
+    let child = |id: i32| {
+        mosaic
+            .tiles
+            .iter()
+            .find(|tile| tile.id == id)
+            .map(|tile| describe_tile(tile, locale))
+            .unwrap_or_else(|| "?".to_string())
+    };
+    format!(
+        "{} {}: {} x {} -> {} + {}",
+        locale.tr("Cut at"),
+        position,
+        cut.node.width,
+        cut.node.height,
+        child(cut.piece_ids[0]),
+        child(cut.piece_ids[1])
+    )
+}
+
+fn describe_tile(tile: &Tile, locale: Locale) -> String {
+    let mut text = format!("{} x {}", tile.width, tile.height);
+    if let (true, Some(id)) = (tile.is_final, tile.request_obj_id) {
+        let rotated = if tile.is_rotated { format!(", {}", locale.tr("rotated")) } else { String::new() };
+        write!(text, " [#{}{}{}]", id, format_label(tile.label.as_deref().unwrap_or("")), rotated).unwrap();
+    }
+    text
+}
+
+fn format_label(label: &str) -> String {
+    if label.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", label)
+    }
+}
+
+/// Placed copies per request panel id, in their original orientation
+fn get_checklist(response: &CalculationResponse) -> BTreeMap<i32, ChecklistPart> {
+    let mut parts: BTreeMap<i32, ChecklistPart> = BTreeMap::new();
+    for tile in response.mosaics.iter().flat_map(|mosaic| &mosaic.tiles) {
+        let Some(id) = tile.request_obj_id.filter(|_| tile.is_final) else {
+            continue;
+        };
+        let (width, height) = if tile.is_rotated {
+            (tile.height, tile.width)
+        } else {
+            (tile.width, tile.height)
+        };
+        parts
+            .entry(id)
+            .or_insert_with(|| ChecklistPart {
+                label: tile.label.clone().unwrap_or_default(),
+                width,
+                height,
+                count: 0,
+            })
+            .count += 1;
+    }
+    parts
+}
@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+
+use crate::errors::{CoreError, Result};
+use crate::features::engine::model::calculation_response::CalculationResponse;
+use crate::i18n::locale::Locale;
+use crate::features::export::{
+    csv::export_csv,
+    cut_list_text::{export_cut_list, CutListFormat},
+    svg::export_svg,
+};
+
+/// Formats written by `ExportManager::export_all`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportConfig {
+    /// `<name>.json`, the response itself
+    pub json: bool,
+    /// `<name>.csv`, see `export_csv`
+    pub csv: bool,
+    /// `<name>_sheet<n>.svg` for every sheet, see `export_svg`
+    pub svg: bool,
+    /// `<name>.txt`, the plain text cut list
+    pub cut_list: bool,
+    /// `<name>.md`, the markdown cut list
+    pub markdown: bool,
+    /// `<name>.xlsx`, see `export_xlsx`
+    #[cfg(feature = "xlsx")]
+    pub xlsx: bool,
+    /// `<name>_sheet<n>.png` for every sheet at this dpi, see `render_png`, None - no PNG
+    #[cfg(feature = "png")]
+    pub png_dpi: Option<u32>,
+    /// Language of the headers and labels of the csv, cut lists and xlsx
+    pub locale: Locale,
+}
+
+impl ExportConfig {
+    /// Every format the crate can write
+    pub fn all() -> Self {
+        Self {
+            json: true,
+            csv: true,
+            svg: true,
+            cut_list: true,
+            markdown: true,
+            #[cfg(feature = "xlsx")]
+            xlsx: true,
+            #[cfg(feature = "png")]
+            png_dpi: Some(crate::features::export::png::DEFAULT_PNG_DPI),
+            locale: Locale::default(),
+        }
+    }
+}
+
+/// Writes the exports of a response side by side
+pub struct ExportManager;
+
+impl ExportManager {
+    /// Render the formats of `config` concurrently into `out_dir`, files are named after the
+    /// response task id. Returns the written files in the order of `ExportConfig`.
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::engine::layout_evaluator::evaluate_layout;
+    /// use rezalnyas_core::features::engine::model::calculation_request::PinnedPlacement;
+    /// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+    /// use rezalnyas_core::features::export::export_manager::{ExportConfig, ExportManager};
+    ///
+    /// let request = CalculationRequestBuilder::new()
+    ///     .add_panel(400.0, 600.0).id(1)
+    ///     .add_stock(1000.0, 600.0).id(10)
+    ///     .build()
+    ///     .unwrap();
+    /// let report = evaluate_layout(&request, &[PinnedPlacement::new(1, 10, 0, "0", "0", false)]);
+    ///
+    /// let out_dir = std::env::temp_dir().join("rezalnyas_export_all_doc");
+    /// let config = ExportConfig { json: true, svg: true, ..ExportConfig::default() };
+    /// let files = ExportManager::export_all(&report.response, config, &out_dir).unwrap();
+    ///
+    /// let names: Vec<_> = files.iter().map(|file| file.file_name().unwrap().to_str().unwrap()).collect();
+    /// assert_eq!(names, ["layout.json", "layout_sheet1.svg"]);
+    /// assert!(files.iter().all(|file| file.exists()));
+    /// std::fs::remove_dir_all(&out_dir).unwrap();
+    /// ```
+    pub fn export_all<P: AsRef<Path>>(
+        response: &CalculationResponse,
+        config: ExportConfig,
+        out_dir: P,
+    ) -> Result<Vec<PathBuf>> {
+        let out_dir = out_dir.as_ref();
+        std::fs::create_dir_all(out_dir)?;
+        let name = Self::get_file_stem(response);
+        let path = |suffix: &str| out_dir.join(format!("{}{}", name, suffix));
+        let locale = config.locale;
+
+        let mut jobs: Vec<Box<dyn FnOnce() -> Result<Vec<PathBuf>> + Send + '_>> = Vec::new();
+        if config.json {
+            let file = path(".json");
+            jobs.push(Box::new(move || {
+                std::fs::write(&file, serde_json::to_vec_pretty(response)?)?;
+                Ok(vec![file])
+            }));
+        }
+        if config.csv {
+            let file = path(".csv");
+            jobs.push(Box::new(move || Self::write(file, export_csv(response, locale))));
+        }
+        if config.svg {
+            let files: Vec<PathBuf> = (1..=response.mosaics.len())
+                .map(|number| path(&format!("_sheet{}.svg", number)))
+                .collect();
+            jobs.push(Box::new(move || {
+                for (file, mosaic) in files.iter().zip(&response.mosaics) {
+                    std::fs::write(file, export_svg(mosaic))?;
+                }
+                Ok(files)
+            }));
+        }
+        if config.cut_list {
+            let file = path(".txt");
+            jobs.push(Box::new(move || {
+                Self::write(file, export_cut_list(response, CutListFormat::PlainText, locale))
+            }));
+        }
+        if config.markdown {
+            let file = path(".md");
+            jobs.push(Box::new(move || {
+                Self::write(file, export_cut_list(response, CutListFormat::Markdown, locale))
+            }));
+        }
+        #[cfg(feature = "xlsx")]
+        if config.xlsx {
+            let file = path(".xlsx");
+            jobs.push(Box::new(move || {
+                crate::features::export::xlsx::save_xlsx(response, &file, locale)?;
+                Ok(vec![file])
+            }));
+        }
+        #[cfg(feature = "png")]
+        if let Some(dpi) = config.png_dpi {
+            let files: Vec<PathBuf> = (1..=response.mosaics.len())
+                .map(|number| path(&format!("_sheet{}.png", number)))
+                .collect();
+            jobs.push(Box::new(move || {
+                for (file, mosaic) in files.iter().zip(&response.mosaics) {
+                    std::fs::write(file, crate::features::export::png::render_png(mosaic, dpi as f64)?)?;
+                }
+                Ok(files)
+            }));
+        }
+
+        let results: Vec<Result<Vec<PathBuf>>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = jobs.into_iter().map(|job| scope.spawn(job)).collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| {
+                        Err(CoreError::Internal {
+                            message: "Export thread panicked".to_string(),
+                        }
+                        .into())
+                    })
+                })
+                .collect()
+        });
+
+        let mut files = Vec::new();
+        for result in results {
+            files.extend(result?);
+        }
+        Ok(files)
+    }
+
+    /// Task id of the response with path separators replaced, `layout` without one
+    fn get_file_stem(response: &CalculationResponse) -> String {
+        response
+            .task_id
+            .as_deref()
+            .filter(|id| !id.is_empty())
+            .map(|id| id.replace(['/', '\\'], "_"))
+            .unwrap_or_else(|| "layout".to_string())
+    }
+
+    fn write(file: PathBuf, content: String) -> Result<Vec<PathBuf>> {
+        std::fs::write(&file, content)?;
+        Ok(vec![file])
+    }
+}
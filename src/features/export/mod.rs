@@ -0,0 +1,9 @@
+pub mod csv;
+pub mod cut_list_text;
+pub mod export_manager;
+pub mod ndjson;
+#[cfg(feature = "png")]
+pub mod png;
+pub mod svg;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
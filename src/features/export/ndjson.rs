@@ -0,0 +1,41 @@
+use std::io::Write;
+use std::sync::mpsc::Receiver;
+
+use crate::errors::Result;
+use crate::features::engine::model::task_event::{TaskEvent, TaskEventKind};
+
+/// Write task events as NDJSON, one JSON object per line, flushed after each line so a pipe
+/// or a chunked response sees progress ticks and new best solutions as they happen.
+/// Stops after the first finished or failed task, or when the service is dropped
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::cutlist_optimizer_service_impl::CutListOptimizerServiceImpl;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::export::ndjson::write_ndjson_events;
+///
+/// let service = CutListOptimizerServiceImpl::new(2, false).unwrap();
+/// let events = service.subscribe();
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0)
+///     .add_stock(1000.0, 600.0)
+///     .build()
+///     .unwrap();
+/// service.submit_task(request).unwrap();
+///
+/// let mut out = Vec::new();
+/// write_ndjson_events(&events, &mut out).unwrap();
+/// let lines: Vec<&str> = std::str::from_utf8(&out).unwrap().lines().collect();
+/// assert!(lines[0].contains("\"Submitted\""));
+/// assert!(lines.last().unwrap().contains("\"Finished\""));
+/// ```
+pub fn write_ndjson_events<W: Write>(events: &Receiver<TaskEvent>, mut writer: W) -> Result<()> {
+    for event in events {
+        serde_json::to_writer(&mut writer, &event)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        if matches!(event.kind, TaskEventKind::Finished | TaskEventKind::Error { .. }) {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use tiny_skia::{Color, Paint, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+use crate::constants::UtilityConstants;
+use crate::errors::{CoreError, Result};
+use crate::features::engine::model::calculation_response::Mosaic;
+use crate::features::export::svg::SheetLayout;
+
+/// Resolution of the PNG files of `ExportConfig::all`
+pub const DEFAULT_PNG_DPI: u32 = 150;
+
+/// Raster drawing of one response sheet, the same picture as `export_svg`, for clients
+/// without SVG support. The mosaic is in millimeters, `dpi` sets the pixels per inch.
+/// Labels are drawn with a built-in pixel font: digits, latin letters and `#-._`
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::layout_evaluator::evaluate_layout;
+/// use rezalnyas_core::features::engine::model::calculation_request::PinnedPlacement;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::export::png::render_png;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0).id(1).label("door")
+///     .add_stock(1000.0, 600.0).id(10)
+///     .build()
+///     .unwrap();
+/// let report = evaluate_layout(&request, &[PinnedPlacement::new(1, 10, 0, "0", "0", false)]);
+///
+/// let png = render_png(&report.response.mosaics[0], 25.4).unwrap();
+/// assert_eq!(&png[1..4], b"PNG");
+/// // Ширина и высота из заголовка IHDR
+/// assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 1000);
+/// assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 600);
+/// ```
+pub fn render_png(mosaic: &Mosaic, dpi: f64) -> Result<Vec<u8>> {
+    let sheet = SheetLayout::new(mosaic).ok_or_else(|| CoreError::InvalidInput {
+        details: "mosaic without tiles can't be rendered".to_string(),
+    })?;
+    let scale = (dpi / UtilityConstants::INCHES_TO_MM) as f32;
+    let (width, height) = ((sheet.width as f32 * scale).ceil(), (sheet.height as f32 * scale).ceil());
+    let mut pixmap = Pixmap::new(width.max(1.0) as u32, height.max(1.0) as u32).ok_or_else(|| {
+        CoreError::InvalidInput {
+            details: format!("{}x{} pixels is too big for a PNG, lower the dpi", width, height),
+        }
+    })?;
+
+    let sheet_rect = (0.0, 0.0, sheet.width as f32, sheet.height as f32);
+    draw_rect(&mut pixmap, sheet_rect, Color::from_rgba8(0xe0, 0xe0, 0xe0, 0xff), scale);
+    for panel in &sheet.panels {
+        let rect = (panel.x as f32, panel.y as f32, panel.width as f32, panel.height as f32);
+        draw_rect(&mut pixmap, rect, Color::from_rgba8(0xc8, 0xe6, 0xc9, 0xff), scale);
+        if let Some(text) = &panel.text {
+            // Высота заглавной буквы - около 0.7 размера шрифта, как в SVG
+            let pixel = (sheet.font_size as f32 * scale * 0.7 / GLYPH_HEIGHT as f32).round().max(1.0);
+            let center = (
+                (panel.x + panel.width / 2.0) as f32 * scale,
+                (panel.y + panel.height / 2.0) as f32 * scale,
+            );
+            draw_text(&mut pixmap, text, center, pixel);
+        }
+    }
+
+    pixmap.encode_png().map_err(|e| {
+        CoreError::Internal {
+            message: format!("PNG encoding failed: {}", e),
+        }
+        .into()
+    })
+}
+
+/// Filled rectangle with a one pixel black border, in sheet units
+fn draw_rect(pixmap: &mut Pixmap, (x, y, width, height): (f32, f32, f32, f32), fill: Color, scale: f32) {
+    let Some(rect) = Rect::from_xywh(x, y, width, height) else {
+        return;
+    };
+    let transform = Transform::from_scale(scale, scale);
+    let mut paint = Paint::default();
+    paint.set_color(fill);
+    pixmap.fill_rect(rect, &paint, transform, None);
+
+    paint.set_color(Color::BLACK);
+    let stroke = Stroke {
+        width: 1.0 / scale,
+        ..Stroke::default()
+    };
+    let path = PathBuilder::from_rect(rect);
+    pixmap.stroke_path(&path, &paint, &stroke, transform, None);
+}
+
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+
+/// Text centered at `center` in pixels, every dot of a glyph is `pixel` pixels wide
+fn draw_text(pixmap: &mut Pixmap, text: &str, (center_x, center_y): (f32, f32), pixel: f32) {
+    let advance = (GLYPH_WIDTH + 1) as f32 * pixel;
+    let text_width = text.chars().count() as f32 * advance - pixel;
+    let (left, top) = (
+        (center_x - text_width / 2.0).round(),
+        (center_y - GLYPH_HEIGHT as f32 * pixel / 2.0).round(),
+    );
+    let mut paint = Paint::default();
+    paint.set_color(Color::BLACK);
+
+    for (index, c) in text.chars().enumerate() {
+        // Символы без глифа остаются пробелом
+        let Some(rows) = glyph(c) else {
+            continue;
+        };
+        for (row, bits) in rows.iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                    continue;
+                }
+                let x = left + index as f32 * advance + column as f32 * pixel;
+                if let Some(dot) = Rect::from_xywh(x, top + row as f32 * pixel, pixel, pixel) {
+                    pixmap.fill_rect(dot, &paint, Transform::identity(), None);
+                }
+            }
+        }
+    }
+}
+
+/// Rows of a 3x5 glyph, the high bit of each row is its left dot
+fn glyph(c: char) -> Option<[u8; GLYPH_HEIGHT]> {
+    Some(match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        _ => return None,
+    })
+}
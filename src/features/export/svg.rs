@@ -0,0 +1,129 @@
+use std::fmt::Write;
+
+use crate::enums::coordinate_system::CoordinateSystem;
+use crate::features::engine::model::calculation_response::Mosaic;
+
+/// Drawing of one response sheet: the sheet, its panels with their labels and the waste,
+/// in request units with y pointing down whatever the coordinate system of the mosaic
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::layout_evaluator::evaluate_layout;
+/// use rezalnyas_core::features::engine::model::calculation_request::PinnedPlacement;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::export::svg::export_svg;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0).id(1).label("door")
+///     .add_stock(1000.0, 600.0).id(10)
+///     .build()
+///     .unwrap();
+/// let report = evaluate_layout(&request, &[PinnedPlacement::new(1, 10, 0, "0", "0", false)]);
+///
+/// let svg = export_svg(&report.response.mosaics[0]);
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains(r#"viewBox="0 0 1000 600""#));
+/// assert!(svg.contains(">#1 door</text>"));
+/// ```
+pub fn export_svg(mosaic: &Mosaic) -> String {
+    let mut out = String::new();
+    let Some(sheet) = SheetLayout::new(mosaic) else {
+        return out;
+    };
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" width="{}" height="{}">"#,
+        sheet.width, sheet.height, sheet.width, sheet.height
+    )
+    .unwrap();
+    writeln!(
+        out,
+        r##"  <rect x="0" y="0" width="{}" height="{}" fill="#e0e0e0" stroke="#000000"/>"##,
+        sheet.width, sheet.height
+    )
+    .unwrap();
+
+    for panel in &sheet.panels {
+        writeln!(
+            out,
+            r##"  <rect x="{}" y="{}" width="{}" height="{}" fill="#c8e6c9" stroke="#000000"/>"##,
+            panel.x, panel.y, panel.width, panel.height
+        )
+        .unwrap();
+        let Some(text) = &panel.text else {
+            continue;
+        };
+        writeln!(
+            out,
+            r#"  <text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="middle">{}</text>"#,
+            panel.x + panel.width / 2.0,
+            panel.y + panel.height / 2.0,
+            sheet.font_size,
+            escape(text)
+        )
+        .unwrap();
+    }
+    writeln!(out, "</svg>").unwrap();
+    out
+}
+
+/// What a drawing of a sheet shows, shared by the SVG and PNG exporters
+pub(crate) struct SheetLayout {
+    pub width: f64,
+    pub height: f64,
+    pub font_size: f64,
+    pub panels: Vec<PanelLayout>,
+}
+
+/// Placed panel of a `SheetLayout`, y pointing down
+pub(crate) struct PanelLayout {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// "#<part id> <label>", None for panels not from the request
+    pub text: Option<String>,
+}
+
+impl SheetLayout {
+    /// None for a mosaic without tiles
+    pub fn new(mosaic: &Mosaic) -> Option<Self> {
+        let sheet = mosaic.tiles.first()?;
+        let panels = mosaic
+            .tiles
+            .iter()
+            .skip(1)
+            .filter(|tile| tile.is_final)
+            .map(|tile| PanelLayout {
+                x: tile.x,
+                y: mosaic
+                    .coordinate_system
+                    .convert_y(CoordinateSystem::TopLeftYDown, tile.y, tile.height, sheet.height),
+                width: tile.width,
+                height: tile.height,
+                text: tile.request_obj_id.map(|part_id| {
+                    let mut text = format!("#{}", part_id);
+                    if let Some(label) = tile.label.as_deref().filter(|label| !label.is_empty()) {
+                        write!(text, " {}", label).unwrap();
+                    }
+                    text
+                }),
+            })
+            .collect();
+
+        Some(Self {
+            width: sheet.width,
+            height: sheet.height,
+            font_size: (sheet.width.min(sheet.height) / 30.0).max(1.0),
+            panels,
+        })
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
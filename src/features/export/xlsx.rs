@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+
+use crate::enums::cut_axis::CutAxis;
+use crate::errors::{CoreError, Result};
+use crate::features::engine::model::calculation_response::CalculationResponse;
+use crate::i18n::locale::Locale;
+use crate::scaled_math::PrecisionAnalyzer;
+
+/// Column headers of the tabs in English, in the order the ERP import reads them
+pub const PARTS_COLUMNS: [&str; 7] = ["Part ID", "Label", "Material", "Width", "Height", "Quantity", "Not placed"];
+pub const SHEETS_COLUMNS: [&str; 11] = [
+    "Sheet",
+    "Stock ID",
+    "Stock label",
+    "Material",
+    "Width",
+    "Height",
+    "Parts",
+    "Used area",
+    "Wasted area",
+    "Used %",
+    "Cut length",
+];
+pub const CUTS_COLUMNS: [&str; 8] = ["Sheet", "Cut", "Stage", "Direction", "Position", "Width", "Height", "Part ID"];
+pub const SUMMARY_COLUMNS: [&str; 2] = ["Metric", "Value"];
+
+/// Workbook with Parts, Sheets, Cuts and Summary tabs of a response, tab names, headers
+/// and metric names in `locale`
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::layout_evaluator::evaluate_layout;
+/// use rezalnyas_core::features::engine::model::calculation_request::PinnedPlacement;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::export::xlsx::export_xlsx;
+/// use rezalnyas_core::i18n::locale::Locale;
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(400.0, 600.0).id(1)
+///     .add_stock(1000.0, 600.0).id(10)
+///     .build()
+///     .unwrap();
+/// let report = evaluate_layout(&request, &[PinnedPlacement::new(1, 10, 0, "0", "0", false)]);
+///
+/// let workbook = export_xlsx(&report.response, Locale::Ru).unwrap();
+/// assert_eq!(&workbook[..2], b"PK");
+/// ```
+pub fn export_xlsx(response: &CalculationResponse, locale: Locale) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let header = Format::new().set_bold();
+
+    write_parts(add_sheet(&mut workbook, "Parts", &PARTS_COLUMNS, &header, locale)?, response)
+        .map_err(xlsx_error)?;
+    write_sheets(add_sheet(&mut workbook, "Sheets", &SHEETS_COLUMNS, &header, locale)?, response)
+        .map_err(xlsx_error)?;
+    write_cuts(add_sheet(&mut workbook, "Cuts", &CUTS_COLUMNS, &header, locale)?, response, locale)
+        .map_err(xlsx_error)?;
+    write_summary(add_sheet(&mut workbook, "Summary", &SUMMARY_COLUMNS, &header, locale)?, response, locale)
+        .map_err(xlsx_error)?;
+
+    Ok(workbook.save_to_buffer().map_err(xlsx_error)?)
+}
+
+/// Write the workbook of `export_xlsx` to a file
+pub fn save_xlsx<P: AsRef<Path>>(response: &CalculationResponse, path: P, locale: Locale) -> Result<()> {
+    std::fs::write(path, export_xlsx(response, locale)?)?;
+    Ok(())
+}
+
+fn add_sheet<'a>(
+    workbook: &'a mut Workbook,
+    name: &'static str,
+    columns: &[&'static str],
+    header: &Format,
+    locale: Locale,
+) -> Result<&'a mut Worksheet> {
+    let worksheet = workbook.add_worksheet().set_name(locale.tr(name)).map_err(xlsx_error)?;
+    for (column, title) in columns.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, column as u16, locale.tr(title), header)
+            .map_err(xlsx_error)?;
+    }
+    Ok(worksheet)
+}
+
+fn write_parts(worksheet: &mut Worksheet, response: &CalculationResponse) -> std::result::Result<(), XlsxError> {
+    let not_placed: HashMap<u32, i32> = response
+        .no_fit_panels
+        .iter()
+        .map(|panel| (panel.id, panel.count))
+        .collect();
+
+    for (index, panel) in response.request.panels.iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_number(row, 0, panel.id)?;
+        worksheet.write_string(row, 1, &panel.label)?;
+        worksheet.write_string(row, 2, panel.get_material_key())?;
+        worksheet.write_number(row, 3, PrecisionAnalyzer::parse_f64(&panel.width).unwrap_or(0.0))?;
+        worksheet.write_number(row, 4, PrecisionAnalyzer::parse_f64(&panel.height).unwrap_or(0.0))?;
+        worksheet.write_number(row, 5, panel.count)?;
+        worksheet.write_number(row, 6, not_placed.get(&panel.id).copied().unwrap_or(0))?;
+    }
+    Ok(())
+}
+
+fn write_sheets(worksheet: &mut Worksheet, response: &CalculationResponse) -> std::result::Result<(), XlsxError> {
+    for (index, mosaic) in response.mosaics.iter().enumerate() {
+        let row = index as u32 + 1;
+        let (width, height) = mosaic
+            .tiles
+            .first()
+            .map_or((0.0, 0.0), |sheet| (sheet.width, sheet.height));
+        let nbr_parts = mosaic
+            .tiles
+            .iter()
+            .filter(|tile| tile.is_final && tile.request_obj_id.is_some())
+            .count();
+
+        worksheet.write_number(row, 0, row)?;
+        if let Some(stock_id) = mosaic.request_stock_id {
+            worksheet.write_number(row, 1, stock_id)?;
+        }
+        worksheet.write_string(row, 2, mosaic.stock_label.as_deref().unwrap_or(""))?;
+        worksheet.write_string(row, 3, mosaic.material.as_deref().unwrap_or(""))?;
+        worksheet.write_number(row, 4, width)?;
+        worksheet.write_number(row, 5, height)?;
+        worksheet.write_number(row, 6, nbr_parts as u32)?;
+        worksheet.write_number(row, 7, mosaic.used_area)?;
+        worksheet.write_number(row, 8, mosaic.wasted_area)?;
+        worksheet.write_number(row, 9, mosaic.used_area_ratio as f64 * 100.0)?;
+        worksheet.write_number(row, 10, mosaic.cut_length)?;
+    }
+    Ok(())
+}
+
+fn write_cuts(
+    worksheet: &mut Worksheet,
+    response: &CalculationResponse,
+    locale: Locale,
+) -> std::result::Result<(), XlsxError> {
+    let mut row = 1;
+    for (sheet_index, mosaic) in response.mosaics.iter().enumerate() {
+        for (cut_index, cut) in mosaic.cuts.iter().enumerate() {
+            // Вертикальный рез проходит по x, горизонтальный - по y
+            let direction = match cut.axis {
+                CutAxis::X => "Vertical",
+                CutAxis::Y => "Horizontal",
+            };
+            worksheet.write_number(row, 0, sheet_index as u32 + 1)?;
+            worksheet.write_number(row, 1, cut_index as u32 + 1)?;
+            worksheet.write_number(row, 2, cut.stage)?;
+            worksheet.write_string(row, 3, locale.tr(direction))?;
+            worksheet.write_number(row, 4, cut.position)?;
+            worksheet.write_number(row, 5, cut.node.width)?;
+            worksheet.write_number(row, 6, cut.node.height)?;
+            if let Some(part_id) = cut.request_obj_id {
+                worksheet.write_number(row, 7, part_id)?;
+            }
+            row += 1;
+        }
+    }
+    Ok(())
+}
+
+fn write_summary(
+    worksheet: &mut Worksheet,
+    response: &CalculationResponse,
+    locale: Locale,
+) -> std::result::Result<(), XlsxError> {
+    let nbr_not_placed: i32 = response.no_fit_panels.iter().map(|panel| panel.count).sum();
+    let mut metrics = vec![
+        ("Sheets", response.mosaics.len() as f64),
+        ("Used area", response.total_used_area),
+        ("Wasted area", response.total_wasted_area),
+        ("Used %", response.total_used_area_ratio * 100.0),
+        ("Cuts", response.total_nbr_cuts as f64),
+        ("Cut length", response.total_cut_length),
+        ("Not placed parts", nbr_not_placed as f64),
+    ];
+    if let Some(seconds) = response.total_estimated_time_seconds {
+        metrics.push(("Estimated time, s", seconds));
+    }
+    if let Some(cost) = &response.cost_summary {
+        metrics.push(("Cuts cost", cost.cuts_cost.amount().to_f64()));
+        metrics.push(("Sheets cost", cost.sheets_cost.amount().to_f64()));
+        metrics.push(("Total cost", cost.total_cost.amount().to_f64()));
+    }
+
+    for (index, (name, value)) in metrics.into_iter().enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string(row, 0, locale.tr(name))?;
+        worksheet.write_number(row, 1, value)?;
+    }
+    Ok(())
+}
+
+fn xlsx_error(err: XlsxError) -> CoreError {
+    CoreError::Internal {
+        message: format!("Failed to write xlsx workbook: {}", err),
+    }
+}
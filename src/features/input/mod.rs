@@ -2,4 +2,6 @@
 
 pub mod traits;
 pub mod models;
+pub mod normalizer;
+pub mod request_reader;
 
@@ -5,6 +5,7 @@ use std::fmt;
 use std::hash::{Hash, Hasher};
 
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub struct GroupedTileDimensions {
     pub group: u8,
     pub instance: TileDimensions,
@@ -68,10 +69,12 @@ impl fmt::Display for GroupedTileDimensions {
 impl PartialEq for GroupedTileDimensions {
     fn eq(&self, other: &Self) -> bool {
         // Compare id, width, height, and group (as mentioned in the comment in basic_usage.rs)
-        self.instance.id == other.instance.id &&
-        self.instance.width == other.instance.width &&
-        self.instance.height == other.instance.height &&
+        // Копии одной интернированной плитки равны без сравнения полей
         self.group == other.group
+            && (self.instance.ptr_eq(&other.instance)
+                || (self.instance.id == other.instance.id
+                    && self.instance.width == other.instance.width
+                    && self.instance.height == other.instance.height))
     }
 }
 
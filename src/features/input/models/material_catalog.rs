@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::enums::orientation::Orientation;
+use crate::errors::Result;
+
+/// Material of the catalog, requests may name it by id, display name or any alias
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::calculation_request::Edge;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::input::models::material_catalog::MaterialCatalog;
+/// use rezalnyas_core::features::input::normalizer::normalize_request;
+///
+/// let catalog = MaterialCatalog::from_json(r#"{
+///     "materials": [{ "id": "abs-2", "name": "ABS 2mm", "edge_band_thickness": "2" }]
+/// }"#).unwrap();
+/// let edge = Edge {
+///     top: Some("ABS 2mm".to_string()),
+///     left: Some("abs-2".to_string()),
+///     bottom: None,
+///     right: Some("abs-2".to_string()),
+/// };
+/// let request = CalculationRequestBuilder::new()
+///     .material_catalog(catalog)
+///     .add_panel(600.0, 400.0).label("Door").edge(edge)
+///     .add_stock(2800.0, 2070.0)
+///     .build()
+///     .unwrap();
+///
+/// let panel = &normalize_request(&request).unwrap().request.panels[0];
+/// assert_eq!((panel.width.as_str(), panel.height.as_str()), ("596", "398"));
+/// assert_eq!(panel.label, "Door 600x400");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogMaterial {
+    /// Material key the engine groups panels and stock by
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Thickness given to rows of the material that don't set one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thickness: Option<String>,
+    /// Grain given to rows of the material that don't set one
+    #[serde(default)]
+    pub orientation: Orientation,
+    /// Price of one sheet of the material
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_per_sheet: Option<f64>,
+    /// Edge band materials only: band thickness in millimeters, taken off the cut size of
+    /// every panel side banded with it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge_band_thickness: Option<String>,
+}
+
+/// Known materials used to fold free-text material names of a request into one bucket
+///
+/// ```rust
+/// use rezalnyas_core::features::input::models::material_catalog::MaterialCatalog;
+///
+/// let catalog = MaterialCatalog::from_json(r#"{
+///     "materials": [{
+///         "id": "mfc-white-16",
+///         "name": "MFC White",
+///         "aliases": ["мфц белый", "ЛДСП белый"],
+///         "thickness": "16"
+///     }]
+/// }"#).unwrap();
+///
+/// assert_eq!(catalog.find(" mfc  WHITE ").unwrap().id, "mfc-white-16");
+/// assert_eq!(catalog.find("МФЦ Белый").unwrap().id, "mfc-white-16");
+/// assert!(catalog.find("oak veneer").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MaterialCatalog {
+    pub materials: Vec<CatalogMaterial>,
+}
+
+impl MaterialCatalog {
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Material whose id, name or alias matches `material`, ignoring case and extra spaces
+    pub fn find(&self, material: &str) -> Option<&CatalogMaterial> {
+        let key = Self::normalize_name(material);
+        self.materials.iter().find(|entry| {
+            std::iter::once(&entry.id)
+                .chain(std::iter::once(&entry.name))
+                .chain(&entry.aliases)
+                .any(|name| Self::normalize_name(name) == key)
+        })
+    }
+
+    fn normalize_name(name: &str) -> String {
+        name.split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
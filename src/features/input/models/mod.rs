@@ -4,6 +4,9 @@
 
 pub mod tile_dimensions;
 pub mod grouped_tile_dimensions;
+pub mod panel_input;
+pub mod normalization_report;
+pub mod material_catalog;
 
 
 
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::length_unit::LengthUnit;
+
+/// Request rows merged into one because they only differed by id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergedRows {
+    /// Id of the row that was kept
+    pub id: u32,
+    pub merged_ids: Vec<u32>,
+    /// Count of the kept row after the merge
+    pub count: u32,
+    pub is_stock: bool,
+}
+
+/// Material name of the request replaced by the id of a catalog material
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedMaterial {
+    pub material: String,
+    pub catalog_id: String,
+}
+
+/// What the input normalization changed in a request
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NormalizationReport {
+    /// Unit the dimensions were converted from, None - no conversion
+    pub converted_from: Option<LengthUnit>,
+    pub merged_rows: Vec<MergedRows>,
+    /// Panel rows computed as one row and mapped back to their own ids in the response
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aggregated_rows: Vec<MergedRows>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolved_materials: Vec<ResolvedMaterial>,
+    pub nbr_panels: usize,
+    pub nbr_stock_panels: usize,
+}
+
+impl NormalizationReport {
+    pub fn has_changes(&self) -> bool {
+        self.converted_from.is_some() || !self.merged_rows.is_empty() || !self.resolved_materials.is_empty()
+    }
+}
@@ -0,0 +1,105 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{length_unit::LengthUnit, orientation::Orientation};
+use crate::errors::{ComputationError, CoreError, Result};
+use crate::features::engine::model::calculation_request::Panel;
+use crate::scaled_math::PrecisionAnalyzer;
+
+use super::tile_dimensions::{TileDimensions, TileDimensionsData};
+
+/// Panel row of a request with parsed dimensions in millimeters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelInput {
+    pub id: u32,
+    pub width: f64,
+    pub height: f64,
+    pub count: u32,
+    /// Material key, see `Panel::get_material_key`
+    pub material: String,
+    pub label: String,
+    pub orientation: Orientation,
+    /// Minimum distance to the sheet edges in millimeters, 0 - none
+    #[serde(default)]
+    pub edge_clearance: f64,
+    pub is_stock: bool,
+    /// See `Panel::priority`, 0 when not set
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acquired_date: Option<NaiveDate>,
+}
+
+/// Stock sheets go through the same validation as the panels
+pub type StockInput = PanelInput;
+
+impl PanelInput {
+    /// Parse and validate a request row, converting its dimensions from `unit` when set
+    pub fn from_panel(panel: &Panel, is_stock: bool, unit: Option<LengthUnit>) -> Result<Self> {
+        let kind = if is_stock { "stock panel" } else { "panel" };
+        let width = Self::parse_dimension(kind, panel.id, "width", &panel.width, unit)?;
+        let height = Self::parse_dimension(kind, panel.id, "height", &panel.height, unit)?;
+        let edge_clearance = match panel.edge_clearance.as_deref() {
+            Some(value) => Self::parse_clearance(kind, panel.id, value, unit)?,
+            None => 0.0,
+        };
+
+        Ok(Self {
+            id: panel.id,
+            width,
+            height,
+            count: panel.count,
+            material: panel.get_material_key(),
+            label: panel.label.clone(),
+            orientation: panel.get_grain().into(),
+            edge_clearance,
+            is_stock,
+            priority: panel.priority.unwrap_or(0),
+            acquired_date: panel.acquired_date,
+        })
+    }
+
+    fn parse_dimension(kind: &str, id: u32, name: &str, value: &str, unit: Option<LengthUnit>) -> Result<f64> {
+        let parsed = PrecisionAnalyzer::parse_f64(value).map_err(|_| ComputationError::InvalidNumber {
+            field: format!("{} {} {}", kind, id, name),
+            value: value.to_string(),
+        })?;
+        if !(parsed.is_finite() && parsed > 0.0) {
+            return Err(CoreError::InvalidInput {
+                details: format!("{} {} has invalid {} {}", kind, id, name, value),
+            }
+            .into());
+        }
+        Ok(unit.map_or(parsed, |unit| unit.to_millimeters(parsed)))
+    }
+
+    fn parse_clearance(kind: &str, id: u32, value: &str, unit: Option<LengthUnit>) -> Result<f64> {
+        let parsed = PrecisionAnalyzer::parse_f64(value).map_err(|_| ComputationError::InvalidNumber {
+            field: format!("{} {} edge clearance", kind, id),
+            value: value.to_string(),
+        })?;
+        if !(parsed.is_finite() && parsed >= 0.0) {
+            return Err(CoreError::InvalidInput {
+                details: format!("{} {} has invalid edge clearance {}", kind, id, value),
+            }
+            .into());
+        }
+        Ok(unit.map_or(parsed, |unit| unit.to_millimeters(parsed)))
+    }
+
+    /// One copy of the row scaled by the task factor
+    pub fn to_tile_dimensions(&self, internal_id: u32, factor: u32) -> TileDimensions {
+        TileDimensions::intern(TileDimensionsData {
+            id: internal_id,
+            width: (self.width * factor as f64).round() as u32,
+            height: (self.height * factor as f64).round() as u32,
+            material: self.material.clone(),
+            label: self.label.clone(),
+            orientation: self.orientation,
+            is_rotated: false,
+            edge_clearance: (self.edge_clearance * factor as f64).round() as u32,
+            priority: self.priority,
+            acquired_date: self.acquired_date,
+        })
+    }
+}
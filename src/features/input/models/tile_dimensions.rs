@@ -1,10 +1,15 @@
-use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
 use crate::enums::orientation::Orientation;
 
 
 // 3. РАЗВЕРНУТАЯ МОДЕЛЬ - готова для алгоритма размещения (count всегда = 1)
-#[derive(Serialize, Debug, Clone,  Deserialize)]
-pub struct TileDimensions {
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct TileDimensionsData {
     pub id: u32,
     pub width: u32,
     pub height: u32,
@@ -12,9 +17,111 @@ pub struct TileDimensions {
     pub label: String,
     pub orientation: Orientation,
     pub is_rotated: bool,
+    /// Scaled minimum distance to the sheet edges, 0 - none
+    #[serde(default)]
+    pub edge_clearance: u32,
+    /// Priority of a stock sheet, higher ones are used first
+    #[serde(default)]
+    pub priority: i32,
+    /// Day a stock sheet was acquired, older ones are used first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acquired_date: Option<NaiveDate>,
+}
+
+/// Interned `TileDimensionsData`: equal tiles share one allocation, so the copies made for
+/// permutations and groups are pointer copies and equal tiles compare by pointer first.
+/// Fields are read through `Deref`, `make_mut` gives a private copy to change
+///
+/// ```rust
+/// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+///
+/// let tile = TileDimensions::new(1, 400, 600, false, "door", "MFC");
+/// let same = TileDimensions::new(1, 400, 600, false, "door", "MFC");
+/// assert!(tile.ptr_eq(&same));
+/// assert!(tile.rotate_90().rotate_90().ptr_eq(&tile));
+///
+/// let mut other = tile.clone();
+/// other.make_mut().material = "HDF".to_string();
+/// assert!(!other.ptr_eq(&tile) && other != tile);
+/// assert_eq!((tile.material.as_str(), other.width), ("MFC", 400));
+/// ```
+#[derive(Clone)]
+pub struct TileDimensions(Arc<SharedTile>);
+
+/// Allocation of an interned tile with its turned copy, made by the first `rotate_90`
+#[derive(Clone)]
+struct SharedTile {
+    data: TileDimensionsData,
+    rotated: OnceLock<RotatedTile>,
+}
+
+/// The unrotated tile keeps its turned copy alive, the turned one only points back,
+/// so the pair never keeps itself alive
+#[derive(Clone)]
+enum RotatedTile {
+    Strong(TileDimensions),
+    Weak(Weak<SharedTile>),
+}
+
+/// Tiles created so far by `dimensions_based_hash_code`, split into shards so threads
+/// creating tiles of different sizes don't wait for each other
+static INTERNER: [Mutex<Option<InternerShard>>; INTERNER_SHARDS] = [const { Mutex::new(None) }; INTERNER_SHARDS];
+const INTERNER_SHARDS: usize = 16;
+/// Entries of a shard after which its dead entries are swept
+const MIN_PURGE_ENTRIES: usize = 1024;
+
+#[derive(Default)]
+struct InternerShard {
+    buckets: HashMap<i32, Vec<Weak<SharedTile>>>,
+    nbr_entries: usize,
+    /// `nbr_entries` at which the whole shard is swept, twice the live entries of the last sweep
+    purge_at: usize,
+}
+
+impl InternerShard {
+    fn purge(&mut self) {
+        self.buckets.retain(|_, bucket| {
+            bucket.retain(|weak| weak.strong_count() > 0);
+            !bucket.is_empty()
+        });
+        self.nbr_entries = self.buckets.values().map(Vec::len).sum();
+        self.purge_at = (self.nbr_entries * 2).max(MIN_PURGE_ENTRIES);
+    }
 }
 
 impl TileDimensions {
+    /// Shared copy of `data`, the one of an equal tile when there is one
+    pub fn intern(data: TileDimensionsData) -> Self {
+        let key = (data.width as i32).wrapping_mul(31).wrapping_add(data.height as i32);
+        let shard = &INTERNER[key.unsigned_abs() as usize % INTERNER_SHARDS];
+        let mut shard = shard.lock().unwrap_or_else(|e| e.into_inner());
+        let shard = shard.get_or_insert_with(InternerShard::default);
+        let bucket = shard.buckets.entry(key).or_default();
+        if let Some(shared) = bucket.iter().filter_map(Weak::upgrade).find(|shared| shared.data == data) {
+            return Self(shared);
+        }
+        let shared = Arc::new(SharedTile { data, rotated: OnceLock::new() });
+        bucket.push(Arc::downgrade(&shared));
+        shard.nbr_entries += 1;
+        // Записи плиток, которых больше нет, выбрасываются по всему шарду
+        if shard.nbr_entries >= shard.purge_at.max(MIN_PURGE_ENTRIES) {
+            shard.purge();
+        }
+        Self(shared)
+    }
+
+    /// Fields of this copy only, the other copies of the tile keep theirs
+    pub fn make_mut(&mut self) -> &mut TileDimensionsData {
+        let shared = Arc::make_mut(&mut self.0);
+        shared.rotated = OnceLock::new();
+        &mut shared.data
+    }
+
+    /// True when both are the same interned tile
+    pub fn ptr_eq(&self, other: &TileDimensions) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
     pub fn new(
         id: u32,
         width: u32,
@@ -23,7 +130,7 @@ impl TileDimensions {
         label: &str,
         material: &str,
     ) -> Self {
-        Self {
+        Self::intern(TileDimensionsData {
             id,
             width,
             height,
@@ -31,7 +138,10 @@ impl TileDimensions {
             material: material.to_string(),
             orientation: Orientation::Default,
             is_rotated,
-        }
+            edge_clearance: 0,
+            priority: 0,
+            acquired_date: None,
+        })
     }
     //
     /// Реализуем toString() ТОЧНО как в Java Это критично для правильной работы HashMap в алгоритме группировки
@@ -56,9 +166,18 @@ impl TileDimensions {
         self.width == self.height
     }
 
-    /// Rotate the tile 90 degrees
+    /// Rotate the tile 90 degrees. The turned copy is kept with the tile, turning it again
+    /// doesn't go through the interner
     pub fn rotate_90(&self) -> Self {
-        Self {
+        let cached = self.0.rotated.get().and_then(|rotated| match rotated {
+            RotatedTile::Strong(tile) => Some(tile.clone()),
+            RotatedTile::Weak(weak) => weak.upgrade().map(Self),
+        });
+        if let Some(rotated) = cached {
+            return rotated;
+        }
+
+        let rotated = Self::intern(TileDimensionsData {
             id: self.id,
             width: self.height,
             height: self.width,
@@ -66,13 +185,89 @@ impl TileDimensions {
             label: self.label.clone(),
             orientation: self.orientation,
             is_rotated: !self.is_rotated,
+            edge_clearance: self.edge_clearance,
+            priority: self.priority,
+            acquired_date: self.acquired_date,
+        });
+        if self.is_rotated {
+            let _ = self.0.rotated.set(RotatedTile::Weak(Arc::downgrade(&rotated.0)));
+        } else {
+            let _ = self.0.rotated.set(RotatedTile::Strong(rotated.clone()));
+            let _ = rotated.0.rotated.set(RotatedTile::Weak(Arc::downgrade(&self.0)));
+        }
+        rotated
+    }
+
+    /// Width and height with the longer side first if the tile may be rotated, as is otherwise
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+    ///
+    /// let tile = TileDimensions::new(1, 400, 600, false, "", "");
+    /// assert_eq!(tile.get_canonical_dimensions(true), (600, 400));
+    /// assert_eq!(tile.get_canonical_dimensions(false), (400, 600));
+    /// ```
+    pub fn get_canonical_dimensions(&self, can_rotate: bool) -> (u32, u32) {
+        if can_rotate {
+            (self.width.max(self.height), self.width.min(self.height))
+        } else {
+            (self.width, self.height)
         }
     }
 
     /// Check if this tile fits in another tile (dimensions)
     pub fn fits(&self, other: &TileDimensions) -> bool {
-        (self.width >= other.width && self.height >= other.height) ||
-        (self.width >= other.height && self.height >= other.width)
+        self.fits_with_tolerance(other, 0)
+    }
+
+    /// `fits` where the other tile's sides may be up to `tolerance` longer
+    ///
+    /// ```rust
+    /// use rezalnyas_core::features::input::models::tile_dimensions::TileDimensions;
+    ///
+    /// let sheet = TileDimensions::new(1, 60000, 30000, false, "", "MFC");
+    /// let panel = TileDimensions::new(2, 60002, 20000, false, "", "MFC");
+    /// assert!(!sheet.fits(&panel));
+    /// assert!(sheet.fits_with_tolerance(&panel, 2));
+    /// assert!(sheet.fits_with_tolerance(&panel, u32::MAX));
+    /// ```
+    pub fn fits_with_tolerance(&self, other: &TileDimensions, tolerance: u32) -> bool {
+        let (width, height) = (self.width.saturating_add(tolerance), self.height.saturating_add(tolerance));
+        (width >= other.width && height >= other.height) || (width >= other.height && height >= other.width)
+    }
+
+}
+
+impl Deref for TileDimensions {
+    type Target = TileDimensionsData;
+
+    fn deref(&self) -> &TileDimensionsData {
+        &self.0.data
+    }
+}
+
+impl PartialEq for TileDimensions {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.0.data == other.0.data
     }
+}
 
+impl Eq for TileDimensions {}
+
+impl fmt::Debug for TileDimensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.data.fmt(f)
+    }
+}
+
+impl Serialize for TileDimensions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.data.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TileDimensions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TileDimensionsData::deserialize(deserializer).map(Self::intern)
+    }
 }
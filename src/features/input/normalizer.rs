@@ -0,0 +1,330 @@
+use std::collections::HashSet;
+
+use crate::enums::{length_unit::LengthUnit, orientation::Orientation, panel_shape::PanelShape};
+use crate::errors::{ComputationError, CoreError, Result};
+use crate::features::engine::model::calculation_request::{CalculationRequest, Panel};
+use crate::features::input::models::{
+    material_catalog::MaterialCatalog,
+    normalization_report::{MergedRows, NormalizationReport, ResolvedMaterial},
+    panel_input::{PanelInput, StockInput},
+};
+use crate::scaled_math::PrecisionAnalyzer;
+
+/// Request after the input normalization, rows of `stock_panels` follow the rows of `request`,
+/// rows of `panels` too except the ones folded into `NormalizationReport::aggregated_rows`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NormalizedInput {
+    pub request: CalculationRequest,
+    pub panels: Vec<PanelInput>,
+    pub stock_panels: Vec<StockInput>,
+    pub report: NormalizationReport,
+}
+
+/// Validate the rows of a request, fold their material names into `CalculationRequest::material_catalog`
+/// ids, take the catalog edge band thickness off banded panel sides, convert the lengths to
+/// millimeters and merge rows that only differ by id when
+/// `Configuration::merge_duplicate_panels` is set. Panel rows that only differ by id are
+/// always computed as one row, the response maps the placed panels back to them
+///
+/// ```rust
+/// use rezalnyas_core::enums::length_unit::LengthUnit;
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::engine::model::configuration::Configuration;
+/// use rezalnyas_core::features::input::normalizer::normalize_request;
+///
+/// let configuration = Configuration {
+///     input_unit: Some(LengthUnit::Centimeter),
+///     merge_duplicate_panels: true,
+///     ..Configuration::default()
+/// };
+/// let request = CalculationRequestBuilder::new()
+///     .configuration(configuration)
+///     .add_panel(60.0, 40.5).count(2)
+///     .add_panel(60.0, 40.5)
+///     .add_stock(280.0, 207.0)
+///     .build()
+///     .unwrap();
+///
+/// let normalized = normalize_request(&request).unwrap();
+/// assert_eq!(normalized.request.panels.len(), 1);
+/// assert_eq!(normalized.request.panels[0].height, "405");
+/// assert_eq!(normalized.panels[0].count, 3);
+/// assert_eq!(normalized.report.merged_rows[0].merged_ids, vec![2]);
+///
+/// let request = CalculationRequestBuilder::new()
+///     .add_panel(600.0, 405.0).count(2)
+///     .add_panel(600.0, 405.0)
+///     .add_stock(2800.0, 2070.0)
+///     .build()
+///     .unwrap();
+/// let normalized = normalize_request(&request).unwrap();
+/// assert_eq!(normalized.request.panels.len(), 2);
+/// assert_eq!(normalized.panels.len(), 1);
+/// assert_eq!(normalized.report.aggregated_rows[0].count, 3);
+/// ```
+pub fn normalize_request(request: &CalculationRequest) -> Result<NormalizedInput> {
+    let unit = request
+        .configuration
+        .input_unit
+        .filter(|unit| *unit != LengthUnit::Millimeter);
+    let mut request = request.clone();
+    let mut report = NormalizationReport {
+        converted_from: unit,
+        ..NormalizationReport::default()
+    };
+
+    if let Some(catalog) = request.material_catalog.take() {
+        for panel in request.panels.iter_mut().chain(request.stock_panels.iter_mut()) {
+            resolve_material(&catalog, panel, &mut report.resolved_materials);
+        }
+        request.material_catalog = Some(catalog);
+    }
+
+    for panel in &mut request.panels {
+        apply_shape(panel)?;
+    }
+
+    let mut panels = get_inputs(&request.panels, false, unit)?;
+    let mut stock_panels = get_inputs(&request.stock_panels, true, unit)?;
+
+    if let Some(catalog) = &request.material_catalog {
+        for (panel, input) in request.panels.iter_mut().zip(panels.iter_mut()) {
+            deduct_edge_bands(catalog, panel, input)?;
+        }
+    }
+
+    // Закреплённые детали и детали последовательностей ссылаются на id строки, их не объединяем
+    let pinned_panel_ids: HashSet<u32> = request
+        .pinned_placements
+        .iter()
+        .map(|pinned| pinned.panel_id)
+        .chain(request.sequence_groups.iter().flat_map(|group| group.panel_ids.iter().copied()))
+        .collect();
+    if request.configuration.merge_duplicate_panels {
+        let pinned_stock_ids: HashSet<u32> = request.pinned_placements.iter().map(|pinned| pinned.stock_id).collect();
+        merge_rows(&mut request.panels, &mut panels, &pinned_panel_ids, &mut report.merged_rows);
+        merge_rows(&mut request.stock_panels, &mut stock_panels, &pinned_stock_ids, &mut report.merged_rows);
+    }
+
+    let configuration = &mut request.configuration;
+    for (field, value) in [
+        ("cut_thickness", &mut configuration.cut_thickness),
+        ("min_trim_dimension", &mut configuration.min_trim_dimension),
+        ("min_offcut_dimension", &mut configuration.min_offcut_dimension),
+        ("dimension_tolerance", &mut configuration.dimension_tolerance),
+        ("lead_in_allowance", &mut configuration.lead_in_allowance),
+    ] {
+        if let Some(value) = value {
+            *value = convert_length(field, value, unit)?;
+        }
+    }
+
+    if unit.is_some() {
+        for (panel, input) in request
+            .panels
+            .iter_mut()
+            .zip(&panels)
+            .chain(request.stock_panels.iter_mut().zip(&stock_panels))
+        {
+            panel.width = input.width.to_string();
+            panel.height = input.height.to_string();
+            if panel.edge_clearance.is_some() {
+                panel.edge_clearance = Some(input.edge_clearance.to_string());
+            }
+        }
+        for panel in &mut request.panels {
+            panel.shape = panel.shape.scaled(unit.map_or(1.0, |unit| unit.to_millimeters(1.0)));
+        }
+        for pinned in &mut request.pinned_placements {
+            pinned.x = convert_length("pinned x", &pinned.x, unit)?;
+            pinned.y = convert_length("pinned y", &pinned.y, unit)?;
+        }
+    }
+
+    // Одинаковые строки деталей раскраиваются одной строкой, ответ раскладывает их обратно по строкам
+    let mut rows = request.panels.clone();
+    merge_rows(&mut rows, &mut panels, &pinned_panel_ids, &mut report.aggregated_rows);
+
+    report.nbr_panels = panels.len();
+    report.nbr_stock_panels = stock_panels.len();
+    Ok(NormalizedInput {
+        request,
+        panels,
+        stock_panels,
+        report,
+    })
+}
+
+/// Size a circle to its diameter and check the shape fits its bounding box
+fn apply_shape(panel: &mut Panel) -> Result<()> {
+    let invalid = |details: &str| -> Result<()> {
+        Err(CoreError::InvalidInput {
+            details: format!("panel {} {}", panel.id, details),
+        }
+        .into())
+    };
+    match panel.shape {
+        PanelShape::Rect => Ok(()),
+        PanelShape::Circle { diameter } => {
+            if !(diameter.is_finite() && diameter > 0.0) {
+                return invalid(&format!("has invalid diameter {}", diameter));
+            }
+            panel.width = diameter.to_string();
+            panel.height = diameter.to_string();
+            Ok(())
+        }
+        PanelShape::RoundedRect { radius } => {
+            // Неверные размеры строки сообщает её проверка, здесь проверяется только радиус
+            let size = PrecisionAnalyzer::parse_f64(&panel.width)
+                .and_then(|width| Ok(width.min(PrecisionAnalyzer::parse_f64(&panel.height)?)))
+                .unwrap_or(f64::INFINITY);
+            if !(radius.is_finite() && radius >= 0.0 && 2.0 * radius <= size) {
+                return invalid(&format!("has invalid corner radius {}", radius));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Give the row the catalog id of its material, and the catalog thickness and grain it doesn't set
+fn resolve_material(catalog: &MaterialCatalog, panel: &mut Panel, resolved: &mut Vec<ResolvedMaterial>) {
+    let Some(entry) = catalog.find(&panel.material) else {
+        return;
+    };
+    if panel.material != entry.id {
+        let material = std::mem::replace(&mut panel.material, entry.id.clone());
+        if !resolved.iter().any(|resolved| resolved.material == material) {
+            resolved.push(ResolvedMaterial {
+                material,
+                catalog_id: entry.id.clone(),
+            });
+        }
+    }
+    if panel.thickness.is_none() {
+        panel.thickness = entry.thickness.clone();
+    }
+    if panel.orientation == Orientation::Default {
+        panel.orientation = entry.orientation;
+    }
+}
+
+/// Cut the banded sides of the panel smaller by the band thickness of the catalog,
+/// the finished size goes to the label
+fn deduct_edge_bands(catalog: &MaterialCatalog, panel: &mut Panel, input: &mut PanelInput) -> Result<()> {
+    let Some(edge) = &panel.edge else {
+        return Ok(());
+    };
+    let band_thickness = |band: &Option<String>| -> Result<f64> {
+        let Some(thickness) = band
+            .as_deref()
+            .and_then(|band| catalog.find(band))
+            .and_then(|entry| entry.edge_band_thickness.as_deref())
+        else {
+            return Ok(0.0);
+        };
+        PrecisionAnalyzer::parse_f64(thickness).map_err(|_| {
+            ComputationError::InvalidNumber {
+                field: format!("edge band thickness of panel {}", panel.id),
+                value: thickness.to_string(),
+            }
+            .into()
+        })
+    };
+    let width_deduction = band_thickness(&edge.left)? + band_thickness(&edge.right)?;
+    let height_deduction = band_thickness(&edge.top)? + band_thickness(&edge.bottom)?;
+    if width_deduction == 0.0 && height_deduction == 0.0 {
+        return Ok(());
+    }
+
+    let width = ((input.width - width_deduction) * 100.0).round() / 100.0;
+    let height = ((input.height - height_deduction) * 100.0).round() / 100.0;
+    if width <= 0.0 || height <= 0.0 {
+        return Err(CoreError::InvalidInput {
+            details: format!("panel {} is smaller than its edge bands", panel.id),
+        }
+        .into());
+    }
+
+    let finished_size = format!("{}x{}", input.width, input.height);
+    let label = if panel.label.is_empty() {
+        finished_size
+    } else {
+        format!("{} {}", panel.label, finished_size)
+    };
+    panel.width = width.to_string();
+    panel.height = height.to_string();
+    panel.label = label.clone();
+    input.width = width;
+    input.height = height;
+    input.label = label;
+    Ok(())
+}
+
+fn get_inputs(panels: &[Panel], is_stock: bool, unit: Option<LengthUnit>) -> Result<Vec<PanelInput>> {
+    panels
+        .iter()
+        .map(|panel| PanelInput::from_panel(panel, is_stock, unit))
+        .collect()
+}
+
+fn convert_length(field: &str, value: &str, unit: Option<LengthUnit>) -> Result<String> {
+    let parsed = PrecisionAnalyzer::parse_f64(value).map_err(|_| ComputationError::InvalidNumber {
+        field: field.to_string(),
+        value: value.to_string(),
+    })?;
+    Ok(match unit {
+        Some(unit) => unit.to_millimeters(parsed).to_string(),
+        None => value.to_string(),
+    })
+}
+
+/// Fold rows with the same dimensions, material, grain, clearance, label and edges into the first one
+fn merge_rows(
+    panels: &mut Vec<Panel>,
+    inputs: &mut Vec<PanelInput>,
+    pinned_ids: &HashSet<u32>,
+    merged_rows: &mut Vec<MergedRows>,
+) {
+    let mut index = 0;
+    while index < panels.len() {
+        let mut merged = MergedRows {
+            id: panels[index].id,
+            merged_ids: Vec::new(),
+            count: panels[index].count,
+            is_stock: inputs[index].is_stock,
+        };
+
+        let mut other = index + 1;
+        while other < panels.len() {
+            let is_duplicate = !pinned_ids.contains(&panels[index].id)
+                && !pinned_ids.contains(&panels[other].id)
+                && inputs[other].width == inputs[index].width
+                && inputs[other].height == inputs[index].height
+                && inputs[other].material == inputs[index].material
+                && inputs[other].orientation == inputs[index].orientation
+                && inputs[other].edge_clearance == inputs[index].edge_clearance
+                && inputs[other].label == inputs[index].label
+                && inputs[other].priority == inputs[index].priority
+                && inputs[other].acquired_date == inputs[index].acquired_date
+                && panels[other].edge == panels[index].edge
+                && panels[other].shape == panels[index].shape;
+
+            if is_duplicate {
+                let duplicate = panels.remove(other);
+                inputs.remove(other);
+                merged.merged_ids.push(duplicate.id);
+                merged.count += duplicate.count;
+            } else {
+                other += 1;
+            }
+        }
+
+        if !merged.merged_ids.is_empty() {
+            panels[index].count = merged.count;
+            inputs[index].count = merged.count;
+            merged_rows.push(merged);
+        }
+        index += 1;
+    }
+}
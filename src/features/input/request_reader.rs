@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{BufReader, Read};
+
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserializer;
+
+use crate::constants::EngineConstants;
+use crate::errors::{AppError, CoreError, Result};
+use crate::features::engine::model::calculation_request::{CalculationRequest, Panel};
+use crate::features::input::models::panel_input::PanelInput;
+
+/// Read a `CalculationRequest` from JSON without holding the whole document in memory.
+/// Rows of `panels` and `stock_panels` are validated as they are parsed, the first bad
+/// row stops the reading with its 1-based row number
+///
+/// ```rust
+/// use rezalnyas_core::features::engine::model::calculation_request_builder::CalculationRequestBuilder;
+/// use rezalnyas_core::features::input::request_reader::read_request;
+///
+/// let mut request = CalculationRequestBuilder::new()
+///     .add_panel(600.0, 400.0).count(2)
+///     .add_panel(300.0, 200.0)
+///     .add_stock(2800.0, 2070.0)
+///     .build()
+///     .unwrap();
+/// let json = serde_json::to_vec(&request).unwrap();
+/// assert_eq!(read_request(json.as_slice()).unwrap().panels.len(), 2);
+///
+/// request.panels[1].width = "abc".to_string();
+/// let json = serde_json::to_vec(&request).unwrap();
+/// let error = read_request(json.as_slice()).unwrap_err();
+/// assert!(error.to_string().contains("panels row 2"));
+/// ```
+pub fn read_request<R: Read>(reader: R) -> Result<CalculationRequest> {
+    let mut row_error = None;
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+    let request = deserializer.deserialize_map(RequestVisitor {
+        row_error: &mut row_error,
+    });
+    // Ошибка строки важнее ошибки JSON, которой чтение было прервано
+    if let Some(error) = row_error {
+        return Err(error);
+    }
+    let request = request?;
+    deserializer.end()?;
+    Ok(request)
+}
+
+struct RequestVisitor<'a> {
+    row_error: &'a mut Option<AppError>,
+}
+
+impl<'de> Visitor<'de> for RequestVisitor<'_> {
+    type Value = CalculationRequest;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a calculation request")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<Self::Value, A::Error> {
+        let mut request = CalculationRequest::default();
+        let (mut has_configuration, mut has_panels, mut has_stock_panels) = (false, false, false);
+        let (mut has_client_info, mut has_performance_thresholds) = (false, false);
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "configuration" => {
+                    request.configuration = map.next_value()?;
+                    has_configuration = true;
+                }
+                "panels" => {
+                    request.panels = map.next_value_seed(RowsSeed::new("panels", false, self.row_error))?;
+                    has_panels = true;
+                }
+                "stock_panels" => {
+                    request.stock_panels = map.next_value_seed(RowsSeed::new("stock_panels", true, self.row_error))?;
+                    has_stock_panels = true;
+                }
+                "client_info" => {
+                    request.client_info = map.next_value()?;
+                    has_client_info = true;
+                }
+                "performance_thresholds" => {
+                    request.performance_thresholds = map.next_value()?;
+                    has_performance_thresholds = true;
+                }
+                "pinned_placements" => request.pinned_placements = map.next_value()?,
+                "sequence_groups" => request.sequence_groups = map.next_value()?,
+                "material_catalog" => request.material_catalog = map.next_value()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        // Обязательные поля те же, что у derive(Deserialize) запроса
+        for (is_set, field) in [
+            (has_configuration, "configuration"),
+            (has_panels, "panels"),
+            (has_stock_panels, "stock_panels"),
+            (has_client_info, "client_info"),
+            (has_performance_thresholds, "performance_thresholds"),
+        ] {
+            if !is_set {
+                return Err(de::Error::missing_field(field));
+            }
+        }
+        Ok(request)
+    }
+}
+
+/// `panels` or `stock_panels` array, each row is checked before the next one is parsed
+struct RowsSeed<'a> {
+    field: &'static str,
+    is_stock: bool,
+    row_error: &'a mut Option<AppError>,
+}
+
+impl<'a> RowsSeed<'a> {
+    fn new(field: &'static str, is_stock: bool, row_error: &'a mut Option<AppError>) -> Self {
+        Self {
+            field,
+            is_stock,
+            row_error,
+        }
+    }
+
+    fn validate(&self, panel: &Panel, ids: &mut HashSet<u32>, total_count: &mut usize) -> Result<()> {
+        let kind = if self.is_stock { "stock panel" } else { "panel" };
+        let max_count = if self.is_stock {
+            EngineConstants::MAX_STOCK_PANELS_LIMIT
+        } else {
+            EngineConstants::MAX_PANELS_LIMIT
+        };
+
+        PanelInput::from_panel(panel, self.is_stock, None)?;
+        let details = if !ids.insert(panel.id) {
+            format!("duplicate {} id {}", kind, panel.id)
+        } else if panel.count == 0 {
+            format!("{} {} has zero count", kind, panel.id)
+        } else {
+            *total_count += panel.count as usize;
+            if *total_count <= max_count {
+                return Ok(());
+            }
+            format!("too many {}s: more than {}", kind, max_count)
+        };
+        Err(CoreError::InvalidInput { details }.into())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for RowsSeed<'_> {
+    type Value = Vec<Panel>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RowsSeed<'_> {
+    type Value = Vec<Panel>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of panel rows")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error> {
+        let mut panels = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        let mut ids = HashSet::new();
+        let mut total_count = 0;
+
+        loop {
+            let row = panels.len() + 1;
+            let panel: Panel = match seq.next_element() {
+                Ok(Some(panel)) => panel,
+                Ok(None) => break,
+                Err(e) => return Err(de::Error::custom(format!("{} row {}: {}", self.field, row, e))),
+            };
+            if let Err(e) = self.validate(&panel, &mut ids, &mut total_count) {
+                let message = format!("{} row {}: {}", self.field, row, e);
+                *self.row_error = Some(CoreError::InvalidInput { details: message.clone() }.into());
+                return Err(de::Error::custom(message));
+            }
+            panels.push(panel);
+        }
+        Ok(panels)
+    }
+}
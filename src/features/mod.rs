@@ -8,4 +8,5 @@ pub mod cut;
 pub mod cutting_optimizer;
 pub mod panel_grouper;
 pub mod engine;
+pub mod export;
 
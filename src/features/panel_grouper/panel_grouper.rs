@@ -3,6 +3,8 @@ use crate::features::input::models::{
     grouped_tile_dimensions::GroupedTileDimensions, tile_dimensions::TileDimensions,
 };
 
+use crate::logging::log_debug;
+
 use std::collections::HashMap;
 
 /// Отвечает за группировку панелей по размерам (точная копия Java generateGroups)
@@ -10,51 +12,28 @@ pub struct PanelGrouper;
 
 impl PanelGrouper {
     /// Check if optimization is one-dimensional
-    fn is_one_dimensional_optimization(tiles: &[TileDimensions], stock_tiles: &[TileDimensions]) -> bool {
-        if tiles.is_empty() {
-            return false;
-        }
-
-        // Initialize with first tile's dimensions
-        let mut common_dimensions = vec![tiles[0].width, tiles[0].height];
-
-        // Process all tiles
-        for tile in tiles {
-            let mut surviving_dimensions = Vec::new();
-
-            for &dim in &common_dimensions {
-                if dim == tile.width || dim == tile.height {
-                    surviving_dimensions.push(dim);
-                }
-            }
-
-            common_dimensions = surviving_dimensions;
-
-            // Early exit if no common dimensions remain
-            if common_dimensions.is_empty() {
-                return false;
-            }
-        }
+    pub fn is_one_dimensional_optimization(tiles: &[TileDimensions], stock_tiles: &[TileDimensions]) -> bool {
+        Self::get_common_dimension(tiles, stock_tiles).is_some()
+    }
 
-        // Process stock tiles
-        for tile in stock_tiles {
-            let mut surviving_dimensions = Vec::new();
+    /// Dimension every panel and stock sheet has as its width or height, None when there is none
+    pub fn get_common_dimension(tiles: &[TileDimensions], stock_tiles: &[TileDimensions]) -> Option<u32> {
+        let first = tiles.first()?;
 
-            for &dim in &common_dimensions {
-                if dim == tile.width || dim == tile.height {
-                    surviving_dimensions.push(dim);
-                }
-            }
+        // Initialize with first tile's dimensions
+        let mut common_dimensions = vec![first.width, first.height];
 
-            common_dimensions = surviving_dimensions;
+        // Process all tiles and stock tiles
+        for tile in tiles.iter().chain(stock_tiles) {
+            common_dimensions.retain(|&dim| dim == tile.width || dim == tile.height);
 
             // Early exit if no common dimensions remain
             if common_dimensions.is_empty() {
-                return false;
+                return None;
             }
         }
 
-        !common_dimensions.is_empty()
+        common_dimensions.first().copied()
     }
 
     /// Группирует панели - точная копия Java логики generateGroups
@@ -72,11 +51,15 @@ impl PanelGrouper {
         }
 
         // Логирование статистики панелей
-        let mut sb = String::new();
-        for (tile_type, count) in &tile_counts {
-            sb.push_str(&format!("{}*{} ", tile_type, count));
-        }
-        println!("TotalNbrTiles[{}] Tiles: {}", tiles.len(), sb);
+        log_debug!(
+            "TotalNbrTiles[{}] Tiles: {}",
+            tiles.len(),
+            tile_counts
+                .iter()
+                .map(|(tile_type, count)| format!("{}*{}", tile_type, count))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
 
         // Шаг 2: Определение порога для разбивки (iMax в Java)
         let mut max_group_size = std::cmp::max(tiles.len() / 100, 1);
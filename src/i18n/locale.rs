@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::engine::model::status::Status;
+
+/// Language of the report labels and the human-readable response texts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ru => "ru",
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Locale::default()
+    }
+
+    /// Report label given in English in this locale, the label itself when it has no translation
+    ///
+    /// ```rust
+    /// use rezalnyas_core::i18n::locale::Locale;
+    ///
+    /// assert_eq!(Locale::En.tr("Cut list"), "Cut list");
+    /// assert_eq!(Locale::Ru.tr("Cut list"), "Карта раскроя");
+    /// assert_eq!(Locale::Ru.tr("X"), "X");
+    /// ```
+    pub fn tr(&self, text: &'static str) -> &'static str {
+        match self {
+            Locale::En => text,
+            Locale::Ru => Self::get_ru(text).unwrap_or(text),
+        }
+    }
+
+    /// What the task is doing in `status`, for people rather than programs
+    pub fn describe_status(&self, status: Status) -> &'static str {
+        match (self, status) {
+            (Locale::En, Status::Idle) => "Waiting",
+            (Locale::En, Status::Queued) => "Queued",
+            (Locale::En, Status::Running) => "Computing the layout",
+            (Locale::En, Status::Improving) => "Improving the layout",
+            (Locale::En, Status::Finishing) => "Building the response",
+            (Locale::En, Status::Finished) => "Finished",
+            (Locale::En, Status::Stopping) => "Stopping",
+            (Locale::En, Status::Stopped) => "Stopped by the client",
+            (Locale::En, Status::Terminated) => "Terminated",
+            (Locale::En, Status::Error) => "Failed",
+            (Locale::Ru, Status::Idle) => "Ожидает",
+            (Locale::Ru, Status::Queued) => "В очереди",
+            (Locale::Ru, Status::Running) => "Идёт расчёт раскроя",
+            (Locale::Ru, Status::Improving) => "Раскрой улучшается",
+            (Locale::Ru, Status::Finishing) => "Формируется ответ",
+            (Locale::Ru, Status::Finished) => "Готово",
+            (Locale::Ru, Status::Stopping) => "Останавливается",
+            (Locale::Ru, Status::Stopped) => "Остановлено клиентом",
+            (Locale::Ru, Status::Terminated) => "Прервано",
+            (Locale::Ru, Status::Error) => "Ошибка",
+        }
+    }
+
+    fn get_ru(text: &str) -> Option<&'static str> {
+        Some(match text {
+            // Карта раскроя
+            "Cut list" => "Карта раскроя",
+            "Parts checklist" => "Список деталей",
+            "Not placed" => "Не размещено",
+            "Sheet" => "Лист",
+            "stock" => "заготовка",
+            "Pieces" => "Детали",
+            "Cut at" => "Рез по",
+            "rotated" => "повёрнута",
+            // Столбцы CSV и книги Excel
+            "Stock ID" => "ID заготовки",
+            "Stock label" => "Метка заготовки",
+            "Material" => "Материал",
+            "Part ID" => "ID детали",
+            "Label" => "Метка",
+            "Width" => "Ширина",
+            "Height" => "Высота",
+            "Rotated" => "Повёрнута",
+            "Quantity" => "Количество",
+            "Parts" => "Детали",
+            "Sheets" => "Листы",
+            "Cuts" => "Резы",
+            "Cut" => "Рез",
+            "Stage" => "Этап",
+            "Direction" => "Направление",
+            "Position" => "Позиция",
+            "Vertical" => "Вертикальный",
+            "Horizontal" => "Горизонтальный",
+            "Summary" => "Итоги",
+            "Metric" => "Показатель",
+            "Value" => "Значение",
+            "Used area" => "Использованная площадь",
+            "Wasted area" => "Площадь отходов",
+            "Used %" => "Использовано, %",
+            "Cut length" => "Длина резов",
+            "Not placed parts" => "Неразмещённые детали",
+            "Estimated time, s" => "Расчётное время, с",
+            "Cuts cost" => "Стоимость резов",
+            "Sheets cost" => "Стоимость листов",
+            "Total cost" => "Общая стоимость",
+            _ => return None,
+        })
+    }
+}
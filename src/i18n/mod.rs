@@ -0,0 +1,2 @@
+pub mod locale;
+pub mod warning;
@@ -0,0 +1,116 @@
+use crate::i18n::locale::Locale;
+
+/// Problem of a request that didn't stop the computation, reported as text in
+/// `CalculationResponse::warnings` in the locale of the request
+///
+/// ```rust
+/// use rezalnyas_core::i18n::{locale::Locale, warning::Warning};
+///
+/// let warning = Warning::TimeLimitReached { max_time_ms: 5000 };
+/// assert_eq!(
+///     warning.to_text(Locale::En),
+///     "Time limit of 5000 ms reached, the best layout found so far is returned"
+/// );
+/// assert!(warning.to_text(Locale::Ru).starts_with("Лимит времени 5000 мс"));
+///
+/// #[cfg(feature = "serde")]
+/// {
+///     let json = serde_json::to_string(&warning).unwrap();
+///     assert_eq!(serde_json::from_str::<Warning>(&json).unwrap(), warning);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Warning {
+    /// The material has stock, but none of the panel thickness; None - thickness not given
+    ThicknessMismatch {
+        panel_id: u32,
+        material: String,
+        thickness: Option<String>,
+        available: Vec<Option<String>>,
+    },
+    SequenceGroupNotContiguous { group_id: u32 },
+    ThreadsFailed { nbr_threads: usize },
+    TimeLimitReached { max_time_ms: u64 },
+    /// Response sheet below `Configuration::min_sheet_utilization_percent`, 1-based
+    LowSheetUtilization {
+        sheet_number: usize,
+        used_percent: f64,
+        min_percent: f64,
+        panel_ids: Vec<i32>,
+    },
+}
+
+impl Warning {
+    pub fn to_text(&self, locale: Locale) -> String {
+        let thickness = |thickness: &Option<String>| match (thickness, locale) {
+            (Some(thickness), _) => thickness.clone(),
+            (None, Locale::En) => "unspecified".to_string(),
+            (None, Locale::Ru) => "не указана".to_string(),
+        };
+        match (self, locale) {
+            (Warning::ThicknessMismatch { panel_id, material, thickness: panel_thickness, available }, _) => {
+                let available: Vec<String> = available.iter().map(thickness).collect();
+                match locale {
+                    Locale::En => format!(
+                        "panel {} of material '{}' has thickness {}, stock is available in {}",
+                        panel_id,
+                        material,
+                        thickness(panel_thickness),
+                        available.join(", ")
+                    ),
+                    Locale::Ru => format!(
+                        "деталь {} из материала '{}' толщиной {}, заготовки есть толщиной {}",
+                        panel_id,
+                        material,
+                        thickness(panel_thickness),
+                        available.join(", ")
+                    ),
+                }
+            }
+            (Warning::SequenceGroupNotContiguous { group_id }, Locale::En) => format!(
+                "Sequence group {} can't be cut as one contiguous piece from any sheet",
+                group_id
+            ),
+            (Warning::SequenceGroupNotContiguous { group_id }, Locale::Ru) => format!(
+                "Группу последовательности {} нельзя вырезать одним куском ни из одного листа",
+                group_id
+            ),
+            (Warning::ThreadsFailed { nbr_threads }, Locale::En) => format!(
+                "{} cut list threads failed and were skipped, the layout may be less optimal",
+                nbr_threads
+            ),
+            (Warning::ThreadsFailed { nbr_threads }, Locale::Ru) => format!(
+                "{} потоков раскроя завершились с ошибкой и пропущены, раскрой может быть хуже",
+                nbr_threads
+            ),
+            (Warning::TimeLimitReached { max_time_ms }, Locale::En) => format!(
+                "Time limit of {} ms reached, the best layout found so far is returned",
+                max_time_ms
+            ),
+            (Warning::TimeLimitReached { max_time_ms }, Locale::Ru) => format!(
+                "Лимит времени {} мс исчерпан, возвращён лучший найденный раскрой",
+                max_time_ms
+            ),
+            (Warning::LowSheetUtilization { sheet_number, used_percent, min_percent, panel_ids }, _) => {
+                let panel_ids: Vec<String> = panel_ids.iter().map(|id| id.to_string()).collect();
+                match locale {
+                    Locale::En => format!(
+                        "Sheet {} is {:.1}% used, below the target of {}%: consider moving panels {} to a future order",
+                        sheet_number,
+                        used_percent,
+                        min_percent,
+                        panel_ids.join(", ")
+                    ),
+                    Locale::Ru => format!(
+                        "Лист {} заполнен на {:.1}%, меньше цели {}%: детали {} можно перенести в следующий заказ",
+                        sheet_number,
+                        used_percent,
+                        min_percent,
+                        panel_ids.join(", ")
+                    ),
+                }
+            }
+        }
+    }
+}
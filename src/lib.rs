@@ -1,6 +1,7 @@
 pub mod constants;
 pub mod enums;
 pub mod errors;
+pub mod i18n;
 pub mod logging;
 
 // pub mod services;
@@ -3,6 +3,7 @@ use std::fmt;
 
 /// Уровни логирования
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LogLevel {
     /// Только критические ошибки
     Error = 1,
@@ -14,6 +15,46 @@ pub enum LogLevel {
     Debug = 4,
 }
 
+/// Most detailed level compiled in, 0 - none. Set by the `max_level_*` features, in release
+/// builds `release_max_level_*` take precedence; messages above it cost nothing at run time
+pub const STATIC_MAX_LEVEL: u8 = if cfg!(debug_assertions) {
+    STATIC_LEVEL
+} else {
+    get_static_level(
+        [
+            cfg!(feature = "release_max_level_off"),
+            cfg!(feature = "release_max_level_error"),
+            cfg!(feature = "release_max_level_warn"),
+            cfg!(feature = "release_max_level_info"),
+            cfg!(feature = "release_max_level_debug"),
+        ],
+        STATIC_LEVEL,
+    )
+};
+
+const STATIC_LEVEL: u8 = get_static_level(
+    [
+        cfg!(feature = "max_level_off"),
+        cfg!(feature = "max_level_error"),
+        cfg!(feature = "max_level_warn"),
+        cfg!(feature = "max_level_info"),
+        cfg!(feature = "max_level_debug"),
+    ],
+    LogLevel::Debug as u8,
+);
+
+/// Самый строгий из включённых уровней: off, error, warn, info, debug
+const fn get_static_level(features: [bool; 5], default: u8) -> u8 {
+    let mut level = 0;
+    while level < features.len() {
+        if features[level] {
+            return level as u8;
+        }
+        level += 1;
+    }
+    default
+}
+
 impl fmt::Display for LogLevel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1,28 +1,47 @@
-use crate::logging::enums::LogLevel;
+use crate::logging::enums::{LogLevel, STATIC_MAX_LEVEL};
 use crate::logging::structs::LogConfig;
+use std::collections::HashMap;
+use std::fmt;
 use std::io::{self, Write};
-use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock, RwLock};
 
 /// Глобальная конфигурация логирования
 static LOGGER_CONFIG: OnceLock<Mutex<LogConfig>> = OnceLock::new();
 
+/// Уровни, заданные для отдельных модулей через `set_level`
+static MODULE_LEVELS: RwLock<Option<HashMap<String, LogLevel>>> = RwLock::new(None);
+
+/// Самый подробный из включённых уровней, отсекает лишние сообщения без блокировок
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
 /// Простой логгер
 pub struct AppLogger;
 
 impl AppLogger {
     /// Записать сообщение в лог
     pub fn log(level: LogLevel, message: &str) {
-        let config = LOGGER_CONFIG
-            .get()
-            .and_then(|mutex| mutex.lock().ok())
-            .map(|guard| guard.clone())
-            .unwrap();
+        if Self::is_enabled(level, "") {
+            Self::write(level, message);
+        }
+    }
 
-        // Проверяем, нужно ли выводить сообщение
-        if level > config.level {
-            return;
+    /// True when messages of `level` from `module` (a `module_path!()`) pass the filters
+    #[inline]
+    pub fn is_enabled(level: LogLevel, module: &str) -> bool {
+        if level as u8 > STATIC_MAX_LEVEL || level as u8 > Self::get_max_level() {
+            return false;
         }
+        level <= Self::get_module_level(module)
+    }
 
+    /// Вывести сообщение без проверки уровня
+    pub fn write(level: LogLevel, message: &str) {
+        Self::write_args(level, format_args!("{}", message));
+    }
+
+    /// Вывести сообщение без проверки уровня, аргументы форматируются прямо в поток
+    pub fn write_args(level: LogLevel, message: fmt::Arguments<'_>) {
         // ANSI коды цветов
         let (color_code, reset_code) = match level {
             LogLevel::Error => ("\x1b[31m", "\x1b[0m"), // Красный
@@ -41,6 +60,55 @@ impl AppLogger {
             }
         }
     }
+
+    fn get_level() -> LogLevel {
+        LOGGER_CONFIG
+            .get()
+            .and_then(|mutex| mutex.lock().ok())
+            .map_or_else(|| LogConfig::default().level, |config| config.level)
+    }
+
+    fn get_max_level() -> u8 {
+        match MAX_LEVEL.load(Ordering::Relaxed) {
+            0 => Self::update_max_level(),
+            level => level,
+        }
+    }
+
+    fn update_max_level() -> u8 {
+        let modules = MODULE_LEVELS.read().map(|levels| {
+            levels
+                .as_ref()
+                .and_then(|levels| levels.values().max().copied())
+        });
+        let level = Self::get_level().max(modules.ok().flatten().unwrap_or(LogLevel::Error)) as u8;
+        MAX_LEVEL.store(level, Ordering::Relaxed);
+        level
+    }
+
+    /// Уровень самого точного фильтра, подходящего к модулю, иначе общий уровень
+    fn get_module_level(module: &str) -> LogLevel {
+        let Ok(levels) = MODULE_LEVELS.read() else {
+            return Self::get_level();
+        };
+        levels
+            .iter()
+            .flatten()
+            .filter(|(filter, _)| Self::matches_module(module, filter))
+            .max_by_key(|(filter, _)| filter.len())
+            .map_or_else(Self::get_level, |(_, level)| *level)
+    }
+
+    /// Фильтр совпадает с путём модуля целиком, с его началом или с его последними сегментами
+    fn matches_module(module: &str, filter: &str) -> bool {
+        module == filter
+            || module
+                .strip_prefix(filter)
+                .is_some_and(|rest| rest.starts_with("::"))
+            || module
+                .strip_suffix(filter)
+                .is_some_and(|rest| rest.ends_with("::"))
+    }
 }
 
 /// Инициализация логирования
@@ -48,5 +116,35 @@ pub fn init_logging(config: LogConfig) -> Result<(), Box<dyn std::error::Error +
     LOGGER_CONFIG
         .set(Mutex::new(config))
         .map_err(|_| "Logger already initialized")?;
+    AppLogger::update_max_level();
     Ok(())
 }
+
+/// Set the level of one module at runtime, `module` is a module path like
+/// `rezalnyas_core::features::engine::cut_list_thread` or its tail like `cut_list_thread`;
+/// the most specific filter wins, other modules keep the level of `init_logging`
+///
+/// ```rust
+/// use rezalnyas_core::logging::{set_level, AppLogger, LogLevel, STATIC_MAX_LEVEL};
+///
+/// // Уровни выше заданного features max_level_* не включаются никогда
+/// let is_compiled_in = |level: LogLevel| level as u8 <= STATIC_MAX_LEVEL;
+///
+/// set_level("cut_list_thread", LogLevel::Debug);
+/// assert_eq!(
+///     AppLogger::is_enabled(LogLevel::Debug, "rezalnyas_core::features::engine::cut_list_thread"),
+///     is_compiled_in(LogLevel::Debug)
+/// );
+/// assert!(!AppLogger::is_enabled(LogLevel::Debug, "rezalnyas_core::features::engine::cutlist_optimizer_service_impl"));
+///
+/// set_level("rezalnyas_core::features::engine", LogLevel::Error);
+/// assert!(!AppLogger::is_enabled(LogLevel::Warn, "rezalnyas_core::features::engine::result_cache"));
+/// ```
+pub fn set_level(module: &str, level: LogLevel) {
+    if let Ok(mut levels) = MODULE_LEVELS.write() {
+        levels
+            .get_or_insert_with(HashMap::new)
+            .insert(module.to_string(), level);
+    }
+    AppLogger::update_max_level();
+}
@@ -5,10 +5,11 @@ use crate::logging::init::AppLogger;
 #[macro_export]
 macro_rules! log_error {
     ($($arg:tt)*) => {
-        $crate::logging::init::AppLogger::log(
-            $crate::logging::enums::LogLevel::Error,
-            &format!($($arg)*)
-        );
+        if $crate::logging::enums::LogLevel::Error as u8 <= $crate::logging::enums::STATIC_MAX_LEVEL
+            && $crate::logging::init::AppLogger::is_enabled($crate::logging::enums::LogLevel::Error, module_path!())
+        {
+            $crate::logging::init::AppLogger::write_args($crate::logging::enums::LogLevel::Error, format_args!($($arg)*));
+        }
     };
 }
 
@@ -16,10 +17,11 @@ macro_rules! log_error {
 #[macro_export]
 macro_rules! log_warn {
     ($($arg:tt)*) => {
-        $crate::logging::init::AppLogger::log(
-            $crate::logging::enums::LogLevel::Warn,
-            &format!($($arg)*)
-        );
+        if $crate::logging::enums::LogLevel::Warn as u8 <= $crate::logging::enums::STATIC_MAX_LEVEL
+            && $crate::logging::init::AppLogger::is_enabled($crate::logging::enums::LogLevel::Warn, module_path!())
+        {
+            $crate::logging::init::AppLogger::write_args($crate::logging::enums::LogLevel::Warn, format_args!($($arg)*));
+        }
     };
 }
 
@@ -27,10 +29,11 @@ macro_rules! log_warn {
 #[macro_export]
 macro_rules! log_info {
     ($($arg:tt)*) => {
-        $crate::logging::init::AppLogger::log(
-            $crate::logging::enums::LogLevel::Info,
-            &format!($($arg)*)
-        );
+        if $crate::logging::enums::LogLevel::Info as u8 <= $crate::logging::enums::STATIC_MAX_LEVEL
+            && $crate::logging::init::AppLogger::is_enabled($crate::logging::enums::LogLevel::Info, module_path!())
+        {
+            $crate::logging::init::AppLogger::write_args($crate::logging::enums::LogLevel::Info, format_args!($($arg)*));
+        }
     };
 }
 
@@ -38,10 +41,11 @@ macro_rules! log_info {
 #[macro_export]
 macro_rules! log_debug {
     ($($arg:tt)*) => {
-        $crate::logging::init::AppLogger::log(
-            $crate::logging::enums::LogLevel::Debug,
-            &format!($($arg)*)
-        );
+        if $crate::logging::enums::LogLevel::Debug as u8 <= $crate::logging::enums::STATIC_MAX_LEVEL
+            && $crate::logging::init::AppLogger::is_enabled($crate::logging::enums::LogLevel::Debug, module_path!())
+        {
+            $crate::logging::init::AppLogger::write_args($crate::logging::enums::LogLevel::Debug, format_args!($($arg)*));
+        }
     };
 }
 
@@ -49,10 +53,11 @@ macro_rules! log_debug {
 #[macro_export]
 macro_rules! log_trace {
     ($($arg:tt)*) => {
-        $crate::logging::init::AppLogger::log(
-            $crate::logging::enums::LogLevel::Debug,
-            &format!($($arg)*)
-        );
+        if $crate::logging::enums::LogLevel::Debug as u8 <= $crate::logging::enums::STATIC_MAX_LEVEL
+            && $crate::logging::init::AppLogger::is_enabled($crate::logging::enums::LogLevel::Debug, module_path!())
+        {
+            $crate::logging::init::AppLogger::write_args($crate::logging::enums::LogLevel::Debug, format_args!($($arg)*));
+        }
     };
 }
 
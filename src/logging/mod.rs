@@ -2,10 +2,12 @@ pub mod enums;
 pub mod init;
 pub mod macros;
 pub mod structs;
+pub mod task_logs;
 
-pub use enums::LogLevel;
-pub use init::{init_logging, AppLogger};
+pub use enums::{LogLevel, STATIC_MAX_LEVEL};
+pub use init::{init_logging, set_level, AppLogger};
 pub use macros::{debug, error, info, warn};
-pub use structs::LogConfig;
+pub use structs::{LogConfig, LogRecord};
+pub use task_logs::TaskLogBuffer;
 
-pub use crate::{log_debug, log_error, log_info, log_warn};
+pub use crate::{log_debug, log_error, log_info, log_trace, log_warn};
@@ -4,7 +4,26 @@ use crate::logging::enums::LogLevel;
 
 /// Простая конфигурация логирования
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LogConfig {
     /// Уровень логирования
     pub level: LogLevel,
 }
+
+impl Default for LogConfig {
+    /// Без `init_logging` выводятся только предупреждения и ошибки
+    fn default() -> Self {
+        Self { level: LogLevel::Warn }
+    }
+}
+
+/// Запись лога, относящаяся к одной задаче
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogRecord {
+    pub task_id: String,
+    /// Миллисекунды с начала эпохи UNIX
+    pub timestamp: u64,
+    pub level: LogLevel,
+    pub message: String,
+}
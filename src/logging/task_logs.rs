@@ -0,0 +1,108 @@
+//! Кольцевые буферы логов по задачам
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::logging::enums::LogLevel;
+use crate::logging::structs::LogRecord;
+
+/// Последние записи лога каждой задачи
+///
+/// ```rust
+/// use rezalnyas_core::logging::{LogLevel, TaskLogBuffer};
+///
+/// let mut logs = TaskLogBuffer::new(2);
+/// logs.push("task-1", LogLevel::Info, "started");
+/// logs.push("task-1", LogLevel::Warn, "no stock for MDF@16");
+/// logs.push("task-1", LogLevel::Debug, "permutation 1 done");
+///
+/// // Старые записи вытесняются, фильтр оставляет уровни не ниже заданного
+/// assert_eq!(logs.get("task-1", LogLevel::Debug).len(), 2);
+/// assert_eq!(logs.get("task-1", LogLevel::Warn)[0].message, "no stock for MDF@16");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TaskLogBuffer {
+    capacity: usize,
+    max_tasks: usize,
+    records: HashMap<String, VecDeque<LogRecord>>,
+    /// Задачи в порядке появления, чтобы забывать самые старые
+    task_ids: VecDeque<String>,
+}
+
+impl TaskLogBuffer {
+    /// Записей на задачу по умолчанию
+    pub const DEFAULT_CAPACITY: usize = 1000;
+    /// Сколько задач хранится по умолчанию
+    pub const DEFAULT_MAX_TASKS: usize = 100;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            max_tasks: Self::DEFAULT_MAX_TASKS,
+            records: HashMap::new(),
+            task_ids: VecDeque::new(),
+        }
+    }
+
+    /// Записей на задачу, 0 - логи не сохраняются
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        for records in self.records.values_mut() {
+            while records.len() > capacity {
+                records.pop_front();
+            }
+        }
+    }
+
+    pub fn push(&mut self, task_id: &str, level: LogLevel, message: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if !self.records.contains_key(task_id) {
+            while self.task_ids.len() >= self.max_tasks {
+                if let Some(oldest) = self.task_ids.pop_front() {
+                    self.records.remove(&oldest);
+                }
+            }
+            self.task_ids.push_back(task_id.to_string());
+        }
+
+        let records = self.records.entry(task_id.to_string()).or_default();
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(LogRecord {
+            task_id: task_id.to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_millis() as u64,
+            level,
+            message: message.to_string(),
+        });
+    }
+
+    /// Записи задачи уровня `min_level` и более важных (Error < Warn < Info < Debug)
+    pub fn get(&self, task_id: &str, min_level: LogLevel) -> Vec<LogRecord> {
+        self.records
+            .get(task_id)
+            .map(|records| {
+                records
+                    .iter()
+                    .filter(|record| record.level <= min_level)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.task_ids.clear();
+    }
+}
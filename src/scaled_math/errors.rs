@@ -19,6 +19,12 @@ pub enum ScaledError {
     NegativeToUnsigned,
     /// Значение слишком большое для целевого типа
     ValueTooLarge,
+    /// NaN или бесконечность вместо числа
+    NotFinite,
+    /// Преобразование отбросило бы дробную часть
+    PrecisionLoss,
+    /// Операция над суммами в разных валютах
+    CurrencyMismatch { left: String, right: String },
 }
 
 impl fmt::Display for ScaledError {
@@ -33,6 +39,11 @@ impl fmt::Display for ScaledError {
             ScaledError::ParseError(s) => write!(f, "Parse error: {}", s),
             ScaledError::NegativeToUnsigned => write!(f, "Cannot convert negative number to unsigned type"),
             ScaledError::ValueTooLarge => write!(f, "Value too large for target type"),
+            ScaledError::NotFinite => write!(f, "Value is not a finite number"),
+            ScaledError::PrecisionLoss => write!(f, "Conversion would lose the fractional part"),
+            ScaledError::CurrencyMismatch { left, right } => {
+                write!(f, "Currency mismatch: {} vs {}", left, right)
+            }
         }
     }
 }
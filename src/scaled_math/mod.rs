@@ -7,11 +7,15 @@ mod errors;
 mod scaled_number;
 mod precision_analyzer;
 mod converter;
+mod rounding;
+mod money;
 
 
 
 // Публичные экспорты
 pub use errors::ScaledError;
 pub use scaled_number::ScaledNumber;
-pub use precision_analyzer::PrecisionAnalyzer;
+pub use precision_analyzer::{DecimalSeparator, NormalizedNumber, PrecisionAnalyzer};
 pub use converter::ScaledConverter;
+pub use rounding::RoundingMode;
+pub use money::Money;
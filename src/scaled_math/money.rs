@@ -0,0 +1,146 @@
+//! Денежные суммы поверх ScaledNumber
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::errors::ScaledError;
+use super::rounding::RoundingMode;
+use super::scaled_number::ScaledNumber;
+
+/// Денежная сумма в заданной валюте с фиксированным числом знаков
+///
+/// # Примеры
+///
+/// ```rust
+/// use rezalnyas_core::scaled_math::{Money, RoundingMode, ScaledNumber};
+///
+/// // Цена за м² и площадь листа
+/// let price = Money::from_str("12.35", "EUR").unwrap();
+/// let area = ScaledNumber::from_str("2.5", 1).unwrap();
+///
+/// // 12.35 * 2.5 = 30.875 -> банковское округление до 30.88
+/// let cost = price.checked_mul(area, RoundingMode::HalfEven).unwrap();
+/// assert_eq!(cost.to_string(), "30.88 EUR");
+///
+/// // Суммы разных валют не складываются
+/// let usd = Money::from_str("1.00", "USD").unwrap();
+/// assert!(cost.checked_add(&usd).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Money {
+    amount: ScaledNumber,
+    currency: String,
+}
+
+impl Money {
+    /// Точность по умолчанию - копейки/центы
+    pub const DEFAULT_PRECISION: u8 = 2;
+
+    /// Создает сумму из ScaledNumber, приводя её к точности по умолчанию
+    pub fn new(amount: ScaledNumber, currency: &str) -> Result<Self, ScaledError> {
+        Self::with_precision(amount, currency, Self::DEFAULT_PRECISION, RoundingMode::HalfEven)
+    }
+
+    /// Создает сумму с заданной точностью и режимом округления
+    pub fn with_precision(
+        amount: ScaledNumber,
+        currency: &str,
+        precision: u8,
+        mode: RoundingMode,
+    ) -> Result<Self, ScaledError> {
+        Ok(Self {
+            amount: amount.round_to(precision, mode)?,
+            currency: currency.to_string(),
+        })
+    }
+
+    /// Создает из строки ("12.34")
+    pub fn from_str(s: &str, currency: &str) -> Result<Self, ScaledError> {
+        Self::new(ScaledNumber::from_str_auto(s)?, currency)
+    }
+
+    /// Создает из суммы в минимальных единицах (центах)
+    pub fn from_minor_units(minor_units: i64, currency: &str) -> Result<Self, ScaledError> {
+        Ok(Self {
+            amount: ScaledNumber::from_raw(minor_units, Self::DEFAULT_PRECISION)?,
+            currency: currency.to_string(),
+        })
+    }
+
+    /// Нулевая сумма
+    pub fn zero(currency: &str) -> Self {
+        Self {
+            amount: ScaledNumber::from_raw(0, Self::DEFAULT_PRECISION)
+                .expect("default precision is valid"),
+            currency: currency.to_string(),
+        }
+    }
+
+    pub fn amount(&self) -> ScaledNumber {
+        self.amount
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    /// Сумма в минимальных единицах
+    pub fn minor_units(&self) -> i64 {
+        self.amount.raw_value()
+    }
+
+    /// Сложение сумм одной валюты
+    pub fn checked_add(&self, other: &Self) -> Result<Self, ScaledError> {
+        self.check_currency(other)?;
+        Ok(Self {
+            amount: self.amount.checked_add(other.amount)?,
+            currency: self.currency.clone(),
+        })
+    }
+
+    /// Вычитание сумм одной валюты
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, ScaledError> {
+        self.check_currency(other)?;
+        Ok(Self {
+            amount: self.amount.checked_sub(other.amount)?,
+            currency: self.currency.clone(),
+        })
+    }
+
+    /// Умножение на количество (площадь, длину реза и т.п.) с округлением до точности суммы
+    pub fn checked_mul(&self, factor: ScaledNumber, mode: RoundingMode) -> Result<Self, ScaledError> {
+        Ok(Self {
+            amount: self
+                .amount
+                .checked_mul_rounded(factor, self.amount.precision(), mode)?,
+            currency: self.currency.clone(),
+        })
+    }
+
+    /// Деление на количество с округлением до точности суммы
+    pub fn checked_div(&self, divisor: ScaledNumber, mode: RoundingMode) -> Result<Self, ScaledError> {
+        Ok(Self {
+            amount: self
+                .amount
+                .checked_div_rounded(divisor, self.amount.precision(), mode)?,
+            currency: self.currency.clone(),
+        })
+    }
+
+    fn check_currency(&self, other: &Self) -> Result<(), ScaledError> {
+        if self.currency != other.currency {
+            return Err(ScaledError::CurrencyMismatch {
+                left: self.currency.clone(),
+                right: other.currency.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
@@ -3,6 +3,29 @@
 use super::errors::ScaledError;
 use super::scaled_number::ScaledNumber;
 
+/// Разделитель дробной части во входных строках
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecimalSeparator {
+    /// Определяется по строке: из точки и запятой дробной считается последняя,
+    /// одиночная запятая - дробная ("1,5"), несколько запятых - разделители тысяч
+    #[default]
+    Auto,
+    /// "1,234.5"
+    Dot,
+    /// "1.234,5"
+    Comma,
+}
+
+/// Число в каноническом виде: знак, цифры и точка как разделитель дробной части
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NormalizedNumber {
+    pub canonical: String,
+    /// Количество знаков после точки в `canonical`
+    pub precision: u8,
+}
+
 /// Утилиты для анализа точности в коллекциях чисел
 pub struct PrecisionAnalyzer;
 
@@ -23,6 +46,10 @@ impl PrecisionAnalyzer {
 
     /// Подсчитывает количество знаков после запятой в строке
     pub fn count_decimal_places(s: &str) -> u8 {
+        if let Ok(normalized) = Self::normalize(s) {
+            return normalized.precision;
+        }
+
         if let Some(dot_pos) = s.find('.') {
             (s.len() - dot_pos - 1) as u8
         } else {
@@ -32,6 +59,8 @@ impl PrecisionAnalyzer {
 
     /// Подсчитывает количество цифр до запятой в строке
     pub fn count_integer_places(s: &str) -> u8 {
+        let normalized = Self::normalize(s).map(|n| n.canonical);
+        let s = normalized.as_deref().unwrap_or(s);
         let s = s.trim_start_matches('-'); // Убираем знак минус
         if let Some(dot_pos) = s.find('.') {
             dot_pos as u8
@@ -40,6 +69,189 @@ impl PrecisionAnalyzer {
         }
     }
 
+    /// Разбирает строку в f64 с учётом запятой и дробей
+    pub fn parse_f64(s: &str) -> Result<f64, ScaledError> {
+        Self::normalize(s)?
+            .canonical
+            .parse()
+            .map_err(|_| ScaledError::ParseError(s.to_string()))
+    }
+
+    /// Приводит строку к каноническому виду, определяя разделитель автоматически
+    ///
+    /// # Примеры
+    ///
+    /// ```rust
+    /// use rezalnyas_core::scaled_math::PrecisionAnalyzer;
+    ///
+    /// assert_eq!(PrecisionAnalyzer::normalize("1,5").unwrap().canonical, "1.5");
+    /// assert_eq!(PrecisionAnalyzer::normalize("1.234,56").unwrap().canonical, "1234.56");
+    /// assert_eq!(PrecisionAnalyzer::normalize("1/2").unwrap().canonical, "0.5");
+    ///
+    /// let mixed = PrecisionAnalyzer::normalize("-2 3/16").unwrap();
+    /// assert_eq!(mixed.canonical, "-2.1875");
+    /// assert_eq!(mixed.precision, 4);
+    ///
+    /// assert!(PrecisionAnalyzer::normalize("1/3").is_err());
+    /// ```
+    pub fn normalize(s: &str) -> Result<NormalizedNumber, ScaledError> {
+        Self::normalize_with(s, DecimalSeparator::Auto)
+    }
+
+    /// Приводит строку к каноническому виду с заданным разделителем дробной части.
+    /// Дроби ("1/2", "1 1/2") переводятся в десятичную запись, если она конечна.
+    pub fn normalize_with(
+        s: &str,
+        separator: DecimalSeparator,
+    ) -> Result<NormalizedNumber, ScaledError> {
+        let parse_error = || ScaledError::ParseError(s.to_string());
+
+        let trimmed = s.trim();
+        let (negative, body) = if let Some(rest) = trimmed.strip_prefix('-') {
+            (true, rest.trim_start())
+        } else {
+            (false, trimmed.strip_prefix('+').unwrap_or(trimmed).trim_start())
+        };
+        if body.is_empty() {
+            return Err(parse_error());
+        }
+
+        let unsigned = if body.contains('/') {
+            Self::normalize_fraction(body).ok_or_else(parse_error)?
+        } else {
+            Self::normalize_decimal(body, separator).ok_or_else(parse_error)?
+        };
+
+        let precision = unsigned
+            .find('.')
+            .map(|dot_pos| unsigned.len() - dot_pos - 1)
+            .unwrap_or(0);
+        if precision > ScaledNumber::MAX_PRECISION as usize {
+            return Err(ScaledError::InvalidPrecision(precision.min(u8::MAX as usize) as u8));
+        }
+
+        let is_zero = unsigned.chars().all(|c| c == '0' || c == '.');
+        let canonical = if negative && !is_zero {
+            format!("-{}", unsigned)
+        } else {
+            unsigned
+        };
+
+        Ok(NormalizedNumber {
+            canonical,
+            precision: precision as u8,
+        })
+    }
+
+    /// "1.234,5" / "1,234.5" / "1 234,5" -> "1234.5"
+    fn normalize_decimal(body: &str, separator: DecimalSeparator) -> Option<String> {
+        let decimal_separator = match separator {
+            DecimalSeparator::Dot => '.',
+            DecimalSeparator::Comma => ',',
+            DecimalSeparator::Auto => match (body.rfind('.'), body.rfind(',')) {
+                (Some(dot_pos), Some(comma_pos)) => {
+                    if dot_pos > comma_pos {
+                        '.'
+                    } else {
+                        ','
+                    }
+                }
+                (None, Some(_)) if body.matches(',').count() == 1 => ',',
+                _ => '.',
+            },
+        };
+        let thousands_separator = if decimal_separator == '.' { ',' } else { '.' };
+
+        let (integer_part, fraction_part) = match body.split_once(decimal_separator) {
+            Some((integer_part, fraction_part)) => (integer_part, Some(fraction_part)),
+            None => (body, None),
+        };
+
+        let integer_digits: String = integer_part
+            .chars()
+            .filter(|c| *c != thousands_separator && *c != ' ' && *c != '\u{a0}' && *c != '\'')
+            .collect();
+        if !integer_digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        match fraction_part {
+            Some(fraction) => {
+                if !fraction.chars().all(|c| c.is_ascii_digit())
+                    || (integer_digits.is_empty() && fraction.is_empty())
+                {
+                    return None;
+                }
+                let integer_digits = if integer_digits.is_empty() { "0" } else { &integer_digits };
+                if fraction.is_empty() {
+                    Some(integer_digits.to_string())
+                } else {
+                    Some(format!("{}.{}", integer_digits, fraction))
+                }
+            }
+            None if integer_digits.is_empty() => None,
+            None => Some(integer_digits),
+        }
+    }
+
+    /// "1/2" / "1 1/2" -> "0.5" / "1.5", только для конечных десятичных дробей
+    fn normalize_fraction(body: &str) -> Option<String> {
+        let parts: Vec<&str> = body.split_whitespace().collect();
+        let (whole, fraction) = match parts.as_slice() {
+            [fraction] => (0u64, *fraction),
+            [whole, fraction] => (whole.parse::<u64>().ok()?, *fraction),
+            _ => return None,
+        };
+
+        let (numerator, denominator) = fraction.split_once('/')?;
+        let numerator: u64 = numerator.trim().parse().ok()?;
+        let denominator: u64 = denominator.trim().parse().ok()?;
+        if denominator == 0 {
+            return None;
+        }
+
+        // Десятичная запись конечна, только если знаменатель раскладывается на 2 и 5
+        let gcd = Self::gcd(numerator, denominator);
+        let (numerator, denominator) = (numerator / gcd, denominator / gcd);
+        let mut rest = denominator;
+        let (mut twos, mut fives) = (0u32, 0u32);
+        while rest % 2 == 0 {
+            rest /= 2;
+            twos += 1;
+        }
+        while rest % 5 == 0 {
+            rest /= 5;
+            fives += 1;
+        }
+        if rest != 1 {
+            return None;
+        }
+
+        let places = twos.max(fives);
+        let scale = 10u128.checked_pow(places)?;
+        let scaled = (whole as u128 * denominator as u128 + numerator as u128) * scale
+            / denominator as u128;
+
+        if places == 0 {
+            Some(scaled.to_string())
+        } else {
+            Some(format!(
+                "{}.{:0width$}",
+                scaled / scale,
+                scaled % scale,
+                width = places as usize
+            ))
+        }
+    }
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a.max(1)
+        } else {
+            Self::gcd(b, a % b)
+        }
+    }
+
     /// Проверяет, не превышает ли общее количество цифр лимит
     pub fn validate_total_digits(numbers: &[&str], max_digits: u8) -> Result<u8, ScaledError> {
         let max_decimal = Self::max_decimal_places(numbers);
@@ -0,0 +1,55 @@
+//! Режимы округления для операций с масштабированными числами
+
+use serde::{Deserialize, Serialize};
+
+/// Способ округления при уменьшении точности, умножении и делении
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum RoundingMode {
+    /// Половина - от нуля (2.5 -> 3, -2.5 -> -3)
+    #[default]
+    HalfUp,
+    /// Банковское округление: половина - к ближайшему чётному (2.5 -> 2, 3.5 -> 4)
+    HalfEven,
+    /// Отбрасывание дробной части (к нулю)
+    Down,
+    /// От нуля
+    Up,
+    /// К минус бесконечности
+    Floor,
+    /// К плюс бесконечности
+    Ceiling,
+}
+
+impl RoundingMode {
+    /// Делит `numerator` на `denominator` с округлением частного по режиму
+    pub(crate) fn divide(self, numerator: i128, denominator: i128) -> i128 {
+        let quotient = numerator / denominator;
+        let remainder = numerator % denominator;
+        if remainder == 0 {
+            return quotient;
+        }
+
+        // Знак точного результата
+        let sign = if (numerator < 0) != (denominator < 0) { -1 } else { 1 };
+        let twice_remainder = remainder.abs() * 2;
+        let denominator_abs = denominator.abs();
+
+        let away_from_zero = match self {
+            RoundingMode::Down => false,
+            RoundingMode::Up => true,
+            RoundingMode::Floor => sign < 0,
+            RoundingMode::Ceiling => sign > 0,
+            RoundingMode::HalfUp => twice_remainder >= denominator_abs,
+            RoundingMode::HalfEven => {
+                twice_remainder > denominator_abs
+                    || (twice_remainder == denominator_abs && quotient % 2 != 0)
+            }
+        };
+
+        if away_from_zero {
+            quotient + sign
+        } else {
+            quotient
+        }
+    }
+}
@@ -2,11 +2,15 @@
 
 use std::cmp::Ordering;
 use std::fmt;
-use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
 use super::errors::ScaledError;
+use super::precision_analyzer::PrecisionAnalyzer;
+use super::rounding::RoundingMode;
 
 /// Масштабированное число - дробное число, представленное как целое
 ///
@@ -33,6 +37,12 @@ use super::errors::ScaledError;
 /// let b = ScaledNumber::from_f64(0.2, 3).unwrap();
 /// let c = ScaledNumber::from_f64(0.3, 3).unwrap();
 /// assert_eq!(a + b, c); // Работает правильно!
+///
+/// // Стандартные преобразования с явными ошибками
+/// let parsed: ScaledNumber = "2,5".parse().unwrap();
+/// assert_eq!(f64::from(parsed), 2.5);
+/// assert!(i64::try_from(parsed).is_err());
+/// assert!(ScaledNumber::try_from(f64::NAN).is_err());
 /// ```
 #[derive(Debug, Clone, Copy, Hash, Serialize, Deserialize)]
 pub struct ScaledNumber {
@@ -142,6 +152,9 @@ impl ScaledNumber {
         if precision > Self::MAX_PRECISION {
             return Err(ScaledError::InvalidPrecision(precision));
         }
+        if !value.is_finite() {
+            return Err(ScaledError::NotFinite);
+        }
 
         let scale = 10_i64.pow(precision as u32);
         let scaled_value = (value as f64 * scale as f64).round() as i64;
@@ -158,6 +171,9 @@ impl ScaledNumber {
         if precision > Self::MAX_PRECISION {
             return Err(ScaledError::InvalidPrecision(precision));
         }
+        if !value.is_finite() {
+            return Err(ScaledError::NotFinite);
+        }
 
         let scale = 10_i64.pow(precision as u32);
         let scaled_value = (value * scale as f64).round() as i64;
@@ -247,31 +263,22 @@ impl ScaledNumber {
         })
     }
 
-    /// Создает из строки с автоматическим определением точности
+    /// Создает из строки с автоматическим определением точности.
+    /// Понимает запятую как разделитель дробной части и дроби ("1/2").
     pub fn from_str_auto(s: &str) -> Result<Self, ScaledError> {
-        let s = s.trim();
-
-        // Определяем точность по количеству знаков после точки
-        let precision = if let Some(dot_pos) = s.find('.') {
-            let decimal_part = &s[dot_pos + 1..];
-            decimal_part.len() as u8
-        } else {
-            0
-        };
+        let normalized = PrecisionAnalyzer::normalize(s)?;
 
-        let value: f64 = s
+        let value: f64 = normalized
+            .canonical
             .parse()
             .map_err(|_| ScaledError::ParseError(s.to_string()))?;
 
-        Self::from_f64(value, precision)
+        Self::from_f64(value, normalized.precision)
     }
 
     /// Создает из строки с заданной точностью
     pub fn from_str(s: &str, precision: u8) -> Result<Self, ScaledError> {
-        let value: f64 = s
-            .trim()
-            .parse()
-            .map_err(|_| ScaledError::ParseError(s.to_string()))?;
+        let value = PrecisionAnalyzer::parse_f64(s)?;
 
         Self::from_f64(value, precision)
     }
@@ -464,6 +471,89 @@ impl ScaledNumber {
         })
     }
 
+    /// Приводит к другой точности с заданным режимом округления
+    pub fn round_to(&self, new_precision: u8, mode: RoundingMode) -> Result<Self, ScaledError> {
+        if new_precision >= self.precision {
+            return self.with_precision(new_precision);
+        }
+
+        let factor = 10_i128.pow((self.precision - new_precision) as u32);
+        let new_value = mode.divide(self.value as i128, factor);
+        Self::from_i128_raw(new_value, new_precision)
+    }
+
+    /// Банковское округление до заданной точности
+    pub fn round_bankers(&self, new_precision: u8) -> Result<Self, ScaledError> {
+        self.round_to(new_precision, RoundingMode::HalfEven)
+    }
+
+    /// Умножение с явной точностью результата и режимом округления.
+    /// Промежуточный результат считается без потери знаков.
+    pub fn checked_mul_rounded(
+        self,
+        other: Self,
+        precision: u8,
+        mode: RoundingMode,
+    ) -> Result<Self, ScaledError> {
+        if precision > Self::MAX_PRECISION {
+            return Err(ScaledError::InvalidPrecision(precision));
+        }
+
+        // Точность произведения - сумма точностей множителей
+        let product = (self.value as i128) * (other.value as i128);
+        let product_precision = self.precision as i32 + other.precision as i32;
+        let shift = product_precision - precision as i32;
+
+        let new_value = if shift >= 0 {
+            mode.divide(product, 10_i128.pow(shift as u32))
+        } else {
+            product
+                .checked_mul(10_i128.pow((-shift) as u32))
+                .ok_or(ScaledError::Overflow)?
+        };
+        Self::from_i128_raw(new_value, precision)
+    }
+
+    /// Деление с явной точностью результата и режимом округления
+    pub fn checked_div_rounded(
+        self,
+        other: Self,
+        precision: u8,
+        mode: RoundingMode,
+    ) -> Result<Self, ScaledError> {
+        if precision > Self::MAX_PRECISION {
+            return Err(ScaledError::InvalidPrecision(precision));
+        }
+        if other.value == 0 {
+            return Err(ScaledError::DivisionByZero);
+        }
+
+        // a/10^p1 : b/10^p2 = (a * 10^(precision + p2 - p1) / b) / 10^precision
+        let shift = precision as i32 + other.precision as i32 - self.precision as i32;
+        let (numerator, denominator) = if shift >= 0 {
+            (
+                (self.value as i128)
+                    .checked_mul(10_i128.pow(shift as u32))
+                    .ok_or(ScaledError::Overflow)?,
+                other.value as i128,
+            )
+        } else {
+            (
+                self.value as i128,
+                (other.value as i128)
+                    .checked_mul(10_i128.pow((-shift) as u32))
+                    .ok_or(ScaledError::Overflow)?,
+            )
+        };
+
+        Self::from_i128_raw(mode.divide(numerator, denominator), precision)
+    }
+
+    fn from_i128_raw(value: i128, precision: u8) -> Result<Self, ScaledError> {
+        let value = i64::try_from(value).map_err(|_| ScaledError::Overflow)?;
+        Self::from_raw(value, precision)
+    }
+
     /// Возвращает абсолютное значение
     pub fn abs(&self) -> Self {
         Self {
@@ -639,6 +729,97 @@ impl SubAssign for ScaledNumber {
     }
 }
 
+impl MulAssign for ScaledNumber {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for ScaledNumber {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Neg for ScaledNumber {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            value: -self.value,
+            precision: self.precision,
+            scale: self.scale,
+        }
+    }
+}
+
+impl Sum for ScaledNumber {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::from(0_i64), |acc, item| acc + item)
+    }
+}
+
+// Преобразования
+impl From<i64> for ScaledNumber {
+    fn from(value: i64) -> Self {
+        Self {
+            value,
+            precision: 0,
+            scale: 1,
+        }
+    }
+}
+
+impl From<i32> for ScaledNumber {
+    fn from(value: i32) -> Self {
+        Self::from(value as i64)
+    }
+}
+
+impl From<u32> for ScaledNumber {
+    fn from(value: u32) -> Self {
+        Self::from(value as i64)
+    }
+}
+
+/// Точность определяется по кратчайшему десятичному представлению числа
+impl TryFrom<f64> for ScaledNumber {
+    type Error = ScaledError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if !value.is_finite() {
+            return Err(ScaledError::NotFinite);
+        }
+        Self::from_str_auto(&value.to_string())
+    }
+}
+
+impl From<ScaledNumber> for f64 {
+    fn from(value: ScaledNumber) -> Self {
+        value.to_f64()
+    }
+}
+
+/// Без потерь: число с ненулевой дробной частью даёт ошибку
+impl TryFrom<ScaledNumber> for i64 {
+    type Error = ScaledError;
+
+    fn try_from(value: ScaledNumber) -> Result<Self, Self::Error> {
+        if value.value % value.scale != 0 {
+            return Err(ScaledError::PrecisionLoss);
+        }
+        Ok(value.value / value.scale)
+    }
+}
+
+impl FromStr for ScaledNumber {
+    type Err = ScaledError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_auto(s)
+    }
+}
+
 // Сравнения
 impl PartialOrd for ScaledNumber {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -0,0 +1,66 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of the current time of tasks and task ids, replaced to simulate time in tests
+pub trait Clock: Debug + Send + Sync {
+    /// Milliseconds since UNIX epoch
+    fn now_millis(&self) -> u64;
+}
+
+/// Time of the system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as u64
+    }
+}
+
+/// Clock that only moves when it is told to
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use rezalnyas_core::features::engine::model::task::Task;
+/// use rezalnyas_core::utils::clock::{Clock, ManualClock};
+///
+/// let clock = Arc::new(ManualClock::new(1_000));
+/// let mut task = Task::default();
+/// task.set_clock(clock.clone());
+/// task.time_limit_deadline = Some(task.now_millis() + 500);
+///
+/// assert!(!task.is_time_limit_reached());
+/// clock.advance(500);
+/// assert!(task.is_time_limit_reached());
+/// assert_eq!(clock.now_millis(), 1_500);
+/// ```
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    millis: AtomicU64,
+}
+
+impl ManualClock {
+    pub fn new(millis: u64) -> Self {
+        Self {
+            millis: AtomicU64::new(millis),
+        }
+    }
+
+    pub fn set(&self, millis: u64) {
+        self.millis.store(millis, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, millis: u64) {
+        self.millis.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_millis(&self) -> u64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+}